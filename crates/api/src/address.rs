@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use super::BASE_WALLET_API_V1;
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, RequestPriority, RequestPriorityExt, ResponseBody},
     error::Error,
     transaction::ApiTransactionStatus,
     ProtonWalletApiClient,
@@ -25,10 +25,11 @@ pub struct AddressBalance {
     pub MempoolSpentBitcoin: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetAddressBalanceResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub Balance: AddressBalance,
 }
 
@@ -71,17 +72,19 @@ pub struct ApiTx {
     pub TransactionStatus: ApiTransactionStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetScriptHashTransactionsResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub Transactions: Vec<ApiTx>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetScriptHashTransactionsAtTransactionIDResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub Transactions: Vec<ApiTx>,
 }
 
@@ -100,10 +103,11 @@ pub struct GetScriptHashesTransactionsRequestBody {
 
 pub type TransactionsByScriptHash = HashMap<String, Vec<ApiTx>>;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetScriptHashesTransactionsResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub Transactions: TransactionsByScriptHash,
 }
 
@@ -126,9 +130,7 @@ impl AddressClient {
     pub async fn get_address_balance(&self, address: String) -> Result<AddressBalance, Error> {
         let request = self.get(format!("addresses/{}/balance", address));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetAddressBalanceResponseBody>()?;
-
-        Ok(parsed.Balance)
+        response.parse_body::<GetAddressBalanceResponseBody>()
     }
 
     /// Get transaction history for the specified scripthash, sorted by newest
@@ -137,9 +139,7 @@ impl AddressClient {
     pub async fn get_scripthash_transactions(&self, script_hash: String) -> Result<Vec<ApiTx>, Error> {
         let request = self.get(format!("addresses/scripthash/{}/transactions", script_hash));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetScriptHashTransactionsResponseBody>()?;
-
-        Ok(parsed.Transactions)
+        response.parse_body::<GetScriptHashTransactionsResponseBody>()
     }
 
     /// Get transaction history for the specified scripthash, sorted by newest
@@ -155,15 +155,16 @@ impl AddressClient {
             script_hash, transaction_id
         ));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetScriptHashTransactionsResponseBody>()?;
-
-        Ok(parsed.Transactions)
+        response.parse_body::<GetScriptHashTransactionsResponseBody>()
     }
 
     /// Get transaction history for multiple scripthashes, sorted by newest
     /// first. Returns up to 50 mempool transactions plus the first 25
     /// confirmed transactions. Pass TxID in the structure to get more
     /// transactions at TxID.
+    ///
+    /// This is the wallet sync's bulk request, it is sent with background
+    /// priority so that it doesn't compete with user-initiated actions.
     pub async fn get_scripthashes_transactions(
         &self,
         script_hashes: Vec<ScriptHashTransactionsPayload>,
@@ -172,11 +173,17 @@ impl AddressClient {
             ScriptHashes: script_hashes,
         };
 
-        let request = self.post("addresses/scripthashes/transactions").body_json(payload)?;
-        let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetScriptHashesTransactionsResponseBody>()?;
-
-        Ok(parsed.Transactions)
+        let response = self
+            .api_client
+            .send_json(
+                || {
+                    self.post("addresses/scripthashes/transactions")
+                        .with_priority(RequestPriority::Background)
+                },
+                &payload,
+            )
+            .await?;
+        response.parse_body::<GetScriptHashesTransactionsResponseBody>()
     }
 }
 
@@ -1,11 +1,10 @@
-use std::sync::Arc;
-
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 const ONLY_WITHOUT_BITCOIN_ADDRESS_KEY: &str = "OnlyWithoutBitcoinAddresses[]";
@@ -37,48 +36,33 @@ pub struct ApiBitcoinAddressCreationPayload {
     pub BitcoinAddressIndex: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetBitcoinAddressHighestIndexResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub HighestIndex: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetBitcoinAddressesResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletBitcoinAddresses: Vec<ApiWalletBitcoinAddress>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateBitcoinAddressResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletBitcoinAddress: ApiWalletBitcoinAddress,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct BitcoinAddressClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for BitcoinAddressClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-}
+pub struct BitcoinAddressClient;
 
 impl BitcoinAddressClient {
     pub async fn get_bitcoin_addresses(
@@ -98,9 +82,7 @@ impl BitcoinAddressClient {
             ));
         }
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetBitcoinAddressesResponseBody>()?;
-
-        Ok(parsed.WalletBitcoinAddresses)
+        response.parse_body::<GetBitcoinAddressesResponseBody>()
     }
 
     pub async fn get_bitcoin_address_highest_index(
@@ -113,8 +95,7 @@ impl BitcoinAddressClient {
             wallet_id, wallet_account_id,
         ));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetBitcoinAddressHighestIndexResponseBody>()?;
-        Ok(parsed.HighestIndex)
+        response.parse_body::<GetBitcoinAddressHighestIndexResponseBody>()
     }
 
     pub async fn add_bitcoin_addresses(
@@ -135,8 +116,7 @@ impl BitcoinAddressClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetBitcoinAddressesResponseBody>()?;
-        Ok(parsed.WalletBitcoinAddresses)
+        response.parse_body::<GetBitcoinAddressesResponseBody>()
     }
 
     pub async fn update_bitcoin_address(
@@ -154,8 +134,7 @@ impl BitcoinAddressClient {
             .body_json(bitcoin_address)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateBitcoinAddressResponseBody>()?;
-        Ok(parsed.WalletBitcoinAddress)
+        response.parse_body::<UpdateBitcoinAddressResponseBody>()
     }
 }
 
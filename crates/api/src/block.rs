@@ -1,13 +1,18 @@
-use std::{str::FromStr, sync::Arc};
-
-use bitcoin::{block::Header as BlockHeader, consensus::deserialize, hashes::hex::FromHex, Block, BlockHash};
+use std::{collections::BTreeMap, ops::RangeInclusive, str::FromStr};
+
+use andromeda_macros::api_client;
+use bitcoin::{
+    block::{Header as BlockHeader, Version},
+    consensus::deserialize,
+    hashes::{hex::FromHex, Hash},
+    Block, BlockHash, CompactTarget, TxMerkleNode,
+};
 use serde::Deserialize;
 
 use super::BASE_WALLET_API_V1;
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient,
 };
 
 #[derive(Debug, Deserialize)]
@@ -28,29 +33,47 @@ pub struct ApiBlock {
     pub Difficulty: f32,
 }
 
-//TODO:: code need to be used. remove all #[allow(dead_code)]
+impl ApiBlock {
+    /// Rebuilds the consensus [`BlockHeader`] from the block summary fields.
+    pub fn to_header(&self) -> Result<BlockHeader, Error> {
+        let prev_blockhash = match &self.PreviousBlockHash {
+            Some(hash) => BlockHash::from_str(hash)?,
+            // Genesis block has no previous block
+            None => BlockHash::all_zeros(),
+        };
+
+        Ok(BlockHeader {
+            version: Version::from_consensus(self.Version as i32),
+            prev_blockhash,
+            merkle_root: TxMerkleNode::from_str(&self.MerkleRoot)?,
+            time: self.Timestamp as u32,
+            bits: CompactTarget::from_consensus(self.Bits as u32),
+            nonce: self.Nonce as u32,
+        })
+    }
+}
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetBlocksResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Blocks: Vec<ApiBlock>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetHeaderByHashResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub BlockHeader: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetBlockHashByBlockHeightResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub BlockHash: String,
 }
 
@@ -62,11 +85,11 @@ pub struct BlockStatus {
     pub NextBest: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetBlockStatusResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub BlockStatus: BlockStatus,
 }
 
@@ -77,48 +100,33 @@ struct GetBlockByHashResponseBody {
     pub Details: Block,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTxIdAtBlockIndexResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub TransactionID: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTipHeightResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Height: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTipHashResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub BlockHash: String,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct BlockClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for BlockClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-}
+pub struct BlockClient;
 
 impl BlockClient {
     /// Get recent block summaries, starting at tip or height if provided
@@ -128,9 +136,55 @@ impl BlockClient {
             None => "blocks".to_string(),
         });
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetBlocksResponseBody>()?;
+        response.parse_body::<GetBlocksResponseBody>()
+    }
+
+    /// Get the [`BlockHeader`]s of every block in `heights`, ordered by
+    /// ascending height.
+    ///
+    /// Headers are rebuilt from the block summaries returned by
+    /// [`BlockClient::get_blocks`], so a whole page of headers is fetched per
+    /// request instead of one hash and one header request per height.
+    pub async fn get_headers_by_height_range(
+        &self,
+        heights: RangeInclusive<u32>,
+    ) -> Result<Vec<(u32, BlockHeader)>, Error> {
+        if heights.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        Ok(parsed.Blocks)
+        let mut headers = BTreeMap::new();
+        let mut cursor = *heights.end();
+        loop {
+            let blocks = self.get_blocks(Some(cursor)).await?;
+            let Some(lowest_height) = blocks.iter().map(|block| block.BlockHeight).min() else {
+                break;
+            };
+
+            for block in blocks.iter().filter(|block| heights.contains(&block.BlockHeight)) {
+                headers.insert(block.BlockHeight, block.to_header()?);
+            }
+
+            // Stop once the page reached the start of the range, or if the backend didn't
+            // return anything below the cursor
+            if lowest_height <= *heights.start() || lowest_height > cursor {
+                break;
+            }
+            cursor = lowest_height - 1;
+        }
+
+        Ok(headers.into_iter().collect())
+    }
+
+    /// Get the height and hash of the most recent block having at least
+    /// `min_confirmations` confirmations. The tip itself counts as 1
+    /// confirmation, so `0` and `1` both return the tip.
+    pub async fn get_tip_with_min_confirmations(&self, min_confirmations: u32) -> Result<(u32, BlockHash), Error> {
+        let tip_height = self.get_tip_height().await?;
+        let height = tip_height.saturating_sub(min_confirmations.saturating_sub(1));
+        let block_hash = self.get_block_hash(height).await?;
+
+        Ok((height, block_hash))
     }
 
     /// Get a [`BlockHeader`] given a particular block hash.
@@ -156,9 +210,7 @@ impl BlockClient {
         let request = self.get(format!("blocks/{}/status", block_hash));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetBlockStatusResponseBody>()?;
-
-        Ok(parsed.BlockStatus)
+        response.parse_body::<GetBlockStatusResponseBody>()
     }
 
     pub async fn get_block_by_hash(&self, block_hash: &BlockHash) -> Result<Block, Error> {
@@ -173,16 +225,14 @@ impl BlockClient {
         let request = self.get(format!("blocks/{}/txid/{}", block_hash, index));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetTxIdAtBlockIndexResponseBody>()?;
-        Ok(parsed.TransactionID)
+        response.parse_body::<GetTxIdAtBlockIndexResponseBody>()
     }
 
     pub async fn get_tip_height(&self) -> Result<u32, Error> {
         let request = self.get("blocks/tip/height");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetTipHeightResponseBody>()?;
-        Ok(parsed.Height)
+        response.parse_body::<GetTipHeightResponseBody>()
     }
 
     pub async fn get_tip_hash(&self) -> Result<BlockHash, Error> {
@@ -390,6 +440,87 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_headers_by_height_range_success() {
+        let mock_server = MockServer::start().await;
+        let contents = read_mock_file!("get_blocks_1000_body");
+        let response = ResponseTemplate::new(200).set_body_string(contents);
+        let req_path: String = format!("{}/blocks/871864", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockClient::new(Arc::new(api_client));
+        let result = client.get_headers_by_height_range(871863..=871864).await;
+        match result {
+            Ok(headers) => {
+                assert_eq!(headers.len(), 2);
+                assert_eq!(headers[0].0, 871863);
+                assert_eq!(
+                    headers[0].1.block_hash().to_string(),
+                    "0000000000000000000084855fd06ffed9ac4cf7df513e09ed1011522b67275c"
+                );
+                assert_eq!(headers[1].0, 871864);
+                assert_eq!(
+                    headers[1].1.block_hash().to_string(),
+                    "000000000000000000013b1489869b5537ef7d3880be22f713258f1cb83f0f10"
+                );
+                assert_eq!(headers[1].1.prev_blockhash, headers[0].1.block_hash());
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_headers_by_height_range_empty() {
+        let mock_server = MockServer::start().await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockClient::new(Arc::new(api_client));
+        #[allow(clippy::reversed_empty_ranges)]
+        let headers = client.get_headers_by_height_range(10..=9).await.unwrap();
+        assert!(headers.is_empty());
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_tip_with_min_confirmations_success() {
+        let mock_server = MockServer::start().await;
+        let tip_height_body = serde_json::json!({
+            "Code": 1000,
+            "Height": 871866
+            }
+        );
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/tip/height", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(tip_height_body))
+            .mount(&mock_server)
+            .await;
+        let contents = read_mock_file!("get_block_hash_1000_body");
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/height/871864/hash", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(contents))
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockClient::new(Arc::new(api_client));
+        let result = client.get_tip_with_min_confirmations(3).await;
+        match result {
+            Ok((height, block_hash)) => {
+                assert_eq!(height, 871864);
+                assert_eq!(
+                    block_hash.to_string(),
+                    "00000000000000000000b3f5fed64a5eefd02589a036ce9f9bd40b627b4cb9a3"
+                );
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
     #[tokio::test]
     async fn test_get_header_by_hash_success() {
         let mock_server = MockServer::start().await;
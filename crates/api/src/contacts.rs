@@ -1,11 +1,11 @@
-use std::sync::Arc;
-
-use serde::Deserialize;
+use andromeda_macros::api_client;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
-    error::Error,
-    ProtonWalletApiClient, BASE_CONTACTS_API_V4,
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
+    error::{Error, ResponseError},
+    proton_users::EmptyResponseBody,
+    BASE_CONTACTS_API_V4,
 };
 
 #[derive(Debug, Deserialize)]
@@ -26,25 +26,84 @@ pub struct ApiContactEmails {
     pub IsProton: u32,
 }
 
-#[derive(Clone)]
-pub struct ContactsClient {
-    api_client: Arc<ProtonWalletApiClient>,
+/// A vCard of a contact. The card is prepared, and encrypted and/or signed
+/// depending on its type, by the caller
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ApiContactCard {
+    /// 0: clear text, 1: encrypted, 2: signed, 3: encrypted and signed
+    pub Type: u8,
+    pub Data: String,
+    pub Signature: Option<String>,
 }
 
-impl ApiClient for ContactsClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ApiContact {
+    pub ID: String,
+    pub Name: String,
+    pub Cards: Vec<ApiContactCard>,
+    #[serde(default)]
+    pub ContactEmails: Vec<ApiContactEmails>,
+}
 
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
+#[derive(Debug, Deserialize, ResponseBody)]
+#[allow(non_snake_case)]
+struct GetContactResponseBody {
+    pub Code: u16,
+    #[response_body(data)]
+    pub Contact: ApiContact,
+}
 
-    fn base_url(&self) -> &str {
-        BASE_CONTACTS_API_V4
-    }
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+struct ContactCards {
+    pub Cards: Vec<ApiContactCard>,
 }
 
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+struct CreateContactsRequestBody {
+    pub Contacts: Vec<ContactCards>,
+    pub Overwrite: u8,
+    pub Labels: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct CreateContactResult {
+    pub Code: u16,
+    pub Contact: Option<ApiContact>,
+    #[serde(default)]
+    pub Error: String,
+    #[serde(default)]
+    pub Details: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct CreateContactResponse {
+    pub Response: CreateContactResult,
+}
+
+#[derive(Debug, Deserialize, ResponseBody)]
+#[allow(non_snake_case)]
+struct CreateContactsResponseBody {
+    pub Code: u16,
+    #[response_body(data)]
+    pub Responses: Vec<CreateContactResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+struct DeleteContactsRequestBody {
+    pub IDs: Vec<String>,
+}
+
+#[api_client(base = BASE_CONTACTS_API_V4)]
+#[derive(Clone)]
+pub struct ContactsClient;
+
 impl ContactsClient {
     pub async fn get_contacts(
         &self,
@@ -66,6 +125,66 @@ impl ContactsClient {
 
         Ok(parsed.ContactEmails)
     }
+
+    pub async fn get_contact(&self, contact_id: String) -> Result<ApiContact, Error> {
+        let request = self.get(format!("contacts/{}", contact_id));
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<GetContactResponseBody>()
+    }
+
+    /// Creates a contact from its cards
+    pub async fn create_contact(&self, cards: Vec<ApiContactCard>) -> Result<ApiContact, Error> {
+        let payload = CreateContactsRequestBody {
+            Contacts: vec![ContactCards { Cards: cards }],
+            Overwrite: 0,
+            Labels: 0,
+        };
+        let request = self.post("contacts").body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        let status = response.status();
+        let parsed = response.parse_response::<CreateContactsResponseBody>()?;
+
+        // The endpoint creates contacts in batch, with a response per contact
+        let result = parsed
+            .Responses
+            .into_iter()
+            .next()
+            .map(|response| response.Response)
+            .ok_or_else(|| Error::Deserialize("Missing contact creation response".to_string()))?;
+
+        match result.Contact {
+            Some(contact) if result.Code == 1000 => Ok(contact),
+            _ => Err(Error::from_response_error(
+                status,
+                ResponseError {
+                    Code: result.Code,
+                    Error: result.Error,
+                    Details: result.Details,
+                },
+            )),
+        }
+    }
+
+    /// Replaces the cards of a contact
+    pub async fn update_contact(&self, contact_id: String, cards: Vec<ApiContactCard>) -> Result<ApiContact, Error> {
+        let payload = ContactCards { Cards: cards };
+        let request = self.put(format!("contacts/{}", contact_id)).body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<GetContactResponseBody>()
+    }
+
+    pub async fn delete_contacts(&self, contact_ids: Vec<String>) -> Result<(), Error> {
+        let payload = DeleteContactsRequestBody { IDs: contact_ids };
+        let request = self.put("contacts/delete").body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        response.parse_response::<EmptyResponseBody>()?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -76,9 +195,10 @@ mod tests {
         Mock, MockServer, ResponseTemplate,
     };
 
-    use super::ContactsClient;
+    use super::{ApiContactCard, ContactsClient};
     use crate::{
         core::ApiClient,
+        error::Error,
         tests::utils::{common_api_client, setup_test_connection_arc},
         BASE_CONTACTS_API_V4,
     };
@@ -151,4 +271,160 @@ mod tests {
         let contacts = client.get_contacts(Some(100), Some(0)).await;
         assert!(contacts.is_err());
     }
+
+    #[tokio::test]
+    async fn test_get_contact_success() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "Contact": {
+                    "ID": "contact_id",
+                    "Name": "Test",
+                    "Cards": [
+                        {
+                            "Type": 2,
+                            "Data": "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Test\r\nEND:VCARD",
+                            "Signature": "-----BEGIN PGP SIGNATURE-----"
+                        }
+                    ],
+                    "ContactEmails": []
+                }
+            }
+        );
+        let req_path = format!("{}/contacts/contact_id", BASE_CONTACTS_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = ContactsClient::new(api_client);
+        let contact = client.get_contact("contact_id".to_string()).await.unwrap();
+        assert_eq!(contact.ID, "contact_id");
+        assert_eq!(contact.Cards.len(), 1);
+        assert_eq!(contact.Cards[0].Type, 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_contact_success() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1001,
+                "Responses": [
+                    {
+                        "Index": 0,
+                        "Response": {
+                            "Code": 1000,
+                            "Contact": {
+                                "ID": "contact_id",
+                                "Name": "Test",
+                                "Cards": []
+                            }
+                        }
+                    }
+                ]
+            }
+        );
+        let req_path = format!("{}/contacts", BASE_CONTACTS_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = ContactsClient::new(api_client);
+        let contact = client
+            .create_contact(vec![ApiContactCard {
+                Type: 0,
+                Data: "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Test\r\nEND:VCARD".to_string(),
+                Signature: None,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(contact.ID, "contact_id");
+        assert!(contact.ContactEmails.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_contact_error() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1001,
+                "Responses": [
+                    {
+                        "Index": 0,
+                        "Response": {
+                            "Code": 2001,
+                            "Error": "Invalid vCard"
+                        }
+                    }
+                ]
+            }
+        );
+        let req_path = format!("{}/contacts", BASE_CONTACTS_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = ContactsClient::new(api_client);
+        let result = client.create_contact(vec![]).await;
+        match result {
+            Err(Error::ErrorCode(_, error)) => {
+                assert_eq!(error.Code, 2001);
+                assert_eq!(error.Error, "Invalid vCard");
+            }
+            _ => panic!("Expected ErrorCode variant."),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_contact_success() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "Contact": {
+                    "ID": "contact_id",
+                    "Name": "Updated",
+                    "Cards": []
+                }
+            }
+        );
+        let req_path = format!("{}/contacts/contact_id", BASE_CONTACTS_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("PUT"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = ContactsClient::new(api_client);
+        let contact = client.update_contact("contact_id".to_string(), vec![]).await.unwrap();
+        assert_eq!(contact.Name, "Updated");
+    }
+
+    #[tokio::test]
+    async fn test_delete_contacts_success() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!({ "Code": 1000 });
+        let req_path = format!("{}/contacts/delete", BASE_CONTACTS_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("PUT"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = ContactsClient::new(api_client);
+        let result = client.delete_contacts(vec!["contact_id".to_string()]).await;
+        assert!(result.is_ok());
+    }
 }
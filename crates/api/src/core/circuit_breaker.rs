@@ -0,0 +1,196 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use andromeda_common::utils::now;
+
+/// Thresholds of the per-endpoint circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures after which the circuit opens
+    pub failure_threshold: u32,
+    /// Time during which requests fast-fail once the circuit is open
+    pub cool_down: Duration,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    consecutive_failures: u32,
+    /// Instant (since epoch) until which the circuit stays open
+    open_until: Option<Duration>,
+    /// Instant (since epoch) at which the probe request of the half-open
+    /// circuit was let through
+    probe_started_at: Option<Duration>,
+}
+
+/// Per-endpoint circuit breaker.
+///
+/// Transport errors and server errors (5xx) count as failures. Once an
+/// endpoint family failed `failure_threshold` times in a row, requests to it
+/// fast-fail for `cool_down`. After the cool-down, a single request is let
+/// through to probe the endpoint while the others keep fast-failing: a
+/// success closes the circuit, a failure opens it again. A probe without
+/// outcome after another `cool_down`, e.g. a cancelled request, is replaced
+/// by a new one.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the remaining cool-down if the endpoint's circuit is open, or
+    /// if it is half-open and another request is already probing it
+    pub fn check(&self, endpoint: &str) -> Result<(), Duration> {
+        self.check_at(endpoint, now())
+    }
+
+    fn check_at(&self, endpoint: &str, at: Duration) -> Result<(), Duration> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(circuit) = circuits.get_mut(endpoint) else {
+            return Ok(());
+        };
+
+        match (circuit.open_until, circuit.probe_started_at) {
+            (Some(open_until), _) if open_until > at => Err(open_until - at),
+            (Some(_), Some(probe_started_at)) if probe_started_at + self.config.cool_down > at => {
+                Err(probe_started_at + self.config.cool_down - at)
+            }
+            (Some(_), _) => {
+                circuit.probe_started_at = Some(at);
+                Ok(())
+            }
+            (None, _) => Ok(()),
+        }
+    }
+
+    pub fn record_success(&self, endpoint: &str) {
+        self.circuits.lock().unwrap().remove(endpoint);
+    }
+
+    pub fn record_failure(&self, endpoint: &str) {
+        self.record_failure_at(endpoint, now());
+    }
+
+    fn record_failure_at(&self, endpoint: &str, at: Duration) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(endpoint.to_string()).or_default();
+
+        circuit.consecutive_failures += 1;
+        if circuit.consecutive_failures >= self.config.failure_threshold {
+            circuit.open_until = Some(at + self.config.cool_down);
+            circuit.probe_started_at = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{CircuitBreaker, CircuitBreakerConfig};
+    use crate::{
+        core::ApiClient,
+        error::Error,
+        network::NetworkClient,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    const ENDPOINT: &str = "wallet/v1/network";
+
+    #[test]
+    fn test_circuit_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cool_down: Duration::from_secs(10),
+        });
+        let start = Duration::from_secs(1000);
+
+        breaker.record_failure_at(ENDPOINT, start);
+        assert!(breaker.check_at(ENDPOINT, start).is_ok());
+
+        breaker.record_failure_at(ENDPOINT, start);
+        assert_eq!(breaker.check_at(ENDPOINT, start), Err(Duration::from_secs(10)));
+        // Other endpoints are not affected
+        assert!(breaker.check_at("wallet/v1/wallets", start).is_ok());
+
+        // Half-open after the cool-down, a new failure opens the circuit again
+        let after_cool_down = start + Duration::from_secs(10);
+        assert!(breaker.check_at(ENDPOINT, after_cool_down).is_ok());
+        breaker.record_failure_at(ENDPOINT, after_cool_down);
+        assert!(breaker.check_at(ENDPOINT, after_cool_down).is_err());
+
+        let after_second_cool_down = after_cool_down + Duration::from_secs(10);
+        assert!(breaker.check_at(ENDPOINT, after_second_cool_down).is_ok());
+        breaker.record_success(ENDPOINT);
+        assert!(breaker.check_at(ENDPOINT, after_second_cool_down).is_ok());
+        assert!(breaker.check_at(ENDPOINT, after_second_cool_down).is_ok());
+    }
+
+    #[test]
+    fn test_half_open_circuit_admits_a_single_probe() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            cool_down: Duration::from_secs(10),
+        });
+        let start = Duration::from_secs(1000);
+        breaker.record_failure_at(ENDPOINT, start);
+
+        let after_cool_down = start + Duration::from_secs(10);
+        assert!(breaker.check_at(ENDPOINT, after_cool_down).is_ok());
+        // Concurrent requests fast-fail while the probe is in flight
+        assert_eq!(
+            breaker.check_at(ENDPOINT, after_cool_down + Duration::from_secs(1)),
+            Err(Duration::from_secs(9))
+        );
+
+        // The probe never completed, a new one is let through
+        let probe_lost = after_cool_down + Duration::from_secs(10);
+        assert!(breaker.check_at(ENDPOINT, probe_lost).is_ok());
+        assert!(breaker.check_at(ENDPOINT, probe_lost).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_fast_fails_when_circuit_open() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 2,
+                cool_down: Duration::from_secs(60),
+            }),
+            ..test_config(mock_server.uri())
+        };
+        let client = NetworkClient::new(std::sync::Arc::new(setup_test_connection_with_config(config)));
+
+        for _ in 0..2 {
+            assert!(matches!(client.refresh_network().await, Err(Error::ErrorCode(_, _))));
+        }
+
+        match client.refresh_network().await {
+            Err(Error::Unavailable(endpoint, retry_after)) => {
+                assert_eq!(endpoint, ENDPOINT);
+                assert!(retry_after <= Duration::from_secs(60));
+            }
+            other => panic!("Expected Unavailable error, got {:?}", other),
+        }
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+}
@@ -39,7 +39,92 @@ pub trait ApiClient {
             .allowed_time(DEFAULT_TIME_CONSTRAINT)
             .service_type(DEFAULT_INTERACTIVITY, true);
     }
+    /// Adds per-call headers to a request built with the methods above. They
+    /// take precedence over the custom headers set in `ApiConfig`
+    fn with_headers(&self, request: ProtonRequest, headers: Vec<(String, String)>) -> ProtonRequest {
+        headers
+            .into_iter()
+            .fold(request, |request, header| request.header(header))
+    }
     fn build_request(&self, version: &str, endpoint: impl ToString) -> String {
         return self.api_client().build_full_url(version, endpoint);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::ApiClient;
+    use crate::{
+        network::NetworkClient,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    #[tokio::test]
+    async fn test_custom_headers() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .and(header("x-ab-cohort", "B"))
+            .and(header("x-client-build", "1234"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000, "Network": 0 })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            custom_headers: vec![
+                ("x-ab-cohort".to_string(), "A".to_string()),
+                ("x-client-build".to_string(), "1234".to_string()),
+            ],
+            ..test_config(mock_server.uri())
+        };
+        let client = NetworkClient::new(Arc::new(setup_test_connection_with_config(config)));
+
+        // Per-call header overrides the one from the config
+        let request = client.with_headers(
+            client.get("network"),
+            vec![("x-ab-cohort".to_string(), "B".to_string())],
+        );
+        let response = client.api_client().send(request).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let request = client.with_headers(
+            client.get("network"),
+            vec![("X-AB-Cohort".to_string(), "B".to_string())],
+        );
+        let response = client.api_client().send(request).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_locale_headers() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .and(header("accept-language", "fr-FR"))
+            .and(header("x-pm-locale", "fr_FR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000, "Network": 0 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            locale: Some("fr-FR".to_string()),
+            ..test_config(mock_server.uri())
+        };
+        let client = NetworkClient::new(Arc::new(setup_test_connection_with_config(config)));
+
+        let response = client.api_client().send(client.get("network")).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+    }
+}
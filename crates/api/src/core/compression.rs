@@ -0,0 +1,172 @@
+use std::{
+    io::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use flate2::{write::GzEncoder, Compression};
+
+use crate::error::Error;
+
+/// Gzip compression of outgoing request bodies.
+///
+/// Compression is opt-in: bodies are only compressed when a threshold has been
+/// configured and the serialized body is at least that large. If the backend
+/// rejects a compressed body, compression is turned off for the rest of the
+/// session.
+#[derive(Debug)]
+pub struct RequestCompression {
+    /// Minimum body size (in bytes) from which bodies get compressed
+    threshold: Option<usize>,
+    /// Cleared once the backend answered a compressed request with `415
+    /// Unsupported Media Type`
+    supported: AtomicBool,
+}
+
+impl RequestCompression {
+    pub fn new(threshold: Option<usize>) -> Self {
+        Self {
+            threshold,
+            supported: AtomicBool::new(true),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold.is_some() && self.supported.load(Ordering::Relaxed)
+    }
+
+    /// Returns the gzip-compressed body if compression applies to it
+    pub fn compress(&self, body: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self.threshold {
+            Some(threshold) if self.is_enabled() && body.len() >= threshold => {
+                Ok(Some(gzip(body).map_err(Error::Compression)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Disables compression after the backend refused a compressed body
+    pub fn set_unsupported(&self) {
+        self.supported.store(false, Ordering::Relaxed);
+    }
+}
+
+fn gzip(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, sync::Arc};
+
+    use flate2::read::GzDecoder;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::RequestCompression;
+    use crate::{
+        core::ToProtonRequest,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    fn gunzip(data: &[u8]) -> Vec<u8> {
+        let mut decoded = Vec::new();
+        GzDecoder::new(data).read_to_end(&mut decoded).unwrap();
+        decoded
+    }
+
+    fn config_with_threshold(url: String, threshold: Option<usize>) -> ApiConfig {
+        ApiConfig {
+            compression_threshold: threshold,
+            ..test_config(url)
+        }
+    }
+
+    #[test]
+    fn test_compress_threshold() {
+        let compression = RequestCompression::new(Some(10));
+        assert!(compression.compress(b"short").unwrap().is_none());
+
+        let body = b"a body long enough to be compressed";
+        let compressed = compression.compress(body).unwrap().unwrap();
+        assert_eq!(gunzip(&compressed), body.to_vec());
+
+        compression.set_unsupported();
+        assert!(!compression.is_enabled());
+        assert!(compression.compress(body).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_compress_disabled() {
+        let compression = RequestCompression::new(None);
+        assert!(!compression.is_enabled());
+        assert!(compression.compress(&[0u8; 1024]).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_send_json_compressed() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = Arc::new(setup_test_connection_with_config(config_with_threshold(
+            mock_server.uri(),
+            Some(0),
+        )));
+        let body = serde_json::json!({ "ScriptHashes": [] });
+        let url = format!("/{}", req_path);
+        let response = api_client.send_json(|| url.to_post_request(), &body).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(gunzip(&requests[0].body), serde_json::to_vec(&body).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_send_json_fallback_when_unsupported() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(415))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .with_priority(2)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = Arc::new(setup_test_connection_with_config(config_with_threshold(
+            mock_server.uri(),
+            Some(0),
+        )));
+        let body = serde_json::json!({ "ScriptHashes": [] });
+        let url = format!("/{}", req_path);
+
+        let response = api_client.send_json(|| url.to_post_request(), &body).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        // Compression is now disabled, next request is sent uncompressed right away
+        let response = api_client.send_json(|| url.to_post_request(), &body).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].headers.get("content-encoding").is_none());
+        assert_eq!(requests[2].body, serde_json::to_vec(&body).unwrap());
+    }
+}
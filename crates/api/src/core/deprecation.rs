@@ -0,0 +1,183 @@
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use muon::{ProtonRequest, ProtonResponse};
+
+use super::{metrics::endpoint_family, proton_response_ext::header_value};
+
+const DEPRECATION_HEADER: &str = "deprecation";
+const SUNSET_HEADER: &str = "sunset";
+const LINK_HEADER: &str = "link";
+
+/// Deprecation announced by the backend for an endpoint, through the
+/// `Deprecation` and `Sunset` response headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecationNotice {
+    /// Endpoint family the request belongs to, see
+    /// [`endpoint_family`](super::metrics::endpoint_family)
+    pub endpoint_family: String,
+    pub method: String,
+    /// Raw `Deprecation` header value, e.g. `@1735689600` or `true`
+    pub deprecation: Option<String>,
+    /// Raw `Sunset` header value: the HTTP date after which the endpoint may
+    /// stop responding
+    pub sunset: Option<String>,
+    /// Raw `Link` header value, usually pointing to the migration docs
+    pub link: Option<String>,
+}
+
+/// Receives deprecation notices. Host apps implement it to forward them to
+/// their telemetry.
+pub trait DeprecationHook: Debug + Send + Sync {
+    fn on_deprecation(&self, notice: DeprecationNotice);
+}
+
+/// Endpoint information captured before the request is consumed by the
+/// session
+pub(crate) struct PendingDeprecationCheck {
+    endpoint_family: String,
+    method: String,
+}
+
+impl PendingDeprecationCheck {
+    pub(crate) fn from_request(request: &ProtonRequest, url_prefix: Option<&str>) -> Self {
+        Self {
+            endpoint_family: endpoint_family(request.uri().path(), url_prefix),
+            method: request.method().to_string(),
+        }
+    }
+
+    pub(crate) fn finish(self, response: &ProtonResponse) -> Option<DeprecationNotice> {
+        let deprecation = header_value(response, DEPRECATION_HEADER);
+        let sunset = header_value(response, SUNSET_HEADER);
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        Some(DeprecationNotice {
+            endpoint_family: self.endpoint_family,
+            method: self.method,
+            deprecation,
+            sunset,
+            link: header_value(response, LINK_HEADER),
+        })
+    }
+}
+
+/// Forwards deprecation notices to the hook, once per endpoint family and
+/// method for the lifetime of the client
+#[derive(Debug, Default)]
+pub(crate) struct DeprecationMonitor {
+    hook: RwLock<Option<Arc<dyn DeprecationHook>>>,
+    reported: Mutex<HashSet<(String, String)>>,
+}
+
+impl DeprecationMonitor {
+    pub(crate) fn set_hook(&self, hook: Option<Arc<dyn DeprecationHook>>) {
+        *self.hook.write().unwrap() = hook;
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.hook.read().unwrap().is_some()
+    }
+
+    pub(crate) fn report(&self, notice: DeprecationNotice) {
+        let Some(hook) = self.hook.read().unwrap().clone() else {
+            return;
+        };
+
+        let is_new = self
+            .reported
+            .lock()
+            .unwrap()
+            .insert((notice.endpoint_family.clone(), notice.method.clone()));
+        if is_new {
+            hook.on_deprecation(notice);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{DeprecationHook, DeprecationNotice};
+    use crate::{core::ApiClient, network::NetworkClient, tests::utils::setup_test_connection, BASE_WALLET_API_V1};
+
+    #[derive(Debug, Default)]
+    struct RecordingHook {
+        notices: Mutex<Vec<DeprecationNotice>>,
+    }
+
+    impl DeprecationHook for RecordingHook {
+        fn on_deprecation(&self, notice: DeprecationNotice) {
+            self.notices.lock().unwrap().push(notice);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deprecation_hook() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200)
+            .insert_header("Deprecation", "@1735689600")
+            .insert_header("Sunset", "Wed, 01 Jan 2025 00:00:00 GMT")
+            .set_body_json(serde_json::json!({ "Code": 1000, "Network": 0 }));
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let hook = Arc::new(RecordingHook::default());
+        api_client.set_deprecation_hook(Some(hook.clone()));
+        let client = api_client.clients().network;
+
+        client.refresh_network().await.unwrap();
+        client.refresh_network().await.unwrap();
+
+        // Reported once per endpoint
+        let notices = hook.notices.lock().unwrap();
+        assert_eq!(
+            *notices,
+            vec![DeprecationNotice {
+                endpoint_family: "wallet/v1/network".to_string(),
+                method: "GET".to_string(),
+                deprecation: Some("@1735689600".to_string()),
+                sunset: Some("Wed, 01 Jan 2025 00:00:00 GMT".to_string()),
+                link: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_deprecation_headers() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000, "Network": 0 })))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let hook = Arc::new(RecordingHook::default());
+        api_client.set_deprecation_hook(Some(hook.clone()));
+
+        NetworkClient::new(Arc::new(api_client))
+            .refresh_network()
+            .await
+            .unwrap();
+        assert!(hook.notices.lock().unwrap().is_empty());
+    }
+}
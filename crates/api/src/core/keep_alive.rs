@@ -0,0 +1,78 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use andromeda_common::utils::now;
+
+/// Tracks the session activity to decide when an idle session must be
+/// pinged, see
+/// [`ProtonWalletApiClient::keep_alive`](crate::ProtonWalletApiClient::keep_alive)
+#[derive(Debug)]
+pub struct KeepAliveTimer {
+    interval: Duration,
+    /// Time, in milliseconds since epoch, at which the last request was sent
+    last_activity: AtomicU64,
+}
+
+impl KeepAliveTimer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_activity: AtomicU64::new(now().as_millis() as u64),
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn record_activity(&self) {
+        self.record_activity_at(now());
+    }
+
+    fn record_activity_at(&self, at: Duration) {
+        self.last_activity.store(at.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the time left before the session must be pinged, `None` when a
+    /// ping is due
+    pub fn next_ping_in(&self) -> Option<Duration> {
+        self.next_ping_in_at(now())
+    }
+
+    fn next_ping_in_at(&self, at: Duration) -> Option<Duration> {
+        let last_activity = Duration::from_millis(self.last_activity.load(Ordering::Relaxed));
+        let idle = at.saturating_sub(last_activity);
+
+        (idle < self.interval).then(|| self.interval - idle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::KeepAliveTimer;
+
+    #[test]
+    fn test_next_ping_in() {
+        let timer = KeepAliveTimer::new(Duration::from_secs(60));
+        let start = Duration::from_secs(1000);
+        timer.record_activity_at(start);
+
+        assert_eq!(timer.next_ping_in_at(start), Some(Duration::from_secs(60)));
+        assert_eq!(
+            timer.next_ping_in_at(start + Duration::from_secs(45)),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(timer.next_ping_in_at(start + Duration::from_secs(60)), None);
+
+        // Any request postpones the ping
+        timer.record_activity_at(start + Duration::from_secs(50));
+        assert_eq!(
+            timer.next_ping_in_at(start + Duration::from_secs(60)),
+            Some(Duration::from_secs(50))
+        );
+    }
+}
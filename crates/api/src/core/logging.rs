@@ -0,0 +1,275 @@
+use muon::{ProtonRequest, ProtonResponse};
+use serde_json::Value;
+
+pub const REDACTED: &str = "<redacted>";
+
+/// Headers carrying credentials, compared case-insensitively
+const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "cookie",
+    "set-cookie",
+    "x-pm-uid",
+    "x-pm-human-verification-token",
+    "x-pm-session",
+];
+
+/// JSON fields holding encrypted payloads, key material or secrets
+const SENSITIVE_FIELDS: [&str; 37] = [
+    "Address",
+    "Body",
+    "ClientEphemeral",
+    "ClientProof",
+    "ClientSecret",
+    "DataPacket",
+    "Email",
+    "Fingerprint",
+    "HashedTransactionID",
+    "KeyPackets",
+    "Label",
+    "Mnemonic",
+    "MnemonicSalt",
+    "Modulus",
+    "Name",
+    "OrganizationPrivateKey",
+    "Password",
+    "PrivateKey",
+    "RecoverySecret",
+    "RecoverySecretSignature",
+    "Salt",
+    "Sender",
+    "ServerEphemeral",
+    "ServerProof",
+    "Signature",
+    "SRPSession",
+    "Subject",
+    "Token",
+    "ToList",
+    "TOTPConfirmation",
+    "TOTPSharedSecret",
+    "TransactionID",
+    "TwoFactorCode",
+    "TwoFactorRecoveryCodes",
+    "Verifier",
+    "WalletKey",
+    "WalletKeySignature",
+];
+
+/// Returns the header value to log, hiding credentials
+pub fn redact_header(name: &str, value: &str) -> String {
+    if SENSITIVE_HEADERS.iter().any(|header| header.eq_ignore_ascii_case(name)) {
+        REDACTED.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns the URI to log, with the values of its query parameters (e.g. the
+/// email addresses looked up) replaced. Parameter names are kept
+pub fn redact_query(uri: &str) -> String {
+    let Some((path, query)) = uri.split_once('?') else {
+        return uri.to_string();
+    };
+
+    let params = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((name, _)) => format!("{}={}", name, REDACTED),
+            None => param.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}?{}", path, params.join("&"))
+}
+
+/// Recursively replaces sensitive fields' values in a JSON document
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SENSITIVE_FIELDS.contains(&key.as_str()) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Returns a loggable version of a body: redacted JSON, or its size when the
+/// body isn't JSON (raw transactions, blocks...)
+pub fn redact_body(body: &[u8]) -> String {
+    if body.is_empty() {
+        return String::new();
+    }
+
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            json.to_string()
+        }
+        Err(_) => format!("<{} bytes>", body.len()),
+    }
+}
+
+/// Logs an outgoing request, returns the request line to reuse when logging
+/// the response
+pub(crate) fn log_request(request: &ProtonRequest) -> String {
+    let request_line = format!("{} {}", request.method(), redact_query(&request.uri().to_string()));
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            format!("{}: {}", name, redact_header(name.as_str(), value))
+        })
+        .collect::<Vec<_>>();
+
+    tracing::debug!(
+        target: "andromeda_api::request",
+        request = %request_line,
+        headers = ?headers,
+        body = %redact_body(request.body()),
+        "sending api request"
+    );
+
+    request_line
+}
+
+pub(crate) fn log_response(request_line: &str, response: &ProtonResponse, elapsed_ms: u128) {
+    let status = response.status().as_u16();
+    if response.status().is_client_error() || response.status().is_server_error() {
+        tracing::warn!(
+            target: "andromeda_api::request",
+            request = %request_line,
+            status,
+            elapsed_ms,
+            body = %redact_body(response.body()),
+            "api request failed"
+        );
+    } else {
+        tracing::debug!(
+            target: "andromeda_api::request",
+            request = %request_line,
+            status,
+            elapsed_ms,
+            body = %redact_body(response.body()),
+            "api request succeeded"
+        );
+    }
+}
+
+pub(crate) fn log_transport_error(request_line: &str, error: &impl std::fmt::Display, elapsed_ms: u128) {
+    tracing::warn!(
+        target: "andromeda_api::request",
+        request = %request_line,
+        elapsed_ms,
+        error = %error,
+        "api request could not be sent"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{log_request, redact_body, redact_header, redact_json, redact_query, REDACTED};
+    use crate::{core::ToProtonRequest, wallet::CreateWalletTransactionRequestBody};
+
+    #[test]
+    fn test_redact_header() {
+        assert_eq!(redact_header("Authorization", "Bearer token"), REDACTED);
+        assert_eq!(redact_header("x-pm-uid", "uid"), REDACTED);
+        assert_eq!(redact_header("Content-Type", "application/json"), "application/json");
+    }
+
+    #[test]
+    fn test_redact_query() {
+        assert_eq!(redact_query("/wallet/v1/network"), "/wallet/v1/network");
+        assert_eq!(
+            redact_query("/wallet/v1/emails/lookup?Email=alice%40proton.me&Page=1&flag"),
+            format!("/wallet/v1/emails/lookup?Email={}&Page={}&flag", REDACTED, REDACTED)
+        );
+    }
+
+    #[test]
+    fn test_log_request_redacts_email_query() {
+        let request = "/wallet/v1/emails/lookup"
+            .to_string()
+            .to_get_request()
+            .query(("Email", "alice@proton.me"));
+
+        let request_line = log_request(&request);
+        assert!(!request_line.contains("alice"), "{}", request_line);
+        assert!(request_line.ends_with(&format!("/wallet/v1/emails/lookup?Email={}", REDACTED)));
+    }
+
+    #[test]
+    fn test_redact_json_nested() {
+        let mut body = json!({
+            "Code": 1000,
+            "Wallets": [{
+                "Wallet": { "ID": "wallet_id", "Name": "encrypted_name", "Mnemonic": "encrypted_mnemonic" },
+                "WalletKey": { "WalletKey": "armored_key" },
+            }],
+            "Label": null,
+        });
+        redact_json(&mut body);
+
+        assert_eq!(body["Code"], 1000);
+        assert_eq!(body["Wallets"][0]["Wallet"]["ID"], "wallet_id");
+        assert_eq!(body["Wallets"][0]["Wallet"]["Name"], REDACTED);
+        assert_eq!(body["Wallets"][0]["Wallet"]["Mnemonic"], REDACTED);
+        assert_eq!(body["Wallets"][0]["WalletKey"], REDACTED);
+        // null values are kept to ease debugging
+        assert!(body["Label"].is_null());
+    }
+
+    #[test]
+    fn test_redact_body() {
+        assert_eq!(redact_body(b""), "");
+        assert_eq!(redact_body(&[0x01, 0x00, 0x00]), "<3 bytes>");
+        assert_eq!(
+            redact_body(br#"{"Code":1000,"Token":"secret"}"#),
+            format!(r#"{{"Code":1000,"Token":"{}"}}"#, REDACTED)
+        );
+    }
+
+    #[test]
+    fn test_redact_wallet_transaction_body() {
+        let body = CreateWalletTransactionRequestBody {
+            TransactionID: "encrypted_txid".to_string(),
+            HashedTransactionID: "hashed_txid".to_string(),
+            Label: Some("encrypted_label".to_string()),
+            ExchangeRateID: None,
+            TransactionTime: None,
+        };
+        let logged = redact_body(&serde_json::to_vec(&body).unwrap());
+
+        let json = serde_json::from_str::<serde_json::Value>(&logged).unwrap();
+        for (field, value) in json.as_object().unwrap() {
+            assert!(*value == REDACTED || value.is_null(), "{} was logged in clear", field);
+        }
+        assert!(!logged.contains("txid") && !logged.contains("label"));
+    }
+
+    #[test]
+    fn test_redact_wallet_transaction_sender_and_recipients() {
+        let mut body = json!({
+            "WalletTransaction": {
+                "TransactionID": "encrypted_txid",
+                "Subject": "encrypted_subject",
+                "Body": "encrypted_body",
+                "ToList": "encrypted_recipients",
+                "Sender": "-----BEGIN PGP MESSAGE-----",
+            }
+        });
+        redact_json(&mut body);
+
+        for field in ["TransactionID", "Subject", "Body", "ToList", "Sender"] {
+            assert_eq!(body["WalletTransaction"][field], REDACTED);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::Mutex,
+    time::Duration,
+};
+
+use muon::{Error as MuonError, ProtonRequest, ProtonResponse};
+
+use crate::error::ResponseError;
+
+/// Counter of sent requests, labelled with `endpoint`, `method` and `status`
+#[cfg(feature = "metrics")]
+pub const REQUESTS_TOTAL: &str = "andromeda_api_requests_total";
+/// Histogram of requests latencies in seconds, labelled with `endpoint` and
+/// `method`
+#[cfg(feature = "metrics")]
+pub const REQUEST_DURATION_SECONDS: &str = "andromeda_api_request_duration_seconds";
+
+/// Number of path segments identifying an endpoint family, e.g.
+/// `wallet/v1/wallets` for `/wallet/v1/wallets/{id}/accounts`
+const ENDPOINT_FAMILY_SEGMENTS: usize = 3;
+
+/// Measurement of a single api request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetric {
+    /// Endpoint family the request belongs to, see [`endpoint_family`]
+    pub endpoint_family: String,
+    pub method: String,
+    /// HTTP status, `None` when no response was received
+    pub status: Option<u16>,
+    /// Proton error code returned along with an error status
+    pub error_code: Option<u16>,
+    pub latency: Duration,
+}
+
+/// Destination of request metrics. Host apps implement it to forward
+/// measurements to their own telemetry pipeline.
+pub trait MetricsSink: Debug + Send + Sync {
+    fn record(&self, metric: RequestMetric);
+}
+
+/// Returns the family of an endpoint path: its first path segments, without
+/// the configured url prefix, the `api` mount, the query string nor resource
+/// identifiers.
+pub fn endpoint_family(path: &str, url_prefix: Option<&str>) -> String {
+    let path = path.split('?').next().unwrap_or_default().trim_start_matches('/');
+
+    let url_prefix = url_prefix.unwrap_or_default().trim_matches('/');
+    let path = path
+        .strip_prefix(url_prefix)
+        .filter(|rest| !url_prefix.is_empty() && (rest.is_empty() || rest.starts_with('/')))
+        .unwrap_or(path)
+        .trim_start_matches('/');
+    let path = path.strip_prefix("api/").unwrap_or(path);
+
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .take(ENDPOINT_FAMILY_SEGMENTS)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Endpoint information captured before the request is consumed by the
+/// session
+pub(crate) struct PendingMetric {
+    endpoint_family: String,
+    method: String,
+}
+
+impl PendingMetric {
+    pub(crate) fn from_request(request: &ProtonRequest, url_prefix: Option<&str>) -> Self {
+        Self {
+            endpoint_family: endpoint_family(request.uri().path(), url_prefix),
+            method: request.method().to_string(),
+        }
+    }
+
+    pub(crate) fn finish(self, result: &Result<ProtonResponse, MuonError>, latency: Duration) -> RequestMetric {
+        let (status, error_code) = match result {
+            Ok(response) if response.status().is_client_error() || response.status().is_server_error() => {
+                let error_code = response.body_json::<ResponseError>().ok().map(|error| error.Code);
+                (Some(response.status().as_u16()), error_code)
+            }
+            Ok(response) => (Some(response.status().as_u16()), None),
+            Err(_) => (None, None),
+        };
+
+        RequestMetric {
+            endpoint_family: self.endpoint_family,
+            method: self.method,
+            status,
+            error_code,
+            latency,
+        }
+    }
+}
+
+/// Forwards the measurement to the recorder installed for the `metrics`
+/// facade, if any
+#[cfg(feature = "metrics")]
+pub(crate) fn record_to_facade(metric: &RequestMetric) {
+    let status = metric.status.map_or("none".to_string(), |status| status.to_string());
+
+    ::metrics::counter!(
+        REQUESTS_TOTAL,
+        "endpoint" => metric.endpoint_family.clone(),
+        "method" => metric.method.clone(),
+        "status" => status
+    )
+    .increment(1);
+    ::metrics::histogram!(
+        REQUEST_DURATION_SECONDS,
+        "endpoint" => metric.endpoint_family.clone(),
+        "method" => metric.method.clone()
+    )
+    .record(metric.latency.as_secs_f64());
+}
+
+/// Aggregated metrics of an endpoint family
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointMetrics {
+    pub request_count: u64,
+    /// Requests that got an error status or no response at all
+    pub error_count: u64,
+    pub total_latency: Duration,
+    pub max_latency: Duration,
+    /// Number of occurrences of each Proton error code
+    pub error_codes: BTreeMap<u16, u64>,
+}
+
+impl EndpointMetrics {
+    pub fn average_latency(&self) -> Duration {
+        if self.request_count == 0 {
+            return Duration::ZERO;
+        }
+
+        self.total_latency / self.request_count as u32
+    }
+}
+
+/// [`MetricsSink`] keeping aggregated metrics in memory, to be periodically
+/// drained by the host app
+#[derive(Debug, Default)]
+pub struct InMemoryMetricsSink {
+    metrics: Mutex<HashMap<String, EndpointMetrics>>,
+}
+
+impl InMemoryMetricsSink {
+    pub fn snapshot(&self) -> HashMap<String, EndpointMetrics> {
+        self.metrics.lock().unwrap().clone()
+    }
+
+    /// Returns the aggregated metrics and resets them
+    pub fn drain(&self) -> HashMap<String, EndpointMetrics> {
+        std::mem::take(&mut *self.metrics.lock().unwrap())
+    }
+}
+
+impl MetricsSink for InMemoryMetricsSink {
+    fn record(&self, metric: RequestMetric) {
+        let mut metrics = self.metrics.lock().unwrap();
+        let endpoint = metrics.entry(metric.endpoint_family).or_default();
+
+        endpoint.request_count += 1;
+        endpoint.total_latency += metric.latency;
+        endpoint.max_latency = endpoint.max_latency.max(metric.latency);
+
+        let is_error = metric.status.map_or(true, |status| status >= 400);
+        if is_error {
+            endpoint.error_count += 1;
+        }
+        if let Some(code) = metric.error_code {
+            *endpoint.error_codes.entry(code).or_default() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{endpoint_family, InMemoryMetricsSink, MetricsSink, RequestMetric};
+    use crate::{
+        core::ApiClient,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        wallet::WalletClient,
+        wallet_ext::WalletClientExt,
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    #[test]
+    fn test_endpoint_family() {
+        assert_eq!(endpoint_family("/wallet/v1/wallets", None), "wallet/v1/wallets");
+        assert_eq!(
+            endpoint_family("/wallet/v1/wallets/abc==/accounts/def==/transactions", None),
+            "wallet/v1/wallets"
+        );
+        assert_eq!(endpoint_family("/core/v4/keys/all?Email=a@b.c", None), "core/v4/keys");
+    }
+
+    #[test]
+    fn test_endpoint_family_strips_url_prefix() {
+        assert_eq!(
+            endpoint_family("prefix/wallet/v1/wallets/abc==/accounts", Some("prefix")),
+            "wallet/v1/wallets"
+        );
+        assert_eq!(
+            endpoint_family("/prefix/core/v4/keys", Some("/prefix/")),
+            "core/v4/keys"
+        );
+        assert_eq!(
+            endpoint_family("/api/wallet/v1/network", Some("api")),
+            "wallet/v1/network"
+        );
+        // The `api` mount is stripped even without a configured prefix
+        assert_eq!(endpoint_family("/api/wallet/v1/network", None), "wallet/v1/network");
+        // A path segment merely starting like the prefix is kept
+        assert_eq!(endpoint_family("/prefixes/v1/all", Some("prefix")), "prefixes/v1/all");
+    }
+
+    #[test]
+    fn test_in_memory_sink_aggregation() {
+        let sink = InMemoryMetricsSink::default();
+        let metric = |status, error_code, latency_ms| RequestMetric {
+            endpoint_family: "wallet/v1/wallets".to_string(),
+            method: "GET".to_string(),
+            status,
+            error_code,
+            latency: Duration::from_millis(latency_ms),
+        };
+
+        sink.record(metric(Some(200), None, 100));
+        sink.record(metric(Some(422), Some(2001), 300));
+        sink.record(metric(None, None, 200));
+
+        let snapshot = sink.drain();
+        let wallets = snapshot.get("wallet/v1/wallets").unwrap();
+        assert_eq!(wallets.request_count, 3);
+        assert_eq!(wallets.error_count, 2);
+        assert_eq!(wallets.average_latency(), Duration::from_millis(200));
+        assert_eq!(wallets.max_latency, Duration::from_millis(300));
+        assert_eq!(wallets.error_codes.get(&2001), Some(&1));
+
+        assert!(sink.snapshot().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_recorded_on_send() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/wallets", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "Code": 2011,
+            "Details": {},
+            "Error": "Wallet does not exist"
+        }));
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let sink = Arc::new(InMemoryMetricsSink::default());
+        let config = ApiConfig {
+            metrics_sink: Some(sink.clone()),
+            ..test_config(mock_server.uri())
+        };
+        let api_client = Arc::new(setup_test_connection_with_config(config));
+        let client = WalletClient::new(api_client);
+        assert!(client.get_wallets().await.is_err());
+
+        let snapshot = sink.snapshot();
+        let wallets = snapshot.get("wallet/v1/wallets").unwrap();
+        assert_eq!(wallets.request_count, 1);
+        assert_eq!(wallets.error_count, 1);
+        assert_eq!(wallets.error_codes.get(&2011), Some(&1));
+    }
+}
@@ -1,9 +1,32 @@
+mod circuit_breaker;
 mod client;
+mod compression;
+pub mod deprecation;
+mod keep_alive;
+pub mod logging;
+pub mod metrics;
+mod offline_cache;
+mod priority;
 mod proton_response_ext;
+mod rate_limit;
 mod request;
+mod response_body;
+pub mod validation;
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
 pub use client::ApiClient;
+pub use compression::RequestCompression;
+pub use keep_alive::KeepAliveTimer;
+pub(crate) use offline_cache::{cache_key, is_unreachable};
+pub use offline_cache::{CachedResponse, InMemoryResponseCache, MaybeStale, ResponseCache};
+pub use priority::{RequestPriority, RequestPriorityExt};
+pub(crate) use proton_response_ext::header_value;
+pub(crate) use proton_response_ext::ApiResponse;
 pub use proton_response_ext::ProtonResponseExt;
-pub use request::ToProtonRequest;
+pub use rate_limit::{RateLimitConfig, RateLimiter};
+pub use request::{MultipartForm, MultipartRequestExt, ToProtonRequest};
+pub use response_body::{ResponseBody, SUCCESS_CODES};
 
+mod secure_auth_store;
 mod wallet_auth_store;
+pub use secure_auth_store::{SecureAuthStore, SecureStorage, StoredAuth};
 pub use wallet_auth_store::WalletAuthStore;
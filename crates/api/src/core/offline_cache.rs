@@ -0,0 +1,217 @@
+use std::{collections::HashMap, fmt::Debug, sync::Mutex};
+
+use muon::ProtonRequest;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ApiErrorKind, Error};
+
+/// Last successful response of a read endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// JSON body of the response
+    pub body: Vec<u8>,
+    /// Unix timestamp, in seconds, of when the response was received
+    pub fetched_at: u64,
+}
+
+/// Storage of the last successful responses of the read endpoints (wallets,
+/// accounts, settings, exchange rates, discover content), served while the
+/// API is unreachable. Apps implement it on top of their own storage so that
+/// they can open offline across restarts.
+///
+/// # Notes
+///
+/// Keys don't include the user: the client clears the cache whenever a user
+/// logs in, and apps must call
+/// [`ProtonWalletApiClient::clear_response_cache`](crate::ProtonWalletApiClient::clear_response_cache)
+/// on logout, so that a user is never served another user's responses.
+pub trait ResponseCache: Debug + Send + Sync {
+    fn read(&self, key: &str) -> Option<CachedResponse>;
+
+    fn write(&self, key: &str, response: CachedResponse);
+
+    /// Removes all the cached responses
+    fn clear(&self);
+}
+
+/// [`ResponseCache`] keeping the responses in memory, for the lifetime of the
+/// client
+#[derive(Debug, Default)]
+pub struct InMemoryResponseCache(Mutex<HashMap<String, CachedResponse>>);
+
+impl ResponseCache for InMemoryResponseCache {
+    fn read(&self, key: &str) -> Option<CachedResponse> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    fn write(&self, key: &str, response: CachedResponse) {
+        self.0.lock().unwrap().insert(key.to_string(), response);
+    }
+
+    fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// Data returned by a read endpoint, flagged as stale when it was served from
+/// the [`ResponseCache`] because the API couldn't be reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaybeStale<T> {
+    pub data: T,
+    pub is_stale: bool,
+    /// Unix timestamp, in seconds, of when the data was fetched from the API
+    pub fetched_at: u64,
+}
+
+impl<T> MaybeStale<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> MaybeStale<U> {
+        MaybeStale {
+            data: f(self.data),
+            is_stale: self.is_stale,
+            fetched_at: self.fetched_at,
+        }
+    }
+}
+
+/// Key of the cached response of a request: its method and full uri,
+/// including the query string
+pub(crate) fn cache_key(request: &ProtonRequest) -> String {
+    format!("{} {}", request.method(), request.uri())
+}
+
+/// Returns whether the error means that the API couldn't serve the request,
+/// as opposed to refusing it, in which case the cached response is served
+pub(crate) fn is_unreachable(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ApiErrorKind::Network | ApiErrorKind::ServiceUnavailable | ApiErrorKind::ServerError
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::InMemoryResponseCache;
+    use crate::{
+        core::ApiClient,
+        error::Error,
+        settings::SettingsClient,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    fn settings_body() -> serde_json::Value {
+        serde_json::json!({
+            "Code": 1000,
+            "WalletUserSettings": {
+                "BitcoinUnit": "BTC",
+                "FiatCurrency": "CHF",
+                "HideEmptyUsedAddresses": 1,
+                "TwoFactorAmountThreshold": 1000,
+                "ReceiveInviterNotification": 1,
+                "ReceiveEmailIntegrationNotification": 1,
+                "ReceiveTransactionNotification": 1,
+                "WalletCreated": 1
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_serve_cached_response_when_unreachable() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/settings", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(settings_body()))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            response_cache: Some(Arc::new(InMemoryResponseCache::default())),
+            ..test_config(mock_server.uri())
+        };
+        let client = SettingsClient::new(Arc::new(setup_test_connection_with_config(config)));
+
+        let fresh = client.get_user_settings_or_cached().await.unwrap();
+        assert!(!fresh.is_stale);
+
+        let stale = client.get_user_settings_or_cached().await.unwrap();
+        assert!(stale.is_stale);
+        assert_eq!(stale.fetched_at, fresh.fetched_at);
+        assert_eq!(stale.data.HideEmptyUsedAddresses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_refused_request_not_served_from_cache() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/settings", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(settings_body()))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "Code": 2011,
+                "Error": "Permission denied",
+                "Details": {}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            response_cache: Some(Arc::new(InMemoryResponseCache::default())),
+            ..test_config(mock_server.uri())
+        };
+        let client = SettingsClient::new(Arc::new(setup_test_connection_with_config(config)));
+
+        client.get_user_settings_or_cached().await.unwrap();
+        assert!(matches!(
+            client.get_user_settings_or_cached().await,
+            Err(Error::ErrorCode(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cleared_cache_not_served() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/settings", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(settings_body()))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            response_cache: Some(Arc::new(InMemoryResponseCache::default())),
+            ..test_config(mock_server.uri())
+        };
+        let api_client = Arc::new(setup_test_connection_with_config(config));
+        let client = SettingsClient::new(api_client.clone());
+
+        client.get_user_settings_or_cached().await.unwrap();
+        api_client.clear_response_cache();
+
+        assert!(client.get_user_settings_or_cached().await.is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use muon::{common::ServiceType, ProtonRequest};
+
+use crate::{DEFAULT_INTERACTIVITY, DEFAULT_SERVICE_TYPE};
+
+/// Priority of a request on constrained connections.
+///
+/// By default GET requests are sent as [`RequestPriority::Background`] and
+/// mutating requests as [`RequestPriority::Interactive`]. Requests that don't
+/// fit that rule (user-initiated lookups, bulk sync payloads sent with POST...)
+/// can override it with [`RequestPriorityExt::with_priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestPriority {
+    /// User-initiated action, the user is waiting for the response
+    Interactive,
+    /// Sync or polling request, can be delayed in favour of interactive ones
+    Background,
+}
+
+impl RequestPriority {
+    pub fn service_type(&self) -> ServiceType {
+        match self {
+            RequestPriority::Interactive => DEFAULT_INTERACTIVITY,
+            RequestPriority::Background => DEFAULT_SERVICE_TYPE,
+        }
+    }
+}
+
+pub trait RequestPriorityExt {
+    /// Overrides the service type set by the request builder
+    fn with_priority(self, priority: RequestPriority) -> ProtonRequest;
+}
+
+impl RequestPriorityExt for ProtonRequest {
+    fn with_priority(self, priority: RequestPriority) -> ProtonRequest {
+        self.service_type(priority.service_type(), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use muon::common::ServiceType;
+
+    use super::RequestPriority;
+
+    #[test]
+    fn test_priority_service_type() {
+        assert!(matches!(
+            RequestPriority::Interactive.service_type(),
+            ServiceType::Interactive
+        ));
+        assert!(matches!(
+            RequestPriority::Background.service_type(),
+            ServiceType::Normal
+        ));
+    }
+}
@@ -1,12 +1,67 @@
+use std::ops::Deref;
+
 use muon::{Error as MuonError, ProtonResponse};
 use serde::de::DeserializeOwned;
 
+use super::{
+    response_body::{ResponseBody, SUCCESS_CODES},
+    validation::parse_strict,
+};
 use crate::error::{Error, ResponseError};
 
 pub trait ProtonResponseExt {
     fn parse_response<T>(&self) -> Result<T, Error>
     where
         T: DeserializeOwned + std::fmt::Debug;
+
+    /// Parses the response body and checks its response code, returning the
+    /// data of the body. A body with an unsuccessful code is returned as an
+    /// [`Error::ErrorCode`], even if the HTTP status is successful.
+    fn parse_body<T>(&self) -> Result<T::Data, Error>
+    where
+        T: ResponseBody;
+}
+
+/// Response of a request sent through the
+/// [`ProtonWalletApiClient`](crate::ProtonWalletApiClient), parsed according
+/// to the client's config. It dereferences to the muon response.
+#[derive(Debug)]
+pub(crate) struct ApiResponse {
+    response: ProtonResponse,
+    strict_validation: bool,
+}
+
+impl ApiResponse {
+    pub(crate) fn new(response: ProtonResponse, strict_validation: bool) -> Self {
+        Self {
+            response,
+            strict_validation,
+        }
+    }
+}
+
+impl Deref for ApiResponse {
+    type Target = ProtonResponse;
+
+    fn deref(&self) -> &ProtonResponse {
+        &self.response
+    }
+}
+
+impl ProtonResponseExt for ApiResponse {
+    fn parse_response<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned + std::fmt::Debug,
+    {
+        parse_response(&self.response, self.strict_validation)
+    }
+
+    fn parse_body<T>(&self) -> Result<T::Data, Error>
+    where
+        T: ResponseBody,
+    {
+        parse_body::<T>(&self.response, self.strict_validation)
+    }
 }
 
 impl ProtonResponseExt for ProtonResponse {
@@ -14,40 +69,197 @@ impl ProtonResponseExt for ProtonResponse {
     where
         T: DeserializeOwned + std::fmt::Debug,
     {
-        let response_status = self.status();
+        parse_response(self, false)
+    }
+
+    fn parse_body<T>(&self) -> Result<T::Data, Error>
+    where
+        T: ResponseBody,
+    {
+        parse_body::<T>(self, false)
+    }
+}
+
+fn parse_response<T>(response: &ProtonResponse, strict_validation: bool) -> Result<T, Error>
+where
+    T: DeserializeOwned + std::fmt::Debug,
+{
+    let response_status = response.status();
+
+    let handle_error = |response_parse_error: Option<MuonError>| -> Result<T, Error> {
+        // Attempt to parse the response into the error type.
+        if let Ok(parsed_error_payload) = response.body_json::<ResponseError>() {
+            return Err(Error::from_response_error(response_status, parsed_error_payload));
+        }
+
+        match response_parse_error {
+            Some(parsing_error) => {
+                // If parsing the known error type fails, check if the body can be read as a
+                // string.
+                let body = response.body().to_vec();
+
+                // We either return details about the parsing error with the body as string
+                let error_details = match String::from_utf8(body) {
+                    Ok(text) => format!("Failed to parse response: Error: {}, Body: {}", parsing_error, text),
+                    // Or just the parsing error
+                    Err(_) => parsing_error.to_string(),
+                };
 
-        let handle_error = |response_parse_error: Option<MuonError>| -> Result<T, Error> {
-            // Attempt to parse the response into the error type.
-            if let Ok(parsed_error_payload) = self.body_json::<ResponseError>() {
-                return Err(Error::ErrorCode(response_status, parsed_error_payload));
+                Err(Error::Deserialize(error_details))
             }
+            None => Err(Error::ErrorCode(response_status, ResponseError::default())),
+        }
+    };
+
+    if response_status.is_client_error() || response_status.is_server_error() {
+        return handle_error(None);
+    }
+
+    if strict_validation {
+        return parse_response_strict(response);
+    }
+
+    match response.body_json::<T>() {
+        Ok(res) => Ok(res),
+        Err(response_parse_error) => handle_error(Some(response_parse_error)),
+    }
+}
+
+fn parse_body<T>(response: &ProtonResponse, strict_validation: bool) -> Result<T::Data, Error>
+where
+    T: ResponseBody,
+{
+    let body = parse_response::<T>(response, strict_validation)?;
+
+    let code = body.code();
+    if !SUCCESS_CODES.contains(&code) {
+        return Err(Error::from_response_error(
+            response.status(),
+            ResponseError {
+                Code: code,
+                Details: serde_json::Value::Null,
+                Error: format!("Unexpected response code: {}", code),
+            },
+        ));
+    }
+
+    Ok(body.into_data())
+}
+
+/// Returns the value of a response header, looked up case-insensitively
+pub(crate) fn header_value(response: &ProtonResponse, name: &str) -> Option<String> {
+    response
+        .headers()
+        .iter()
+        .find(|(header, _)| header.as_str().eq_ignore_ascii_case(name))
+        .and_then(|(_, value)| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+/// Parses a successful response in strict mode, see
+/// [`ApiConfig::strict_response_validation`](crate::ApiConfig::strict_response_validation)
+fn parse_response_strict<T>(response: &ProtonResponse) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    parse_strict(response.body()).map_err(|error| {
+        // The body might still be a known error payload
+        match serde_json::from_slice::<ResponseError>(response.body()) {
+            Ok(response_error) => Error::from_response_error(response.status(), response_error),
+            Err(_) => error,
+        }
+    })
+}
 
-            match response_parse_error {
-                Some(parsing_error) => {
-                    // If parsing the known error type fails, check if the body can be read as a
-                    // string.
-                    let body = self.body().to_vec();
-
-                    // We either return details about the parsing error with the body as string
-                    let error_details = match String::from_utf8(body) {
-                        Ok(text) => format!("Failed to parse response: Error: {}, Body: {}", parsing_error, text),
-                        // Or just the parsing error
-                        Err(_) => parsing_error.to_string(),
-                    };
-
-                    Err(Error::Deserialize(error_details))
-                }
-                None => Err(Error::ErrorCode(response_status, ResponseError::default())),
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{ApiResponse, ProtonResponseExt};
+    use crate::{
+        core::{ResponseBody, ToProtonRequest},
+        error::Error,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    #[derive(Debug, Deserialize, ResponseBody)]
+    #[allow(non_snake_case)]
+    struct TestResponseBody {
+        Code: u16,
+        #[response_body(data)]
+        Items: Vec<u32>,
+    }
+
+    async fn mock_response(status: u16, body: serde_json::Value) -> (MockServer, ApiResponse) {
+        mock_response_with_config(status, body, test_config).await
+    }
+
+    async fn mock_response_with_config(
+        status: u16,
+        body: serde_json::Value,
+        config: impl FnOnce(String) -> ApiConfig,
+    ) -> (MockServer, ApiResponse) {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/items", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_with_config(config(mock_server.uri()));
+        let response = api_client
+            .send(format!("/{}", req_path).to_get_request())
+            .await
+            .unwrap();
+
+        (mock_server, response)
+    }
+
+    #[tokio::test]
+    async fn test_parse_body() {
+        let (_server, response) = mock_response(200, serde_json::json!({ "Code": 1000, "Items": [1, 2, 3] })).await;
+
+        let items = response.parse_body::<TestResponseBody>().unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_body_unexpected_code() {
+        let (_server, response) = mock_response(200, serde_json::json!({ "Code": 2001, "Items": [] })).await;
+
+        match response.parse_body::<TestResponseBody>() {
+            Err(Error::ErrorCode(status, error)) => {
+                assert_eq!(status.as_u16(), 200);
+                assert_eq!(error.Code, 2001);
             }
-        };
+            other => panic!("Expected ErrorCode, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_response_validation_is_per_client() {
+        let body = serde_json::json!({ "Code": 1000, "Items": [1, "2"] });
 
-        if response_status.is_client_error() || response_status.is_server_error() {
-            return handle_error(None);
+        let (_server, response) = mock_response(200, body.clone()).await;
+        match response.parse_body::<TestResponseBody>() {
+            Err(Error::Deserialize(details)) => assert!(details.contains("Body:"), "{}", details),
+            other => panic!("Expected Deserialize, got {:?}", other),
         }
 
-        match self.body_json::<T>() {
-            Ok(res) => Ok(res),
-            Err(response_parse_error) => handle_error(Some(response_parse_error)),
+        let (_server, response) = mock_response_with_config(200, body, |url| ApiConfig {
+            strict_response_validation: true,
+            ..test_config(url)
+        })
+        .await;
+        match response.parse_body::<TestResponseBody>() {
+            Err(Error::Deserialize(details)) => assert!(details.ends_with("at `Items[1]`"), "{}", details),
+            other => panic!("Expected Deserialize, got {:?}", other),
         }
     }
 }
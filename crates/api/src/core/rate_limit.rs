@@ -0,0 +1,153 @@
+use std::{sync::Mutex, time::Duration};
+
+use andromeda_common::utils::now;
+use futures_timer::Delay;
+
+/// Client-side request budget, expressed as a token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    /// Maximum number of requests that can be sent in a burst
+    pub burst: u32,
+    /// Number of requests allowed per second once the burst is consumed
+    pub requests_per_second: f64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Duration,
+}
+
+/// Token-bucket rate limiter shared by every client created from the same
+/// [`crate::ProtonWalletApiClient`], so that background sync loops can't
+/// exhaust the server-side rate limit of interactive requests.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            bucket: Mutex::new(Bucket {
+                tokens: config.burst as f64,
+                last_refill: now(),
+            }),
+        }
+    }
+
+    pub fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+
+    /// Takes a token if one is available, otherwise returns the time to wait
+    /// before the next one
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(now())
+    }
+
+    fn try_acquire_at(&self, at: Duration) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+
+        let elapsed = at.saturating_sub(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.config.requests_per_second).min(self.config.burst as f64);
+        bucket.last_refill = bucket.last_refill.max(at);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+
+        if self.config.requests_per_second <= 0.0 {
+            // Bucket never refills, poll again later rather than waiting forever
+            return Err(Duration::from_secs(1));
+        }
+
+        let missing = 1.0 - bucket.tokens;
+        Err(Duration::from_secs_f64(missing / self.config.requests_per_second))
+    }
+
+    /// Waits until a token is available and takes it
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            Delay::new(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use andromeda_common::utils::now;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{RateLimitConfig, RateLimiter};
+    use crate::{
+        core::ToProtonRequest,
+        tests::utils::{setup_test_connection_with_config, test_config},
+        ApiConfig, BASE_WALLET_API_V1,
+    };
+
+    #[test]
+    fn test_token_bucket() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            burst: 2,
+            requests_per_second: 4.0,
+        });
+        let start = limiter.bucket.lock().unwrap().last_refill;
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert_eq!(limiter.try_acquire_at(start), Err(Duration::from_millis(250)));
+
+        // Half a token refilled
+        assert_eq!(
+            limiter.try_acquire_at(start + Duration::from_millis(125)),
+            Err(Duration::from_millis(125))
+        );
+        assert!(limiter.try_acquire_at(start + Duration::from_millis(250)).is_ok());
+
+        // Refill is capped to the burst size
+        let later = start + Duration::from_secs(10);
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_shared_across_clients() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000, "Network": 0 })))
+            .mount(&mock_server)
+            .await;
+
+        let config = ApiConfig {
+            rate_limit: Some(RateLimitConfig {
+                burst: 1,
+                requests_per_second: 10.0,
+            }),
+            ..test_config(mock_server.uri())
+        };
+        let api_client = setup_test_connection_with_config(config);
+        // Clients share the limiter of the api client they were created from
+        let cloned_client = api_client.clone();
+
+        let url = format!("/{}", req_path);
+        let started_at = now();
+        api_client.send(url.to_get_request()).await.unwrap();
+        cloned_client.send(url.to_get_request()).await.unwrap();
+
+        // Second request had to wait for the bucket to refill (100ms)
+        assert!(now() - started_at >= Duration::from_millis(50));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+}
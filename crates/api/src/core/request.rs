@@ -1,3 +1,4 @@
+use andromeda_common::utils::now;
 use muon::{Method, ProtonRequest};
 
 pub trait ToProtonRequest {
@@ -24,3 +25,192 @@ impl ToProtonRequest for String {
         ProtonRequest::new(Method::DELETE, self)
     }
 }
+
+/// A single field of a `multipart/form-data` body
+#[derive(Debug, Clone)]
+struct MultipartPart {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: Vec<u8>,
+}
+
+/// A `multipart/form-data` body, to be attached to a request with
+/// [`MultipartRequestExt::body_multipart`]
+///
+/// ```rust
+/// use andromeda_api::core::MultipartForm;
+/// let form = MultipartForm::new()
+///     .text("Name", "avatar")
+///     .file("File", "avatar.png", "image/png", vec![0x89, 0x50, 0x4e, 0x47]);
+/// assert!(form.content_type().starts_with("multipart/form-data; boundary="));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultipartForm {
+    boundary: String,
+    parts: Vec<MultipartPart>,
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultipartForm {
+    /// Creates an empty form with a boundary derived from the current time
+    pub fn new() -> Self {
+        Self::with_boundary(format!("----AndromedaFormBoundary{:x}", now().as_nanos()))
+    }
+
+    /// Creates an empty form using the provided boundary. The boundary must
+    /// not appear in any of the parts' data.
+    pub fn with_boundary(boundary: impl ToString) -> Self {
+        Self {
+            boundary: boundary.to_string(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Adds a plain text field to the form
+    pub fn text(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.to_string(),
+            filename: None,
+            content_type: None,
+            data: value.to_string().into_bytes(),
+        });
+        self
+    }
+
+    /// Adds a file field to the form
+    pub fn file(
+        mut self,
+        name: impl ToString,
+        filename: impl ToString,
+        content_type: impl ToString,
+        data: Vec<u8>,
+    ) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.to_string(),
+            filename: Some(filename.to_string()),
+            content_type: Some(content_type.to_string()),
+            data,
+        });
+        self
+    }
+
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Value of the `Content-Type` header to send along with the form
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Encodes the form as described in RFC 7578
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+
+            let mut disposition = format!("Content-Disposition: form-data; name=\"{}\"", escape_quotes(&part.name));
+            if let Some(filename) = &part.filename {
+                disposition.push_str(&format!("; filename=\"{}\"", escape_quotes(filename)));
+            }
+            body.extend_from_slice(format!("{}\r\n", disposition).as_bytes());
+
+            if let Some(content_type) = &part.content_type {
+                body.extend_from_slice(format!("Content-Type: {}\r\n", content_type).as_bytes());
+            }
+
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        body
+    }
+}
+
+fn escape_quotes(value: &str) -> String {
+    value.replace('"', "%22")
+}
+
+pub trait MultipartRequestExt {
+    /// Sets the request body to the encoded form along with the matching
+    /// `Content-Type` header
+    fn body_multipart(self, form: &MultipartForm) -> ProtonRequest;
+}
+
+impl MultipartRequestExt for ProtonRequest {
+    fn body_multipart(self, form: &MultipartForm) -> ProtonRequest {
+        self.header(("Content-Type", form.content_type())).body(form.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_bytes, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{MultipartForm, MultipartRequestExt, ToProtonRequest};
+    use crate::{tests::utils::setup_test_connection, BASE_CORE_API_V4};
+
+    #[test]
+    fn test_multipart_form_encoding() {
+        let form = MultipartForm::with_boundary("boundary").text("Name", "avatar").file(
+            "File",
+            "avatar.png",
+            "image/png",
+            vec![1, 2, 3],
+        );
+
+        assert_eq!(form.content_type(), "multipart/form-data; boundary=boundary");
+
+        let mut expected = b"--boundary\r\n\
+Content-Disposition: form-data; name=\"Name\"\r\n\
+\r\n\
+avatar\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"File\"; filename=\"avatar.png\"\r\n\
+Content-Type: image/png\r\n\
+\r\n"
+            .to_vec();
+        expected.extend_from_slice(&[1, 2, 3]);
+        expected.extend_from_slice(b"\r\n--boundary--\r\n");
+
+        assert_eq!(form.to_bytes(), expected);
+    }
+
+    #[test]
+    fn test_multipart_form_empty() {
+        let form = MultipartForm::with_boundary("boundary");
+        assert_eq!(form.to_bytes(), b"--boundary--\r\n".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_send_multipart_request() {
+        let mock_server = MockServer::start().await;
+        let form = MultipartForm::with_boundary("boundary").file("File", "note.txt", "text/plain", b"hello".to_vec());
+        let req_path: String = format!("{}/attachments", BASE_CORE_API_V4);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(header("content-type", "multipart/form-data; boundary=boundary"))
+            .and(body_bytes(form.to_bytes()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let request = format!("/{}", req_path).to_post_request().body_multipart(&form);
+        let response = api_client.send(request).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+    }
+}
@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+pub use andromeda_macros::ResponseBody;
+use serde::de::DeserializeOwned;
+
+/// Response codes of successful responses: `1000` for single responses and
+/// `1001` for batch ones
+pub const SUCCESS_CODES: [u16; 2] = [1000, 1001];
+
+/// Body of an API response, with its Proton response code and the data the
+/// client returns from it. Derive it with `#[derive(ResponseBody)]` rather
+/// than implementing it by hand, and parse responses with
+/// [`ProtonResponseExt::parse_body`](super::ProtonResponseExt::parse_body).
+pub trait ResponseBody: DeserializeOwned + Debug {
+    type Data;
+
+    fn code(&self) -> u16;
+
+    fn into_data(self) -> Self::Data;
+}
@@ -0,0 +1,181 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use muon::{
+    client::{Auth, Tokens},
+    env::EnvId,
+    store::{Store, StoreFailure},
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::WalletAuthStore;
+
+const AUTH_KEY_BASE: &str = "AUTH";
+
+/// Serialised form of muon's [`Auth`], for the stores persisting the session.
+/// External sessions only have a uid, the tokens being handled by the caller
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoredAuth {
+    pub uid: String,
+    pub access_token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+impl StoredAuth {
+    /// Returns `None` when there is no session to store, e.g. after a logout
+    pub fn from_auth(auth: &Auth) -> Option<Self> {
+        Some(StoredAuth {
+            uid: auth.uid()?.to_string(),
+            access_token: auth.acc_tok().map(|token| token.to_string()),
+            refresh_token: auth.ref_tok().map(|token| token.to_string()),
+            scopes: auth.scopes().map(|scopes| scopes.to_vec()).unwrap_or_default(),
+        })
+    }
+
+    pub fn into_auth(self) -> Auth {
+        match (self.access_token, self.refresh_token) {
+            (Some(access_token), Some(refresh_token)) => {
+                Auth::internal(self.uid, Tokens::access(access_token, refresh_token, self.scopes))
+            }
+            _ => Auth::external(self.uid),
+        }
+    }
+}
+
+/// Storage of secrets protected by the platform, e.g. Android Keystore or iOS
+/// Keychain, implemented by the host app. Errors are the platform's messages
+pub trait SecureStorage: Debug + Send + Sync {
+    fn read(&self, key: String) -> Result<Option<String>, String>;
+    fn write(&self, key: String, value: String) -> Result<(), String>;
+    fn delete(&self, key: String) -> Result<(), String>;
+}
+
+/// Auth store persisting the session in a [`SecureStorage`], so that the
+/// session tokens never sit unencrypted in the app's storage.
+///
+/// # Notes
+///
+/// Persistence is best effort: storage failures are logged and the session
+/// is kept in memory, so that a locked keychain doesn't log the user out.
+#[derive(Debug, Clone)]
+pub struct SecureAuthStore {
+    env: EnvId,
+    storage_key: String,
+    storage: Arc<dyn SecureStorage>,
+    auth: Arc<Mutex<Auth>>,
+}
+
+impl SecureAuthStore {
+    /// Creates a store for the given environment, restoring the session
+    /// persisted under `key` if any
+    pub fn new(env: String, key: String, storage: Arc<dyn SecureStorage>) -> Self {
+        let storage_key = format!("{}_{}", AUTH_KEY_BASE, key);
+        let auth = match storage.read(storage_key.clone()) {
+            Ok(serialized) => serialized
+                .and_then(|serialized| serde_json::from_str::<StoredAuth>(&serialized).ok())
+                .map_or(Auth::None, StoredAuth::into_auth),
+            Err(error) => {
+                warn!("Could not read the persisted session: {error}");
+                Auth::None
+            }
+        };
+
+        Self {
+            env: WalletAuthStore::from_env_str(env, Arc::new(Mutex::new(Auth::None))).env,
+            storage_key,
+            storage,
+            auth: Arc::new(Mutex::new(auth)),
+        }
+    }
+
+    fn persist(&self, auth: &Auth) {
+        let result = match StoredAuth::from_auth(auth) {
+            Some(stored_auth) => serde_json::to_string(&stored_auth)
+                .map_err(|error| error.to_string())
+                .and_then(|serialized| self.storage.write(self.storage_key.clone(), serialized)),
+            // Session was logged out
+            None => self.storage.delete(self.storage_key.clone()),
+        };
+
+        if let Err(error) = result {
+            warn!("Could not persist the session: {error}");
+        }
+    }
+}
+
+impl Store for SecureAuthStore {
+    fn env(&self) -> EnvId {
+        self.env.clone()
+    }
+
+    fn get_auth(&self) -> Auth {
+        self.auth.lock().unwrap().clone()
+    }
+
+    fn set_auth(&mut self, auth: Auth) -> Result<Auth, StoreFailure> {
+        self.persist(&auth);
+        *self.auth.lock().unwrap() = auth.clone();
+
+        Ok(auth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use muon::{
+        client::{Auth, Tokens},
+        store::Store,
+    };
+
+    use super::{SecureAuthStore, SecureStorage, StoredAuth};
+
+    #[derive(Debug, Default)]
+    struct MemorySecureStorage(Mutex<HashMap<String, String>>);
+
+    impl SecureStorage for MemorySecureStorage {
+        fn read(&self, key: String) -> Result<Option<String>, String> {
+            Ok(self.0.lock().unwrap().get(&key).cloned())
+        }
+
+        fn write(&self, key: String, value: String) -> Result<(), String> {
+            self.0.lock().unwrap().insert(key, value);
+            Ok(())
+        }
+
+        fn delete(&self, key: String) -> Result<(), String> {
+            self.0.lock().unwrap().remove(&key);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_restore_persisted_session() {
+        let storage = Arc::new(MemorySecureStorage::default());
+
+        let mut store = SecureAuthStore::new("prod".to_string(), "user".to_string(), storage.clone());
+        store
+            .set_auth(Auth::internal(
+                "uid".to_string(),
+                Tokens::access("access".to_string(), "refresh".to_string(), vec!["wallet".to_string()]),
+            ))
+            .unwrap();
+        assert!(storage.0.lock().unwrap().contains_key("AUTH_user"));
+
+        let mut restored = SecureAuthStore::new("prod".to_string(), "user".to_string(), storage.clone());
+        let auth = StoredAuth::from_auth(&restored.get_auth()).unwrap();
+        assert_eq!(auth.uid, "uid");
+        assert_eq!(auth.access_token, Some("access".to_string()));
+
+        restored.set_auth(Auth::None).unwrap();
+        assert!(storage.0.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+
+/// Deserializes a response body, logging the unknown fields and reporting
+/// the path of the field that failed to deserialize
+pub(crate) fn parse_strict<T>(body: &[u8]) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let (parsed, unknown_fields) = deserialize_tracking_fields::<T>(body);
+
+    for field in unknown_fields {
+        tracing::warn!(
+            target: "andromeda_api::validation",
+            field = %field,
+            response = std::any::type_name::<T>(),
+            "unknown field in response"
+        );
+    }
+
+    parsed.map_err(|error| {
+        Error::Deserialize(format!(
+            "Failed to parse response: Error: {} at `{}`",
+            error.inner(),
+            error.path()
+        ))
+    })
+}
+
+fn deserialize_tracking_fields<T>(
+    body: &[u8],
+) -> (Result<T, serde_path_to_error::Error<serde_json::Error>>, Vec<String>)
+where
+    T: DeserializeOwned,
+{
+    let mut unknown_fields = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let mut track_unknown = |path: serde_ignored::Path| unknown_fields.push(path.to_string());
+
+    let parsed =
+        serde_path_to_error::deserialize(serde_ignored::Deserializer::new(&mut deserializer, &mut track_unknown));
+
+    (parsed, unknown_fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::{deserialize_tracking_fields, parse_strict};
+    use crate::error::Error;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    struct Account {
+        ID: String,
+        Label: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    struct AccountsResponseBody {
+        Code: u16,
+        Accounts: Vec<Account>,
+    }
+
+    #[test]
+    fn test_unknown_fields_are_tracked() {
+        let body = serde_json::json!({
+            "Code": 1000,
+            "Accounts": [{ "ID": "1", "Label": "Primary", "Hidden": 0 }],
+            "Total": 1
+        });
+
+        let (parsed, unknown_fields) = deserialize_tracking_fields::<AccountsResponseBody>(body.to_string().as_bytes());
+        assert!(parsed.is_ok());
+        assert_eq!(unknown_fields, vec!["Accounts.0.Hidden", "Total"]);
+    }
+
+    #[test]
+    fn test_missing_field_path() {
+        let body = serde_json::json!({
+            "Code": 1000,
+            "Accounts": [{ "ID": "1", "Label": "Primary" }, { "ID": "2" }]
+        });
+
+        match parse_strict::<AccountsResponseBody>(body.to_string().as_bytes()) {
+            Err(Error::Deserialize(message)) => {
+                assert!(message.contains("missing field `Label`"));
+                assert!(message.ends_with("at `Accounts[1]`"));
+            }
+            other => panic!("Expected Deserialize error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use andromeda_macros::api_client;
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use futures_timer::Delay;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{ApiClient, ProtonResponseExt},
+    error::Error,
+    proton_users::EmptyResponseBody,
+    BASE_CORE_API_V4,
+};
+
+/// API error code returned when a request from a new device must be verified
+/// before being processed
+pub const DEVICE_VERIFICATION_REQUIRED: u16 = 9002;
+
+/// Highest proof-of-work difficulty solved locally. Each extra bit doubles
+/// the expected work, 24 bits being around 16 million hashes
+pub const MAX_PROOF_OF_WORK_DIFFICULTY: u8 = 24;
+
+/// Number of hashes computed between two yields, so that solving doesn't
+/// hold the executor, or the main thread on web
+const PROOF_OF_WORK_BATCH_SIZE: u64 = 10_000;
+
+/// Challenge to solve to verify the current device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceVerificationChallenge {
+    /// Find a nonce such that `sha256(seed || nonce)` starts with `difficulty`
+    /// zero bits, see [`solve_proof_of_work`]
+    ProofOfWork {
+        token: String,
+        seed: String,
+        difficulty: u8,
+    },
+    /// Enter the code sent to the user, optionally to the given (obfuscated)
+    /// destination
+    Code { token: String, destination: Option<String> },
+}
+
+impl DeviceVerificationChallenge {
+    /// Parses the challenge from the `Details` of a
+    /// [`DEVICE_VERIFICATION_REQUIRED`] error
+    pub fn from_details(details: &serde_json::Value) -> Option<Self> {
+        let details = ApiDeviceVerificationDetails::deserialize(details).ok()?;
+
+        match details.ChallengeType.as_str() {
+            "ProofOfWork" => Some(DeviceVerificationChallenge::ProofOfWork {
+                token: details.ChallengeToken,
+                seed: details.Seed?,
+                difficulty: details.Difficulty?,
+            }),
+            "Code" => Some(DeviceVerificationChallenge::Code {
+                token: details.ChallengeToken,
+                destination: details.Destination,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        match self {
+            DeviceVerificationChallenge::ProofOfWork { token, .. } => token,
+            DeviceVerificationChallenge::Code { token, .. } => token,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct ApiDeviceVerificationDetails {
+    pub ChallengeToken: String,
+    pub ChallengeType: String,
+    pub Seed: Option<String>,
+    pub Difficulty: Option<u8>,
+    pub Destination: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+struct CompleteDeviceVerificationRequestBody {
+    pub ChallengeToken: String,
+    pub Solution: String,
+}
+
+fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+fn is_proof_of_work_solution(seed: &str, nonce: u64, difficulty: u8) -> bool {
+    let mut engine = sha256::Hash::engine();
+    engine.input(seed.as_bytes());
+    engine.input(&nonce.to_be_bytes());
+    let hash = sha256::Hash::from_engine(engine);
+
+    leading_zero_bits(hash.as_byte_array()) >= difficulty as u32
+}
+
+/// Finds the smallest nonce such that `sha256(seed || nonce)`, `nonce` being
+/// big-endian encoded, starts with at least `difficulty` zero bits.
+///
+/// Difficulties above [`MAX_PROOF_OF_WORK_DIFFICULTY`] are rejected, and the
+/// search gives up after 16 times the expected number of hashes, which only
+/// happens by chance with a probability of about 1e-7.
+pub async fn solve_proof_of_work(seed: &str, difficulty: u8) -> Result<u64, Error> {
+    if difficulty > MAX_PROOF_OF_WORK_DIFFICULTY {
+        return Err(Error::DeviceVerificationUnsolvable(difficulty));
+    }
+
+    let max_nonce = 16u64 << difficulty;
+    let mut batch_start = 0;
+    while batch_start < max_nonce {
+        let batch_end = (batch_start + PROOF_OF_WORK_BATCH_SIZE).min(max_nonce);
+        if let Some(nonce) = (batch_start..batch_end).find(|nonce| is_proof_of_work_solution(seed, *nonce, difficulty))
+        {
+            return Ok(nonce);
+        }
+
+        batch_start = batch_end;
+        // A timer rather than a plain yield, so that the browser also gets to
+        // run its event loop
+        Delay::new(Duration::ZERO).await;
+    }
+
+    Err(Error::DeviceVerificationUnsolvable(difficulty))
+}
+
+#[api_client(base = BASE_CORE_API_V4)]
+#[derive(Clone)]
+pub struct DeviceVerificationClient;
+
+impl DeviceVerificationClient {
+    /// Sends the challenge's solution. Proof-of-work challenges are solved
+    /// locally, code challenges need the code received by the user.
+    pub async fn complete(&self, challenge: &DeviceVerificationChallenge, code: Option<String>) -> Result<(), Error> {
+        let solution = match challenge {
+            DeviceVerificationChallenge::ProofOfWork { seed, difficulty, .. } => {
+                solve_proof_of_work(seed, *difficulty).await?.to_string()
+            }
+            DeviceVerificationChallenge::Code { .. } => code.ok_or(Error::DeviceVerificationCodeMissing)?,
+        };
+
+        let payload = CompleteDeviceVerificationRequestBody {
+            ChallengeToken: challenge.token().to_string(),
+            Solution: solution,
+        };
+        let request = self.post("auth/device/verify").body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        response.parse_response::<EmptyResponseBody>()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::{sha256, Hash, HashEngine};
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{
+        leading_zero_bits, solve_proof_of_work, DeviceVerificationChallenge, DeviceVerificationClient,
+        MAX_PROOF_OF_WORK_DIFFICULTY,
+    };
+    use crate::{
+        core::ApiClient, error::Error, tests::utils::setup_test_connection_arc, wallet::WalletClient,
+        wallet_ext::WalletClientExt, BASE_CORE_API_V4, BASE_WALLET_API_V1,
+    };
+
+    #[tokio::test]
+    async fn test_solve_proof_of_work() {
+        let nonce = solve_proof_of_work("seed", 8).await.unwrap();
+
+        let mut engine = sha256::Hash::engine();
+        engine.input(b"seed");
+        engine.input(&nonce.to_be_bytes());
+        let hash = sha256::Hash::from_engine(engine);
+        assert!(leading_zero_bits(hash.as_byte_array()) >= 8);
+    }
+
+    #[tokio::test]
+    async fn test_proof_of_work_difficulty_too_high() {
+        let difficulty = MAX_PROOF_OF_WORK_DIFFICULTY + 1;
+
+        assert!(matches!(
+            solve_proof_of_work("seed", difficulty).await,
+            Err(Error::DeviceVerificationUnsolvable(d)) if d == difficulty
+        ));
+        assert!(matches!(
+            solve_proof_of_work("seed", u8::MAX).await,
+            Err(Error::DeviceVerificationUnsolvable(u8::MAX))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_device_verification_required_error() {
+        let mock_server = MockServer::start().await;
+        let response = ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "Code": 9002,
+            "Error": "Device verification required",
+            "Details": {
+                "ChallengeToken": "challenge_token",
+                "ChallengeType": "Code",
+                "Destination": "a***@proton.me"
+            }
+        }));
+        Mock::given(method("GET"))
+            .and(path(format!("{}/wallets", BASE_WALLET_API_V1)))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let client = WalletClient::new(setup_test_connection_arc(mock_server.uri()));
+
+        match client.get_wallets().await {
+            Err(Error::DeviceVerificationRequired(challenge)) => assert_eq!(
+                challenge,
+                DeviceVerificationChallenge::Code {
+                    token: "challenge_token".to_string(),
+                    destination: Some("a***@proton.me".to_string()),
+                }
+            ),
+            other => panic!("Expected DeviceVerificationRequired, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_code_challenge() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/auth/device/verify", BASE_CORE_API_V4)))
+            .and(body_json(
+                serde_json::json!({ "ChallengeToken": "challenge_token", "Solution": "123456" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = DeviceVerificationClient::new(setup_test_connection_arc(mock_server.uri()));
+        let challenge = DeviceVerificationChallenge::Code {
+            token: "challenge_token".to_string(),
+            destination: None,
+        };
+
+        assert!(matches!(
+            client.complete(&challenge, None).await,
+            Err(Error::DeviceVerificationCodeMissing)
+        ));
+        client.complete(&challenge, Some("123456".to_string())).await.unwrap();
+    }
+}
@@ -1,14 +1,22 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
+use andromeda_common::utils::now;
 use serde::Deserialize;
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, MaybeStale, ProtonResponseExt, ResponseBody},
     error::Error,
     ProtonWalletApiClient, BASE_WALLET_API_V1,
 };
 
-#[derive(Deserialize, Debug, PartialEq)]
+/// Duration during which fetched discover content is reused before querying
+/// the API again
+pub const DEFAULT_DISCOVER_CONTENT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[allow(non_snake_case)]
 pub struct Content {
     #[serde(alias = "title")]
@@ -27,16 +35,23 @@ pub struct Content {
     pub Category: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetDiscoveryContentResponseBody {
-    pub Code: i32,
+    pub Code: u16,
+    #[response_body(data)]
     pub DiscoverContent: Vec<Content>,
 }
 
+/// Last fetched content, along with the locale it was fetched for and the
+/// time it was fetched at
+type CachedContent = (Option<String>, Vec<Content>, Duration);
+
 #[derive(Clone)]
 pub struct DiscoverContentClient {
     api_client: Arc<ProtonWalletApiClient>,
+    cache: Arc<Mutex<Option<CachedContent>>>,
+    cache_ttl: Duration,
 }
 
 impl ApiClient for DiscoverContentClient {
@@ -49,23 +64,77 @@ impl ApiClient for DiscoverContentClient {
     }
 
     fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
+        Self {
+            api_client,
+            cache: Arc::new(Mutex::new(None)),
+            cache_ttl: DEFAULT_DISCOVER_CONTENT_CACHE_TTL,
+        }
     }
 }
 
 impl DiscoverContentClient {
+    /// Overrides the duration during which the fetched content is cached
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
     pub async fn get_discovery_contents(&self) -> Result<Vec<Content>, Error> {
-        let request = self.get("discover-content");
+        self.get_localized_discovery_contents(None).await
+    }
+
+    /// Returns the discover content localized for the given locale (e.g.
+    /// `fr_FR`), falling back to the backend's default when `None`. The
+    /// result is cached for the client's TTL, use
+    /// [`DiscoverContentClient::refresh_discovery_contents`] to bypass the
+    /// cache.
+    pub async fn get_localized_discovery_contents(&self, locale: Option<String>) -> Result<Vec<Content>, Error> {
+        if let Some((cached_locale, contents, fetched_at)) = &*self.cache.lock().unwrap() {
+            if *cached_locale == locale && now().saturating_sub(*fetched_at) < self.cache_ttl {
+                return Ok(contents.clone());
+            }
+        }
+
+        self.refresh_discovery_contents(locale).await
+    }
+
+    /// Fetches the discover content from the API and updates the cache
+    pub async fn refresh_discovery_contents(&self, locale: Option<String>) -> Result<Vec<Content>, Error> {
+        let mut request = self.get("discover-content");
+        if let Some(locale) = locale.clone() {
+            request = request.query(("Locale", locale));
+        }
+
         let response = self.api_client.send(request).await?;
         let parsed = response.parse_response::<GetDiscoveryContentResponseBody>()?;
+
+        *self.cache.lock().unwrap() = Some((locale, parsed.DiscoverContent.clone(), now()));
+
         Ok(parsed.DiscoverContent)
     }
+
+    /// Fetches the discover content from the API, serving the last fetched
+    /// content when the API is unreachable, see
+    /// [`ApiConfig::response_cache`](crate::ApiConfig::response_cache)
+    pub async fn get_discovery_contents_or_cached(
+        &self,
+        locale: Option<String>,
+    ) -> Result<MaybeStale<Vec<Content>>, Error> {
+        let mut request = self.get("discover-content");
+        if let Some(locale) = locale {
+            request = request.query(("Locale", locale));
+        }
+
+        self.api_client
+            .send_or_cached::<GetDiscoveryContentResponseBody>(request)
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use wiremock::{
-        matchers::{method, path},
+        matchers::{method, path, query_param},
         Mock, MockServer, ResponseTemplate,
     };
 
@@ -140,4 +209,54 @@ mod tests {
             ]
         )
     }
+
+    #[tokio::test]
+    async fn test_get_localized_discovery_content_cached() {
+        let mock_server = MockServer::start().await;
+        let json_body = serde_json::json!(
+        {
+            "Code": 1000,
+            "DiscoverContent": [
+                {
+                    "Title": "Qu'est-ce que le Bitcoin ?",
+                    "Link": "https://proton.me/fr/blog/what-is-bitcoin",
+                    "Description": "Le Bitcoin est un réseau de paiement pair-à-pair.",
+                    "PubDate": 1721701601,
+                    "Author": "Proton Team",
+                    "Category": "Bitcoin basics"
+                },
+            ]
+        });
+
+        let req_path: String = format!("{}/discover-content", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_body_json(json_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .and(query_param("Locale", "fr_FR"))
+            .respond_with(response)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = DiscoverContentClient::new(api_client);
+
+        let first = client
+            .get_localized_discovery_contents(Some("fr_FR".to_string()))
+            .await
+            .unwrap();
+        let second = client
+            .get_localized_discovery_contents(Some("fr_FR".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first, second);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+        client
+            .refresh_discovery_contents(Some("fr_FR".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
 }
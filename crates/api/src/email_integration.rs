@@ -1,11 +1,10 @@
-use std::sync::Arc;
-
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Debug, Deserialize, Clone)]
@@ -21,48 +20,43 @@ pub struct CreateBitcoinAddressRequestBody {
     pub Email: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct LookupBitcoinAddressResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletBitcoinAddress: ApiWalletBitcoinAddressLookup,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct CreateBitcoinAddressRequestResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
 }
 
-#[derive(Clone)]
-pub struct EmailIntegrationClient {
-    api_client: Arc<ProtonWalletApiClient>,
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+pub struct SendBitcoinViaEmailReminderRequestBody {
+    pub Email: String,
+    pub InviterAddressID: String,
 }
 
-impl ApiClient for EmailIntegrationClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
+#[derive(Debug, Deserialize, ResponseBody)]
+#[allow(non_snake_case)]
+struct BitcoinViaEmailReminderResponseBody {
+    pub Code: u16,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
+#[derive(Clone)]
+pub struct EmailIntegrationClient;
+
 impl EmailIntegrationClient {
     pub async fn lookup_bitcoin_address(&self, email: String) -> Result<ApiWalletBitcoinAddressLookup, Error> {
         let request = self.get("emails/lookup").query(("Email", email));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<LookupBitcoinAddressResponseBody>()?;
-
-        Ok(parsed.WalletBitcoinAddress)
+        response.parse_body::<LookupBitcoinAddressResponseBody>()
     }
 
     pub async fn create_bitcoin_addresses_request(&self, email: String) -> Result<(), Error> {
@@ -71,7 +65,37 @@ impl EmailIntegrationClient {
         let request = self.post("emails/requests").body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        response.parse_response::<CreateBitcoinAddressRequestResponseBody>()?;
+        response.parse_body::<CreateBitcoinAddressRequestResponseBody>()
+    }
+
+    /// Sends a reminder to set up Bitcoin via Email to a recipient who hasn't
+    /// enabled it yet. Returns [`Error::RateLimited`] when a reminder was
+    /// already sent recently.
+    pub async fn send_bve_setup_reminder(&self, email: String, inviter_address_id: String) -> Result<(), Error> {
+        let payload = SendBitcoinViaEmailReminderRequestBody {
+            Email: email,
+            InviterAddressID: inviter_address_id,
+        };
+
+        let request = self.post("emails/reminders").body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        response
+            .parse_response::<BitcoinViaEmailReminderResponseBody>()
+            .map_err(|error| error.with_rate_limit(&response))?;
+
+        Ok(())
+    }
+
+    /// Cancels the pending Bitcoin via Email setup reminder sent to a
+    /// recipient
+    pub async fn cancel_bve_setup_reminder(&self, email: String) -> Result<(), Error> {
+        let request = self.delete("emails/reminders").query(("Email", email));
+
+        let response = self.api_client.send(request).await?;
+        response
+            .parse_response::<BitcoinViaEmailReminderResponseBody>()
+            .map_err(|error| error.with_rate_limit(&response))?;
 
         Ok(())
     }
@@ -81,9 +105,10 @@ impl EmailIntegrationClient {
 mod tests {
     use super::EmailIntegrationClient;
     use crate::{
-        core::ApiClient, tests::utils::common_api_client, tests::utils::setup_test_connection, BASE_WALLET_API_V1,
+        core::ApiClient, error::Error, tests::utils::common_api_client, tests::utils::setup_test_connection,
+        BASE_WALLET_API_V1,
     };
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
     use wiremock::{
         matchers::{body_json, method, path, query_param},
         Mock, MockServer, ResponseTemplate,
@@ -162,4 +187,73 @@ mod tests {
         let result = client.create_bitcoin_addresses_request(email.to_string()).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_send_bve_setup_reminder_success() {
+        let mock_server = MockServer::start().await;
+        let email = "test@proton.me";
+        let req_path: String = format!("{}/emails/reminders", BASE_WALLET_API_V1);
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .and(body_json(serde_json::json!(
+            {
+                "Email": email,
+                "InviterAddressID": "address_id",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = EmailIntegrationClient::new(Arc::new(api_client));
+        let result = client
+            .send_bve_setup_reminder(email.to_string(), "address_id".to_string())
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_bve_setup_reminder_rate_limited() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/emails/reminders", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(422)
+            .insert_header("Retry-After", "3600")
+            .set_body_json(serde_json::json!({
+                "Code": 2028,
+                "Error": "A reminder was already sent recently",
+                "Details": {}
+            }));
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = EmailIntegrationClient::new(Arc::new(api_client));
+        let result = client
+            .send_bve_setup_reminder("test@proton.me".to_string(), "address_id".to_string())
+            .await;
+        match result {
+            Err(Error::RateLimited(retry_after)) => assert_eq!(retry_after, Some(Duration::from_secs(3600))),
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_bve_setup_reminder_success() {
+        let mock_server = MockServer::start().await;
+        let email = "test@proton.me";
+        let req_path: String = format!("{}/emails/reminders", BASE_WALLET_API_V1);
+        Mock::given(method("DELETE"))
+            .and(path(req_path))
+            .and(query_param("Email", email))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = EmailIntegrationClient::new(Arc::new(api_client));
+        let result = client.cancel_bve_setup_reminder(email.to_string()).await;
+        assert!(result.is_ok());
+    }
 }
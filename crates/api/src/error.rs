@@ -1,4 +1,6 @@
-use std::str::Utf8Error;
+use std::{io::Error as IoError, str::Utf8Error, time::Duration};
+
+use andromeda_common::{cancellation::Cancelled, error::ErrorCategory};
 
 use bitcoin::{
     consensus::encode::Error as BitcoinEncodingError,
@@ -7,12 +9,20 @@ use bitcoin::{
 use muon::{
     client::middleware::AuthErr,
     error::{ParseAppVersionErr, StatusErr},
-    Status,
+    ProtonResponse, Status,
 };
 pub use muon::{error::ErrorKind as MuonErrorKind, Error as MuonError};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror;
 
+use crate::{
+    core::header_value,
+    device_verification::{DeviceVerificationChallenge, DEVICE_VERIFICATION_REQUIRED},
+};
+
+/// API error code returned when an action is attempted too often
+pub const TOO_MANY_REQUESTS: u16 = 2028;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("A muon {0} error was caused by a non-existent auth session")]
@@ -47,6 +57,230 @@ pub enum Error {
     Deserialize(String),
     #[error("Utf8 parsing error")]
     Utf8Error(#[from] Utf8Error),
+    #[error("Request serialization error: \n\t{0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("An error occurred when compressing the request body: \n\t{0}")]
+    Compression(IoError),
+    #[error("Endpoint {0} is temporarily unavailable, retry in {1:?}")]
+    Unavailable(String, Duration),
+    #[error("Device verification is required to proceed")]
+    DeviceVerificationRequired(DeviceVerificationChallenge),
+    #[error("A code is required to complete the device verification")]
+    DeviceVerificationCodeMissing,
+    #[error("The device verification proof of work of difficulty {0} could not be solved")]
+    DeviceVerificationUnsolvable(u8),
+    #[error("Too many requests, retry in {0:?}")]
+    RateLimited(Option<Duration>),
+    #[error(transparent)]
+    Cancelled(#[from] Cancelled),
+    #[error("Missing field {0} in request body")]
+    MissingField(&'static str),
+    #[error("Invalid request body: {0}")]
+    InvalidRequestBody(String),
+}
+
+impl Error {
+    /// Builds the error matching an API error payload, surfacing typed errors
+    /// for the codes the client can act upon
+    pub fn from_response_error(status: Status, error: ResponseError) -> Self {
+        if error.Code == DEVICE_VERIFICATION_REQUIRED {
+            if let Some(challenge) = DeviceVerificationChallenge::from_details(&error.Details) {
+                return Error::DeviceVerificationRequired(challenge);
+            }
+        }
+
+        Error::ErrorCode(status, error)
+    }
+
+    /// Types rate-limit responses, HTTP 429 or [`TOO_MANY_REQUESTS`] code, as
+    /// [`Error::RateLimited`] with the delay from the `Retry-After` header
+    pub(crate) fn with_rate_limit(self, response: &ProtonResponse) -> Self {
+        match self {
+            Error::ErrorCode(status, error) if status.as_u16() == 429 || error.Code == TOO_MANY_REQUESTS => {
+                let retry_after = header_value(response, "retry-after")
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs);
+
+                Error::RateLimited(retry_after)
+            }
+            error => error,
+        }
+    }
+
+    /// Stable, machine-readable kind of the error, for the apps to display a
+    /// translated message
+    pub fn kind(&self) -> ApiErrorKind {
+        match self {
+            Error::AuthSession(_) | Error::AuthRefresh(_) => ApiErrorKind::SessionExpired,
+            Error::ForkAuthSession | Error::ForkSession | Error::LoginError => ApiErrorKind::LoginFailed,
+            Error::UnsupportedTwoFactor => ApiErrorKind::UnsupportedTwoFactor,
+            Error::MuonError(_) | Error::Http => ApiErrorKind::Network,
+            Error::ErrorCode(status, error) => ApiErrorKind::from_response_error(*status, error),
+            Error::Unavailable(_, _) => ApiErrorKind::ServiceUnavailable,
+            Error::RateLimited(_) => ApiErrorKind::RateLimited,
+            Error::Cancelled(_) => ApiErrorKind::Cancelled,
+            Error::MissingField(_) => ApiErrorKind::MissingField,
+            Error::InvalidRequestBody(_) => ApiErrorKind::InvalidInput,
+            Error::DeviceVerificationRequired(_)
+            | Error::DeviceVerificationCodeMissing
+            | Error::DeviceVerificationUnsolvable(_) => ApiErrorKind::DeviceVerificationRequired,
+            Error::BitcoinDeserialize(_)
+            | Error::HexToArrayDecoding(_)
+            | Error::HexToBytesErrorDecoding(_)
+            | Error::Deserialize(_)
+            | Error::Utf8Error(_) => ApiErrorKind::InvalidResponse,
+            Error::MuonAppVersion(_) | Error::MuonStatus(_) | Error::Serialize(_) | Error::Compression(_) => {
+                ApiErrorKind::Internal
+            }
+        }
+    }
+
+    /// Category of the error in the taxonomy shared across crates
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::BitcoinDeserialize(_) => ErrorCategory::Consensus,
+            Error::Cancelled(_) => ErrorCategory::Cancelled,
+            error => error.kind().into(),
+        }
+    }
+
+    /// Shorthand for `self.kind().localization_key()`
+    pub fn localization_key(&self) -> &'static str {
+        self.kind().localization_key()
+    }
+}
+
+/// Stable error kinds the apps map to translated messages. Unlike API error
+/// codes and variants of [`Error`], these are part of the public contract
+/// with the clients and must not be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    SessionExpired,
+    LoginFailed,
+    UnsupportedTwoFactor,
+    HumanVerificationRequired,
+    DeviceVerificationRequired,
+    AppVersionOutdated,
+    MissingField,
+    InvalidInput,
+    PermissionDenied,
+    NotFound,
+    AlreadyExists,
+    RateLimited,
+    ServiceUnavailable,
+    ServerError,
+    Network,
+    InvalidResponse,
+    Internal,
+    Cancelled,
+    Unknown,
+}
+
+impl ApiErrorKind {
+    /// Maps an API error payload, by its code first and then by the HTTP
+    /// status when the code isn't a generic one
+    fn from_response_error(status: Status, error: &ResponseError) -> Self {
+        match error.Code {
+            2000 => ApiErrorKind::MissingField,
+            2001 => ApiErrorKind::InvalidInput,
+            2011 => ApiErrorKind::PermissionDenied,
+            TOO_MANY_REQUESTS => ApiErrorKind::RateLimited,
+            2500 => ApiErrorKind::AlreadyExists,
+            2501 => ApiErrorKind::NotFound,
+            5003 | 5005 => ApiErrorKind::AppVersionOutdated,
+            9001 => ApiErrorKind::HumanVerificationRequired,
+            DEVICE_VERIFICATION_REQUIRED => ApiErrorKind::DeviceVerificationRequired,
+            _ => match status.as_u16() {
+                401 => ApiErrorKind::SessionExpired,
+                403 => ApiErrorKind::PermissionDenied,
+                404 => ApiErrorKind::NotFound,
+                409 => ApiErrorKind::AlreadyExists,
+                422 => ApiErrorKind::InvalidInput,
+                429 => ApiErrorKind::RateLimited,
+                503 => ApiErrorKind::ServiceUnavailable,
+                500..=599 => ApiErrorKind::ServerError,
+                _ => ApiErrorKind::Unknown,
+            },
+        }
+    }
+
+    /// Machine-readable identifier of the kind, e.g. `session_expired`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiErrorKind::SessionExpired => "session_expired",
+            ApiErrorKind::LoginFailed => "login_failed",
+            ApiErrorKind::UnsupportedTwoFactor => "unsupported_two_factor",
+            ApiErrorKind::HumanVerificationRequired => "human_verification_required",
+            ApiErrorKind::DeviceVerificationRequired => "device_verification_required",
+            ApiErrorKind::AppVersionOutdated => "app_version_outdated",
+            ApiErrorKind::MissingField => "missing_field",
+            ApiErrorKind::InvalidInput => "invalid_input",
+            ApiErrorKind::PermissionDenied => "permission_denied",
+            ApiErrorKind::NotFound => "not_found",
+            ApiErrorKind::AlreadyExists => "already_exists",
+            ApiErrorKind::RateLimited => "rate_limited",
+            ApiErrorKind::ServiceUnavailable => "service_unavailable",
+            ApiErrorKind::ServerError => "server_error",
+            ApiErrorKind::Network => "network",
+            ApiErrorKind::InvalidResponse => "invalid_response",
+            ApiErrorKind::Internal => "internal",
+            ApiErrorKind::Cancelled => "cancelled",
+            ApiErrorKind::Unknown => "unknown",
+        }
+    }
+
+    /// Key of the translated message in the apps' string catalogs, e.g.
+    /// `error.api.session_expired`
+    pub fn localization_key(&self) -> &'static str {
+        match self {
+            ApiErrorKind::SessionExpired => "error.api.session_expired",
+            ApiErrorKind::LoginFailed => "error.api.login_failed",
+            ApiErrorKind::UnsupportedTwoFactor => "error.api.unsupported_two_factor",
+            ApiErrorKind::HumanVerificationRequired => "error.api.human_verification_required",
+            ApiErrorKind::DeviceVerificationRequired => "error.api.device_verification_required",
+            ApiErrorKind::AppVersionOutdated => "error.api.app_version_outdated",
+            ApiErrorKind::MissingField => "error.api.missing_field",
+            ApiErrorKind::InvalidInput => "error.api.invalid_input",
+            ApiErrorKind::PermissionDenied => "error.api.permission_denied",
+            ApiErrorKind::NotFound => "error.api.not_found",
+            ApiErrorKind::AlreadyExists => "error.api.already_exists",
+            ApiErrorKind::RateLimited => "error.api.rate_limited",
+            ApiErrorKind::ServiceUnavailable => "error.api.service_unavailable",
+            ApiErrorKind::ServerError => "error.api.server_error",
+            ApiErrorKind::Network => "error.api.network",
+            ApiErrorKind::InvalidResponse => "error.api.invalid_response",
+            ApiErrorKind::Internal => "error.api.internal",
+            ApiErrorKind::Cancelled => "error.api.cancelled",
+            ApiErrorKind::Unknown => "error.api.unknown",
+        }
+    }
+}
+
+impl From<ApiErrorKind> for ErrorCategory {
+    fn from(value: ApiErrorKind) -> Self {
+        match value {
+            ApiErrorKind::SessionExpired
+            | ApiErrorKind::LoginFailed
+            | ApiErrorKind::UnsupportedTwoFactor
+            | ApiErrorKind::HumanVerificationRequired
+            | ApiErrorKind::DeviceVerificationRequired
+            | ApiErrorKind::PermissionDenied => ErrorCategory::Auth,
+            ApiErrorKind::MissingField | ApiErrorKind::InvalidInput | ApiErrorKind::AlreadyExists => {
+                ErrorCategory::InvalidInput
+            }
+            ApiErrorKind::NotFound => ErrorCategory::NotFound,
+            ApiErrorKind::RateLimited
+            | ApiErrorKind::ServiceUnavailable
+            | ApiErrorKind::ServerError
+            | ApiErrorKind::Network => ErrorCategory::Network,
+            ApiErrorKind::AppVersionOutdated
+            | ApiErrorKind::InvalidResponse
+            | ApiErrorKind::Internal
+            | ApiErrorKind::Unknown => ErrorCategory::Internal,
+            ApiErrorKind::Cancelled => ErrorCategory::Cancelled,
+        }
+    }
 }
 
 impl From<MuonError> for Error {
@@ -76,3 +310,42 @@ pub struct ResponseError {
     pub Details: serde_json::Value,
     pub Error: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use muon::Status;
+
+    use super::{ApiErrorKind, Error, ResponseError};
+
+    fn error_code(status: u16, code: u16) -> Error {
+        Error::ErrorCode(
+            Status::from_u16(status).unwrap(),
+            ResponseError {
+                Code: code,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_error_kind_mapping() {
+        // API codes take precedence over the HTTP status
+        assert_eq!(error_code(422, 2001).kind(), ApiErrorKind::InvalidInput);
+        assert_eq!(error_code(422, 2501).kind(), ApiErrorKind::NotFound);
+        assert_eq!(error_code(422, 9001).kind(), ApiErrorKind::HumanVerificationRequired);
+
+        assert_eq!(error_code(429, 0).kind(), ApiErrorKind::RateLimited);
+        assert_eq!(error_code(502, 0).kind(), ApiErrorKind::ServerError);
+        assert_eq!(error_code(418, 0).kind(), ApiErrorKind::Unknown);
+
+        let unavailable = Error::Unavailable("wallet/v1/wallets".to_string(), Duration::from_secs(1));
+        assert_eq!(unavailable.kind(), ApiErrorKind::ServiceUnavailable);
+        assert_eq!(unavailable.localization_key(), "error.api.service_unavailable");
+        assert_eq!(
+            Error::Deserialize(String::new()).localization_key(),
+            "error.api.invalid_response"
+        );
+    }
+}
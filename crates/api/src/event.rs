@@ -1,10 +1,18 @@
-use std::sync::Arc;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
+use futures_timer::Delay;
 use serde::Deserialize;
 
 use crate::{
     contacts::ApiContactEmails,
-    core::{ApiClient, ProtonResponseExt, ToProtonRequest},
+    core::{ApiClient, ProtonResponseExt, ResponseBody, ToProtonRequest},
     error::Error,
     proton_users::{ProtonUser, ProtonUserSettings},
     settings::UserSettings,
@@ -13,11 +21,13 @@ use crate::{
 };
 
 const MAX_EVENTS_PER_POLL: usize = 50;
+pub const DEFAULT_EVENT_LOOP_INTERVAL: Duration = Duration::from_secs(30);
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetLatestEventIDResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub EventID: String,
 }
 
@@ -87,14 +97,43 @@ pub struct ApiWalletTransactionsEvent {
     pub WalletTransaction: Option<ApiWalletTransaction>,
 }
 
+/// Persistence of the event loop's cursor, i.e. the id of the last processed
+/// event. Apps implement it on top of their own storage so that the event loop
+/// resumes where it stopped after a restart.
+pub trait EventCursorStore: Debug + Send + Sync {
+    fn load(&self) -> Option<String>;
+    fn save(&self, event_id: String);
+}
+
+/// [`EventCursorStore`] keeping the cursor in memory only, used when no other
+/// store is provided
+#[derive(Debug, Default)]
+pub struct InMemoryEventCursorStore {
+    event_id: Mutex<Option<String>>,
+}
+
+impl EventCursorStore for InMemoryEventCursorStore {
+    fn load(&self) -> Option<String> {
+        self.event_id.lock().unwrap().clone()
+    }
+
+    fn save(&self, event_id: String) {
+        *self.event_id.lock().unwrap() = Some(event_id);
+    }
+}
+
 #[derive(Clone)]
 pub struct EventClient {
     api_client: Arc<ProtonWalletApiClient>,
+    cursor_store: Arc<dyn EventCursorStore>,
 }
 
 impl ApiClient for EventClient {
     fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
+        Self {
+            api_client,
+            cursor_store: Arc::new(InMemoryEventCursorStore::default()),
+        }
     }
 
     fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
@@ -107,6 +146,43 @@ impl ApiClient for EventClient {
 }
 
 impl EventClient {
+    /// Uses the provided store to persist the event cursor across restarts
+    pub fn with_cursor_store(mut self, cursor_store: Arc<dyn EventCursorStore>) -> Self {
+        self.cursor_store = cursor_store;
+        self
+    }
+
+    /// Collects the events that happened since the stored cursor and moves the
+    /// cursor to the last collected event.
+    ///
+    /// When no cursor has been stored yet, the cursor is initialised with the
+    /// latest event id and no event is returned: the caller is expected to do
+    /// a full fetch of the data first.
+    pub async fn poll_events(&self) -> Result<Vec<ApiProtonEvent>, Error> {
+        let Some(event_id) = self.cursor_store.load() else {
+            let latest_event_id = self.get_latest_event_id().await?;
+            self.cursor_store.save(latest_event_id);
+            return Ok(Vec::new());
+        };
+
+        let events = self.collect_events(event_id).await?;
+        if let Some(last_event) = events.last() {
+            self.cursor_store.save(last_event.EventID.clone());
+        }
+
+        Ok(events)
+    }
+
+    /// Overrides the stored cursor, e.g. after a full refresh requested by an
+    /// event
+    pub fn set_cursor(&self, event_id: String) {
+        self.cursor_store.save(event_id);
+    }
+
+    pub fn cursor(&self) -> Option<String> {
+        self.cursor_store.load()
+    }
+
     pub async fn collect_events(&self, latest_event_id: String) -> Result<Vec<ApiProtonEvent>, Error> {
         let mut events = Vec::with_capacity(4);
         let event = self.get_event(&latest_event_id).await?;
@@ -171,9 +247,115 @@ impl EventClient {
         let request = self.get("events/latest");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetLatestEventIDResponseBody>()?;
+        response.parse_body::<GetLatestEventIDResponseBody>()
+    }
+}
 
-        Ok(parsed.EventID)
+/// Receiver of the events dispatched by an [`EventLoop`], implemented by the
+/// host app. Events the listener doesn't handle are ignored
+#[allow(unused_variables)]
+pub trait EventListener: Send + Sync {
+    fn on_wallet_event(&self, event: ApiWalletEvent) {}
+    fn on_wallet_account_event(&self, event: ApiWalletAccountEvent) {}
+    fn on_wallet_key_event(&self, event: ApiWalletKeyEvent) {}
+    fn on_wallet_settings_event(&self, event: ApiWalletSettingsEvent) {}
+    fn on_wallet_transaction_event(&self, event: ApiWalletTransactionsEvent) {}
+    fn on_contact_email_event(&self, event: ApiContactsEmailEvent) {}
+    fn on_user_settings_event(&self, user_settings: UserSettings) {}
+    /// Called with the refresh flags when the server asks for a full refetch
+    /// of the data
+    fn on_refresh(&self, refresh: u32) {}
+    /// Called with the polling errors while the loop is running
+    fn on_error(&self, error: Error) {}
+}
+
+/// Polls the events of the user and pushes them, typed, to a listener, so
+/// that apps don't need to poll themselves
+#[derive(Clone)]
+pub struct EventLoop {
+    client: EventClient,
+    listener: Arc<dyn EventListener>,
+    running: Arc<AtomicBool>,
+}
+
+impl EventLoop {
+    pub fn new(client: EventClient, listener: Arc<dyn EventListener>) -> Self {
+        Self {
+            client,
+            listener,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn dispatch_event(&self, event: ApiProtonEvent) {
+        if event.Refresh != 0 {
+            self.listener.on_refresh(event.Refresh);
+        }
+
+        for event in event.Wallets.unwrap_or_default() {
+            self.listener.on_wallet_event(event);
+        }
+        for event in event.WalletAccounts.unwrap_or_default() {
+            self.listener.on_wallet_account_event(event);
+        }
+        for event in event.WalletKeys.unwrap_or_default() {
+            self.listener.on_wallet_key_event(event);
+        }
+        for event in event.WalletSettings.unwrap_or_default() {
+            self.listener.on_wallet_settings_event(event);
+        }
+        for event in event.WalletTransactions.unwrap_or_default() {
+            self.listener.on_wallet_transaction_event(event);
+        }
+        for event in event.ContactEmails.unwrap_or_default() {
+            self.listener.on_contact_email_event(event);
+        }
+
+        if let Some(user_settings) = event.WalletUserSettings {
+            self.listener.on_user_settings_event(user_settings);
+        }
+    }
+
+    pub fn client(&self) -> &EventClient {
+        &self.client
+    }
+
+    /// Polls the events once and dispatches them. Returns the number of
+    /// dispatched events.
+    ///
+    /// On the first poll without cursor, only the cursor is initialised.
+    pub async fn poll(&self) -> Result<usize, Error> {
+        let events = self.client.poll_events().await?;
+        let count = events.len();
+
+        for event in events {
+            self.dispatch_event(event);
+        }
+
+        Ok(count)
+    }
+
+    /// Polls the events every `interval` until [`EventLoop::stop`] is called.
+    /// Polling errors are passed to the listener and don't stop the loop
+    pub async fn start(&self, interval: Duration) {
+        self.running.store(true, Ordering::SeqCst);
+
+        while self.running.load(Ordering::SeqCst) {
+            if let Err(error) = self.poll().await {
+                self.listener.on_error(error);
+            }
+
+            Delay::new(interval).await;
+        }
+    }
+
+    /// Stops the loop after the current iteration
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
     }
 }
 
@@ -185,7 +367,11 @@ mod tests {
         Mock, MockServer, ResponseTemplate,
     };
 
-    use super::EventClient;
+    use std::sync::{Arc, Mutex};
+
+    use super::{
+        ApiWalletTransactionsEvent, EventClient, EventCursorStore, EventListener, EventLoop, InMemoryEventCursorStore,
+    };
     use crate::{
         core::ApiClient,
         read_mock_file,
@@ -335,4 +521,78 @@ mod tests {
             Err(e) => panic!("Got Err. {:?}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_poll_events_resumes_from_stored_cursor() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/events/latest", BASE_CORE_API_V4)))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "Code": 1000, "EventID": "latest_event_id" })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/events/{}", BASE_CORE_API_V5, "latest_event_id")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_events_1000_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/events/{}", BASE_CORE_API_V5, "ACXDmTaBub14w==")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_events_1000_body_2")))
+            .mount(&mock_server)
+            .await;
+
+        let cursor_store = Arc::new(InMemoryEventCursorStore::default());
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = EventClient::new(api_client.clone()).with_cursor_store(cursor_store.clone());
+
+        // First poll only initialises the cursor
+        let events = client.poll_events().await.unwrap();
+        assert!(events.is_empty());
+        assert_eq!(cursor_store.load(), Some("latest_event_id".to_string()));
+
+        // A new client, e.g. after an app restart, resumes from the stored cursor
+        let client = EventClient::new(api_client).with_cursor_store(cursor_store.clone());
+        let events = client.poll_events().await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(cursor_store.load(), Some("AC22222222222==".to_string()));
+    }
+
+    #[derive(Default)]
+    struct TransactionEventsListener {
+        ids: Mutex<Vec<String>>,
+    }
+
+    impl EventListener for TransactionEventsListener {
+        fn on_wallet_transaction_event(&self, event: ApiWalletTransactionsEvent) {
+            self.ids.lock().unwrap().push(event.ID);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_loop_dispatches_events() {
+        let contents = read_mock_file!("get_events_1000_body_2");
+        let req_path: String = format!("{}/events/{}", BASE_CORE_API_V5, "latest_event_id");
+        let response = ResponseTemplate::new(200).set_body_string(contents);
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = EventClient::new(api_client);
+        client.set_cursor("latest_event_id".to_string());
+
+        let listener = Arc::new(TransactionEventsListener::default());
+        let event_loop = EventLoop::new(client, listener.clone());
+
+        assert_eq!(event_loop.poll().await.unwrap(), 1);
+        assert!(!listener.ids.lock().unwrap().is_empty());
+        assert_eq!(event_loop.client().cursor(), Some("AC22222222222==".to_string()));
+    }
 }
@@ -1,13 +1,12 @@
-use std::sync::Arc;
-
 use andromeda_common::BitcoinUnit;
+use andromeda_macros::api_client;
 use serde::Deserialize;
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, MaybeStale, ProtonResponseExt, ResponseBody},
     error::Error,
     settings::FiatCurrencySymbol,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Debug, Deserialize)]
@@ -29,12 +28,11 @@ pub struct ApiExchangeRate {
     pub Cents: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetExchangeRateResponseBody {
-    //TODO:: code need to be used. remove all #[allow(dead_code)]
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub ExchangeRate: ApiExchangeRate,
 }
 
@@ -48,33 +46,17 @@ pub struct ApiFiatCurrency {
     pub Cents: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetAllFiatCurrenciesResponseBody {
-    //TODO:: code need to be used. remove all #[allow(dead_code)]
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub FiatCurrencies: Vec<ApiFiatCurrency>,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct ExchangeRateClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for ExchangeRateClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-}
+pub struct ExchangeRateClient;
 
 impl ExchangeRateClient {
     pub async fn get_exchange_rate(
@@ -89,8 +71,25 @@ impl ExchangeRateClient {
 
         let response = self.api_client.send(request).await?;
 
-        let parsed = response.parse_response::<GetExchangeRateResponseBody>()?;
-        Ok(parsed.ExchangeRate)
+        response.parse_body::<GetExchangeRateResponseBody>()
+    }
+
+    /// Same as [`ExchangeRateClient::get_exchange_rate`], serving the last
+    /// fetched rate when the API is unreachable, see
+    /// [`ApiConfig::response_cache`](crate::ApiConfig::response_cache)
+    pub async fn get_exchange_rate_or_cached(
+        &self,
+        fiat_currency: FiatCurrencySymbol,
+        time: Option<u64>,
+    ) -> Result<MaybeStale<ApiExchangeRate>, Error> {
+        let mut request = self.get("rates").query(("FiatCurrency", fiat_currency.to_string()));
+        if let Some(time) = time {
+            request = request.query(("Time", time.to_string()))
+        }
+
+        self.api_client
+            .send_or_cached::<GetExchangeRateResponseBody>(request)
+            .await
     }
 
     pub async fn get_all_fiat_currencies(&self) -> Result<Vec<ApiFiatCurrency>, Error> {
@@ -98,8 +97,7 @@ impl ExchangeRateClient {
 
         let response = self.api_client.send(request).await?;
 
-        let parsed = response.parse_response::<GetAllFiatCurrenciesResponseBody>()?;
-        Ok(parsed.FiatCurrencies)
+        response.parse_body::<GetAllFiatCurrenciesResponseBody>()
     }
 }
 
@@ -0,0 +1,121 @@
+//! Conversions between bitcoin amounts and fiat values using an
+//! [`ApiExchangeRate`].
+//!
+//! Exchange rates are expressed in fiat minor units (cents) per
+//! [`BitcoinUnit`], `Cents` giving the number of minor units in a major one.
+//! All conversions are done on integers and rounded half-up to the nearest
+//! minor unit (resp. satoshi), so every frontend displays the same values.
+
+use andromeda_common::BitcoinUnit;
+
+use crate::exchange_rate::ApiExchangeRate;
+
+fn sats_per_unit(unit: BitcoinUnit) -> u128 {
+    unit.sats() as u128
+}
+
+/// Integer division rounded half-up
+fn div_round(numerator: u128, denominator: u128) -> u128 {
+    (numerator + denominator / 2) / denominator
+}
+
+fn cents(exchange_rate: &ApiExchangeRate) -> u64 {
+    // Guard against currencies without minor units wrongly reported with 0
+    exchange_rate.Cents.max(1)
+}
+
+/// Converts a satoshi amount to fiat minor units (e.g. cents for USD)
+pub fn sats_to_fiat_minor(exchange_rate: &ApiExchangeRate, sats: u64) -> u64 {
+    let value = div_round(
+        sats as u128 * exchange_rate.ExchangeRate as u128,
+        sats_per_unit(exchange_rate.BitcoinUnit),
+    );
+
+    value.min(u64::MAX as u128) as u64
+}
+
+/// Converts a satoshi amount to a fiat value, rounded to the currency's
+/// precision
+pub fn sats_to_fiat(exchange_rate: &ApiExchangeRate, sats: u64) -> f64 {
+    sats_to_fiat_minor(exchange_rate, sats) as f64 / cents(exchange_rate) as f64
+}
+
+/// Converts fiat minor units to a satoshi amount. Returns `None` when the
+/// exchange rate is zero.
+pub fn fiat_minor_to_sats(exchange_rate: &ApiExchangeRate, fiat_minor: u64) -> Option<u64> {
+    if exchange_rate.ExchangeRate == 0 {
+        return None;
+    }
+
+    let sats = div_round(
+        fiat_minor as u128 * sats_per_unit(exchange_rate.BitcoinUnit),
+        exchange_rate.ExchangeRate as u128,
+    );
+
+    Some(sats.min(u64::MAX as u128) as u64)
+}
+
+/// Converts a fiat value to a satoshi amount, after rounding the value to
+/// the currency's precision. Returns `None` for a zero exchange rate or an
+/// invalid (negative, NaN) fiat value.
+pub fn fiat_to_sats(exchange_rate: &ApiExchangeRate, fiat: f64) -> Option<u64> {
+    if !fiat.is_finite() || fiat < 0.0 {
+        return None;
+    }
+
+    let fiat_minor = (fiat * cents(exchange_rate) as f64).round() as u64;
+    fiat_minor_to_sats(exchange_rate, fiat_minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use andromeda_common::BitcoinUnit;
+
+    use super::{fiat_minor_to_sats, fiat_to_sats, sats_to_fiat, sats_to_fiat_minor};
+    use crate::{exchange_rate::ApiExchangeRate, settings::FiatCurrencySymbol};
+
+    fn exchange_rate(currency: FiatCurrencySymbol, rate: u64, cents: u64) -> ApiExchangeRate {
+        ApiExchangeRate {
+            ID: "ID".to_string(),
+            BitcoinUnit: BitcoinUnit::BTC,
+            FiatCurrency: currency,
+            Sign: None,
+            ExchangeRateTime: "2024-01-01T00:00:00Z".to_string(),
+            ExchangeRate: rate,
+            Cents: cents,
+        }
+    }
+
+    #[test]
+    fn test_sats_to_fiat() {
+        // 1 BTC = 61,899.00 USD
+        let usd = exchange_rate(FiatCurrencySymbol::USD, 6189900, 100);
+        assert_eq!(sats_to_fiat_minor(&usd, 100_000_000), 6189900);
+        assert_eq!(sats_to_fiat(&usd, 100_000_000), 61899.0);
+        // 12345 sats = 7.6414... USD, rounded to 7.64
+        assert_eq!(sats_to_fiat_minor(&usd, 12345), 764);
+        assert_eq!(sats_to_fiat(&usd, 12345), 7.64);
+        // Half a cent rounds up
+        assert_eq!(
+            sats_to_fiat_minor(&exchange_rate(FiatCurrencySymbol::USD, 100, 100), 500_000),
+            1
+        );
+
+        // JPY doesn't have minor units
+        let jpy = exchange_rate(FiatCurrencySymbol::JPY, 9_500_000, 1);
+        assert_eq!(sats_to_fiat(&jpy, 12345), 1173.0);
+    }
+
+    #[test]
+    fn test_fiat_to_sats() {
+        let usd = exchange_rate(FiatCurrencySymbol::USD, 6189900, 100);
+        assert_eq!(fiat_minor_to_sats(&usd, 6189900), Some(100_000_000));
+        assert_eq!(fiat_to_sats(&usd, 7.64), Some(12343));
+        // Fiat value is rounded to the currency precision first
+        assert_eq!(fiat_to_sats(&usd, 7.6449), fiat_to_sats(&usd, 7.64));
+
+        assert_eq!(fiat_to_sats(&usd, -1.0), None);
+        assert_eq!(fiat_to_sats(&usd, f64::NAN), None);
+        assert_eq!(fiat_to_sats(&exchange_rate(FiatCurrencySymbol::USD, 0, 100), 1.0), None);
+    }
+}
@@ -1,12 +1,11 @@
-use std::sync::Arc;
-
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Deserialize_repr, Serialize_repr, PartialEq, Debug)]
@@ -26,14 +25,15 @@ pub struct InviteRequestBody {
     pub InviterAddressID: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct CanSendInviteResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub CanSend: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct SendInviteResponseBody {
     pub Code: u16,
@@ -46,31 +46,17 @@ pub struct RemainingMonthlyInvitations {
     pub Available: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetRemainingMonthlyInvitationsResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub RemainingInvitations: RemainingMonthlyInvitations,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct InviteClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for InviteClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-}
+pub struct InviteClient;
 
 impl InviteClient {
     pub async fn send_newcomer_invite(&self, invitee_email: String, inviter_address_id: String) -> Result<(), Error> {
@@ -81,9 +67,7 @@ impl InviteClient {
         })?;
 
         let response = self.api_client.send(request).await?;
-        response.parse_response::<SendInviteResponseBody>()?;
-
-        Ok(())
+        response.parse_body::<SendInviteResponseBody>()
     }
 
     /// Call an endpoint to check whether or not user can send an invite to the
@@ -101,9 +85,7 @@ impl InviteClient {
             .query(("InviterAddressID", inviter_address_id));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<CanSendInviteResponseBody>()?;
-
-        Ok(parsed.CanSend)
+        response.parse_body::<CanSendInviteResponseBody>()
     }
 
     pub async fn send_email_integration_invite(
@@ -117,18 +99,14 @@ impl InviteClient {
             InviterAddressID: inviter_address_id,
         })?;
         let response = self.api_client.send(request).await?;
-        response.parse_response::<SendInviteResponseBody>()?;
-
-        Ok(())
+        response.parse_body::<SendInviteResponseBody>()
     }
 
     pub async fn get_remaining_monthly_invitation(&self) -> Result<RemainingMonthlyInvitations, Error> {
         let request = self.get("invites/remaining");
         let response = self.api_client.send(request).await?;
 
-        let parsed = response.parse_response::<GetRemainingMonthlyInvitationsResponseBody>()?;
-
-        Ok(parsed.RemainingInvitations)
+        response.parse_body::<GetRemainingMonthlyInvitationsResponseBody>()
     }
 }
 
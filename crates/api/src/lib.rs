@@ -1,20 +1,28 @@
-use core::ApiClient;
+use core::{
+    deprecation::{DeprecationHook, DeprecationMonitor, PendingDeprecationCheck},
+    logging,
+    metrics::{self, MetricsSink, PendingMetric},
+    ApiClient, ApiResponse, CachedResponse, CircuitBreaker, CircuitBreakerConfig, KeepAliveTimer, MaybeStale,
+    ProtonResponseExt, RateLimitConfig, RateLimiter, RequestCompression, ResponseBody, ResponseCache, ToProtonRequest,
+};
 use std::{
     sync::{Arc, Mutex},
     time::Duration,
 };
 
 use address::AddressClient;
+use andromeda_common::{cancellation::CancellationToken, utils::now};
 use bitcoin_address::BitcoinAddressClient;
 use block::BlockClient;
 use contacts::ContactsClient;
+use device_verification::DeviceVerificationClient;
 use discovery_content::DiscoverContentClient;
 use email_integration::EmailIntegrationClient;
 use error::Error;
 use event::EventClient;
 use exchange_rate::ExchangeRateClient;
+use futures_timer::Delay;
 use invite::InviteClient;
-use log::info;
 use muon::client::flow::ForkFlowResult;
 pub use muon::{
     app::Product,
@@ -24,15 +32,19 @@ pub use muon::{
     rest::core as CoreAPI,
     store::{DynStore, Store, StoreFailure},
     util::ProtonRequestExt,
-    App, Client, Error as MuonError, ProtonRequest, ProtonResponse, GET,
+    App, Client, Error as MuonError, ProtonRequest, GET,
 };
-use network::NetworkClient;
+use network::{NetworkCache, NetworkClient};
 use payment_gateway::PaymentGatewayClient;
 use price_graph::PriceGraphClient;
 use proton_email_address::ProtonEmailAddressClient;
 pub use proton_users::ProtonUsersClient;
+use serde::Serialize;
+use sessions::SessionsClient;
 use settings::SettingsClient;
+use tracing::info;
 use transaction::TransactionClient;
+use unleash::UnleashClient;
 use wallet::WalletClient;
 // expose muon's jni. it needs to be matched when use in client
 #[cfg(target_os = "android")]
@@ -46,7 +58,7 @@ pub use {
 };
 
 pub use crate::{
-    core::WalletAuthStore,
+    core::{SecureAuthStore, SecureStorage, StoredAuth, WalletAuthStore},
     proton_users::{ChildSession, UserData},
 };
 
@@ -59,18 +71,23 @@ pub mod address;
 pub mod bitcoin_address;
 pub mod block;
 pub mod contacts;
+pub mod device_verification;
 pub mod discovery_content;
 pub mod email_integration;
 pub mod error;
 pub mod event;
 pub mod exchange_rate;
+pub mod fiat_conversion;
 pub mod invite;
 pub mod network;
 pub mod payment_gateway;
 pub mod price_graph;
+pub mod price_graph_export;
+pub mod sessions;
 pub mod settings;
 pub mod transaction;
 pub mod wallet;
+pub mod wallet_key_rotation;
 
 pub mod proton_email_address;
 pub mod proton_settings;
@@ -84,12 +101,15 @@ pub const BASE_WALLET_API_V1: &str = "wallet/v1";
 pub const BASE_CORE_API_V4: &str = "core/v4";
 pub const BASE_CORE_API_V5: &str = "core/v5";
 pub const BASE_CONTACTS_API_V4: &str = "contacts/v4";
+pub const BASE_AUTH_API_V4: &str = "auth/v4";
 
 pub const DEFAULT_TIME_CONSTRAINT: Duration = Duration::from_secs(30);
 
 pub const DEFAULT_SERVICE_TYPE: ServiceType = ServiceType::Normal;
 pub const DEFAULT_INTERACTIVITY: ServiceType = ServiceType::Interactive;
 
+const UNSUPPORTED_MEDIA_TYPE: u16 = 415;
+
 /// An API client providing interfaces to send authenticated http requests to
 /// Wallet backend
 ///
@@ -108,6 +128,22 @@ pub struct ProtonWalletApiClient {
     url_prefix: Option<String>,
     // cache the env, when doing the fork, we need to target same env
     env: Option<String>,
+    compression: Arc<RequestCompression>,
+    request_logging: bool,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// Headers added to every request: custom headers and locale
+    default_headers: Arc<Vec<(String, String)>>,
+    deprecation_monitor: Arc<DeprecationMonitor>,
+    keep_alive: Option<Arc<KeepAliveTimer>>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    strict_response_validation: bool,
+    /// Network fetched by the network clients, shared by all of them
+    network_cache: NetworkCache,
+    /// Cancels the requests sent through this client, see
+    /// [`ProtonWalletApiClient::with_cancellation`]
+    cancellation: Option<CancellationToken>,
 }
 
 #[derive(Debug)]
@@ -123,6 +159,37 @@ pub struct ApiConfig {
     pub env: Option<String>,
     /// The muon auth store. web doesn't need but flutter side needs
     pub store: Option<DynStore>,
+    /// Minimum size (in bytes) from which JSON request bodies are gzip
+    /// compressed. `None` disables request compression
+    pub compression_threshold: Option<usize>,
+    /// Logs every request and response through `tracing`, with credentials
+    /// and encrypted fields redacted
+    pub request_logging: bool,
+    /// Receives per-endpoint request metrics (count, latency, error codes)
+    pub metrics_sink: Option<Arc<dyn MetricsSink>>,
+    /// Client-side rate limit shared by all the clients. `None` disables it
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Fast-fails requests to endpoints that keep failing. `None` disables it
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Headers added to every request (e.g. A/B test cohort, build metadata).
+    /// Headers set on a request itself take precedence
+    pub custom_headers: Vec<(String, String)>,
+    /// Locale (e.g. `fr-FR`) sent as `Accept-Language` and `x-pm-locale` so
+    /// that server-provided strings come back localized
+    pub locale: Option<String>,
+    /// Interval after which an idle session gets pinged to keep it alive, see
+    /// [`ProtonWalletApiClient::keep_alive`]. `None` disables it
+    pub keep_alive_interval: Option<Duration>,
+    /// Keeps the last responses of the read endpoints, served when the API
+    /// is unreachable. `None` disables offline reads
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+    /// Validates API responses strictly, meant for development and QA builds.
+    ///
+    /// In strict mode, fields of a response unknown to the client are logged
+    /// and deserialization errors (e.g. missing required fields) report the
+    /// precise path of the faulty field. Parsing outcomes are otherwise
+    /// unchanged: unknown fields are still ignored.
+    pub strict_response_validation: bool,
 }
 
 pub struct Clients {
@@ -142,6 +209,10 @@ pub struct Clients {
     pub email_integration: EmailIntegrationClient,
     pub invite: InviteClient,
     pub discover_content: DiscoverContentClient,
+    pub device_verification: DeviceVerificationClient,
+    pub sessions: SessionsClient,
+    pub proton_users: ProtonUsersClient,
+    pub unleash: UnleashClient,
 }
 
 impl ProtonWalletApiClient {
@@ -158,8 +229,7 @@ impl ProtonWalletApiClient {
     ///     spec: (String::from("android-wallet/1.0.0"), String::from("ProtonWallet/plus-agent-details")),
     ///     auth: Some(auth),
     ///     env: Some("atlas".to_string()),
-    ///     url_prefix: None,
-    ///     store: None,
+    ///     ..Default::default()
     /// };
     /// let api_client = ProtonWalletApiClient::from_config(config);
     /// ```
@@ -181,9 +251,47 @@ impl ProtonWalletApiClient {
             session,
             url_prefix: config.url_prefix,
             env: config.env,
+            compression: Arc::new(RequestCompression::new(config.compression_threshold)),
+            request_logging: config.request_logging,
+            metrics_sink: config.metrics_sink,
+            rate_limiter: config
+                .rate_limit
+                .map(|rate_limit| Arc::new(RateLimiter::new(rate_limit))),
+            circuit_breaker: config
+                .circuit_breaker
+                .map(|circuit_breaker| Arc::new(CircuitBreaker::new(circuit_breaker))),
+            default_headers: Arc::new(default_headers(config.custom_headers, config.locale)),
+            deprecation_monitor: Arc::new(DeprecationMonitor::default()),
+            keep_alive: config
+                .keep_alive_interval
+                .map(|interval| Arc::new(KeepAliveTimer::new(interval))),
+            response_cache: config.response_cache,
+            strict_response_validation: config.strict_response_validation,
+            network_cache: Default::default(),
+            cancellation: None,
         })
     }
 
+    /// Returns a client sharing the same session, whose requests fail with
+    /// [`Error::Cancelled`] once `token` is cancelled. In-flight requests are
+    /// dropped on cancellation.
+    ///
+    /// Clients built from the returned one with
+    /// [`ProtonWalletApiClient::clients`] are cancelled by the same token.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self {
+            cancellation: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Sets the hook notified when the backend flags an endpoint as
+    /// deprecated, once per endpoint. The hook is shared with the clients
+    /// already built from this api client. `None` removes it.
+    pub fn set_deprecation_hook(&self, hook: Option<Arc<dyn DeprecationHook>>) {
+        self.deprecation_monitor.set_hook(hook);
+    }
+
     pub fn clients(&self) -> Clients {
         let api_client = Arc::new(self.clone());
 
@@ -204,6 +312,10 @@ impl ProtonWalletApiClient {
             email_integration: EmailIntegrationClient::new(api_client.clone()),
             invite: InviteClient::new(api_client.clone()),
             discover_content: DiscoverContentClient::new(api_client.clone()),
+            device_verification: DeviceVerificationClient::new(api_client.clone()),
+            sessions: SessionsClient::new(api_client.clone()),
+            proton_users: ProtonUsersClient::new(api_client.clone()),
+            unleash: UnleashClient::new(api_client.clone()),
         }
     }
 
@@ -215,6 +327,7 @@ impl ProtonWalletApiClient {
     /// let mut api_client = ProtonWalletApiClient::default();
     /// api_client.login("my_username", "my_password");
     /// ```
+    #[cfg_attr(feature = "instrumentation", tracing::instrument(name = "api.login", skip_all))]
     pub async fn login(&self, username: &str, password: &str) -> Result<UserData, Error> {
         info!("login start");
         let c = match self.session.clone().auth().login(username, password).await {
@@ -223,12 +336,53 @@ impl ProtonWalletApiClient {
             _ => Err(Error::UnsupportedTwoFactor),
         }?;
         info!("login successful");
+        self.clear_response_cache();
+
+        Self::get_user_data(&c).await
+    }
+
+    /// Same as [`ProtonWalletApiClient::login`], but completes the second
+    /// factor for accounts with TOTP enabled. `get_totp` is only called once
+    /// the password is accepted, e.g. to prompt the user for the code.
+    #[cfg_attr(feature = "instrumentation", tracing::instrument(name = "api.login", skip_all))]
+    pub async fn login_with_two_factor(
+        &self,
+        username: &str,
+        password: &str,
+        get_totp: impl FnOnce() -> String,
+    ) -> Result<UserData, Error> {
+        info!("login start");
+        let c = match self.session.clone().auth().login(username, password).await {
+            LoginFlow::Ok(c) => Ok(c),
+            LoginFlow::TwoFactor(flow) => match flow.totp(&get_totp()).await {
+                LoginFlow::Ok(c) => Ok(c),
+                _ => Err(Error::LoginError),
+            },
+            LoginFlow::Failed { client: _, reason: _ } => Err(Error::LoginError),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UnsupportedTwoFactor),
+        }?;
+        info!("login successful");
+        self.clear_response_cache();
+
+        Self::get_user_data(&c).await
+    }
+
+    /// Removes the responses kept for offline reads. Called on login, apps
+    /// must also call it on logout so that the next user isn't served the
+    /// previous user's data.
+    pub fn clear_response_cache(&self) {
+        if let Some(response_cache) = &self.response_cache {
+            response_cache.clear();
+        }
+    }
 
+    async fn get_user_data(c: &Client) -> Result<UserData, Error> {
         let req = GET!("/core/v4/users");
-        let res = req.send_with(&c).await?;
+        let res = req.send_with(c).await?;
         let user: CoreAPI::v4::users::GetRes = res.ok()?.into_body_json()?;
         let keysalt_req = GET!("/core/v4/keys/salts");
-        let keysalt_res = keysalt_req.send_with(&c).await?;
+        let keysalt_res = keysalt_req.send_with(c).await?;
         let key_salt: CoreAPI::v4::keys::salts::GetRes = keysalt_res.ok()?.into_body_json()?;
         Ok(UserData {
             user: user.user,
@@ -236,6 +390,35 @@ impl ProtonWalletApiClient {
         }) // later return the user
     }
 
+    /// Pings the API with a cheap authenticated request whenever no request
+    /// was sent for the configured keep-alive interval, so that long-idle
+    /// sessions don't get evicted.
+    ///
+    /// The future runs until the session is lost: callers spawn it on their
+    /// runtime and drop it to stop pinging. It returns immediately when
+    /// `keep_alive_interval` isn't set.
+    pub async fn keep_alive(&self) {
+        let Some(keep_alive) = &self.keep_alive else {
+            return;
+        };
+
+        loop {
+            if let Some(next_ping_in) = keep_alive.next_ping_in() {
+                Delay::new(next_ping_in).await;
+                continue;
+            }
+
+            let request = self
+                .build_full_url(BASE_CORE_API_V4, "events/latest")
+                .to_get_request()
+                .allowed_time(DEFAULT_TIME_CONSTRAINT);
+            if let Err(Error::AuthSession(_) | Error::AuthRefresh(_)) = self.send(request).await {
+                info!("keep-alive stopped, session lost");
+                return;
+            }
+        }
+    }
+
     /// fork session, client must be authenticated first
     pub async fn fork(&self, client_child: &str, app_version: &str, user_agent: &str) -> Result<ChildSession, Error> {
         use muon::client::flow::WithSelectorFlow;
@@ -297,16 +480,207 @@ impl ProtonWalletApiClient {
         }
     }
 
-    async fn send(&self, request: ProtonRequest) -> Result<ProtonResponse, MuonError> {
-        self.session.clone().send(request).await
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(
+            name = "api.request",
+            skip_all,
+            fields(
+                method = %request.method(),
+                endpoint = %metrics::endpoint_family(request.uri().path(), self.url_prefix.as_deref())
+            )
+        )
+    )]
+    async fn send(&self, request: ProtonRequest) -> Result<ApiResponse, Error> {
+        match &self.cancellation {
+            Some(token) => token.run(self.send_request(request)).await,
+            None => self.send_request(request).await,
+        }
+    }
+
+    async fn send_request(&self, request: ProtonRequest) -> Result<ApiResponse, Error> {
+        if let Some(keep_alive) = &self.keep_alive {
+            keep_alive.record_activity();
+        }
+        let request = self.with_default_headers(request);
+
+        let endpoint = self
+            .circuit_breaker
+            .as_ref()
+            .map(|_| metrics::endpoint_family(request.uri().path(), self.url_prefix.as_deref()));
+        if let (Some(circuit_breaker), Some(endpoint)) = (&self.circuit_breaker, &endpoint) {
+            if let Err(retry_after) = circuit_breaker.check(endpoint) {
+                return Err(Error::Unavailable(endpoint.clone(), retry_after));
+            }
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let request_line = self.request_logging.then(|| logging::log_request(&request));
+        let pending_metric = (self.metrics_sink.is_some() || cfg!(feature = "metrics"))
+            .then(|| PendingMetric::from_request(&request, self.url_prefix.as_deref()));
+        let pending_deprecation_check = self
+            .deprecation_monitor
+            .is_enabled()
+            .then(|| PendingDeprecationCheck::from_request(&request, self.url_prefix.as_deref()));
+
+        let started_at = now();
+        let result = self.session.clone().send(request).await;
+        let elapsed = now().saturating_sub(started_at);
+
+        if let Some(request_line) = request_line {
+            match &result {
+                Ok(response) => logging::log_response(&request_line, response, elapsed.as_millis()),
+                Err(error) => logging::log_transport_error(&request_line, error, elapsed.as_millis()),
+            }
+        }
+
+        if let Some(pending_metric) = pending_metric {
+            let metric = pending_metric.finish(&result, elapsed);
+
+            #[cfg(feature = "metrics")]
+            metrics::record_to_facade(&metric);
+
+            if let Some(sink) = &self.metrics_sink {
+                sink.record(metric);
+            }
+        }
+
+        if let (Some(pending_deprecation_check), Ok(response)) = (pending_deprecation_check, &result) {
+            if let Some(notice) = pending_deprecation_check.finish(response) {
+                self.deprecation_monitor.report(notice);
+            }
+        }
+
+        if let (Some(circuit_breaker), Some(endpoint)) = (&self.circuit_breaker, &endpoint) {
+            match &result {
+                Ok(response) if !response.status().is_server_error() => circuit_breaker.record_success(endpoint),
+                _ => circuit_breaker.record_failure(endpoint),
+            }
+        }
+
+        result
+            .map(|response| ApiResponse::new(response, self.strict_response_validation))
+            .map_err(Error::from)
+    }
+
+    /// Sends a request to a read endpoint and parses its body. The body is
+    /// kept in the response cache, to be served, flagged as stale, when the
+    /// API can't be reached later on.
+    async fn send_or_cached<T: ResponseBody>(&self, request: ProtonRequest) -> Result<MaybeStale<T::Data>, Error> {
+        let Some(response_cache) = &self.response_cache else {
+            let response = self.send(request).await?;
+            return Ok(MaybeStale {
+                data: response.parse_body::<T>()?,
+                is_stale: false,
+                fetched_at: now().as_secs(),
+            });
+        };
+
+        let key = core::cache_key(&request);
+        let result = async {
+            let response = self.send(request).await?;
+            let data = response.parse_body::<T>()?;
+            Ok::<_, Error>((data, response.body().to_vec()))
+        }
+        .await;
+
+        match result {
+            Ok((data, body)) => {
+                let fetched_at = now().as_secs();
+                response_cache.write(&key, CachedResponse { body, fetched_at });
+
+                Ok(MaybeStale {
+                    data,
+                    is_stale: false,
+                    fetched_at,
+                })
+            }
+            Err(error) if core::is_unreachable(&error) => {
+                let Some(cached) = response_cache.read(&key) else {
+                    return Err(error);
+                };
+                let body = serde_json::from_slice::<T>(&cached.body).map_err(|e| Error::Deserialize(e.to_string()))?;
+
+                Ok(MaybeStale {
+                    data: body.into_data(),
+                    is_stale: true,
+                    fetched_at: cached.fetched_at,
+                })
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Adds the default headers the request doesn't already have
+    fn with_default_headers(&self, request: ProtonRequest) -> ProtonRequest {
+        self.default_headers.iter().fold(request, |request, (name, value)| {
+            let is_overridden = request
+                .headers()
+                .iter()
+                .any(|(header, _)| header.as_str().eq_ignore_ascii_case(name));
+
+            if is_overridden {
+                request
+            } else {
+                request.header((name.clone(), value.clone()))
+            }
+        })
+    }
+
+    /// Sends a request with a JSON body, gzip compressed when request
+    /// compression is enabled and the body is large enough.
+    ///
+    /// If the backend refuses the compressed body, the request is sent again
+    /// uncompressed and compression gets disabled for the session.
+    async fn send_json<T: Serialize>(
+        &self,
+        build_request: impl Fn() -> ProtonRequest,
+        body: &T,
+    ) -> Result<ApiResponse, Error> {
+        let json = serde_json::to_vec(body)?;
+
+        if let Some(compressed) = self.compression.compress(&json)? {
+            let request = build_request()
+                .header(("Content-Type", "application/json"))
+                .header(("Content-Encoding", "gzip"))
+                .body(compressed);
+            let response = self.send(request).await?;
+
+            if response.status().as_u16() != UNSUPPORTED_MEDIA_TYPE {
+                return Ok(response);
+            }
+
+            info!("compressed request body refused, disabling request compression");
+            self.compression.set_unsupported();
+        }
+
+        let request = build_request().header(("Content-Type", "application/json")).body(json);
+        self.send(request).await
+    }
+}
+
+/// Builds the headers sent with every request from the config
+fn default_headers(custom_headers: Vec<(String, String)>, locale: Option<String>) -> Vec<(String, String)> {
+    let mut headers = custom_headers;
+
+    if let Some(locale) = locale {
+        // Proton backend expects the `fr_FR` format in `x-pm-locale`
+        headers.push(("x-pm-locale".to_string(), locale.replace('-', "_")));
+        headers.push(("Accept-Language".to_string(), locale));
     }
+
+    headers
 }
 
-impl Default for ProtonWalletApiClient {
-    /// default Proton Wallet api client. It uses `atlas` env
+impl Default for ApiConfig {
+    /// Config of the default client: `atlas` env, no auth and all the
+    /// optional behaviours disabled
     fn default() -> Self {
         let default_app = App::new("Other").unwrap();
-        let config = ApiConfig {
+        ApiConfig {
             spec: (
                 default_app.app_version().to_string(),
                 default_app.user_agent().to_string(),
@@ -315,7 +689,23 @@ impl Default for ProtonWalletApiClient {
             env: None,
             store: None,
             auth: None,
-        };
-        Self::from_config(config).unwrap()
+            compression_threshold: None,
+            request_logging: false,
+            metrics_sink: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            custom_headers: Vec::new(),
+            locale: None,
+            keep_alive_interval: None,
+            response_cache: None,
+            strict_response_validation: false,
+        }
+    }
+}
+
+impl Default for ProtonWalletApiClient {
+    /// default Proton Wallet api client. It uses `atlas` env
+    fn default() -> Self {
+        Self::from_config(ApiConfig::default()).unwrap()
     }
 }
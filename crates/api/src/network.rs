@@ -1,25 +1,39 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use andromeda_common::Network;
+use andromeda_common::{utils::now, Network};
 use serde::Deserialize;
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
     ProtonWalletApiClient, BASE_WALLET_API_V1,
 };
 
+/// Duration during which a fetched network is reused before querying the API
+/// again. The network never changes within a session, so this is mostly a
+/// safety net.
+pub const DEFAULT_NETWORK_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Last fetched network along with the time it was fetched at. Owned by the
+/// [`ProtonWalletApiClient`], so that it is shared by all the network clients
+/// built from it
+pub(crate) type NetworkCache = Arc<Mutex<Option<(Network, Duration)>>>;
+
 #[derive(Clone)]
 pub struct NetworkClient {
     api_client: Arc<ProtonWalletApiClient>,
+    cache: NetworkCache,
+    cache_ttl: Duration,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetNetworkResponseBody {
-    //TODO:: code need to be used. remove all #[allow(dead_code)]
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Network: u8,
 }
 
@@ -33,12 +47,36 @@ impl ApiClient for NetworkClient {
     }
 
     fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
+        Self {
+            cache: api_client.network_cache.clone(),
+            api_client,
+            cache_ttl: DEFAULT_NETWORK_CACHE_TTL,
+        }
     }
 }
 
 impl NetworkClient {
+    /// Overrides the duration during which the fetched network is cached
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Returns the network the backend runs on. The result is cached for
+    /// the client's TTL, use [`NetworkClient::refresh_network`] to bypass the
+    /// cache.
     pub async fn get_network(&self) -> Result<Network, Error> {
+        if let Some((network, fetched_at)) = *self.cache.lock().unwrap() {
+            if now().saturating_sub(fetched_at) < self.cache_ttl {
+                return Ok(network);
+            }
+        }
+
+        self.refresh_network().await
+    }
+
+    /// Fetches the network from the API and updates the cache
+    pub async fn refresh_network(&self) -> Result<Network, Error> {
         let request = self.get("network");
         let response = self.api_client.send(request).await?;
 
@@ -50,12 +88,18 @@ impl NetworkClient {
             _ => Network::Regtest,
         };
 
+        *self.cache.lock().unwrap() = Some((network, now()));
+
         Ok(network)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use andromeda_common::cancellation::CancellationToken;
+    use futures_timer::Delay;
     use muon::env::EnvId;
     use wiremock::{
         matchers::{method, path},
@@ -65,7 +109,8 @@ mod tests {
     use super::NetworkClient;
     use crate::{
         core::ApiClient,
-        tests::utils::{common_api_client, setup_test_connection_arc},
+        error::Error,
+        tests::utils::{common_api_client, setup_test_connection, setup_test_connection_arc},
         BASE_WALLET_API_V1,
     };
 
@@ -121,6 +166,88 @@ mod tests {
         assert_eq!(unmatched_requests.len(), 1, "There should be no unmatched requests");
     }
 
+    #[tokio::test]
+    async fn test_get_network_cached() {
+        let mock_server = MockServer::start().await;
+        let json_body = serde_json::json!(
+        {
+            "Code": 1000,
+            "Network": 1
+        });
+
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_body_json(json_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let network_client = NetworkClient::new(api_client);
+        let first = network_client.get_network().await.unwrap();
+        let second = network_client.clone().get_network().await.unwrap();
+        assert!(matches!(first, super::Network::Testnet));
+        assert_eq!(first, second);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+        let refreshed = network_client.refresh_network().await.unwrap();
+        assert_eq!(refreshed, first);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_network_cached_across_clients() {
+        let mock_server = MockServer::start().await;
+        let json_body = serde_json::json!(
+        {
+            "Code": 1000,
+            "Network": 1
+        });
+
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_body_json(json_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let first = api_client.clients().network.get_network().await.unwrap();
+        let second = api_client.clients().network.get_network().await.unwrap();
+        assert_eq!(first, second);
+
+        // Clones of the api client share the cache as well
+        let third = api_client.clone().clients().network.get_network().await.unwrap();
+        assert_eq!(first, third);
+    }
+
+    #[tokio::test]
+    async fn test_get_network_cache_expired() {
+        let mock_server = MockServer::start().await;
+        let json_body = serde_json::json!(
+        {
+            "Code": 1000,
+            "Network": 0
+        });
+
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_body_json(json_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let network_client = NetworkClient::new(api_client).with_cache_ttl(std::time::Duration::ZERO);
+        network_client.get_network().await.unwrap();
+        network_client.get_network().await.unwrap();
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_network_timeout() {
         let mock_server = MockServer::start().await;
@@ -142,4 +269,32 @@ mod tests {
         let unmatched_requests = mock_server.received_requests().await.unwrap();
         assert_eq!(unmatched_requests.len(), 1, "There should be no unmatched requests");
     }
+
+    #[tokio::test]
+    async fn test_get_network_cancelled() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(10));
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let token = CancellationToken::new();
+        let api_client = setup_test_connection(mock_server.uri()).with_cancellation(token.clone());
+        let network_client = NetworkClient::new(Arc::new(api_client));
+
+        let cancel = async {
+            Delay::new(std::time::Duration::from_millis(100)).await;
+            token.cancel();
+        };
+        let (res, _) = tokio::join!(network_client.get_network(), cancel);
+        assert!(matches!(res, Err(Error::Cancelled(_))));
+
+        // Requests sent after the cancellation aren't sent at all
+        let res = network_client.get_network().await;
+        assert!(matches!(res, Err(Error::Cancelled(_))));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+    }
 }
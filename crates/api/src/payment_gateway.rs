@@ -1,12 +1,13 @@
-use std::{collections::HashMap, fmt, str, sync::Arc};
+use std::{collections::HashMap, fmt, str};
 
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::{
     core::{ApiClient, ProtonResponseExt},
     error::Error,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -162,24 +163,9 @@ pub struct GetPublicAPIKeyResponseBody {
     pub PublicApiKey: String,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct PaymentGatewayClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for PaymentGatewayClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-}
+pub struct PaymentGatewayClient;
 
 impl PaymentGatewayClient {
     pub async fn get_countries(&self) -> Result<CountriesByProvider, Error> {
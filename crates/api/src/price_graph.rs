@@ -1,6 +1,7 @@
-use std::{str, sync::Arc};
+use std::str;
 
 use andromeda_common::BitcoinUnit;
+use andromeda_macros::api_client;
 use serde::Deserialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -8,7 +9,7 @@ use crate::{
     core::{ApiClient, ProtonResponseExt},
     error::Error,
     settings::FiatCurrencySymbol,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Deserialize_repr, Serialize_repr, PartialEq, Debug)]
@@ -45,24 +46,9 @@ pub struct GetGraphDataResponseBody {
     pub PriceGraph: PriceGraph,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct PriceGraphClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for PriceGraphClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-}
+pub struct PriceGraphClient;
 
 impl PriceGraphClient {
     pub async fn get_graph_data(
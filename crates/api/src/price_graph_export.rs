@@ -0,0 +1,147 @@
+//! Render-ready exports of a [`PriceGraph`]: plain arrays for charting
+//! libraries and CSV for downloads.
+//!
+//! Points are converted from minor units to fiat values, optionally rounded
+//! to a chosen number of decimals, and gaps in the series can be filled by
+//! carrying the last known price forward at a fixed interval.
+
+use crate::price_graph::{DataPoint, PriceGraph};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PriceGraphExportOptions {
+    /// Number of decimals of the exported prices. `None` keeps the
+    /// currency's precision, as given by the points' `Cents`
+    pub decimals: Option<u8>,
+    /// Expected interval between two points, in seconds. When set, missing
+    /// points are filled with the previous price
+    pub gap_fill_interval: Option<u64>,
+}
+
+/// Price series as parallel arrays, sorted by timestamp
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PriceSeries {
+    /// Unix timestamps, in seconds
+    pub timestamps: Vec<u64>,
+    /// Fiat prices of one unit of the graph's bitcoin unit
+    pub prices: Vec<f64>,
+    /// Whether the point was added by gap filling rather than returned by
+    /// the API
+    pub filled: Vec<bool>,
+}
+
+fn round(value: f64, decimals: u8) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    (value * factor).round() / factor
+}
+
+fn decimals_of(point: &DataPoint) -> u8 {
+    // Cents is the number of minor units per major one, e.g. 100 → 2 decimals
+    (point.Cents.max(1) as f64).log10().round() as u8
+}
+
+fn price(point: &DataPoint, decimals: u8) -> f64 {
+    round(point.ExchangeRate as f64 / point.Cents.max(1) as f64, decimals)
+}
+
+impl PriceGraph {
+    /// Converts the graph into parallel arrays of timestamps and prices
+    pub fn to_series(&self, options: &PriceGraphExportOptions) -> PriceSeries {
+        let mut points = self.GraphData.iter().collect::<Vec<_>>();
+        points.sort_by_key(|point| point.Timestamp);
+
+        let mut series = PriceSeries::default();
+        let mut push = |timestamp: u64, point: &DataPoint, filled: bool| {
+            let decimals = options.decimals.unwrap_or_else(|| decimals_of(point));
+            series.timestamps.push(timestamp);
+            series.prices.push(price(point, decimals));
+            series.filled.push(filled);
+        };
+
+        let mut previous: Option<&DataPoint> = None;
+        for point in points {
+            if let (Some(previous), Some(interval)) = (previous, options.gap_fill_interval.filter(|i| *i > 0)) {
+                let mut timestamp = previous.Timestamp + interval;
+                // Only fill actual gaps, i.e. at least one whole interval missing
+                while timestamp + interval <= point.Timestamp {
+                    push(timestamp, previous, true);
+                    timestamp += interval;
+                }
+            }
+
+            push(point.Timestamp, point, false);
+            previous = Some(point);
+        }
+
+        series
+    }
+
+    /// Exports the graph as CSV, with a `timestamp,price_<currency>` header
+    pub fn to_csv(&self, options: &PriceGraphExportOptions) -> String {
+        let series = self.to_series(options);
+        let decimals = options
+            .decimals
+            .or_else(|| self.GraphData.first().map(decimals_of))
+            .unwrap_or_default() as usize;
+
+        let mut csv = format!("timestamp,price_{}\n", self.FiatCurrency);
+        for (timestamp, price) in series.timestamps.iter().zip(series.prices.iter()) {
+            csv.push_str(&format!("{},{:.*}\n", timestamp, decimals, price));
+        }
+
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use andromeda_common::BitcoinUnit;
+
+    use super::{PriceGraphExportOptions, PriceSeries};
+    use crate::{
+        price_graph::{DataPoint, PriceGraph},
+        settings::FiatCurrencySymbol,
+    };
+
+    fn graph(points: Vec<(u64, u64)>) -> PriceGraph {
+        PriceGraph {
+            FiatCurrency: FiatCurrencySymbol::EUR,
+            BitcoinUnit: BitcoinUnit::BTC,
+            GraphData: points
+                .into_iter()
+                .map(|(timestamp, exchange_rate)| DataPoint {
+                    ExchangeRate: exchange_rate,
+                    Cents: 100,
+                    Timestamp: timestamp,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_to_series_with_gap_filling() {
+        let graph = graph(vec![(3600, 6170200), (0, 6189900), (14400, 6171400)]);
+        let options = PriceGraphExportOptions {
+            decimals: Some(0),
+            gap_fill_interval: Some(3600),
+        };
+
+        assert_eq!(
+            graph.to_series(&options),
+            PriceSeries {
+                timestamps: vec![0, 3600, 7200, 10800, 14400],
+                prices: vec![61899.0, 61702.0, 61702.0, 61702.0, 61714.0],
+                filled: vec![false, false, true, true, false],
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let graph = graph(vec![(0, 6189950), (3600, 6170200)]);
+
+        assert_eq!(
+            graph.to_csv(&PriceGraphExportOptions::default()),
+            "timestamp,price_EUR\n0,61899.50\n3600,61702.00\n"
+        );
+    }
+}
@@ -1,18 +1,18 @@
-use std::sync::Arc;
-
-use serde::Deserialize;
+use andromeda_macros::api_client;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient, BASE_CORE_API_V4,
+    proton_users::EmptyResponseBody,
+    BASE_CORE_API_V4,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetApiAllKeyResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Address: ApiAllKeyAddress,
 }
 
@@ -59,41 +59,50 @@ pub struct ApiProtonAddressKey {
     pub Flags: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetApiProtonAddressesResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Addresses: Vec<ApiProtonAddress>,
 }
 
-#[derive(Clone)]
-pub struct ProtonEmailAddressClient {
-    api_client: Arc<ProtonWalletApiClient>,
+#[derive(Debug, Deserialize, ResponseBody)]
+#[allow(non_snake_case)]
+struct GetAvailableDomainsResponseBody {
+    pub Code: u16,
+    #[response_body(data)]
+    pub Domains: Vec<String>,
 }
 
-impl ApiClient for ProtonEmailAddressClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_CORE_API_V4
-    }
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+pub struct CreateProtonAddressRequestBody {
+    /// One of the domains returned by
+    /// [`ProtonEmailAddressClient::get_available_domains`]
+    pub Domain: String,
+    pub DisplayName: String,
+    pub Signature: String,
+}
 
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
+#[derive(Debug, Deserialize, ResponseBody)]
+#[allow(non_snake_case)]
+struct CreateProtonAddressResponseBody {
+    pub Code: u16,
+    #[response_body(data)]
+    pub Address: ApiProtonAddress,
 }
 
+#[api_client(base = BASE_CORE_API_V4)]
+#[derive(Clone)]
+pub struct ProtonEmailAddressClient;
+
 impl ProtonEmailAddressClient {
     pub async fn get_proton_email_addresses(&self) -> Result<Vec<ApiProtonAddress>, Error> {
         let request = self.get("addresses");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetApiProtonAddressesResponseBody>()?;
-
-        Ok(parsed.Addresses)
+        response.parse_body::<GetApiProtonAddressesResponseBody>()
     }
 
     pub async fn get_all_public_keys(
@@ -111,12 +120,44 @@ impl ProtonEmailAddressClient {
 
         Ok(parsed.Address.Keys)
     }
+
+    /// Get the domains on which the user is allowed to create a Proton
+    /// address. Returns an empty list when the user's plan doesn't allow it.
+    pub async fn get_available_domains(&self) -> Result<Vec<String>, Error> {
+        let request = self.get("domains/available");
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<GetAvailableDomainsResponseBody>()
+    }
+
+    /// Create the user's first Proton address, using the account username as
+    /// local part. The address is returned without keys, they need to be
+    /// set up separately before it can be used to receive Bitcoin via Email.
+    pub async fn create_proton_email_address(
+        &self,
+        payload: CreateProtonAddressRequestBody,
+    ) -> Result<ApiProtonAddress, Error> {
+        let request = self.post("addresses/setup").body_json(payload)?;
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<CreateProtonAddressResponseBody>()
+    }
+
+    /// Enable a previously disabled Proton address
+    pub async fn enable_proton_email_address(&self, address_id: String) -> Result<u32, Error> {
+        let request = self.put(format!("addresses/{}/enable", address_id));
+
+        let response = self.api_client.send(request).await?;
+        let parsed = response.parse_response::<EmptyResponseBody>()?;
+
+        Ok(parsed.Code)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::ProtonEmailAddressClient;
+    use super::{CreateProtonAddressRequestBody, ProtonEmailAddressClient};
     use crate::{
         core::ApiClient, read_mock_file, tests::utils::common_api_client, tests::utils::setup_test_connection,
         BASE_CORE_API_V4,
@@ -265,4 +306,98 @@ mod tests {
             Err(e) => panic!("Got Err. {:?}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_get_available_domains_success() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/domains/available", BASE_CORE_API_V4);
+        let response_body = serde_json::json!({
+            "Code": 1000,
+            "Domains": ["proton.me", "protonmail.com"]
+        });
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = ProtonEmailAddressClient::new(Arc::new(api_client));
+        let result = client.get_available_domains().await;
+        match result {
+            Ok(domains) => {
+                assert_eq!(domains, vec!["proton.me".to_string(), "protonmail.com".to_string()]);
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_proton_email_address_success() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/addresses/setup", BASE_CORE_API_V4);
+        let request_body = serde_json::json!({
+            "Domain": "proton.me",
+            "DisplayName": "test",
+            "Signature": ""
+        });
+        let response_body = serde_json::json!({
+            "Code": 1000,
+            "Address": {
+                "ID": "E0QUig1OKNXKfL4-tC78xFNHD8kAw0oBj0HUsxWcHAdtCh8XxojpvX6ApkC4VlgCn5miWpqwH9K0Trj4yF2aLg==",
+                "DomainID": null,
+                "Email": "test@proton.me",
+                "Status": 1,
+                "Type": 1,
+                "Receive": 1,
+                "Send": 1,
+                "DisplayName": "test",
+                "Keys": []
+            }
+        });
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .and(body_json(request_body))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = ProtonEmailAddressClient::new(Arc::new(api_client));
+        let result = client
+            .create_proton_email_address(CreateProtonAddressRequestBody {
+                Domain: "proton.me".to_string(),
+                DisplayName: "test".to_string(),
+                Signature: "".to_string(),
+            })
+            .await;
+        match result {
+            Ok(address) => {
+                assert_eq!(address.Email, "test@proton.me");
+                assert_eq!(address.DisplayName, "test");
+                assert_eq!(address.Status, 1);
+                assert!(address.Keys.unwrap().is_empty());
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enable_proton_email_address_success() {
+        let mock_server = MockServer::start().await;
+        let address_id = "E0QUig1OKNXKfL4-tC78xFNHD8kAw0oBj0HUsxWcHAdtCh8XxojpvX6ApkC4VlgCn5miWpqwH9K0Trj4yF2aLg==";
+        let req_path: String = format!("{}/addresses/{}/enable", BASE_CORE_API_V4, address_id);
+        let response = ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 }));
+        Mock::given(method("PUT"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = ProtonEmailAddressClient::new(Arc::new(api_client));
+        let result = client.enable_proton_email_address(address_id.to_string()).await;
+        assert_eq!(result.unwrap(), 1000);
+    }
 }
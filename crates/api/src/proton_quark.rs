@@ -1,7 +1,266 @@
-use std::sync::{Arc, RwLock};
+use andromeda_macros::api_client;
+use serde::Deserialize;
 
-use muon::Client;
+use crate::{
+    core::ApiClient,
+    error::{Error, ResponseError},
+};
 
-pub struct QuarkCommand {
-    pub session: Arc<RwLock<Client>>,
+pub const BASE_QUARK_API: &str = "internal/quark";
+
+const USER_CREATE: &str = "user:create";
+const USER_CREATE_ADDRESS: &str = "user:create:address";
+const WALLET_CREATE: &str = "wallet:create";
+const WALLET_BVE_ENABLE: &str = "wallet:bve:enable";
+const BITCOIN_REGTEST_FUND: &str = "bitcoin:regtest:fund";
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct QuarkUser {
+    pub ID: String,
+    pub Name: String,
+    pub Password: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct QuarkAddress {
+    pub ID: String,
+    pub Email: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct QuarkWallet {
+    pub WalletID: String,
+    pub WalletAccountID: String,
+    pub Mnemonic: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct QuarkFunding {
+    pub TransactionID: String,
+    pub Amount: u64,
+}
+
+/// Everything created by [`QuarkClient::seed_test_user`]
+#[derive(Debug, Clone)]
+pub struct QuarkTestUser {
+    pub user: QuarkUser,
+    pub wallet: QuarkWallet,
+    /// Address receiving Bitcoin via Email, if one was requested
+    pub bve_address: Option<QuarkAddress>,
+    /// Funding transaction, if an amount was requested
+    pub funding: Option<QuarkFunding>,
+}
+
+/// Client for quark commands, the test-only endpoints seeding data in atlas
+/// environments
+#[api_client(base = BASE_QUARK_API)]
+#[derive(Clone)]
+pub struct QuarkClient;
+
+impl QuarkClient {
+    /// Runs a raw quark command and returns its output
+    pub async fn command(&self, command: &str, args: Vec<(&str, String)>) -> Result<String, Error> {
+        let request = args
+            .into_iter()
+            .fold(self.get(format!("raw::{}", command)), |request, arg| request.query(arg));
+
+        let response = self.api_client.send(request).await?;
+        if response.status().is_client_error() || response.status().is_server_error() {
+            return Err(Error::ErrorCode(response.status(), ResponseError::default()));
+        }
+
+        Ok(std::str::from_utf8(response.body())?.to_string())
+    }
+
+    /// Runs a quark command with JSON output
+    async fn json_command<T>(&self, command: &str, mut args: Vec<(&str, String)>) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        args.push(("--format", "json".to_string()));
+        let output = self.command(command, args).await?;
+
+        serde_json::from_str(&output)
+            .map_err(|e| Error::Deserialize(format!("Failed to parse quark output: {}, Output: {}", e, output)))
+    }
+
+    pub async fn create_user(&self, name: &str, password: &str) -> Result<QuarkUser, Error> {
+        self.json_command(
+            USER_CREATE,
+            vec![
+                ("-N", name.to_string()),
+                ("-p", password.to_string()),
+                ("--gen-keys", "Curve25519".to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Creates a wallet for the user. A random mnemonic is generated when none
+    /// is provided.
+    pub async fn create_wallet(&self, user_id: &str, mnemonic: Option<&str>) -> Result<QuarkWallet, Error> {
+        let mut args = vec![("--user-id", user_id.to_string())];
+        if let Some(mnemonic) = mnemonic {
+            args.push(("--mnemonic", mnemonic.to_string()));
+        }
+
+        self.json_command(WALLET_CREATE, args).await
+    }
+
+    /// Creates a new address for the user and enables Bitcoin via Email on it
+    /// for the given wallet account
+    pub async fn seed_bve_address(
+        &self,
+        user_id: &str,
+        wallet_account_id: &str,
+        email: &str,
+    ) -> Result<QuarkAddress, Error> {
+        let address: QuarkAddress = self
+            .json_command(
+                USER_CREATE_ADDRESS,
+                vec![("--user-id", user_id.to_string()), ("--email", email.to_string())],
+            )
+            .await?;
+
+        self.command(
+            WALLET_BVE_ENABLE,
+            vec![
+                ("--address-id", address.ID.clone()),
+                ("--wallet-account-id", wallet_account_id.to_string()),
+            ],
+        )
+        .await?;
+
+        Ok(address)
+    }
+
+    /// Sends `amount` sats to the wallet account on regtest and mines a block
+    /// to confirm the transaction
+    pub async fn fund_regtest_account(&self, wallet_account_id: &str, amount: u64) -> Result<QuarkFunding, Error> {
+        self.json_command(
+            BITCOIN_REGTEST_FUND,
+            vec![
+                ("--wallet-account-id", wallet_account_id.to_string()),
+                ("--amount", amount.to_string()),
+                ("--confirm", "1".to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Seeds a ready-to-use test user in one call: user, wallet, and optionally
+    /// a BvE address and regtest funds
+    pub async fn seed_test_user(
+        &self,
+        name: &str,
+        password: &str,
+        bve_email: Option<&str>,
+        funding_amount: Option<u64>,
+    ) -> Result<QuarkTestUser, Error> {
+        let user = self.create_user(name, password).await?;
+        let wallet = self.create_wallet(&user.ID, None).await?;
+
+        let bve_address = match bve_email {
+            Some(email) => Some(self.seed_bve_address(&user.ID, &wallet.WalletAccountID, email).await?),
+            None => None,
+        };
+
+        let funding = match funding_amount {
+            Some(amount) => Some(self.fund_regtest_account(&wallet.WalletAccountID, amount).await?),
+            None => None,
+        };
+
+        Ok(QuarkTestUser {
+            user,
+            wallet,
+            bve_address,
+            funding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{QuarkClient, BASE_QUARK_API};
+    use crate::{core::ApiClient, error::Error, tests::utils::setup_test_connection_arc};
+
+    async fn mock_command(mock_server: &MockServer, command: &str, body: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(format!("{}/raw::{}", BASE_QUARK_API, command)))
+            .and(query_param("--format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body.to_string()))
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_command_error() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/raw::user:create", BASE_QUARK_API)))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        let client = QuarkClient::new(setup_test_connection_arc(mock_server.uri()));
+
+        let result = client.command("user:create", vec![]).await;
+        assert!(matches!(result, Err(Error::ErrorCode(status, _)) if status.as_u16() == 404));
+    }
+
+    #[tokio::test]
+    async fn test_seed_test_user() {
+        let mock_server = MockServer::start().await;
+        mock_command(
+            &mock_server,
+            "user:create",
+            serde_json::json!({ "ID": "user_id", "Name": "alice", "Password": "password" }),
+        )
+        .await;
+        mock_command(
+            &mock_server,
+            "wallet:create",
+            serde_json::json!({ "WalletID": "wallet_id", "WalletAccountID": "account_id", "Mnemonic": "abandon" }),
+        )
+        .await;
+        mock_command(
+            &mock_server,
+            "user:create:address",
+            serde_json::json!({ "ID": "address_id", "Email": "alice@proton.black" }),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/raw::wallet:bve:enable", BASE_QUARK_API)))
+            .and(query_param("--address-id", "address_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("done"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        mock_command(
+            &mock_server,
+            "bitcoin:regtest:fund",
+            serde_json::json!({ "TransactionID": "txid", "Amount": 100000 }),
+        )
+        .await;
+
+        let client = QuarkClient::new(setup_test_connection_arc(mock_server.uri()));
+        let seeded = client
+            .seed_test_user("alice", "password", Some("alice@proton.black"), Some(100000))
+            .await
+            .unwrap();
+
+        assert_eq!(seeded.user.ID, "user_id");
+        assert_eq!(seeded.wallet.WalletAccountID, "account_id");
+        assert_eq!(seeded.bve_address.unwrap().Email, "alice@proton.black");
+        assert_eq!(seeded.funding.unwrap().Amount, 100000);
+    }
 }
@@ -1,12 +1,11 @@
-use std::sync::Arc;
-
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     core::{ApiClient, ProtonResponseExt},
     error::Error,
     proton_users::{ApiProtonUserSettingsResponse, EmptyResponseBody, ProtonSrpClientProofs, ProtonUserSettings},
-    ProtonWalletApiClient, BASE_CORE_API_V4,
+    BASE_CORE_API_V4,
 };
 
 #[derive(Deserialize, Debug)]
@@ -70,25 +69,15 @@ pub struct SetTwoFaTOTPResponseBody {
     pub UserSettings: ProtonUserSettings,
 }
 
+#[api_client(base = BASE_CORE_API_V4)]
 #[derive(Clone)]
-pub struct ProtonSettingsClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for ProtonSettingsClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_CORE_API_V4
-    }
-}
+pub struct ProtonSettingsClient;
 
+/// Proton settings routes trait. The trait can be used to mock the settings
+/// client in tests.
+/// Note: when adding new methods to this trait, make sure to implement them in
+///  crates/api/src/tests/proton_settings_mock.rs
+///  crates/api/src/proton_settings.rs
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait ProtonSettingsClientExt {
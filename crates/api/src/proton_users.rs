@@ -1,5 +1,4 @@
-use std::sync::Arc;
-
+use andromeda_macros::api_client;
 use muon::rest::core::v4::{keys::salts::KeySalt, users::User};
 use serde::{Deserialize, Serialize};
 
@@ -18,7 +17,7 @@ pub struct UserData {
 use crate::{
     core::{ApiClient, ProtonResponseExt},
     error::Error,
-    ProtonWalletApiClient, BASE_CORE_API_V4,
+    BASE_CORE_API_V4,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -147,6 +146,22 @@ pub struct ProtonUserKey {
     pub Active: u32,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct ApiKeySalt {
+    /// ID of the user key the salt is used for
+    pub ID: String,
+    pub KeySalt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct GetKeySaltsResponseBody {
+    #[allow(dead_code)]
+    pub Code: u32,
+    pub KeySalts: Vec<ApiKeySalt>,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 pub struct ApiUserInfo {
@@ -213,6 +228,11 @@ pub struct EmptyResponseBody {
     pub Code: u32,
 }
 
+/// Proton users routes trait. The trait can be used to mock the users client
+/// in tests.
+/// Note: when adding new methods to this trait, make sure to implement them in
+///  crates/api/src/tests/proton_users_mock.rs
+///  crates/api/src/proton_users.rs
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait ProtonUsersClientExt {
@@ -232,27 +252,18 @@ pub trait ProtonUsersClientExt {
     // get proton user settings.
     //  used for 2fa settings and password recovery etc..
     async fn get_user_settings(&self) -> Result<ProtonUserSettings, Error>;
-}
-
-#[derive(Clone)]
-pub struct ProtonUsersClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
 
-impl ApiClient for ProtonUsersClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
+    // get the salts used to derive the passphrases of the user's keys.
+    async fn get_key_salts(&self) -> Result<Vec<ApiKeySalt>, Error>;
 
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_CORE_API_V4
-    }
+    // get the user's keys, without the rest of the user info.
+    async fn get_user_keys(&self) -> Result<Vec<ProtonUserKey>, Error>;
 }
 
+#[api_client(base = BASE_CORE_API_V4)]
+#[derive(Clone)]
+pub struct ProtonUsersClient;
+
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl ProtonUsersClientExt for ProtonUsersClient {
@@ -315,6 +326,21 @@ impl ProtonUsersClientExt for ProtonUsersClient {
         let parsed = response.parse_response::<ApiProtonUserSettingsResponse>()?;
         Ok(parsed.UserSettings)
     }
+
+    // get the salts used to derive the passphrases of the user's keys.
+    async fn get_key_salts(&self) -> Result<Vec<ApiKeySalt>, Error> {
+        let request = self.get("keys/salts");
+
+        let response = self.api_client.send(request).await?;
+        let parsed = response.parse_response::<GetKeySaltsResponseBody>()?;
+        Ok(parsed.KeySalts)
+    }
+
+    // get the user's keys, without the rest of the user info.
+    async fn get_user_keys(&self) -> Result<Vec<ProtonUserKey>, Error> {
+        let user = self.get_user_info().await?;
+        Ok(user.Keys.unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
@@ -413,6 +439,39 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_key_salts_code_1000() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "KeySalts": [
+                    {
+                        "ID": "IlnTbqicN-2HfUGIn-ki8bqZfLqNj5ErUB0z24Qx5g-4NvrrIc6GLvEpj2EPfwGDv28aKYVRRrSgEFhR_zhlkA==",
+                        "KeySalt": "7kZ0b7WkN6TtZi2Ng+jDxA=="
+                    },
+                    {
+                        "ID": "Jp8kbqicN-2HfUGIn-ki8bqZfLqNj5ErUB0z24Qx5g-4NvrrIc6GLvEpj2EPfwGDv28aKYVRRrSgEFhR_zhlkA==",
+                        "KeySalt": null
+                    }
+                ]
+            }
+        );
+        let req_path: String = format!("{}/keys/salts", BASE_CORE_API_V4);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let users_client = ProtonUsersClient::new(api_client);
+        let key_salts = users_client.get_key_salts().await.unwrap();
+        assert_eq!(key_salts.len(), 2);
+        assert_eq!(key_salts[0].KeySalt, Some("7kZ0b7WkN6TtZi2Ng+jDxA==".to_string()));
+        assert_eq!(key_salts[1].KeySalt, None);
+    }
+
     #[tokio::test]
     async fn test_get_user_info_deserialize_error() {
         let mock_server = MockServer::start().await;
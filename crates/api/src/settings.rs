@@ -1,14 +1,14 @@
-use core::fmt;
-use std::sync::Arc;
-
 use andromeda_common::BitcoinUnit;
+/// Name of [`andromeda_common::FiatCurrency`] in the API payloads
+pub use andromeda_common::FiatCurrency as FiatCurrencySymbol;
+use andromeda_macros::api_client;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, MaybeStale, ProtonResponseExt, ResponseBody},
     error::Error,
-    ProtonWalletApiClient, BASE_WALLET_API_V1,
+    BASE_WALLET_API_V1,
 };
 
 #[derive(Deserialize_repr, Serialize_repr, PartialEq, Debug)]
@@ -21,111 +21,6 @@ pub enum UserReceiveNotificationEmailTypes {
     Unsupported,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, Default)]
-#[allow(clippy::upper_case_acronyms)]
-pub enum FiatCurrencySymbol {
-    ALL,
-    DZD,
-    ARS,
-    AMD,
-    AUD,
-    AZN,
-    BHD,
-    BDT,
-    BYN,
-    BMD,
-    BOB,
-    BAM,
-    BRL,
-    BGN,
-    KHR,
-    CAD,
-    CLP,
-    CNY,
-    COP,
-    CRC,
-    HRK,
-    CUP,
-    CZK,
-    DKK,
-    DOP,
-    EGP,
-    EUR,
-    GEL,
-    GHS,
-    GTQ,
-    HNL,
-    HKD,
-    HUF,
-    ISK,
-    INR,
-    IDR,
-    IRR,
-    IQD,
-    ILS,
-    JMD,
-    JPY,
-    JOD,
-    KZT,
-    KES,
-    KWD,
-    KGS,
-    LBP,
-    MKD,
-    MYR,
-    MUR,
-    MXN,
-    MDL,
-    MNT,
-    MAD,
-    MMK,
-    NAD,
-    NPR,
-    TWD,
-    NZD,
-    NIO,
-    NGN,
-    NOK,
-    OMR,
-    PKR,
-    PAB,
-    PEN,
-    PHP,
-    PLN,
-    GBP,
-    QAR,
-    RON,
-    RUB,
-    SAR,
-    RSD,
-    SGD,
-    ZAR,
-    KRW,
-    SSP,
-    VES,
-    LKR,
-    SEK,
-    CHF,
-    THB,
-    TTD,
-    TND,
-    TRY,
-    UGX,
-    UAH,
-    AED,
-    #[default]
-    USD,
-    UYU,
-    UZS,
-    VND,
-}
-
-impl fmt::Display for FiatCurrencySymbol {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 pub struct UserSettings {
@@ -147,12 +42,11 @@ pub struct UserSettings {
     pub WalletCreated: Option<u8>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetUserSettingsResponseBody {
-    //TODO:: code need to be used. remove all #[allow(dead_code)]
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletUserSettings: UserSettings,
 }
 
@@ -187,41 +81,35 @@ struct UpdateReceiveNotificationEmailRequestBody {
     pub IsEnabled: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetUserWalletEligibilityResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub IsEligible: u8,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct SettingsClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for SettingsClient {
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-}
+pub struct SettingsClient;
 
 impl SettingsClient {
     pub async fn get_user_settings(&self) -> Result<UserSettings, Error> {
         let request = self.get("settings");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
+        response.parse_body::<GetUserSettingsResponseBody>()
+    }
 
-        Ok(parsed.WalletUserSettings)
+    /// Same as [`SettingsClient::get_user_settings`], serving the last
+    /// fetched settings when the API is unreachable, see
+    /// [`ApiConfig::response_cache`](crate::ApiConfig::response_cache)
+    pub async fn get_user_settings_or_cached(&self) -> Result<MaybeStale<UserSettings>, Error> {
+        let request = self.get("settings");
+
+        self.api_client
+            .send_or_cached::<GetUserSettingsResponseBody>(request)
+            .await
     }
 
     pub async fn update_bitcoin_unit(&self, symbol: BitcoinUnit) -> Result<UserSettings, Error> {
@@ -230,9 +118,7 @@ impl SettingsClient {
             .body_json(UpdateBitcoinUnitRequestBody { Symbol: symbol })?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn update_fiat_currency(&self, symbol: FiatCurrencySymbol) -> Result<UserSettings, Error> {
@@ -241,9 +127,7 @@ impl SettingsClient {
             .body_json(UpdateFiatCurrencyRequestBody { Symbol: symbol })?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn update_two_fa_threshold(&self, amount: u64) -> Result<UserSettings, Error> {
@@ -254,9 +138,7 @@ impl SettingsClient {
             })?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn update_hide_empty_used_addresses(
@@ -270,9 +152,7 @@ impl SettingsClient {
             })?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn update_receive_notification_email(
@@ -288,27 +168,21 @@ impl SettingsClient {
             })?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn accept_terms_and_conditions(&self) -> Result<UserSettings, Error> {
         let request = self.put("settings/terms-and-conditions/accept");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserSettingsResponseBody>()?;
-
-        Ok(parsed.WalletUserSettings)
+        response.parse_body::<GetUserSettingsResponseBody>()
     }
 
     pub async fn get_user_wallet_eligibility(&self) -> Result<u8, Error> {
         let request = self.get("settings/eligible");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetUserWalletEligibilityResponseBody>()?;
-
-        Ok(parsed.IsEligible)
+        response.parse_body::<GetUserWalletEligibilityResponseBody>()
     }
 }
 
@@ -5,8 +5,8 @@ pub mod mock_utils {
     use crate::{
         error::Error,
         proton_users::{
-            GetAuthInfoRequest, GetAuthInfoResponseBody, GetAuthModulusResponse, ProtonSrpClientProofs, ProtonUser,
-            ProtonUserSettings, ProtonUsersClientExt,
+            ApiKeySalt, GetAuthInfoRequest, GetAuthInfoResponseBody, GetAuthModulusResponse, ProtonSrpClientProofs,
+            ProtonUser, ProtonUserKey, ProtonUserSettings, ProtonUsersClientExt,
         },
     };
 
@@ -29,6 +29,10 @@ pub mod mock_utils {
             async fn get_user_info(&self) -> Result<ProtonUser, Error>;
 
             async fn get_user_settings(&self) -> Result<ProtonUserSettings, Error>;
+
+            async fn get_key_salts(&self) -> Result<Vec<ApiKeySalt>, Error>;
+
+            async fn get_user_keys(&self) -> Result<Vec<ProtonUserKey>, Error>;
         }
 
         #[cfg(not(target_arch = "wasm32"))]
@@ -47,6 +51,10 @@ pub mod mock_utils {
             async fn get_user_info(&self) -> Result<ProtonUser, Error>;
 
             async fn get_user_settings(&self) -> Result<ProtonUserSettings, Error>;
+
+            async fn get_key_salts(&self) -> Result<Vec<ApiKeySalt>, Error>;
+
+            async fn get_user_keys(&self) -> Result<Vec<ProtonUserKey>, Error>;
         }
     }
 }
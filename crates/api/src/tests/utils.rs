@@ -6,15 +6,21 @@ pub fn test_spec() -> (String, String) {
     ("web-wallet@5.0.999.999-dev".to_string(),"Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string())
 }
 
-pub fn setup_test_connection(url: String) -> ProtonWalletApiClient {
-    let config = ApiConfig {
+/// Default config targeting the given url, to be tweaked with the struct
+/// update syntax: `ApiConfig { request_logging: true, ..test_config(url) }`
+pub fn test_config(url: String) -> ApiConfig {
+    ApiConfig {
         spec: test_spec(),
-        url_prefix: None,
         env: Some(url),
-        store: None,
-        auth: None,
-    };
+        ..Default::default()
+    }
+}
 
+pub fn setup_test_connection(url: String) -> ProtonWalletApiClient {
+    ProtonWalletApiClient::from_config(test_config(url)).unwrap()
+}
+
+pub fn setup_test_connection_with_config(config: ApiConfig) -> ProtonWalletApiClient {
     ProtonWalletApiClient::from_config(config).unwrap()
 }
 
@@ -24,11 +30,8 @@ pub fn setup_test_connection_arc(url: String) -> Arc<ProtonWalletApiClient> {
 
 pub async fn common_api_client() -> Arc<ProtonWalletApiClient> {
     let config = ApiConfig {
-        spec: test_spec(),
-        url_prefix: None,
         env: None,
-        store: None,
-        auth: None,
+        ..test_config(String::new())
     };
     let api = ProtonWalletApiClient::from_config(config).unwrap();
     api.login("bart", "bart").await.unwrap();
@@ -3,6 +3,7 @@ pub mod mock_utils {
     use mockall::mock;
 
     use crate::{
+        core::MaybeStale,
         error::Error,
         wallet::{
             ApiEmailAddress, ApiWallet, ApiWalletAccount, ApiWalletData, ApiWalletSettings, ApiWalletTransaction,
@@ -20,6 +21,8 @@ pub mod mock_utils {
         impl WalletClientExt for WalletClient {
             async fn get_wallets(&self) -> Result<Vec<ApiWalletData>, Error>;
 
+            async fn get_wallets_or_cached(&self) -> Result<MaybeStale<Vec<ApiWalletData>>, Error>;
+
             async fn create_wallet(&self, payload: CreateWalletRequestBody) -> Result<ApiWalletData, Error>;
 
             async fn migrate(&self, wallet_id: String, payload: WalletMigrateRequestBody) -> Result<(), Error>;
@@ -30,6 +33,13 @@ pub mod mock_utils {
 
             async fn get_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
 
+            async fn get_wallet_accounts_or_cached(
+                &self,
+                wallet_id: String,
+            ) -> Result<MaybeStale<Vec<ApiWalletAccount>>, Error>;
+
+            async fn get_hidden_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
+
             async fn get_wallet_account_addresses(
                 &self,
                 wallet_id: String,
@@ -62,6 +72,18 @@ pub mod mock_utils {
                 wallet_account_ids: Vec<String>,
             ) -> Result<Vec<ApiWalletAccount>, Error>;
 
+            async fn hide_wallet_account(
+                &self,
+                wallet_id: String,
+                wallet_account_id: String,
+            ) -> Result<ApiWalletAccount, Error>;
+
+            async fn unhide_wallet_account(
+                &self,
+                wallet_id: String,
+                wallet_account_id: String,
+            ) -> Result<ApiWalletAccount, Error>;
+
             async fn add_email_address(
                 &self,
                 wallet_id: String,
@@ -153,6 +175,12 @@ pub mod mock_utils {
             ) -> Result<(), Error>;
 
             async fn disable_show_wallet_recovery(&self, wallet_id: String) -> Result<ApiWalletSettings, Error>;
+
+            async fn update_wallet_hide_accounts(
+                &self,
+                wallet_id: String,
+                hide_accounts: bool,
+            ) -> Result<ApiWalletSettings, Error>;
         }
 
         #[cfg(not(target_arch = "wasm32"))]
@@ -160,6 +188,8 @@ pub mod mock_utils {
         impl WalletClientExt for WalletClient {
             async fn get_wallets(&self) -> Result<Vec<ApiWalletData>, Error>;
 
+            async fn get_wallets_or_cached(&self) -> Result<MaybeStale<Vec<ApiWalletData>>, Error>;
+
             async fn create_wallet(&self, payload: CreateWalletRequestBody) -> Result<ApiWalletData, Error>;
 
             async fn migrate(&self, wallet_id: String, payload: WalletMigrateRequestBody) -> Result<(), Error>;
@@ -170,6 +200,13 @@ pub mod mock_utils {
 
             async fn get_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
 
+            async fn get_wallet_accounts_or_cached(
+                &self,
+                wallet_id: String,
+            ) -> Result<MaybeStale<Vec<ApiWalletAccount>>, Error>;
+
+            async fn get_hidden_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
+
             async fn get_wallet_account_addresses(
                 &self,
                 wallet_id: String,
@@ -202,6 +239,18 @@ pub mod mock_utils {
                 wallet_account_ids: Vec<String>,
             ) -> Result<Vec<ApiWalletAccount>, Error>;
 
+            async fn hide_wallet_account(
+                &self,
+                wallet_id: String,
+                wallet_account_id: String,
+            ) -> Result<ApiWalletAccount, Error>;
+
+            async fn unhide_wallet_account(
+                &self,
+                wallet_id: String,
+                wallet_account_id: String,
+            ) -> Result<ApiWalletAccount, Error>;
+
             async fn add_email_address(
                 &self,
                 wallet_id: String,
@@ -293,6 +342,12 @@ pub mod mock_utils {
             ) -> Result<(), Error>;
 
             async fn disable_show_wallet_recovery(&self, wallet_id: String) -> Result<ApiWalletSettings, Error>;
+
+            async fn update_wallet_hide_accounts(
+                &self,
+                wallet_id: String,
+                hide_accounts: bool,
+            ) -> Result<ApiWalletSettings, Error>;
         }
     }
 }
@@ -1,13 +1,13 @@
-use std::{collections::HashMap, sync::Arc};
+use std::collections::HashMap;
 
-use bitcoin::{consensus::deserialize, Transaction};
+use andromeda_macros::api_client;
+use bitcoin::{consensus::deserialize, FeeRate, Transaction};
 use serde::{Deserialize, Serialize};
 
 use super::{error::Error, BASE_WALLET_API_V1};
 use crate::{
     address::ApiTx,
-    core::{ApiClient, ProtonResponseExt},
-    ProtonWalletApiClient,
+    core::{ApiClient, ProtonResponseExt, ResponseBody},
 };
 
 #[derive(Debug, Serialize)]
@@ -33,11 +33,11 @@ struct BroadcastRawTransactionRequestBody {
     IsAnonymous: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct BroadcastRawTransactionResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub TransactionID: String,
 }
 
@@ -57,19 +57,19 @@ pub struct ApiTransactionStatus {
     pub BlockTime: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTransactionStatusResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub TransactionStatus: ApiTransactionStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTransactionInfoResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Transaction: ApiTx,
 }
 #[derive(Debug, Deserialize)]
@@ -80,19 +80,19 @@ pub struct TransactionMerkleProof {
     pub Position: u16,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTransactionMerkleProofResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Proof: TransactionMerkleProof,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetTransactionMerkleBlockProofResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub PartialMerkleTree: String,
 }
 
@@ -105,25 +105,25 @@ pub struct OutpointSpendingStatus {
     pub TransactionStatus: Option<ApiTransactionStatus>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetOutpointSpendingStatusResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Outspend: OutpointSpendingStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetFeeEstimateResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub FeeEstimates: HashMap<String, f64>,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
-pub struct RecommendedFees {
+pub struct ApiRecommendedFees {
     /// Fee rate in sat/vB to place the transaction in the first mempool block
     pub FastestFee: u8,
     /// Fee rate in sat/vB to usually confirm within half hour and place the transaction in between the first and second mempool blocks
@@ -136,12 +136,40 @@ pub struct RecommendedFees {
     pub MinimumFee: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetRecommendedFeesResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
-    pub RecommendedFees: RecommendedFees,
+    #[response_body(data)]
+    pub RecommendedFees: ApiRecommendedFees,
+}
+
+/// Typed version of [`ApiRecommendedFees`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecommendedFees {
+    /// Fee rate to place the transaction in the first mempool block
+    pub fastest_fee: FeeRate,
+    /// Fee rate to usually confirm within half hour
+    pub half_hour_fee: FeeRate,
+    /// Fee rate to usually confirm within one hour
+    pub hour_fee: FeeRate,
+    /// Either 2 times the minimum fees, or the low priority rate (whichever is
+    /// lower)
+    pub economy_fee: FeeRate,
+    /// Minimum fee rate for transaction to be accepted
+    pub minimum_fee: FeeRate,
+}
+
+impl From<ApiRecommendedFees> for RecommendedFees {
+    fn from(fees: ApiRecommendedFees) -> Self {
+        RecommendedFees {
+            fastest_fee: FeeRate::from_sat_per_vb_unchecked(fees.FastestFee.into()),
+            half_hour_fee: FeeRate::from_sat_per_vb_unchecked(fees.HalfHourFee.into()),
+            hour_fee: FeeRate::from_sat_per_vb_unchecked(fees.HourFee.into()),
+            economy_fee: FeeRate::from_sat_per_vb_unchecked(fees.EconomyFee.into()),
+            minimum_fee: FeeRate::from_sat_per_vb_unchecked(fees.MinimumFee.into()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -159,11 +187,11 @@ pub struct MempoolInfo {
     pub FullRbf: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetMempoolInfoResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub MempoolInfo: MempoolInfo,
 }
 
@@ -172,24 +200,9 @@ pub enum ExchangeRateOrTransactionTime {
     TransactionTime(String),
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct TransactionClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for TransactionClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-}
+pub struct TransactionClient;
 
 impl TransactionClient {
     #[allow(clippy::too_many_arguments)]
@@ -228,9 +241,7 @@ impl TransactionClient {
         let request = self.post("transactions").body_json(body)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<BroadcastRawTransactionResponseBody>()?;
-
-        Ok(parsed.TransactionID)
+        response.parse_body::<BroadcastRawTransactionResponseBody>()
     }
 
     pub async fn get_raw_transaction(&self, txid: String) -> Result<Transaction, Error> {
@@ -246,9 +257,7 @@ impl TransactionClient {
         let request = self.get(format!("transactions/{}/status", txid));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetTransactionStatusResponseBody>()?;
-
-        Ok(parsed.TransactionStatus)
+        response.parse_body::<GetTransactionStatusResponseBody>()
     }
 
     pub async fn get_transaction_info(&self, txid: String) -> Result<Option<ApiTx>, Error> {
@@ -268,18 +277,14 @@ impl TransactionClient {
         let request = self.get(format!("transactions/{}/merkle-proof", txid));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetTransactionMerkleProofResponseBody>()?;
-
-        Ok(parsed.Proof)
+        response.parse_body::<GetTransactionMerkleProofResponseBody>()
     }
 
     pub async fn get_transaction_merkle_block_proof(&self, txid: String) -> Result<String, Error> {
         let request = self.get(format!("transactions/{}/merkleblock-proof", txid));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetTransactionMerkleBlockProofResponseBody>()?;
-
-        Ok(parsed.PartialMerkleTree)
+        response.parse_body::<GetTransactionMerkleBlockProofResponseBody>()
     }
 
     pub async fn get_outpoint_spending_status(
@@ -290,36 +295,32 @@ impl TransactionClient {
         let request = self.get(format!("transactions/{}/outspend/{}", txid, index));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetOutpointSpendingStatusResponseBody>()?;
-
-        Ok(parsed.Outspend)
+        response.parse_body::<GetOutpointSpendingStatusResponseBody>()
     }
 
     pub async fn get_fee_estimates(&self) -> Result<HashMap<String, f64>, Error> {
         let request = self.get("transactions/fee-estimates");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetFeeEstimateResponseBody>()?;
-
-        Ok(parsed.FeeEstimates)
+        response.parse_body::<GetFeeEstimateResponseBody>()
     }
 
     pub async fn get_mempool_info(&self) -> Result<MempoolInfo, Error> {
         let request = self.get("mempool/info");
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetMempoolInfoResponseBody>()?;
-
-        Ok(parsed.MempoolInfo)
+        response.parse_body::<GetMempoolInfoResponseBody>()
     }
 
+    /// Get recommended fee rates for the fastest, half-hour, hour, economy and
+    /// minimum confirmation targets
     pub async fn get_recommended_fees(&self) -> Result<RecommendedFees, Error> {
         let request = self.get("fees/recommended");
 
         let response = self.api_client.send(request).await?;
         let parsed = response.parse_response::<GetRecommendedFeesResponseBody>()?;
 
-        Ok(parsed.RecommendedFees)
+        Ok(parsed.RecommendedFees.into())
     }
 }
 
@@ -333,7 +334,7 @@ mod tests {
         transaction::ExchangeRateOrTransactionTime,
         BASE_WALLET_API_V1,
     };
-    use bitcoin::transaction::Version;
+    use bitcoin::{transaction::Version, FeeRate};
     use std::{collections::HashMap, sync::Arc};
     use wiremock::{
         matchers::{body_json, method, path, query_param},
@@ -712,6 +713,44 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_recommended_fees_success() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/fees/recommended", BASE_WALLET_API_V1);
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "RecommendedFees": {
+                    "FastestFee": 12,
+                    "HalfHourFee": 9,
+                    "HourFee": 7,
+                    "EconomyFee": 4,
+                    "MinimumFee": 2
+                }
+            }
+        );
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = TransactionClient::new(Arc::new(api_client));
+        let result = client.get_recommended_fees().await;
+        match result {
+            Ok(fees) => {
+                assert_eq!(fees.fastest_fee, FeeRate::from_sat_per_vb_unchecked(12));
+                assert_eq!(fees.half_hour_fee, FeeRate::from_sat_per_vb_unchecked(9));
+                assert_eq!(fees.hour_fee, FeeRate::from_sat_per_vb_unchecked(7));
+                assert_eq!(fees.economy_fee, FeeRate::from_sat_per_vb_unchecked(4));
+                assert_eq!(fees.minimum_fee, FeeRate::from_sat_per_vb_unchecked(2));
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
     #[tokio::test]
     async fn test_broadcast_transaction_success() {
         let mock_server = MockServer::start().await;
@@ -1,6 +1,12 @@
 use std::sync::Arc;
 
-use crate::{core::ApiClient, error::Error, ProtonWalletApiClient};
+use serde::Deserialize;
+
+use crate::{
+    core::{ApiClient, ProtonResponseExt},
+    error::Error,
+    ProtonWalletApiClient,
+};
 
 #[derive(Debug)]
 pub struct UnleashResponse {
@@ -8,6 +14,36 @@ pub struct UnleashResponse {
     pub body: Vec<u8>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UnleashVariantPayload {
+    #[serde(rename = "type")]
+    pub payload_type: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UnleashVariant {
+    pub name: String,
+    pub enabled: bool,
+    pub payload: Option<UnleashVariantPayload>,
+}
+
+/// A feature flag enabled for the user. Disabled flags are not returned by
+/// the backend.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UnleashToggle {
+    pub name: String,
+    pub enabled: bool,
+    #[serde(rename = "impressionData", default)]
+    pub impression_data: bool,
+    pub variant: Option<UnleashVariant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetTogglesResponseBody {
+    toggles: Vec<UnleashToggle>,
+}
+
 #[derive(Clone)]
 pub struct UnleashClient {
     api_client: Arc<ProtonWalletApiClient>,
@@ -38,6 +74,14 @@ impl UnleashClient {
             body: body.to_vec(),
         })
     }
+
+    /// Same as [`UnleashClient::fetch_toggles`], with the toggles parsed
+    pub async fn get_toggles(&self) -> Result<Vec<UnleashToggle>, Error> {
+        let request = self.get("feature/v2/frontend");
+        let response = self.api_client.send(request).await?;
+        let parsed = response.parse_response::<GetTogglesResponseBody>()?;
+        Ok(parsed.toggles)
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +130,25 @@ mod tests {
             Err(e) => panic!("Got Err. {:?}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_get_toggles_success() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = "feature/v2/frontend".to_string();
+        let contents = read_mock_file!("fetch_toggles_1000_body");
+        let response = ResponseTemplate::new(200).set_body_string(contents);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = UnleashClient::new(Arc::new(api_client));
+        let toggles = client.get_toggles().await.unwrap();
+
+        assert_eq!(toggles.len(), 3);
+        assert_eq!(toggles[0].name, "Mock001");
+        assert!(toggles[0].enabled);
+        assert!(!toggles[0].variant.as_ref().unwrap().enabled);
+    }
 }
@@ -1,17 +1,16 @@
 use core::fmt;
-use std::sync::Arc;
 
+use andromeda_macros::{api_client, Builder};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use super::BASE_WALLET_API_V1;
 use crate::{
-    core::{ApiClient, ProtonResponseExt},
+    core::{ApiClient, MaybeStale, ProtonResponseExt, ResponseBody},
     error::Error,
     exchange_rate::ApiExchangeRate,
     settings::FiatCurrencySymbol,
     wallet_ext::WalletClientExt,
-    ProtonWalletApiClient,
 };
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
@@ -44,17 +43,20 @@ pub struct ApiWallet {
     pub Legacy: Option<u8>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Builder)]
+#[builder(validate = "validate_create_wallet")]
 #[allow(non_snake_case)]
 pub struct CreateWalletRequestBody {
     /// Name of the wallet
     pub Name: String,
     /// 0 if the wallet is created with Proton Wallet
+    #[builder(default)]
     pub IsImported: u8,
     /// 1 is onchain, 2 is lightning
     pub Type: u8,
     /// 1 if the wallet has a passphrase. We don't store it but clients need to
     /// request on first wallet access.
+    #[builder(default)]
     pub HasPassphrase: u8,
     /// Encrypted user Id
     pub UserKeyID: String,
@@ -73,9 +75,20 @@ pub struct CreateWalletRequestBody {
     pub PublicKey: Option<String>,
     /// Flag that indicates the wallet is created from auto creation. 0 for no,
     /// 1 for yes
+    #[builder(default)]
     pub IsAutoCreated: u8,
 }
 
+fn validate_create_wallet(body: &CreateWalletRequestBody) -> Result<(), Error> {
+    if body.Mnemonic.is_some() && body.Fingerprint.is_none() {
+        return Err(Error::InvalidRequestBody(
+            "Fingerprint is required with Mnemonic".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 #[allow(non_snake_case)]
 pub struct ApiWalletKey {
@@ -104,10 +117,11 @@ pub struct ApiWalletData {
     pub WalletSettings: ApiWalletSettings,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 pub struct GetWalletsResponseBody {
     pub Code: u16,
+    #[response_body(data)]
     pub Wallets: Vec<ApiWalletData>,
 }
 
@@ -126,11 +140,11 @@ pub struct UpdateWalletNameRequestBody {
     pub Name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateWalletNameResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Wallet: ApiWallet,
 }
 
@@ -147,6 +161,17 @@ pub struct ApiWalletAccount {
     pub Priority: u32,
     pub ScriptType: u8,
     pub Addresses: Vec<ApiEmailAddress>,
+    /// 1 if the account has been hidden (archived) by the user
+    pub Hidden: Option<u8>,
+    /// Number of consecutive unused addresses after which a full scan stops,
+    /// when the user raised it above the default one
+    pub StopGap: Option<u32>,
+}
+
+impl ApiWalletAccount {
+    pub fn is_hidden(&self) -> bool {
+        self.Hidden.unwrap_or_default() == 1
+    }
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -156,23 +181,23 @@ pub struct ApiEmailAddress {
     pub Email: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetWalletAccountsResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Accounts: Vec<ApiWalletAccount>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetWalletAccountAddressesResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Addresses: Vec<ApiEmailAddress>,
 }
 
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Default, Clone, Builder)]
 #[allow(non_snake_case)]
 pub struct CreateWalletAccountRequestBody {
     pub DerivationPath: String,
@@ -180,11 +205,11 @@ pub struct CreateWalletAccountRequestBody {
     pub ScriptType: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct CreateWalletAccountResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Account: ApiWalletAccount,
 }
 
@@ -218,26 +243,25 @@ pub struct AddEmailAddressRequestBody {
     pub AddressID: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateWalletAccountResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Account: ApiWalletAccount,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateWalletAccountsOrderResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub Accounts: Vec<ApiWalletAccount>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct DeleteWalletAccountResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
 }
 
@@ -292,15 +316,15 @@ impl fmt::Display for WalletTransactionFlag {
 
 const HASHED_TRANSACTION_ID_KEY: &str = "HashedTransactionIDs[]";
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct GetWalletTransactionsResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletTransactions: Vec<ApiWalletTransaction>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Builder)]
 #[allow(non_snake_case)]
 pub struct CreateWalletTransactionRequestBody {
     /// Encrypted with user key
@@ -316,19 +340,19 @@ pub struct CreateWalletTransactionRequestBody {
     pub TransactionTime: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct CreateWalletTransactionResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletTransaction: ApiWalletTransaction,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateWalletTransactionResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletTransaction: ApiWalletTransaction,
 }
 
@@ -353,18 +377,23 @@ pub struct UpdateWalletTransactionExternalSenderRequestBody {
     pub Sender: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct DeleteWalletTransactionResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+pub struct UpdateWalletHideAccountsRequestBody {
+    pub HideAccounts: u8,
+}
+
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct UpdateWalletSettingsResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
+    #[response_body(data)]
     pub WalletSettings: ApiWalletSettings,
 }
 
@@ -414,31 +443,15 @@ pub struct WalletMigrateRequestBody {
     pub WalletTransactions: Vec<MigratedWalletTransaction>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ResponseBody)]
 #[allow(non_snake_case)]
 struct WalletMigrateResponseBody {
-    #[allow(dead_code)]
     pub Code: u16,
 }
 
+#[api_client(base = BASE_WALLET_API_V1)]
 #[derive(Clone)]
-pub struct WalletClient {
-    api_client: Arc<ProtonWalletApiClient>,
-}
-
-impl ApiClient for WalletClient {
-    fn api_client(&self) -> &Arc<ProtonWalletApiClient> {
-        &self.api_client
-    }
-
-    fn base_url(&self) -> &str {
-        BASE_WALLET_API_V1
-    }
-
-    fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
-        Self { api_client }
-    }
-}
+pub struct WalletClient;
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -446,8 +459,12 @@ impl WalletClientExt for WalletClient {
     async fn get_wallets(&self) -> Result<Vec<ApiWalletData>, Error> {
         let request = self.get("wallets");
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetWalletsResponseBody>()?;
-        Ok(parsed.Wallets)
+        response.parse_body::<GetWalletsResponseBody>()
+    }
+
+    async fn get_wallets_or_cached(&self) -> Result<MaybeStale<Vec<ApiWalletData>>, Error> {
+        let request = self.get("wallets");
+        self.api_client.send_or_cached::<GetWalletsResponseBody>(request).await
     }
 
     async fn create_wallet(&self, payload: CreateWalletRequestBody) -> Result<ApiWalletData, Error> {
@@ -465,31 +482,42 @@ impl WalletClientExt for WalletClient {
     async fn migrate(&self, wallet_id: String, payload: WalletMigrateRequestBody) -> Result<(), Error> {
         let request = self.post(format!("wallets/{}/migrate", wallet_id)).body_json(payload)?;
         let response = self.api_client.send(request).await?;
-        response.parse_response::<WalletMigrateResponseBody>()?;
-        Ok(())
+        response.parse_body::<WalletMigrateResponseBody>()
     }
 
     async fn update_wallet_name(&self, wallet_id: String, name: String) -> Result<ApiWallet, Error> {
         let payload = UpdateWalletNameRequestBody { Name: name };
         let request = self.put(format!("wallets/{}/name", wallet_id)).body_json(payload)?;
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletNameResponseBody>()?;
-        Ok(parsed.Wallet)
+        response.parse_body::<UpdateWalletNameResponseBody>()
     }
 
     async fn delete_wallet(&self, wallet_id: String) -> Result<(), Error> {
         let request = self.delete(format!("wallets/{}", wallet_id));
         let response = self.api_client.send(request).await?;
-        response.parse_response::<DeleteWalletAccountResponseBody>()?;
-        Ok(())
+        response.parse_body::<DeleteWalletAccountResponseBody>()
     }
 
     async fn get_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error> {
         let request = self.get(format!("wallets/{}/accounts", wallet_id));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetWalletAccountsResponseBody>()?;
+        response.parse_body::<GetWalletAccountsResponseBody>()
+    }
 
-        Ok(parsed.Accounts)
+    async fn get_wallet_accounts_or_cached(
+        &self,
+        wallet_id: String,
+    ) -> Result<MaybeStale<Vec<ApiWalletAccount>>, Error> {
+        let request = self.get(format!("wallets/{}/accounts", wallet_id));
+        self.api_client
+            .send_or_cached::<GetWalletAccountsResponseBody>(request)
+            .await
+    }
+
+    async fn get_hidden_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error> {
+        let accounts = self.get_wallet_accounts(wallet_id).await?;
+
+        Ok(accounts.into_iter().filter(|account| account.is_hidden()).collect())
     }
 
     async fn get_wallet_account_addresses(
@@ -502,9 +530,7 @@ impl WalletClientExt for WalletClient {
             wallet_id, wallet_account_id
         ));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetWalletAccountAddressesResponseBody>()?;
-
-        Ok(parsed.Addresses)
+        response.parse_body::<GetWalletAccountAddressesResponseBody>()
     }
 
     async fn create_wallet_account(
@@ -517,9 +543,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<CreateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<CreateWalletAccountResponseBody>()
     }
 
     async fn update_wallet_account_fiat_currency(
@@ -539,9 +563,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn update_wallet_account_label(
@@ -557,9 +579,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn update_wallet_accounts_order(
@@ -576,9 +596,29 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountsOrderResponseBody>()?;
+        response.parse_body::<UpdateWalletAccountsOrderResponseBody>()
+    }
+
+    async fn hide_wallet_account(
+        &self,
+        wallet_id: String,
+        wallet_account_id: String,
+    ) -> Result<ApiWalletAccount, Error> {
+        let request = self.put(format!("wallets/{}/accounts/{}/hide", wallet_id, wallet_account_id));
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<UpdateWalletAccountResponseBody>()
+    }
 
-        Ok(parsed.Accounts)
+    async fn unhide_wallet_account(
+        &self,
+        wallet_id: String,
+        wallet_account_id: String,
+    ) -> Result<ApiWalletAccount, Error> {
+        let request = self.put(format!("wallets/{}/accounts/{}/unhide", wallet_id, wallet_account_id));
+
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn add_email_address(
@@ -597,9 +637,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn update_wallet_account_last_used_index(
@@ -620,9 +658,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn remove_email_address(
@@ -637,17 +673,13 @@ impl WalletClientExt for WalletClient {
         ));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletAccountResponseBody>()?;
-
-        Ok(parsed.Account)
+        response.parse_body::<UpdateWalletAccountResponseBody>()
     }
 
     async fn delete_wallet_account(&self, wallet_id: String, wallet_account_id: String) -> Result<(), Error> {
         let request = self.delete(format!("wallets/{}/accounts/{}", wallet_id, wallet_account_id));
         let response = self.api_client.send(request).await?;
-        response.parse_response::<DeleteWalletAccountResponseBody>()?;
-
-        Ok(())
+        response.parse_body::<DeleteWalletAccountResponseBody>()
     }
 
     async fn get_wallet_transactions(
@@ -667,9 +699,7 @@ impl WalletClientExt for WalletClient {
             request = request.query((HASHED_TRANSACTION_ID_KEY, txid));
         }
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetWalletTransactionsResponseBody>()?;
-
-        Ok(parsed.WalletTransactions)
+        response.parse_body::<GetWalletTransactionsResponseBody>()
     }
 
     async fn get_wallet_transactions_to_hash(
@@ -688,9 +718,7 @@ impl WalletClientExt for WalletClient {
         });
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<GetWalletTransactionsResponseBody>()?;
-
-        Ok(parsed.WalletTransactions)
+        response.parse_body::<GetWalletTransactionsResponseBody>()
     }
 
     async fn create_wallet_transaction(
@@ -707,9 +735,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<CreateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<CreateWalletTransactionResponseBody>()
     }
 
     async fn update_wallet_transaction_label(
@@ -729,9 +755,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<UpdateWalletTransactionResponseBody>()
     }
 
     async fn update_wallet_transaction_hashed_txid(
@@ -753,9 +777,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<UpdateWalletTransactionResponseBody>()
     }
 
     async fn update_external_wallet_transaction_sender(
@@ -775,9 +797,7 @@ impl WalletClientExt for WalletClient {
             .body_json(payload)?;
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<UpdateWalletTransactionResponseBody>()
     }
 
     async fn set_wallet_transaction_flag(
@@ -793,9 +813,7 @@ impl WalletClientExt for WalletClient {
         ));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<UpdateWalletTransactionResponseBody>()
     }
 
     async fn delete_wallet_transaction_flag(
@@ -810,9 +828,7 @@ impl WalletClientExt for WalletClient {
             wallet_id, wallet_account_id, wallet_transaction_id, flag
         ));
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletTransactionResponseBody>()?;
-
-        Ok(parsed.WalletTransaction)
+        response.parse_body::<UpdateWalletTransactionResponseBody>()
     }
 
     async fn delete_wallet_transaction(
@@ -826,18 +842,30 @@ impl WalletClientExt for WalletClient {
             wallet_id, wallet_account_id, wallet_transaction_id
         ));
         let response = self.api_client.send(request).await?;
-        response.parse_response::<DeleteWalletTransactionResponseBody>()?;
-
-        Ok(())
+        response.parse_body::<DeleteWalletTransactionResponseBody>()
     }
 
     async fn disable_show_wallet_recovery(&self, wallet_id: String) -> Result<ApiWalletSettings, Error> {
         let request = self.put(format!("wallets/{}/settings/show-wallet-recovery/disable", wallet_id));
 
         let response = self.api_client.send(request).await?;
-        let parsed = response.parse_response::<UpdateWalletSettingsResponseBody>()?;
+        response.parse_body::<UpdateWalletSettingsResponseBody>()
+    }
+
+    async fn update_wallet_hide_accounts(
+        &self,
+        wallet_id: String,
+        hide_accounts: bool,
+    ) -> Result<ApiWalletSettings, Error> {
+        let payload = UpdateWalletHideAccountsRequestBody {
+            HideAccounts: hide_accounts.into(),
+        };
+        let request = self
+            .put(format!("wallets/{}/settings/accounts/hide", wallet_id))
+            .body_json(payload)?;
 
-        Ok(parsed.WalletSettings)
+        let response = self.api_client.send(request).await?;
+        response.parse_body::<UpdateWalletSettingsResponseBody>()
     }
 }
 
@@ -865,13 +893,37 @@ mod tests {
             AddEmailAddressRequestBody, MigratedWallet, MigratedWalletAccount, MigratedWalletTransaction,
             UpdateWalletAccountFiatCurrencyRequestBody, UpdateWalletAccountLabelRequestBody,
             UpdateWalletAccountLastUsedIndexRequestBody, UpdateWalletAccountsOrderRequestBody,
-            UpdateWalletNameRequestBody, UpdateWalletTransactionExternalSenderRequestBody,
-            UpdateWalletTransactionHashedTxidRequestBody, UpdateWalletTransactionLabelRequestBody, WalletClientExt,
-            WalletMigrateRequestBody, WalletTransactionFlag,
+            UpdateWalletHideAccountsRequestBody, UpdateWalletNameRequestBody,
+            UpdateWalletTransactionExternalSenderRequestBody, UpdateWalletTransactionHashedTxidRequestBody,
+            UpdateWalletTransactionLabelRequestBody, WalletClientExt, WalletMigrateRequestBody, WalletTransactionFlag,
         },
         BASE_WALLET_API_V1,
     };
 
+    #[test]
+    fn should_build_create_wallet_request_body() {
+        let builder = CreateWalletRequestBody::builder()
+            .name("wallet name")
+            .r#type(1)
+            .user_key_id("user key id")
+            .wallet_key("wallet key")
+            .wallet_key_signature("wallet key signature");
+
+        let body = builder.clone().public_key("public key".to_string()).build().unwrap();
+        assert_eq!(body.IsImported, 0);
+        assert_eq!(body.Mnemonic, None);
+        assert_eq!(body.PublicKey, Some("public key".to_string()));
+
+        assert!(matches!(
+            CreateWalletRequestBody::builder().name("wallet name").build(),
+            Err(Error::MissingField("Type"))
+        ));
+        assert!(matches!(
+            builder.mnemonic("mnemonic".to_string()).build(),
+            Err(Error::InvalidRequestBody(_))
+        ));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn should_get_wallets() {
@@ -2878,4 +2930,140 @@ mod tests {
             Err(e) => panic!("Got Err. {:?}", e),
         }
     }
+
+    fn wallet_account_json(wallet_account_id: &str, hidden: u8) -> serde_json::Value {
+        serde_json::json!({
+            "ID": wallet_account_id,
+            "WalletID": "_zuc9hOPmSeNUPoBlvFs2JvjWw_hX4ktpVnqKmpAhh3PcAGXNVJqU_jD2ZoZ_qTteGsa30m8mHG8GiWt_7L0xg==",
+            "FiatCurrency": "CHF",
+            "DerivationPath": "84'/0'/0'",
+            "Label": "yEYMWfpITIiHiVtqImHb/4yCvoDnLWbr93FkE8NKwTwjKOEVFQ==",
+            "LastUsedIndex": 0,
+            "PoolSize": 10,
+            "Priority": 1,
+            "ScriptType": 3,
+            "Addresses": [],
+            "Hidden": hidden
+        })
+    }
+
+    #[tokio::test]
+    async fn test_hide_wallet_account_success() {
+        let wallet_id = "_zuc9hOPmSeNUPoBlvFs2JvjWw_hX4ktpVnqKmpAhh3PcAGXNVJqU_jD2ZoZ_qTteGsa30m8mHG8GiWt_7L0xg==";
+        let wallet_account_id =
+            "yYzIuZJobta-FCUwbhCdUwCXtn-BLoW0yZvVNJK5MCh0KT-igpGYa3zd_uNz43gKTD9BXrRaDlT4uRhdo70y_A==";
+
+        let mock_server = MockServer::start().await;
+        for (action, hidden) in [("hide", 1), ("unhide", 0)] {
+            let req_path = format!(
+                "{}/wallets/{}/accounts/{}/{}",
+                BASE_WALLET_API_V1, wallet_id, wallet_account_id, action
+            );
+            let response_body = serde_json::json!({
+                "Code": 1000,
+                "Account": wallet_account_json(wallet_account_id, hidden),
+            });
+            Mock::given(method("PUT"))
+                .and(path(req_path))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+                .mount(&mock_server)
+                .await;
+        }
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = WalletClient::new(api_client);
+
+        let result = client
+            .hide_wallet_account(wallet_id.to_string(), wallet_account_id.to_string())
+            .await;
+        match result {
+            Ok(account) => {
+                assert_eq!(account.ID, wallet_account_id);
+                assert!(account.is_hidden());
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+
+        let result = client
+            .unhide_wallet_account(wallet_id.to_string(), wallet_account_id.to_string())
+            .await;
+        match result {
+            Ok(account) => {
+                assert_eq!(account.ID, wallet_account_id);
+                assert!(!account.is_hidden());
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_hidden_wallet_accounts_success() {
+        let wallet_id = "_zuc9hOPmSeNUPoBlvFs2JvjWw_hX4ktpVnqKmpAhh3PcAGXNVJqU_jD2ZoZ_qTteGsa30m8mHG8GiWt_7L0xg==";
+        let hidden_account_id =
+            "yYzIuZJobta-FCUwbhCdUwCXtn-BLoW0yZvVNJK5MCh0KT-igpGYa3zd_uNz43gKTD9BXrRaDlT4uRhdo70y_A==";
+
+        let mock_server = MockServer::start().await;
+        let req_path = format!("{}/wallets/{}/accounts", BASE_WALLET_API_V1, wallet_id);
+        let mut legacy_account = wallet_account_json("legacy_account_id", 0);
+        // Accounts fetched before the field was introduced
+        legacy_account.as_object_mut().unwrap().remove("Hidden");
+        let response_body = serde_json::json!({
+            "Code": 1000,
+            "Accounts": [
+                wallet_account_json(hidden_account_id, 1),
+                wallet_account_json("visible_account_id", 0),
+                legacy_account,
+            ]
+        });
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = WalletClient::new(api_client);
+
+        let result = client.get_hidden_wallet_accounts(wallet_id.to_string()).await;
+        match result {
+            Ok(accounts) => {
+                assert_eq!(accounts.len(), 1);
+                assert_eq!(accounts[0].ID, hidden_account_id);
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_hide_accounts_success() {
+        let wallet_id = "_zuc9hOPmSeNUPoBlvFs2JvjWw_hX4ktpVnqKmpAhh3PcAGXNVJqU_jD2ZoZ_qTteGsa30m8mHG8GiWt_7L0xg==";
+        let mock_server = MockServer::start().await;
+        let req_path = format!("{}/wallets/{}/settings/accounts/hide", BASE_WALLET_API_V1, wallet_id);
+        let response_body = serde_json::json!({
+            "Code": 1000,
+            "WalletSettings": {
+                "WalletID": wallet_id,
+                "HideAccounts": 1,
+                "InvoiceDefaultDescription": null,
+                "InvoiceExpirationTime": 3600,
+                "MaxChannelOpeningFee": 1,
+                "ShowWalletRecovery": false
+            }
+        });
+        Mock::given(method("PUT"))
+            .and(path(req_path))
+            .and(body_json(UpdateWalletHideAccountsRequestBody { HideAccounts: 1 }))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = WalletClient::new(api_client);
+
+        let result = client.update_wallet_hide_accounts(wallet_id.to_string(), true).await;
+        match result {
+            Ok(wallet_settings) => {
+                assert_eq!(wallet_settings.WalletID, wallet_id);
+                assert_eq!(wallet_settings.HideAccounts, 1);
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
 }
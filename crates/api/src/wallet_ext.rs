@@ -1,4 +1,5 @@
 use crate::{
+    core::MaybeStale,
     error::Error,
     settings::FiatCurrencySymbol,
     wallet::{
@@ -19,6 +20,11 @@ use crate::{
 pub trait WalletClientExt {
     async fn get_wallets(&self) -> Result<Vec<ApiWalletData>, Error>;
 
+    /// Same as [`WalletClientExt::get_wallets`], serving the last fetched
+    /// wallets when the API is unreachable, see
+    /// [`ApiConfig::response_cache`](crate::ApiConfig::response_cache)
+    async fn get_wallets_or_cached(&self) -> Result<MaybeStale<Vec<ApiWalletData>>, Error>;
+
     async fn create_wallet(&self, payload: CreateWalletRequestBody) -> Result<ApiWalletData, Error>;
 
     async fn migrate(&self, wallet_id: String, payload: WalletMigrateRequestBody) -> Result<(), Error>;
@@ -29,6 +35,16 @@ pub trait WalletClientExt {
 
     async fn get_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
 
+    /// Same as [`WalletClientExt::get_wallet_accounts`], serving the last
+    /// fetched accounts when the API is unreachable
+    async fn get_wallet_accounts_or_cached(
+        &self,
+        wallet_id: String,
+    ) -> Result<MaybeStale<Vec<ApiWalletAccount>>, Error>;
+
+    /// Accounts hidden by the user, see [`ApiWalletAccount::is_hidden`]
+    async fn get_hidden_wallet_accounts(&self, wallet_id: String) -> Result<Vec<ApiWalletAccount>, Error>;
+
     async fn get_wallet_account_addresses(
         &self,
         wallet_id: String,
@@ -61,6 +77,20 @@ pub trait WalletClientExt {
         wallet_account_ids: Vec<String>,
     ) -> Result<Vec<ApiWalletAccount>, Error>;
 
+    /// Hides (archives) the account, it stays synced but apps shouldn't list
+    /// it anymore unless the user asks for hidden accounts
+    async fn hide_wallet_account(
+        &self,
+        wallet_id: String,
+        wallet_account_id: String,
+    ) -> Result<ApiWalletAccount, Error>;
+
+    async fn unhide_wallet_account(
+        &self,
+        wallet_id: String,
+        wallet_account_id: String,
+    ) -> Result<ApiWalletAccount, Error>;
+
     async fn add_email_address(
         &self,
         wallet_id: String,
@@ -152,6 +182,13 @@ pub trait WalletClientExt {
     ) -> Result<(), Error>;
 
     async fn disable_show_wallet_recovery(&self, wallet_id: String) -> Result<ApiWalletSettings, Error>;
+
+    /// Updates the wallet's `HideAccounts` setting
+    async fn update_wallet_hide_accounts(
+        &self,
+        wallet_id: String,
+        hide_accounts: bool,
+    ) -> Result<ApiWalletSettings, Error>;
 }
 
 #[cfg(test)]
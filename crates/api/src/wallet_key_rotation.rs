@@ -0,0 +1,391 @@
+//! Wallet key rotation, requested by the backend through the
+//! `MigrationRequired` and `Legacy` flags of a wallet.
+//!
+//! The api crate doesn't hold any key: [`rotate_wallet_key`] fetches what
+//! needs to be re-encrypted, hands it to the caller's
+//! [`WalletKeyRotationCrypto`] and sends the result to the migrate endpoint.
+
+use std::fmt::{Debug, Display};
+
+use crate::{
+    error::Error,
+    wallet::{
+        ApiWallet, ApiWalletData, ApiWalletKey, ApiWalletTransaction, MigratedWallet, MigratedWalletAccount,
+        MigratedWalletTransaction, WalletMigrateRequestBody,
+    },
+    wallet_ext::WalletClientExt,
+};
+
+/// Armored form of a wallet key, as stored by the backend
+#[derive(Debug, Clone, Default)]
+#[allow(non_snake_case)]
+pub struct EncryptedWalletKey {
+    pub UserKeyID: String,
+    /// Wallet key encrypted with the user key, base64 encoded
+    pub WalletKey: String,
+    /// Detached signature of the wallet key, as armored PGP
+    pub WalletKeySignature: String,
+}
+
+/// Crypto operations needed to rotate a wallet key, implemented by the apps
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait WalletKeyRotationCrypto {
+    /// Decrypted wallet key
+    type Key;
+    type Error: Debug + Display;
+
+    /// Decrypts the current wallet key with the user key
+    async fn decrypt_wallet_key(&self, wallet_key: &ApiWalletKey) -> Result<Self::Key, Self::Error>;
+
+    /// Generates a new wallet key, returned along with its armored form
+    async fn generate_wallet_key(&self) -> Result<(Self::Key, EncryptedWalletKey), Self::Error>;
+
+    /// Decrypts data encrypted with the wallet key. `legacy` is set for
+    /// mnemonics of wallets flagged as `Legacy`
+    async fn decrypt(&self, key: &Self::Key, data: &str, legacy: bool) -> Result<String, Self::Error>;
+
+    async fn encrypt(&self, key: &Self::Key, data: &str) -> Result<String, Self::Error>;
+
+    /// Hashes the transaction's id with the new wallet key
+    async fn hash_transaction_id(
+        &self,
+        key: &Self::Key,
+        transaction: &ApiWalletTransaction,
+    ) -> Result<String, Self::Error>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WalletKeyRotationError<E: Debug + Display> {
+    #[error("An API error occurred during the wallet key rotation: \n\t{0}")]
+    Api(#[from] Error),
+    #[error("A crypto error occurred during the wallet key rotation: \n\t{0}")]
+    Crypto(E),
+}
+
+/// Whether the backend asks for the wallet key to be rotated
+pub fn needs_key_rotation(wallet: &ApiWallet) -> bool {
+    wallet.MigrationRequired.unwrap_or_default() == 1 || wallet.Legacy.unwrap_or_default() == 1
+}
+
+/// Rotates the wallet key if the backend asks for it: the wallet name and
+/// mnemonic, the accounts labels and the transactions labels and hashed ids
+/// are re-encrypted with a new wallet key, then sent to the migrate endpoint.
+///
+/// Returns whether the wallet was migrated.
+pub async fn rotate_wallet_key<W, C>(
+    wallet_client: &W,
+    crypto: &C,
+    wallet_data: &ApiWalletData,
+) -> Result<bool, WalletKeyRotationError<C::Error>>
+where
+    W: WalletClientExt,
+    C: WalletKeyRotationCrypto,
+{
+    let wallet = &wallet_data.Wallet;
+    if !needs_key_rotation(wallet) {
+        return Ok(false);
+    }
+
+    let legacy = wallet.Legacy.unwrap_or_default() == 1;
+    let old_key = crypto
+        .decrypt_wallet_key(&wallet_data.WalletKey)
+        .await
+        .map_err(WalletKeyRotationError::Crypto)?;
+    let (new_key, encrypted_key) = crypto
+        .generate_wallet_key()
+        .await
+        .map_err(WalletKeyRotationError::Crypto)?;
+
+    let reencrypt = |data: String, legacy: bool| {
+        let (old_key, new_key) = (&old_key, &new_key);
+        async move {
+            let decrypted = crypto.decrypt(old_key, &data, legacy).await?;
+            crypto.encrypt(new_key, &decrypted).await
+        }
+    };
+
+    let mnemonic = match wallet.Mnemonic.clone() {
+        Some(mnemonic) => reencrypt(mnemonic, legacy)
+            .await
+            .map_err(WalletKeyRotationError::Crypto)?,
+        None => String::new(),
+    };
+    let migrated_wallet = MigratedWallet {
+        Name: reencrypt(wallet.Name.clone(), false)
+            .await
+            .map_err(WalletKeyRotationError::Crypto)?,
+        UserKeyID: encrypted_key.UserKeyID,
+        WalletKey: encrypted_key.WalletKey,
+        WalletKeySignature: encrypted_key.WalletKeySignature,
+        Mnemonic: mnemonic,
+        Fingerprint: wallet.Fingerprint.clone().unwrap_or_default(),
+    };
+
+    let mut migrated_accounts = Vec::new();
+    for account in wallet_client.get_wallet_accounts(wallet.ID.clone()).await? {
+        migrated_accounts.push(MigratedWalletAccount {
+            Label: reencrypt(account.Label, false)
+                .await
+                .map_err(WalletKeyRotationError::Crypto)?,
+            ID: account.ID,
+        });
+    }
+
+    let mut migrated_transactions = Vec::new();
+    for transaction in wallet_client
+        .get_wallet_transactions(wallet.ID.clone(), None, None)
+        .await?
+    {
+        let label = match transaction.Label.clone() {
+            Some(label) => Some(reencrypt(label, false).await.map_err(WalletKeyRotationError::Crypto)?),
+            None => None,
+        };
+        let hashed_transaction_id = crypto
+            .hash_transaction_id(&new_key, &transaction)
+            .await
+            .map_err(WalletKeyRotationError::Crypto)?;
+
+        migrated_transactions.push(MigratedWalletTransaction {
+            ID: transaction.ID,
+            WalletAccountID: transaction.WalletAccountID.unwrap_or_default(),
+            HashedTransactionID: Some(hashed_transaction_id),
+            Label: label,
+        });
+    }
+
+    let payload = WalletMigrateRequestBody {
+        Wallet: migrated_wallet,
+        WalletAccounts: migrated_accounts,
+        WalletTransactions: migrated_transactions,
+    };
+    wallet_client.migrate(wallet.ID.clone(), payload).await?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{rotate_wallet_key, EncryptedWalletKey, WalletKeyRotationCrypto};
+    use crate::{
+        core::ApiClient,
+        tests::utils::setup_test_connection_arc,
+        wallet::{ApiWallet, ApiWalletData, ApiWalletKey, ApiWalletTransaction, WalletClient},
+        BASE_WALLET_API_V1,
+    };
+
+    /// "Encrypts" by prefixing the data with the key
+    struct PrefixCrypto;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl WalletKeyRotationCrypto for PrefixCrypto {
+        type Key = String;
+        type Error = Infallible;
+
+        async fn decrypt_wallet_key(&self, wallet_key: &ApiWalletKey) -> Result<String, Infallible> {
+            Ok(wallet_key.WalletKey.clone())
+        }
+
+        async fn generate_wallet_key(&self) -> Result<(String, EncryptedWalletKey), Infallible> {
+            Ok((
+                "new".to_string(),
+                EncryptedWalletKey {
+                    UserKeyID: "user_key_id".to_string(),
+                    WalletKey: "new".to_string(),
+                    WalletKeySignature: "signature".to_string(),
+                },
+            ))
+        }
+
+        async fn decrypt(&self, key: &String, data: &str, legacy: bool) -> Result<String, Infallible> {
+            let key = if legacy { format!("legacy-{}", key) } else { key.clone() };
+            Ok(data.strip_prefix(&format!("{}:", key)).unwrap().to_string())
+        }
+
+        async fn encrypt(&self, key: &String, data: &str) -> Result<String, Infallible> {
+            Ok(format!("{}:{}", key, data))
+        }
+
+        async fn hash_transaction_id(
+            &self,
+            key: &String,
+            transaction: &ApiWalletTransaction,
+        ) -> Result<String, Infallible> {
+            Ok(format!("{}#{}", key, transaction.TransactionID))
+        }
+    }
+
+    fn wallet_data(migration_required: u8) -> ApiWalletData {
+        ApiWalletData {
+            Wallet: ApiWallet {
+                ID: "wallet_id".to_string(),
+                Name: "old:My wallet".to_string(),
+                Mnemonic: Some("legacy-old:abandon".to_string()),
+                Fingerprint: Some("fingerprint".to_string()),
+                MigrationRequired: Some(migration_required),
+                Legacy: Some(1),
+                ..Default::default()
+            },
+            WalletKey: ApiWalletKey {
+                WalletKey: "old".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotate_wallet_key() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/wallets/wallet_id/accounts", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Accounts": [{
+                    "ID": "account_id",
+                    "WalletID": "wallet_id",
+                    "FiatCurrency": "USD",
+                    "DerivationPath": "m/84'/0'/0'",
+                    "Label": "old:Savings",
+                    "LastUsedIndex": 0,
+                    "PoolSize": 0,
+                    "Priority": 1,
+                    "ScriptType": 3,
+                    "Addresses": []
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/wallets/wallet_id/transactions", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "WalletTransactions": [{
+                    "ID": "transaction_id",
+                    "WalletID": "wallet_id",
+                    "WalletAccountID": "account_id",
+                    "Label": "old:Rent",
+                    "TransactionID": "txid",
+                    "TransactionTime": "1714116130",
+                    "IsSuspicious": 0,
+                    "IsPrivate": 0
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/wallets/wallet_id/migrate", BASE_WALLET_API_V1)))
+            .and(body_partial_json(serde_json::json!({
+                "Wallet": {
+                    "Name": "new:My wallet",
+                    "UserKeyID": "user_key_id",
+                    "WalletKey": "new",
+                    "Mnemonic": "new:abandon",
+                    "Fingerprint": "fingerprint"
+                },
+                "WalletAccounts": [{ "ID": "account_id", "Label": "new:Savings" }],
+                "WalletTransactions": [{
+                    "ID": "transaction_id",
+                    "WalletAccountID": "account_id",
+                    "HashedTransactionID": "new#txid",
+                    "Label": "new:Rent"
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "Code": 1000 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = WalletClient::new(setup_test_connection_arc(mock_server.uri()));
+
+        let migrated = rotate_wallet_key(&client, &PrefixCrypto, &wallet_data(1))
+            .await
+            .unwrap();
+        assert!(migrated);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_wallet_key_not_required() {
+        let mock_server = MockServer::start().await;
+        let client = WalletClient::new(setup_test_connection_arc(mock_server.uri()));
+
+        let mut wallet_data = wallet_data(0);
+        wallet_data.Wallet.Legacy = Some(0);
+
+        let migrated = rotate_wallet_key(&client, &PrefixCrypto, &wallet_data).await.unwrap();
+        assert!(!migrated);
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+
+    /// Exercises the rotation against the mocked client, without a server
+    #[cfg(feature = "mocking")]
+    mod mocked_client {
+        use super::{wallet_data, PrefixCrypto};
+        use crate::{
+            error::Error,
+            tests::wallet_mock::mock_utils::MockWalletClient,
+            wallet::{ApiWalletAccount, ApiWalletTransaction},
+            wallet_key_rotation::{rotate_wallet_key, WalletKeyRotationError},
+        };
+
+        #[tokio::test]
+        async fn test_rotate_wallet_key_sends_reencrypted_payload() {
+            let mut wallet_client = MockWalletClient::new();
+            wallet_client
+                .expect_get_wallet_accounts()
+                .withf(|wallet_id| wallet_id == "wallet_id")
+                .return_once(|_| {
+                    Ok(vec![ApiWalletAccount {
+                        ID: "account_id".to_string(),
+                        Label: "old:Savings".to_string(),
+                        ..Default::default()
+                    }])
+                });
+            wallet_client.expect_get_wallet_transactions().return_once(|_, _, _| {
+                Ok(vec![ApiWalletTransaction {
+                    ID: "transaction_id".to_string(),
+                    TransactionID: "txid".to_string(),
+                    Label: None,
+                    ..Default::default()
+                }])
+            });
+            wallet_client
+                .expect_migrate()
+                .withf(|wallet_id, payload| {
+                    wallet_id == "wallet_id"
+                        && payload.Wallet.Name == "new:My wallet"
+                        && payload.Wallet.Mnemonic == "new:abandon"
+                        && payload.WalletAccounts[0].Label == "new:Savings"
+                        && payload.WalletTransactions[0].HashedTransactionID.as_deref() == Some("new#txid")
+                        && payload.WalletTransactions[0].Label.is_none()
+                })
+                .times(1)
+                .return_once(|_, _| Ok(()));
+
+            let migrated = rotate_wallet_key(&wallet_client, &PrefixCrypto, &wallet_data(1))
+                .await
+                .unwrap();
+            assert!(migrated);
+        }
+
+        #[tokio::test]
+        async fn test_rotate_wallet_key_does_not_migrate_on_api_error() {
+            let mut wallet_client = MockWalletClient::new();
+            wallet_client
+                .expect_get_wallet_accounts()
+                .return_once(|_| Err(Error::Http));
+            wallet_client.expect_migrate().never();
+
+            let result = rotate_wallet_key(&wallet_client, &PrefixCrypto, &wallet_data(1)).await;
+            assert!(matches!(result, Err(WalletKeyRotationError::Api(Error::Http))));
+        }
+    }
+}
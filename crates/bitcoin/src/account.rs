@@ -4,13 +4,15 @@ use andromeda_common::{utils::now, Network, ScriptType};
 use async_std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use bdk_wallet::{
     bitcoin::{
-        bip32::{ChildNumber, DerivationPath, Xpriv},
+        bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
         constants::genesis_block,
         psbt::Psbt as BdkPsbt,
         secp256k1::Secp256k1,
-        Address, Network as BdkNetwork, Transaction, Txid,
+        Address, FeeRate, Network as BdkNetwork, OutPoint, Transaction, Txid,
     },
-    descriptor, AddressInfo, Balance as BdkBalance, ChangeSet, KeychainKind, LocalOutput as LocalUtxo, PersistedWallet,
+    descriptor,
+    error::BuildFeeBumpError,
+    AddressInfo, Balance as BdkBalance, ChangeSet, KeychainKind, LocalOutput as LocalUtxo, PersistedWallet,
     SignOptions, Update, Wallet as BdkWallet, WalletPersister,
 };
 use bitcoin::{params::Params, Amount};
@@ -22,6 +24,7 @@ use crate::{
     bdk_wallet_ext::BdkWalletExt,
     blockchain_client::BlockchainClient,
     error::Error,
+    external_signer::{ExternalSigner, ExternalSignerWrapper, EXTERNAL_SIGNER_ORDERING},
     psbt::Psbt,
     storage::{WalletConnectorFactory, WalletPersisterConnector},
     transactions::{ToTransactionDetails, TransactionDetails},
@@ -73,6 +76,8 @@ fn build_account_descriptors(
         ScriptType::NestedSegwit => |xkey: (Xpriv, DerivationPath)| descriptor!(sh(wpkh(xkey))),
         ScriptType::NativeSegwit => |xkey: (Xpriv, DerivationPath)| descriptor!(wpkh(xkey)),
         ScriptType::Taproot => |xkey: (Xpriv, DerivationPath)| descriptor!(tr(xkey)),
+        // Multisig descriptors need the cosigners' keys
+        ScriptType::NativeSegwitMultisig => return Err(Error::UnsupportedScriptType(script_type)),
     };
 
     let internal = builder((
@@ -94,6 +99,39 @@ fn build_account_descriptors(
     Ok((external, internal))
 }
 
+/// Same as [`build_account_descriptors`] but from the account's extended
+/// public key, hence without any signing key
+fn build_account_public_descriptors(
+    account_xpub: Xpub,
+    script_type: ScriptType,
+) -> Result<(ReturnedDescriptor, ReturnedDescriptor), Error> {
+    let builder = match script_type {
+        ScriptType::Legacy => |xkey: (Xpub, DerivationPath)| descriptor!(pkh(xkey)),
+        ScriptType::NestedSegwit => |xkey: (Xpub, DerivationPath)| descriptor!(sh(wpkh(xkey))),
+        ScriptType::NativeSegwit => |xkey: (Xpub, DerivationPath)| descriptor!(wpkh(xkey)),
+        ScriptType::Taproot => |xkey: (Xpub, DerivationPath)| descriptor!(tr(xkey)),
+        ScriptType::NativeSegwitMultisig => return Err(Error::UnsupportedScriptType(script_type)),
+    };
+
+    let internal = builder((
+        account_xpub,
+        vec![ChildNumber::Normal {
+            index: KeychainKind::Internal as u32,
+        }]
+        .into(),
+    ))?;
+
+    let external = builder((
+        account_xpub,
+        vec![ChildNumber::Normal {
+            index: KeychainKind::External as u32,
+        }]
+        .into(),
+    ))?;
+
+    Ok((external, internal))
+}
+
 impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     fn build_wallet_with_descriptors(
         external_descriptor: ReturnedDescriptor,
@@ -131,13 +169,10 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     }
 
     fn build_wallet(
-        account_xprv: Xpriv,
+        (external_descriptor, internal_descriptor): (ReturnedDescriptor, ReturnedDescriptor),
         network: Network,
-        script_type: ScriptType,
         persister: &mut P,
     ) -> Result<PersistedWallet<P>, Error> {
-        let (external_descriptor, internal_descriptor) = build_account_descriptors(account_xprv, script_type)?;
-
         let wallet = Self::build_wallet_with_descriptors(
             external_descriptor.clone(),
             internal_descriptor.clone(),
@@ -210,9 +245,42 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
             derivation_path,
             persister_connector: connector.clone(),
             wallet: Arc::new(RwLock::new(Self::build_wallet(
-                account_xprv,
+                build_account_descriptors(account_xprv, script_type)?,
+                network,
+                &mut persister,
+            )?)),
+        })
+    }
+
+    /// From an account extended public key, returns a watch-only bitcoin
+    /// account, e.g. to inspect the balance and transactions of a cold wallet.
+    ///
+    /// # Notes
+    ///
+    /// The derivation path is the one the extended public key was derived at,
+    /// it identifies the account but isn't used to derive its addresses.
+    /// Signing with such an account leaves the PSBT untouched.
+    pub fn new_with_xpub<F>(
+        account_xpub: Xpub,
+        network: Network,
+        script_type: ScriptType,
+        derivation_path: DerivationPath,
+        factory: F,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        let store_key = format!("{}_{}", account_xpub.fingerprint(), derivation_path);
+
+        let connector = factory.build(store_key);
+        let mut persister = connector.connect();
+
+        Ok(Self {
+            derivation_path,
+            persister_connector: connector.clone(),
+            wallet: Arc::new(RwLock::new(Self::build_wallet(
+                build_account_public_descriptors(account_xpub, script_type)?,
                 network,
-                script_type,
                 &mut persister,
             )?)),
         })
@@ -433,7 +501,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
             let spks_to_sync = if force_sync {
                 spks
             } else {
-                client.inner().filter_already_fetched(spks).await
+                client.filter_already_fetched(spks).await
             };
 
             if !spks_to_sync.is_empty() {
@@ -501,6 +569,19 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
         Ok(())
     }
 
+    /// Registers a signer holding keys outside of the wallet, e.g. a hardware
+    /// wallet, to be called on [`Account::sign`] once the wallet's own
+    /// signers are done
+    pub async fn add_external_signer(&self, signer: Arc<dyn ExternalSigner>) {
+        // Signers of both keychains are called on signing, so registering it
+        // once prevents the device from being prompted twice
+        self.get_mutable_wallet().await.add_signer(
+            EXTERNAL_KEYCHAIN,
+            EXTERNAL_SIGNER_ORDERING,
+            Arc::new(ExternalSignerWrapper(signer)),
+        );
+    }
+
     /// Returns whether or not the account's wallet has already been synced at
     /// least once
     pub async fn has_sync_data(&self) -> bool {
@@ -530,6 +611,106 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
         Ok(psbt.into())
     }
 
+    /// Cancels an unconfirmed transaction by replacing it with one spending
+    /// the same inputs back to an internal address, at `new_fee_rate` in
+    /// sat/vB. The returned PSBT needs to be signed and broadcasted.
+    ///
+    /// # Notes
+    ///
+    /// The transaction must be unconfirmed and signal RBF, and the new fee
+    /// rate must be higher than its own, otherwise an
+    /// [`Error::BuildFeeBump`] or [`Error::CreateTx`] is returned.
+    pub async fn cancel_transaction(&self, txid: String, new_fee_rate: u64) -> Result<Psbt, Error> {
+        let mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>> = self.get_mutable_wallet().await;
+        let drain_script = wallet_lock.next_unused_address(KeychainKind::Internal).script_pubkey();
+
+        let mut fee_bump_tx = wallet_lock.build_fee_bump(Txid::from_str(&txid)?)?;
+
+        // Sends everything back to the wallet instead of the original recipients
+        fee_bump_tx
+            .set_recipients(vec![])
+            .drain_to(drain_script)
+            .fee_rate(FeeRate::from_sat_per_vb_unchecked(new_fee_rate));
+
+        let psbt = fee_bump_tx.finish()?;
+
+        Ok(psbt.into())
+    }
+
+    /// Builds a child-pays-for-parent transaction, spending the account's
+    /// outputs of an unconfirmed incoming transaction back to an internal
+    /// address. Its fee is set so that the parent and child together pay
+    /// `target_fee_rate` in sat/vB. The returned PSBT needs to be signed and
+    /// broadcasted.
+    ///
+    /// # Notes
+    ///
+    /// The parent's fee is computed from the outputs it spends, which must
+    /// have been fetched by a sync. When the parent already pays the target
+    /// fee rate, the child is built at that fee rate.
+    pub async fn create_cpfp(&self, parent_txid: String, target_fee_rate: u64) -> Result<Psbt, Error> {
+        let parent_txid = Txid::from_str(&parent_txid)?;
+        let target_fee_rate = FeeRate::from_sat_per_vb_unchecked(target_fee_rate);
+
+        let mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>> = self.get_mutable_wallet().await;
+
+        let parent = wallet_lock.get_tx(parent_txid).ok_or(Error::TransactionNotFound)?;
+        if parent.chain_position.is_confirmed() {
+            return Err(BuildFeeBumpError::TransactionConfirmed(parent_txid).into());
+        }
+        let parent_tx = parent.tx_node.tx.clone();
+
+        let parent_fee = wallet_lock
+            .calculate_fee(&parent_tx)
+            .map_err(|e| Error::Other(e.into()))?;
+        let parent_weight = parent_tx.weight();
+
+        let outpoints = wallet_lock
+            .list_unspent()
+            .filter(|utxo| utxo.outpoint.txid == parent_txid)
+            .map(|utxo| utxo.outpoint)
+            .collect::<Vec<_>>();
+        if outpoints.is_empty() {
+            return Err(Error::UtxoNotFound(OutPoint::new(parent_txid, 0)));
+        }
+
+        let drain_script = wallet_lock.next_unused_address(KeychainKind::Internal).script_pubkey();
+
+        // Child's own fee at the target fee rate, which gives its weight
+        let child_fee = {
+            let mut builder = wallet_lock.build_tx();
+            builder
+                .add_utxos(&outpoints)?
+                .manually_selected_only()
+                .drain_to(drain_script.clone())
+                .fee_rate(target_fee_rate);
+
+            builder.finish()?.fee()?
+        };
+
+        // Child also pays for the fee the parent is missing
+        let package_fee = child_fee + target_fee_rate.fee_wu(parent_weight).unwrap_or(Amount::MAX_MONEY);
+        let child_fee = package_fee
+            .checked_sub(parent_fee)
+            .unwrap_or(Amount::ZERO)
+            .max(child_fee);
+
+        let mut builder = wallet_lock.build_tx();
+        builder
+            .add_utxos(&outpoints)?
+            .manually_selected_only()
+            .drain_to(drain_script)
+            .fee_absolute(child_fee);
+
+        let psbt = builder.finish()?;
+
+        Ok(psbt.into())
+    }
+
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(name = "bitcoin.apply_update", skip_all, fields(derivation_path = %self.derivation_path))
+    )]
     pub async fn apply_update(&self, update: impl Into<Update>) -> Result<(), Error> {
         let mut wallet_lock = self.get_mutable_wallet().await;
         wallet_lock.apply_update_at(update, Some(now().as_secs()))?;
@@ -539,7 +720,8 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
         Ok(())
     }
 
-    async fn persist(&self, mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>>) -> Result<(), Error> {
+    #[cfg_attr(feature = "instrumentation", tracing::instrument(name = "bitcoin.persist", skip_all))]
+    pub(crate) async fn persist(&self, mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>>) -> Result<(), Error> {
         let mut persister = self.persister_connector.connect();
 
         wallet_lock.persist(&mut persister).map_err(|_e| Error::PersistError)?;
@@ -566,12 +748,18 @@ mod tests {
         BASE_WALLET_API_V1,
     };
     use andromeda_common::Network;
+    use bdk_chain::{BlockId, ConfirmationBlockTime};
     use bdk_wallet::{
         bitcoin::{
-            bip32::{DerivationPath, Xpriv},
-            Address, NetworkKind,
+            absolute::LockTime,
+            bip32::{DerivationPath, Xpriv, Xpub},
+            constants::genesis_block,
+            hashes::Hash,
+            secp256k1::Secp256k1,
+            transaction::Version,
+            Address, Amount, BlockHash, FeeRate, NetworkKind, OutPoint, ScriptBuf, Transaction, TxIn, TxOut, Txid,
         },
-        serde_json,
+        serde_json, KeychainKind, SignOptions,
     };
     use wiremock::{
         matchers::{body_json, body_string_contains, method, path, path_regex, query_param},
@@ -580,8 +768,8 @@ mod tests {
 
     use super::{Account, ScriptType};
     use crate::{
-        blockchain_client::BlockchainClient, mnemonic::Mnemonic, read_mock_file, storage::MemoryPersisted,
-        transactions::Pagination, utils::SortOrder,
+        blockchain_client::BlockchainClient, detached_sync::SyncUpdate, mnemonic::Mnemonic, read_mock_file,
+        storage::MemoryPersisted, transactions::Pagination, utils::SortOrder,
     };
 
     fn set_test_account(script_type: ScriptType, derivation_path: &str) -> Account<MemoryPersisted, MemoryPersisted> {
@@ -936,6 +1124,165 @@ mod tests {
     #[tokio::test]
     async fn test_bump_transactions_fees_success() {}
 
+    #[tokio::test]
+    async fn test_cancel_transaction() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap();
+        let funding_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_byte_array([1; 32]), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        let genesis = BlockId {
+            height: 0,
+            hash: genesis_block(bdk_wallet::bitcoin::Network::Testnet).block_hash(),
+        };
+        let block = BlockId {
+            height: 1,
+            hash: BlockHash::from_byte_array([1; 32]),
+        };
+        account
+            .apply_sync_update(SyncUpdate {
+                anchors: vec![(
+                    ConfirmationBlockTime {
+                        block_id: block,
+                        confirmation_time: 1714116130,
+                    },
+                    funding_tx.compute_txid(),
+                )],
+                txs: vec![funding_tx.clone()],
+                chain: Some(vec![genesis, block]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let sent_tx = {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let mut builder = wallet_lock.build_tx();
+            builder
+                .add_recipient(
+                    Address::from_str("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx")
+                        .unwrap()
+                        .assume_checked()
+                        .script_pubkey(),
+                    Amount::from_sat(50_000),
+                )
+                .fee_rate(FeeRate::from_sat_per_vb(2).unwrap());
+            let mut psbt = builder.finish().unwrap();
+            wallet_lock.sign(&mut psbt, SignOptions::default()).unwrap();
+
+            psbt.extract_tx().unwrap()
+        };
+        account.insert_unconfirmed_tx(sent_tx.clone()).await.unwrap();
+
+        let psbt = account
+            .cancel_transaction(sent_tx.compute_txid().to_string(), 10)
+            .await
+            .unwrap()
+            .inner();
+
+        assert_eq!(
+            psbt.unsigned_tx
+                .input
+                .iter()
+                .map(|input| input.previous_output)
+                .collect::<Vec<_>>(),
+            sent_tx
+                .input
+                .iter()
+                .map(|input| input.previous_output)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(psbt.unsigned_tx.output.len(), 1);
+        assert!(matches!(
+            account
+                .get_wallet()
+                .await
+                .derivation_of_spk(psbt.unsigned_tx.output[0].script_pubkey.clone()),
+            Some((KeychainKind::Internal, _))
+        ));
+
+        // Confirmed transactions can't be replaced
+        assert!(account
+            .cancel_transaction(funding_tx.compute_txid().to_string(), 10)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_cpfp() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        // Incoming transaction paying 200 sats of fees, far below the target
+        let address = account.get_next_receive_address().await.unwrap();
+        let foreign_outpoint = OutPoint::new(Txid::from_byte_array([1; 32]), 0);
+        let parent_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: foreign_outpoint,
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        account
+            .apply_sync_update(SyncUpdate {
+                txs: vec![parent_tx.clone()],
+                txouts: vec![(
+                    foreign_outpoint,
+                    TxOut {
+                        value: Amount::from_sat(100_200),
+                        script_pubkey: ScriptBuf::new(),
+                    },
+                )],
+                chain: Some(vec![BlockId {
+                    height: 0,
+                    hash: genesis_block(bdk_wallet::bitcoin::Network::Testnet).block_hash(),
+                }]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let mut psbt = account
+            .create_cpfp(parent_tx.compute_txid().to_string(), 20)
+            .await
+            .unwrap()
+            .inner();
+        assert_eq!(psbt.unsigned_tx.input.len(), 1);
+        assert_eq!(psbt.unsigned_tx.input[0].previous_output.txid, parent_tx.compute_txid());
+
+        account
+            .get_wallet()
+            .await
+            .sign(&mut psbt, SignOptions::default())
+            .unwrap();
+        let child_fee = psbt.fee().unwrap();
+        let child_tx = psbt.extract_tx().unwrap();
+
+        let package_fee = child_fee + Amount::from_sat(200);
+        let package_vsize = child_tx.weight().to_vbytes_ceil() + parent_tx.weight().to_vbytes_ceil();
+        assert!(package_fee.to_sat() / package_vsize >= 20);
+
+        // Unknown transactions
+        assert!(account
+            .create_cpfp(Txid::from_byte_array([2; 32]).to_string(), 20)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_has_sync_data() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
@@ -1176,6 +1523,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn get_address_from_watch_only_account() {
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let derivation_path = DerivationPath::from_str("m/86'/1'/0'").unwrap();
+
+        let secp = Secp256k1::new();
+        let account_xpub = Xpub::from_priv(&secp, &master_secret_key.derive_priv(&secp, &derivation_path).unwrap());
+
+        let account: Account<MemoryPersisted, MemoryPersisted> = Account::new_with_xpub(
+            account_xpub,
+            Network::Testnet,
+            ScriptType::Taproot,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            account.get_next_receive_address().await.unwrap().to_string(),
+            "tb1pvv0tcny86mz4lsx97p03fvkkc09cg5nx5nvnxc7c323jv5sr6wnshfu377".to_string()
+        );
+    }
+
     #[tokio::test]
     async fn get_last_unused_address() {
         let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
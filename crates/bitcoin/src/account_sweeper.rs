@@ -0,0 +1,90 @@
+use std::{str::FromStr, sync::Arc};
+
+use andromeda_common::{Network, ScriptType};
+use bdk_wallet::{
+    bitcoin::{Amount, FeeRate, PrivateKey},
+    WalletPersister,
+};
+
+use crate::{
+    account::Account, bip38::decrypt_bip38, blockchain_client::BlockchainClient, error::Error,
+    paper_account::PaperAccount, psbt::Psbt, storage::WalletPersisterConnector,
+};
+
+/// Sweeps the funds held by a single private key (e.g. a paper wallet) into
+/// an account.
+///
+/// A private key doesn't tell which script type its funds were received
+/// with, so every script type the key can be used with is probed.
+#[derive(Clone)]
+pub struct AccountSweeper<C: WalletPersisterConnector<P>, P: WalletPersister> {
+    client: Arc<BlockchainClient>,
+    account: Arc<Account<C, P>>,
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> AccountSweeper<C, P> {
+    pub fn new(client: Arc<BlockchainClient>, account: Arc<Account<C, P>>) -> Self {
+        Self { client, account }
+    }
+
+    /// Imports the private key with each script type, syncs them and returns
+    /// the paper accounts holding funds. The key is either WIF encoded, or
+    /// BIP38 encrypted when a passphrase is provided
+    pub async fn get_funded_paper_accounts(
+        &self,
+        private_key: &str,
+        bip38_passphrase: Option<&str>,
+        network: Network,
+    ) -> Result<Vec<PaperAccount>, Error> {
+        // Decrypted once, as BIP38 decryption is purposely slow
+        let private_key = match bip38_passphrase {
+            Some(passphrase) => decrypt_bip38(private_key, passphrase, network)?,
+            None => PrivateKey::from_str(private_key)?,
+        };
+
+        let mut funded_accounts = Vec::new();
+
+        for script_type in ScriptType::values() {
+            let paper_account = match PaperAccount::from_private_key(private_key, script_type, network) {
+                Ok(paper_account) => paper_account,
+                // Uncompressed keys can't be used with segwit scripts
+                Err(Error::Descriptor(_)) => continue,
+                Err(error) => return Err(error),
+            };
+
+            paper_account.sync(&self.client).await?;
+            if paper_account.get_balance().await.total() > Amount::ZERO {
+                funded_accounts.push(paper_account);
+            }
+        }
+
+        Ok(funded_accounts)
+    }
+
+    /// Returns the total balance held by the private key, across all script
+    /// types
+    pub async fn get_sweep_balance(
+        &self,
+        private_key: &str,
+        bip38_passphrase: Option<&str>,
+        network: Network,
+    ) -> Result<Amount, Error> {
+        let mut balance = Amount::ZERO;
+        for paper_account in self
+            .get_funded_paper_accounts(private_key, bip38_passphrase, network)
+            .await?
+        {
+            balance += paper_account.get_balance().await.total();
+        }
+
+        Ok(balance)
+    }
+
+    /// Builds a signed PSBT sending the whole balance of the paper account to
+    /// the next receive address of the account
+    pub async fn get_sweep_psbt(&self, paper_account: &PaperAccount, fee_rate: FeeRate) -> Result<Psbt, Error> {
+        let address = self.account.get_next_receive_address().await?;
+
+        paper_account.create_sweep_psbt(address.script_pubkey(), fee_rate).await
+    }
+}
@@ -0,0 +1,257 @@
+//! Single entry point wiring the API client, the blockchain client, the
+//! wallet and its accounts, the event loop and the storage together, for
+//! integrators who don't need finer control over each of them.
+
+use std::sync::Arc;
+
+use andromeda_api::{
+    event::{EventListener, EventLoop},
+    transaction::ExchangeRateOrTransactionTime,
+    ApiConfig, ProtonWalletApiClient,
+};
+use andromeda_common::{utils::now, Network, ScriptType};
+use bdk_wallet::{
+    bitcoin::{bip32::DerivationPath, Txid},
+    AddressInfo, WalletPersister,
+};
+use futures::future::try_join_all;
+
+use crate::{
+    account::Account,
+    blockchain_client::BlockchainClient,
+    error::Error,
+    psbt::Psbt,
+    storage::{WalletConnectorFactory, WalletPersisterConnector},
+    transaction_builder::TxBuilder,
+    wallet::Wallet,
+};
+
+/// Everything needed to set up an [`AndromedaWallet`]
+pub struct AndromedaWalletConfig<F> {
+    /// The API session must be authenticated, e.g. with `auth` or `store`,
+    /// for syncs and account discovery to succeed
+    pub api: ApiConfig,
+    pub network: Network,
+    pub mnemonic: String,
+    pub passphrase: Option<String>,
+    /// Accounts to open. When empty, the accounts with a transaction history
+    /// are discovered and opened
+    pub accounts: Vec<(ScriptType, DerivationPath)>,
+    /// Storage of the accounts' wallets
+    pub storage: F,
+    /// Receives the user's events once the event loop is started, see
+    /// [`AndromedaWallet::event_loop`]
+    pub event_listener: Option<Arc<dyn EventListener>>,
+    /// Defaults to [`DEFAULT_STOP_GAP`](crate::blockchain_client::DEFAULT_STOP_GAP)
+    pub stop_gap: Option<usize>,
+}
+
+/// Wallet backed by the Proton API, exposing the common flows: setup, sync,
+/// receive and send.
+///
+/// ```rust, ignore
+/// let wallet = AndromedaWallet::setup(config).await?;
+/// wallet.sync_all().await?;
+///
+/// let balance = wallet.wallet().get_balance().await?;
+/// let address = wallet.receive(&derivation_path).await?;
+/// ```
+pub struct AndromedaWallet<C: WalletPersisterConnector<P>, P: WalletPersister, F: WalletConnectorFactory<C, P>> {
+    api_client: ProtonWalletApiClient,
+    blockchain_client: BlockchainClient,
+    wallet: Wallet<C, P>,
+    storage: F,
+    event_loop: Option<EventLoop>,
+    stop_gap: Option<usize>,
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister, F: WalletConnectorFactory<C, P>> AndromedaWallet<C, P, F> {
+    /// Builds the clients and the wallet from the config, and opens its
+    /// accounts, discovering them when none is provided
+    pub async fn setup(config: AndromedaWalletConfig<F>) -> Result<Self, Error> {
+        let api_client = ProtonWalletApiClient::from_config(config.api)?;
+        let blockchain_client = BlockchainClient::new(api_client.clone());
+        let mut wallet = Wallet::new(config.network, config.mnemonic, config.passphrase)?;
+
+        let accounts = if config.accounts.is_empty() {
+            wallet
+                .discover_accounts(api_client.clone(), config.storage.clone(), None, None)
+                .await?
+                .into_iter()
+                .map(|(script_type, _index, derivation_path)| (script_type, derivation_path))
+                .collect()
+        } else {
+            config.accounts
+        };
+
+        for (script_type, derivation_path) in accounts {
+            wallet.add_account(script_type, derivation_path, config.storage.clone())?;
+        }
+
+        let event_loop = config
+            .event_listener
+            .map(|listener| EventLoop::new(api_client.clients().event, listener));
+
+        Ok(AndromedaWallet {
+            api_client,
+            blockchain_client,
+            wallet,
+            storage: config.storage,
+            event_loop,
+            stop_gap: config.stop_gap,
+        })
+    }
+
+    pub fn api_client(&self) -> &ProtonWalletApiClient {
+        &self.api_client
+    }
+
+    pub fn blockchain_client(&self) -> &BlockchainClient {
+        &self.blockchain_client
+    }
+
+    pub fn wallet(&self) -> &Wallet<C, P> {
+        &self.wallet
+    }
+
+    /// Event loop dispatching the user's events to the configured listener,
+    /// `None` when no listener was provided. Callers spawn
+    /// [`EventLoop::start`] on their runtime
+    pub fn event_loop(&self) -> Option<&EventLoop> {
+        self.event_loop.as_ref()
+    }
+
+    /// Opens another account, stored in the configured storage
+    pub fn add_account(
+        &mut self,
+        script_type: ScriptType,
+        derivation_path: DerivationPath,
+    ) -> Result<Arc<Account<C, P>>, Error> {
+        self.wallet
+            .add_account(script_type, derivation_path, self.storage.clone())
+    }
+
+    fn get_account(&self, derivation_path: &DerivationPath) -> Result<Arc<Account<C, P>>, Error> {
+        self.wallet.get_account(derivation_path).ok_or(Error::AccountNotFound)
+    }
+
+    /// Syncs every account concurrently: a full sync for the accounts never
+    /// synced, a partial sync otherwise
+    pub async fn sync_all(&self) -> Result<(), Error> {
+        let syncs = self.wallet.get_accounts().into_iter().map(|account| async move {
+            if account.has_sync_data().await {
+                let update = self.blockchain_client.partial_sync(account.get_wallet().await).await?;
+                account.apply_update(update).await
+            } else {
+                let update = self.blockchain_client.full_sync(&account, self.stop_gap).await?;
+                account.apply_update(update).await
+            }
+        });
+
+        try_join_all(syncs).await?;
+
+        Ok(())
+    }
+
+    /// Reveals the next receive address of the account
+    pub async fn receive(&self, derivation_path: &DerivationPath) -> Result<AddressInfo, Error> {
+        self.get_account(derivation_path)?.get_next_receive_address().await
+    }
+
+    /// Builds, signs and broadcasts a transaction paying the recipients
+    /// (address and amount in sats) at the given fee rate in sat/vB. The
+    /// transaction is indexed by the API under `wallet_id` and
+    /// `wallet_account_id`.
+    ///
+    /// The transaction is added to the account right away, so that the
+    /// balance reflects it before the next sync.
+    pub async fn send(
+        &self,
+        derivation_path: &DerivationPath,
+        recipients: Vec<(String, u64)>,
+        fee_rate: u64,
+        wallet_id: String,
+        wallet_account_id: String,
+        label: Option<String>,
+    ) -> Result<Txid, Error> {
+        let account = self.get_account(derivation_path)?;
+
+        let tx_builder = recipients.into_iter().fold(
+            TxBuilder::new().set_account(account.clone()).clear_recipients(),
+            |tx_builder, (address, amount)| tx_builder.add_recipient(Some((Some(address), Some(amount)))),
+        );
+
+        let mut psbt = tx_builder
+            .set_fee_rate(fee_rate)
+            .create_psbt(false, false)
+            .await?
+            .inner();
+        account.sign(&mut psbt, None).await?;
+
+        let transaction = Psbt::from(psbt).extract_tx()?;
+        let txid = transaction.compute_txid();
+
+        self.blockchain_client
+            .broadcast(
+                transaction.clone(),
+                wallet_id,
+                wallet_account_id,
+                label,
+                ExchangeRateOrTransactionTime::TransactionTime(now().as_secs().to_string()),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
+
+        account.insert_unconfirmed_tx(transaction).await?;
+
+        Ok(txid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use andromeda_api::tests::utils::test_config;
+    use andromeda_common::{Network, ScriptType};
+    use bdk_wallet::bitcoin::bip32::DerivationPath;
+
+    use super::{AndromedaWallet, AndromedaWalletConfig};
+    use crate::storage::MemoryPersisted;
+
+    #[tokio::test]
+    async fn should_setup_wallet_with_provided_accounts() {
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        let config = AndromedaWalletConfig {
+            api: test_config("http://localhost".to_string()),
+            network: Network::Testnet,
+            mnemonic: "category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string(),
+            passphrase: None,
+            accounts: vec![(ScriptType::NativeSegwit, derivation_path.clone())],
+            storage: MemoryPersisted {},
+            event_listener: None,
+            stop_gap: None,
+        };
+        let wallet = AndromedaWallet::setup(config).await.unwrap();
+
+        assert_eq!(wallet.wallet().get_accounts().len(), 1);
+        assert!(wallet.event_loop().is_none());
+        assert_eq!(
+            wallet.receive(&derivation_path).await.unwrap().address.to_string(),
+            wallet
+                .wallet()
+                .get_account(&derivation_path)
+                .unwrap()
+                .peek_receive_address(0)
+                .await
+                .unwrap()
+                .address
+                .to_string()
+        );
+    }
+}
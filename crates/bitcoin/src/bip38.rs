@@ -0,0 +1,210 @@
+//! Decryption of BIP38 passphrase-protected private keys, as found on paper
+//! wallets. See https://bips.dev/38/
+//!
+//! Both the non EC-multiplied (`6PR`/`6PY` prefixed) and the EC-multiplied
+//! (`6Pf`/`6Pn` prefixed) modes are supported.
+
+use aes::{
+    cipher::{generic_array::GenericArray, BlockDecrypt, KeyInit},
+    Aes256,
+};
+use andromeda_common::Network;
+use bdk_wallet::bitcoin::{
+    base58,
+    hashes::{sha256d, Hash},
+    secp256k1::{Scalar, Secp256k1, SecretKey},
+    Address, Network as BdkNetwork, NetworkKind, PrivateKey,
+};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error::Error;
+
+const ENCRYPTED_KEY_LENGTH: usize = 39;
+const NON_EC_MULTIPLIED_PREFIX: [u8; 2] = [0x01, 0x42];
+const EC_MULTIPLIED_PREFIX: [u8; 2] = [0x01, 0x43];
+
+const COMPRESSED_FLAG: u8 = 0x20;
+const LOT_AND_SEQUENCE_FLAG: u8 = 0x04;
+
+/// Returns whether the string looks like a BIP38 encrypted key, e.g. to
+/// prompt the user for its passphrase
+pub fn is_bip38_encrypted(private_key: &str) -> bool {
+    private_key.starts_with("6P")
+        && base58::decode_check(private_key).is_ok_and(|data| {
+            data.len() == ENCRYPTED_KEY_LENGTH
+                && (data[..2] == NON_EC_MULTIPLIED_PREFIX || data[..2] == EC_MULTIPLIED_PREFIX)
+        })
+}
+
+fn scrypt(password: &[u8], salt: &[u8], log_n: u8, r: u32, p: u32, output: &mut [u8]) {
+    // Parameters are constants from BIP38, which are valid
+    let params = scrypt::Params::new(log_n, r, p, output.len()).expect("valid scrypt parameters");
+    scrypt::scrypt(password, salt, &params, output).expect("valid scrypt output length");
+}
+
+/// Decrypts the 16 bytes block with AES-256 and XORs it with the mask
+fn decrypt_block(key: &[u8], block: &[u8], mask: &[u8]) -> [u8; 16] {
+    let cipher = Aes256::new(GenericArray::from_slice(key));
+    let mut block = GenericArray::clone_from_slice(block);
+    cipher.decrypt_block(&mut block);
+
+    let mut decrypted = [0u8; 16];
+    for (i, byte) in decrypted.iter_mut().enumerate() {
+        *byte = block[i] ^ mask[i];
+    }
+
+    decrypted
+}
+
+fn decrypt_non_ec_multiplied(data: &[u8], passphrase: &str) -> Result<SecretKey, Error> {
+    let address_hash = &data[3..7];
+
+    let mut derived = [0u8; 64];
+    scrypt(passphrase.as_bytes(), address_hash, 14, 8, 8, &mut derived);
+    let (derived_half1, derived_half2) = derived.split_at(32);
+
+    let mut secret = [0u8; 32];
+    secret[..16].copy_from_slice(&decrypt_block(derived_half2, &data[7..23], &derived_half1[..16]));
+    secret[16..].copy_from_slice(&decrypt_block(derived_half2, &data[23..39], &derived_half1[16..]));
+
+    SecretKey::from_slice(&secret).map_err(|_| Error::InvalidBip38Passphrase)
+}
+
+fn decrypt_ec_multiplied(data: &[u8], passphrase: &str) -> Result<SecretKey, Error> {
+    let flag = data[2];
+    let owner_entropy = &data[7..15];
+
+    let owner_salt = if flag & LOT_AND_SEQUENCE_FLAG != 0 {
+        &owner_entropy[..4]
+    } else {
+        owner_entropy
+    };
+
+    let mut pre_factor = [0u8; 32];
+    scrypt(passphrase.as_bytes(), owner_salt, 14, 8, 8, &mut pre_factor);
+    let pass_factor = if flag & LOT_AND_SEQUENCE_FLAG != 0 {
+        sha256d::Hash::hash(&[&pre_factor[..], owner_entropy].concat()).to_byte_array()
+    } else {
+        pre_factor
+    };
+
+    let pass_factor = SecretKey::from_slice(&pass_factor).map_err(|_| Error::InvalidBip38Passphrase)?;
+    let pass_point = pass_factor.public_key(&Secp256k1::new()).serialize();
+
+    // Salted with the address hash followed by the owner entropy
+    let mut derived = [0u8; 64];
+    scrypt(&pass_point, &data[3..15], 10, 1, 1, &mut derived);
+    let (derived_half1, derived_half2) = derived.split_at(32);
+
+    let decrypted_part2 = decrypt_block(derived_half2, &data[23..39], &derived_half1[16..]);
+    let encrypted_part1 = [&data[15..23], &decrypted_part2[..8]].concat();
+    let decrypted_part1 = decrypt_block(derived_half2, &encrypted_part1, &derived_half1[..16]);
+
+    let seed_b = [&decrypted_part1[..], &decrypted_part2[8..]].concat();
+    let factor_b = sha256d::Hash::hash(&seed_b).to_byte_array();
+    let factor_b = Scalar::from_be_bytes(factor_b).map_err(|_| Error::InvalidBip38Passphrase)?;
+
+    let secret_key = pass_factor
+        .mul_tweak(&factor_b)
+        .map_err(|_| Error::InvalidBip38Passphrase)?;
+
+    Ok(secret_key)
+}
+
+/// Decrypts a BIP38 encrypted private key with its passphrase, returning the
+/// key for the given network.
+///
+/// # Notes
+///
+/// The passphrase is checked against the address hash included in the
+/// encrypted key, which commits to the mainnet P2PKH address of the key
+/// as specified by BIP38.
+pub fn decrypt_bip38(encrypted_key: &str, passphrase: &str, network: Network) -> Result<PrivateKey, Error> {
+    let data = base58::decode_check(encrypted_key).map_err(|_| Error::InvalidBip38Key)?;
+    if data.len() != ENCRYPTED_KEY_LENGTH {
+        return Err(Error::InvalidBip38Key);
+    }
+
+    // Passphrases are normalised so that they decrypt the same regardless of
+    // the way they were typed
+    let passphrase = passphrase.nfc().collect::<String>();
+
+    let secret_key = match [data[0], data[1]] {
+        NON_EC_MULTIPLIED_PREFIX => decrypt_non_ec_multiplied(&data, &passphrase)?,
+        EC_MULTIPLIED_PREFIX => decrypt_ec_multiplied(&data, &passphrase)?,
+        _ => return Err(Error::InvalidBip38Key),
+    };
+
+    let compressed = data[2] & COMPRESSED_FLAG != 0;
+    let mainnet_key = if compressed {
+        PrivateKey::new(secret_key, NetworkKind::Main)
+    } else {
+        PrivateKey::new_uncompressed(secret_key, NetworkKind::Main)
+    };
+
+    let address = Address::p2pkh(mainnet_key.public_key(&Secp256k1::new()), NetworkKind::Main);
+    let address_hash = sha256d::Hash::hash(address.to_string().as_bytes()).to_byte_array();
+    if address_hash[..4] != data[3..7] {
+        return Err(Error::InvalidBip38Passphrase);
+    }
+
+    Ok(PrivateKey {
+        network: BdkNetwork::from(network).into(),
+        ..mainnet_key
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use andromeda_common::Network;
+
+    use super::{decrypt_bip38, is_bip38_encrypted};
+    use crate::error::Error;
+
+    // Test vectors from BIP38
+    const NON_EC_MULTIPLIED_UNCOMPRESSED: &str = "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg";
+    const NON_EC_MULTIPLIED_COMPRESSED: &str = "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo";
+    const EC_MULTIPLIED: &str = "6PfQu77ygVyJLZjfvMLyhLMQbYnu5uguoJJ4kMCLqWwPEdfpwANVS76gTX";
+    const PASSPHRASE: &str = "TestingOneTwoThree";
+
+    #[test]
+    fn should_detect_bip38_keys() {
+        assert!(is_bip38_encrypted(NON_EC_MULTIPLIED_COMPRESSED));
+        assert!(is_bip38_encrypted(EC_MULTIPLIED));
+        assert!(!is_bip38_encrypted(
+            "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP"
+        ));
+    }
+
+    #[test]
+    fn should_decrypt_non_ec_multiplied_keys() {
+        let private_key = decrypt_bip38(NON_EC_MULTIPLIED_UNCOMPRESSED, PASSPHRASE, Network::Bitcoin).unwrap();
+        assert_eq!(
+            private_key.to_wif(),
+            "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR"
+        );
+
+        let private_key = decrypt_bip38(NON_EC_MULTIPLIED_COMPRESSED, PASSPHRASE, Network::Bitcoin).unwrap();
+        assert_eq!(
+            private_key.to_wif(),
+            "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP"
+        );
+    }
+
+    #[test]
+    fn should_decrypt_ec_multiplied_key() {
+        let private_key = decrypt_bip38(EC_MULTIPLIED, PASSPHRASE, Network::Bitcoin).unwrap();
+        assert_eq!(
+            private_key.to_wif(),
+            "5K4caxezwjGCGfnoPTZ8tMcJBLB7Jvyjv4xxeacadhq8nLisLR2"
+        );
+    }
+
+    #[test]
+    fn should_reject_wrong_passphrase() {
+        assert!(matches!(
+            decrypt_bip38(NON_EC_MULTIPLIED_COMPRESSED, "wrong passphrase", Network::Bitcoin),
+            Err(Error::InvalidBip38Passphrase)
+        ));
+    }
+}
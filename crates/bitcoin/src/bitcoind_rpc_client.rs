@@ -0,0 +1,60 @@
+//! Sync source reading blocks and mempool from a `bitcoind` node over RPC,
+//! for local development against regtest without the Proton API.
+//!
+//! # Notes
+//!
+//! Unlike esplora and Electrum, the node can't be queried by script pub key:
+//! accounts are synced by going through every block since their last
+//! checkpoint, which is only practical on small chains. The RPC client is
+//! blocking.
+
+pub use bdk_bitcoind_rpc::bitcoincore_rpc::Auth as BitcoindAuth;
+use bdk_bitcoind_rpc::{
+    bitcoincore_rpc::{Client, RpcApi},
+    Emitter,
+};
+use bdk_wallet::WalletPersister;
+
+use crate::{account::Account, error::Error, storage::WalletPersisterConnector};
+
+pub struct BitcoindRpcClient(Client);
+
+impl BitcoindRpcClient {
+    /// Connects to the node's RPC server at `url`, e.g.
+    /// `http://127.0.0.1:18443` for regtest
+    pub fn new(url: &str, auth: BitcoindAuth) -> Result<Self, Error> {
+        let client = Client::new(url, auth)?;
+
+        Ok(BitcoindRpcClient(client))
+    }
+
+    /// Returns the height of the node's chain tip
+    pub fn get_height(&self) -> Result<u32, Error> {
+        Ok(self.0.get_block_count()? as u32)
+    }
+
+    /// Applies the blocks mined since the account's last checkpoint, then the
+    /// mempool transactions, to the account and persists it.
+    ///
+    /// `start_height` is the height from which blocks are fetched when the
+    /// account was never synced, e.g. its birthday.
+    pub async fn sync<C, P>(&self, account: &Account<C, P>, start_height: u32) -> Result<(), Error>
+    where
+        C: WalletPersisterConnector<P>,
+        P: WalletPersister,
+    {
+        let mut wallet_lock = account.get_mutable_wallet().await;
+        let mut emitter = Emitter::new(&self.0, wallet_lock.latest_checkpoint(), start_height);
+
+        while let Some(event) = emitter.next_block()? {
+            wallet_lock
+                .apply_block_connected_to(&event.block, event.block_height(), event.connected_to())
+                .map_err(|e| Error::BitcoindRpc(e.to_string()))?;
+        }
+
+        let mempool = emitter.mempool()?;
+        wallet_lock.apply_unconfirmed_txs(mempool);
+
+        account.persist(wallet_lock).await
+    }
+}
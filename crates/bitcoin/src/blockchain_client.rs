@@ -1,16 +1,21 @@
 use std::collections::HashMap;
+#[cfg(feature = "electrum")]
+use std::sync::Arc;
 
+#[cfg(feature = "electrum")]
+use crate::electrum_client::ElectrumClient;
 use crate::{account::Account, error::Error, storage::WalletPersisterConnector};
 use andromeda_api::transaction::RecommendedFees;
 use andromeda_api::{
     transaction::{BroadcastMessage, ExchangeRateOrTransactionTime},
     ProtonWalletApiClient,
 };
+use andromeda_common::cancellation::CancellationToken;
 use andromeda_esplora::{AsyncClient, EsploraAsyncExt};
 use async_std::sync::RwLockReadGuard;
-use bdk_chain::spk_client::SyncRequest;
+use bdk_chain::spk_client::{FullScanRequest, SyncRequest};
 use bdk_wallet::{
-    bitcoin::{Transaction, Txid},
+    bitcoin::{BlockHash, Transaction, Txid},
     chain::spk_client::{FullScanResult, SyncResult},
     KeychainKind, PersistedWallet, WalletPersister,
 };
@@ -20,8 +25,22 @@ use serde::{Deserialize, Serialize};
 pub const DEFAULT_STOP_GAP: usize = 50;
 pub const PARALLEL_REQUESTS: usize = 5;
 
+/// Histogram of syncs durations in seconds, labelled with `kind` (`full` or
+/// `partial`)
+#[cfg(feature = "metrics")]
+pub const SYNC_DURATION_SECONDS: &str = "andromeda_bitcoin_sync_duration_seconds";
+
 #[derive(Clone)]
-pub struct BlockchainClient(AsyncClient);
+pub struct BlockchainClient(Backend);
+
+/// Source of the chain data
+#[derive(Clone)]
+enum Backend {
+    /// Proton blockchain proxy, exposing an esplora-like API
+    Esplora(AsyncClient),
+    #[cfg(feature = "electrum")]
+    Electrum(Arc<ElectrumClient>),
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
@@ -30,14 +49,143 @@ pub struct MinimumFees {
     pub MinimumIncrementalFee: f32,
 }
 
+/// Progress of a sync, as a number of items (script pub keys, transactions
+/// or outpoints) checked against the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub remaining: usize,
+}
+
+/// Summary of a full scan, to be reported once it is done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanStats {
+    /// Number of script pub keys checked against the chain
+    pub scanned: usize,
+    /// Number of transactions found
+    pub transactions: usize,
+    pub last_active_external_index: Option<u32>,
+    pub last_active_internal_index: Option<u32>,
+}
+
+impl ScanStats {
+    pub fn new(update: &FullScanResult<KeychainKind>, scanned: usize) -> Self {
+        ScanStats {
+            scanned,
+            transactions: update.tx_update.txs.len(),
+            last_active_external_index: update.last_active_indices.get(&KeychainKind::External).copied(),
+            last_active_internal_index: update.last_active_indices.get(&KeychainKind::Internal).copied(),
+        }
+    }
+}
+
 impl BlockchainClient {
     pub fn new(proton_api_client: ProtonWalletApiClient) -> Self {
         let client = AsyncClient::from_client(proton_api_client);
-        BlockchainClient(client)
+        BlockchainClient(Backend::Esplora(client))
+    }
+
+    /// Creates a client syncing against the Electrum server at `url`, e.g.
+    /// `ssl://electrum.blockstream.info:50002`, instead of the Proton
+    /// blockchain proxy.
+    ///
+    /// # Notes
+    ///
+    /// Electrum servers only know about the chain: broadcasts aren't indexed
+    /// by the API, so the label, message and recipients of the transactions
+    /// aren't stored, and [`BlockchainClient::get_recommended_fees`] isn't
+    /// available.
+    ///
+    /// The Electrum client is blocking: although they are `async`,
+    /// [`BlockchainClient::full_scan`], [`BlockchainClient::partial_sync`],
+    /// [`BlockchainClient::broadcast`] and the other requests of this client
+    /// hold the executor thread until the server responds, and never yield.
+    /// On a single-threaded runtime (e.g. wasm or a UI thread) they freeze
+    /// every other task meanwhile, so drive them from a dedicated thread or
+    /// a `spawn_blocking`-style task instead.
+    #[cfg(feature = "electrum")]
+    pub fn from_electrum(url: &str) -> Result<Self, Error> {
+        Ok(BlockchainClient(Backend::Electrum(Arc::new(ElectrumClient::new(url)?))))
+    }
+
+    /// Returns the esplora client, `None` when syncing against an Electrum
+    /// server
+    pub fn inner(&self) -> Option<&AsyncClient> {
+        match &self.0 {
+            Backend::Esplora(client) => Some(client),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(_) => None,
+        }
+    }
+
+    /// Returns a client whose syncs and broadcasts fail with a
+    /// [cancelled](andromeda_common::error::ErrorCategory::Cancelled) error
+    /// once `token` is cancelled, stopping the requests
+    /// they have in flight. The same token can be shared with other clients
+    /// to abort all the work of a screen at once.
+    ///
+    /// Requests to an Electrum server are blocking and can't be cancelled.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        match &self.0 {
+            Backend::Esplora(client) => BlockchainClient(Backend::Esplora(client.with_cancellation(token))),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(_) => self.clone(),
+        }
+    }
+
+    /// Scans the keychains' script pub keys of the request until `stop_gap`
+    /// consecutive ones without history are found
+    pub async fn full_scan<K, R>(&self, request: R, stop_gap: usize) -> Result<FullScanResult<K>, Error>
+    where
+        K: Ord + Clone + Send,
+        R: Into<FullScanRequest<K>> + Send,
+    {
+        match &self.0 {
+            Backend::Esplora(client) => Ok(client.full_scan(request, stop_gap).await?),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => client.full_scan(request, stop_gap),
+        }
+    }
+
+    /// Syncs the script pub keys, transactions and outpoints of the request
+    pub async fn sync<I, R>(&self, request: R) -> Result<SyncResult, Error>
+    where
+        I: Send + 'static,
+        R: Into<SyncRequest<I>> + Send,
+    {
+        match &self.0 {
+            Backend::Esplora(client) => Ok(client.sync(request, PARALLEL_REQUESTS).await?),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => client.sync(request),
+        }
+    }
+
+    /// Returns the script pub keys whose history wasn't fetched yet by this
+    /// client. Electrum syncs don't keep track of them, so all are returned
+    pub async fn filter_already_fetched(&self, spks: Vec<ScriptBuf>) -> Vec<ScriptBuf> {
+        match &self.0 {
+            Backend::Esplora(client) => client.filter_already_fetched(spks).await,
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(_) => spks,
+        }
+    }
+
+    /// Returns the height of the current chain tip
+    pub async fn get_height(&self) -> Result<u32, Error> {
+        match &self.0 {
+            Backend::Esplora(client) => Ok(client.get_height().await?),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => client.get_height(),
+        }
     }
 
-    pub fn inner(&self) -> &AsyncClient {
-        &self.0
+    /// Returns the hash of the current chain tip
+    pub async fn get_tip_hash(&self) -> Result<BlockHash, Error> {
+        match &self.0 {
+            Backend::Esplora(client) => Ok(client.get_tip_hash().await?),
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => client.get_tip_hash(),
+        }
     }
 
     /// Given a stop gap (10 currently, hard-coded) and a descriptor, we query
@@ -69,10 +217,71 @@ impl BlockchainClient {
         C: WalletPersisterConnector<P>,
         P: WalletPersister,
     {
-        let read_lock = account.get_wallet().await;
-        let request = read_lock.start_full_scan();
+        self.full_sync_with_progress(account, stop_gap, |_| {}).await
+    }
 
-        let update = self.0.full_scan(request, stop_gap.unwrap_or(DEFAULT_STOP_GAP)).await?;
+    /// Same as [`BlockchainClient::full_sync`], calling `on_progress` each
+    /// time a script pub key is scanned.
+    ///
+    /// The number of script pub keys left to scan depends on the transactions
+    /// found, so `remaining` is estimated from the revealed addresses and the
+    /// stop gap.
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(
+            name = "bitcoin.full_sync",
+            skip_all,
+            fields(derivation_path = %account.get_derivation_path(), stop_gap = ?stop_gap)
+        )
+    )]
+    pub async fn full_sync_with_progress<'a, C, P, F>(
+        &self,
+        account: &Account<C, P>,
+        stop_gap: Option<usize>,
+        mut on_progress: F,
+    ) -> Result<FullScanResult<KeychainKind>, Error>
+    where
+        C: WalletPersisterConnector<P>,
+        P: WalletPersister,
+        F: FnMut(ScanProgress) + Send + 'static,
+    {
+        let stop_gap = stop_gap.unwrap_or(DEFAULT_STOP_GAP);
+
+        let read_lock = account.get_wallet().await;
+        let estimated_total = [KeychainKind::External, KeychainKind::Internal]
+            .into_iter()
+            .map(|keychain| {
+                read_lock
+                    .derivation_index(keychain)
+                    .map_or(0, |index| index as usize + 1)
+                    + stop_gap
+            })
+            .sum::<usize>();
+
+        let mut scanned = 0;
+        let request = read_lock.start_full_scan().inspect(move |_, _, _| {
+            scanned += 1;
+            on_progress(ScanProgress {
+                scanned,
+                remaining: estimated_total.saturating_sub(scanned),
+            });
+        });
+
+        #[cfg(feature = "metrics")]
+        let started_at = andromeda_common::utils::now();
+
+        let update = self.full_scan(request, stop_gap).await?;
+
+        #[cfg(feature = "metrics")]
+        metrics::histogram!(SYNC_DURATION_SECONDS, "kind" => "full")
+            .record(andromeda_common::utils::now().saturating_sub(started_at).as_secs_f64());
+
+        #[cfg(feature = "instrumentation")]
+        tracing::info!(
+            txs = update.tx_update.txs.len(),
+            last_active_indices = ?update.last_active_indices,
+            "full sync done"
+        );
 
         Ok(update)
     }
@@ -90,6 +299,24 @@ impl BlockchainClient {
     ) -> Result<SyncResult, Error>
     where
         P: WalletPersister,
+    {
+        self.partial_sync_with_progress(wallet, |_| {}).await
+    }
+
+    /// Same as [`BlockchainClient::partial_sync`], calling `on_progress` each
+    /// time a script pub key, transaction or outpoint is checked
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(name = "bitcoin.partial_sync", skip_all)
+    )]
+    pub async fn partial_sync_with_progress<'a, P, F>(
+        &self,
+        wallet: RwLockReadGuard<'a, PersistedWallet<P>>,
+        mut on_progress: F,
+    ) -> Result<SyncResult, Error>
+    where
+        P: WalletPersister,
+        F: FnMut(ScanProgress) + Send + 'static,
     {
         let chain = wallet.local_chain();
         let chain_tip = chain.tip().block_id();
@@ -103,16 +330,43 @@ impl BlockchainClient {
             .map(|canonical_tx| canonical_tx.tx_node.txid)
             .collect::<Vec<Txid>>();
 
+        #[cfg(feature = "instrumentation")]
+        tracing::debug!(
+            utxos = utxos.len(),
+            unconfirmed_txs = unconfirmed_txids.len(),
+            "partial sync started"
+        );
+
         let request = wallet
             .start_sync_with_revealed_spks()
             .outpoints(utxos.into_iter())
-            .txids(unconfirmed_txids.into_iter());
+            .txids(unconfirmed_txids.into_iter())
+            .inspect(move |_, progress| {
+                on_progress(ScanProgress {
+                    scanned: progress.consumed(),
+                    remaining: progress.remaining(),
+                })
+            });
+
+        #[cfg(feature = "metrics")]
+        let started_at = andromeda_common::utils::now();
 
-        let update = self.0.sync(request, PARALLEL_REQUESTS).await?;
+        let update = self.sync(request).await?;
+
+        #[cfg(feature = "metrics")]
+        metrics::histogram!(SYNC_DURATION_SECONDS, "kind" => "partial")
+            .record(andromeda_common::utils::now().saturating_sub(started_at).as_secs_f64());
+
+        #[cfg(feature = "instrumentation")]
+        tracing::info!(txs = update.tx_update.txs.len(), "partial sync done");
 
         Ok(update)
     }
 
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(name = "bitcoin.sync_spks", skip_all, fields(spks = spks_to_sync.len()))
+    )]
     pub async fn sync_spks<'a, P>(
         &self,
         wallet: &RwLockReadGuard<'a, PersistedWallet<P>>,
@@ -122,7 +376,7 @@ impl BlockchainClient {
             .chain_tip(wallet.local_chain().tip())
             .spks(spks_to_sync);
 
-        let update = self.0.sync(request, PARALLEL_REQUESTS).await?;
+        let update = self.sync(request).await?;
 
         Ok(update)
     }
@@ -143,13 +397,19 @@ impl BlockchainClient {
             .map(|spks| spks.clone().take(stop_gap).collect::<Vec<_>>())
             .unwrap_or_default();
 
-        let results = self.0.many_scripthash_txs(spks).await.ok();
+        match &self.0 {
+            Backend::Esplora(client) => {
+                let results = client.many_scripthash_txs(spks).await.ok();
 
-        if let Some(results) = results {
-            return Ok(results.values().any(|(_index, txs)| !txs.is_empty()));
-        };
+                if let Some(results) = results {
+                    return Ok(results.values().any(|(_index, txs)| !txs.is_empty()));
+                };
 
-        Ok(false)
+                Ok(false)
+            }
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => Ok(client.has_history(spks).unwrap_or(false)),
+        }
     }
 
     /// Returns whether or not the wallet needs to be synced again (new block)
@@ -157,7 +417,7 @@ impl BlockchainClient {
     where
         P: WalletPersister,
     {
-        let tip_hash = self.0.get_tip_hash().await?;
+        let tip_hash = self.get_tip_hash().await?;
         let latest_chekpoint_hash = wallet.latest_checkpoint().hash();
 
         Ok(tip_hash != latest_chekpoint_hash)
@@ -166,7 +426,19 @@ impl BlockchainClient {
     /// Returns mempool minimum fee, minimum relay tx fee and incremental relay
     /// fee in sat/vB instead of BTC/kB
     pub async fn get_minimum_fees(&self) -> Result<MinimumFees, Error> {
-        let mempool_info = self.0.get_mempool_info().await?;
+        let mempool_info = match &self.0 {
+            Backend::Esplora(client) => client.get_mempool_info().await?,
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => {
+                // Electrum servers only expose the relay fee
+                let relay_fee = client.relay_fee()? as f32;
+
+                return Ok(MinimumFees {
+                    MinimumBroadcastFee: relay_fee * 100000.0,
+                    MinimumIncrementalFee: relay_fee * 100000.0,
+                });
+            }
+        };
         let minimum_broadcast_fee = f32::max(
             mempool_info.MempoolMinFee * 100000.0,
             mempool_info.MinRelayTxFee * 100000.0,
@@ -181,20 +453,33 @@ impl BlockchainClient {
 
     /// Returns fee estimations in a Map
     pub async fn get_fees_estimation(&self) -> Result<HashMap<String, f64>, Error> {
-        let fees = self.0.get_fee_estimates().await?;
+        let fees = match &self.0 {
+            Backend::Esplora(client) => client.get_fee_estimates().await?,
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => client.get_fee_estimates()?,
+        };
 
         Ok(fees)
     }
 
     /// Returns recommended fees
     pub async fn get_recommended_fees(&self) -> Result<RecommendedFees, Error> {
-        let recommended_fees = self.0.get_recommended_fees().await?;
+        let recommended_fees = match &self.0 {
+            Backend::Esplora(client) => client.get_recommended_fees().await?,
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(_) => return Err(Error::UnsupportedByBackend("electrum")),
+        };
 
         Ok(recommended_fees)
     }
 
-    /// Broadcasts a provided transaction
+    /// Broadcasts a provided transaction. With an Electrum backend, only the
+    /// transaction is sent, see [`BlockchainClient::from_electrum`]
     #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(name = "bitcoin.broadcast", skip_all, fields(txid = %transaction.compute_txid()))
+    )]
     pub async fn broadcast(
         &self,
         transaction: Transaction,
@@ -208,7 +493,16 @@ impl BlockchainClient {
         recipients: Option<HashMap<String, String>>,
         is_anonymous: Option<u8>,
     ) -> Result<(), Error> {
-        self.0
+        let client = match &self.0 {
+            Backend::Esplora(client) => client,
+            #[cfg(feature = "electrum")]
+            Backend::Electrum(client) => {
+                client.broadcast(&transaction)?;
+                return Ok(());
+            }
+        };
+
+        client
             .broadcast(
                 &transaction,
                 wallet_id,
@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, HashSet};
+
+use bdk_wallet::{
+    bitcoin::{FeeRate, Script, ScriptBuf, TxIn},
+    coin_selection::{decide_change, CoinSelectionAlgorithm, CoinSelectionResult, InsufficientFunds},
+    WeightedUtxo,
+};
+use bitcoin::key::rand::RngCore;
+
+/// Coin selection limiting the addresses linked together by a transaction.
+///
+/// Coins are selected by address, all the coins received on an address being
+/// spent at once: once an address appears in a transaction, its remaining
+/// coins would otherwise link the next transaction spending them to this
+/// one. Addresses of the required coins are spent first, then the one whose
+/// coins cover the amount with the smallest leftover, falling back to the
+/// largest ones when none does on its own.
+///
+/// # Notes
+///
+/// Spending whole addresses adds inputs, so this trades higher fees for
+/// privacy compared to [`LargestFirstCoinSelection`](bdk_wallet::coin_selection::LargestFirstCoinSelection).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrivacyPreservingCoinSelection;
+
+fn input_fee(fee_rate: FeeRate, utxo: &WeightedUtxo) -> u64 {
+    (fee_rate * (TxIn::default().segwit_weight() + utxo.satisfaction_weight)).to_sat()
+}
+
+/// Value of the coins minus the fees needed to spend them
+fn effective_value(fee_rate: FeeRate, utxos: &[WeightedUtxo]) -> i64 {
+    utxos
+        .iter()
+        .map(|utxo| utxo.utxo.txout().value.to_sat() as i64 - input_fee(fee_rate, utxo) as i64)
+        .sum()
+}
+
+impl CoinSelectionAlgorithm for PrivacyPreservingCoinSelection {
+    fn coin_select<R: RngCore>(
+        &self,
+        required_utxos: Vec<WeightedUtxo>,
+        optional_utxos: Vec<WeightedUtxo>,
+        fee_rate: FeeRate,
+        target_amount: u64,
+        drain_script: &Script,
+        _rand: &mut R,
+    ) -> Result<CoinSelectionResult, InsufficientFunds> {
+        let required_spks = required_utxos
+            .iter()
+            .map(|utxo| utxo.utxo.txout().script_pubkey.clone())
+            .collect::<HashSet<_>>();
+
+        let mut groups = BTreeMap::<ScriptBuf, Vec<WeightedUtxo>>::new();
+        for utxo in optional_utxos {
+            groups
+                .entry(utxo.utxo.txout().script_pubkey.clone())
+                .or_default()
+                .push(utxo);
+        }
+
+        // Addresses of the required coins are already linked to the transaction
+        let (linked_groups, mut groups): (Vec<_>, Vec<_>) =
+            groups.into_iter().partition(|(spk, _)| required_spks.contains(spk));
+
+        let mut selected = required_utxos;
+        selected.extend(linked_groups.into_iter().flat_map(|(_, utxos)| utxos));
+
+        loop {
+            let missing = target_amount as i64 - effective_value(fee_rate, &selected);
+            if missing <= 0 {
+                break;
+            }
+
+            let covering_group = groups
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, utxos))| effective_value(fee_rate, utxos) >= missing)
+                .min_by_key(|(_, (_, utxos))| effective_value(fee_rate, utxos));
+            let largest_group = groups
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (_, utxos))| effective_value(fee_rate, utxos));
+
+            let Some((index, _)) = covering_group.or(largest_group) else {
+                return Err(InsufficientFunds {
+                    needed: target_amount + selected.iter().map(|utxo| input_fee(fee_rate, utxo)).sum::<u64>(),
+                    available: selected.iter().map(|utxo| utxo.utxo.txout().value.to_sat()).sum(),
+                });
+            };
+
+            selected.extend(groups.swap_remove(index).1);
+        }
+
+        let fee_amount = selected.iter().map(|utxo| input_fee(fee_rate, utxo)).sum::<u64>();
+        let selected_amount = selected
+            .iter()
+            .map(|utxo| utxo.utxo.txout().value.to_sat())
+            .sum::<u64>();
+        let excess = decide_change(selected_amount - target_amount - fee_amount, fee_rate, drain_script);
+
+        Ok(CoinSelectionResult {
+            selected: selected.into_iter().map(|utxo| utxo.utxo).collect(),
+            fee_amount,
+            excess,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk_wallet::{
+        bitcoin::{hashes::Hash, Amount, FeeRate, OutPoint, ScriptBuf, TxOut, Txid, WPubkeyHash, Weight},
+        chain::ConfirmationTime,
+        coin_selection::{
+            BranchAndBoundCoinSelection, CoinSelectionAlgorithm, Excess, LargestFirstCoinSelection, SingleRandomDraw,
+        },
+        KeychainKind, LocalOutput, Utxo, WeightedUtxo,
+    };
+
+    use super::PrivacyPreservingCoinSelection;
+    use crate::transaction_builder::FixedRng;
+
+    // Witness of a P2WPKH input
+    const P2WPKH_SATISFACTION_WEIGHT: u64 = 108;
+
+    fn spk(byte: u8) -> ScriptBuf {
+        ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array([byte; 20]))
+    }
+
+    fn utxo(value: u64, address: u8, index: u32) -> WeightedUtxo {
+        WeightedUtxo {
+            satisfaction_weight: Weight::from_wu(P2WPKH_SATISFACTION_WEIGHT),
+            utxo: Utxo::Local(LocalOutput {
+                outpoint: OutPoint::new(Txid::from_byte_array([index as u8; 32]), index),
+                txout: TxOut {
+                    value: Amount::from_sat(value),
+                    script_pubkey: spk(address),
+                },
+                keychain: KeychainKind::External,
+                is_spent: false,
+                derivation_index: index,
+                confirmation_time: ConfirmationTime::Unconfirmed { last_seen: 0 },
+            }),
+        }
+    }
+
+    fn selected_spks(selected: &[Utxo]) -> Vec<ScriptBuf> {
+        selected.iter().map(|utxo| utxo.txout().script_pubkey.clone()).collect()
+    }
+
+    #[test]
+    fn should_spend_all_coins_of_an_address() {
+        let utxos = vec![utxo(60_000, 1, 0), utxo(10_000, 1, 1), utxo(80_000, 2, 2)];
+        let fee_rate = FeeRate::from_sat_per_vb(2).unwrap();
+
+        let privacy = PrivacyPreservingCoinSelection
+            .coin_select(vec![], utxos.clone(), fee_rate, 50_000, &spk(0xff), &mut FixedRng(0))
+            .unwrap();
+        let largest_first = LargestFirstCoinSelection
+            .coin_select(vec![], utxos, fee_rate, 50_000, &spk(0xff), &mut FixedRng(0))
+            .unwrap();
+
+        // Both coins of the first address are spent, rather than the largest coin
+        assert_eq!(selected_spks(&privacy.selected), vec![spk(1), spk(1)]);
+        assert_eq!(selected_spks(&largest_first.selected), vec![spk(2)]);
+
+        // At the cost of an extra input
+        assert!(privacy.fee_amount > largest_first.fee_amount);
+    }
+
+    #[test]
+    fn should_spend_coins_of_required_addresses_first() {
+        let fee_rate = FeeRate::from_sat_per_vb(2).unwrap();
+
+        let selection = PrivacyPreservingCoinSelection
+            .coin_select(
+                vec![utxo(60_000, 1, 0)],
+                vec![utxo(10_000, 1, 1), utxo(80_000, 2, 2)],
+                fee_rate,
+                50_000,
+                &spk(0xff),
+                &mut FixedRng(0),
+            )
+            .unwrap();
+
+        assert_eq!(selected_spks(&selection.selected), vec![spk(1), spk(1)]);
+    }
+
+    #[test]
+    fn should_link_as_few_addresses_as_possible() {
+        let utxos = vec![utxo(30_000, 1, 0), utxo(40_000, 2, 1), utxo(20_000, 3, 2)];
+
+        let selection = PrivacyPreservingCoinSelection
+            .coin_select(vec![], utxos, FeeRate::ZERO, 60_000, &spk(0xff), &mut FixedRng(0))
+            .unwrap();
+
+        assert_eq!(selected_spks(&selection.selected), vec![spk(2), spk(3)]);
+        assert!(matches!(selection.excess, Excess::NoChange { .. }));
+    }
+
+    #[test]
+    fn should_fail_when_funds_are_insufficient() {
+        let utxos = vec![utxo(30_000, 1, 0), utxo(20_000, 2, 1)];
+
+        let error = PrivacyPreservingCoinSelection
+            .coin_select(vec![], utxos, FeeRate::ZERO, 60_000, &spk(0xff), &mut FixedRng(0))
+            .unwrap_err();
+
+        assert_eq!(error.needed, 60_000);
+        assert_eq!(error.available, 50_000);
+    }
+
+    #[test]
+    fn should_avoid_change_with_branch_and_bound() {
+        let utxos = vec![utxo(80_000, 1, 0), utxo(50_000, 2, 1), utxo(20_000, 3, 2)];
+
+        let branch_and_bound = BranchAndBoundCoinSelection::<SingleRandomDraw>::default()
+            .coin_select(
+                vec![],
+                utxos.clone(),
+                FeeRate::ZERO,
+                70_000,
+                &spk(0xff),
+                &mut FixedRng(0),
+            )
+            .unwrap();
+        let largest_first = LargestFirstCoinSelection
+            .coin_select(vec![], utxos, FeeRate::ZERO, 70_000, &spk(0xff), &mut FixedRng(0))
+            .unwrap();
+
+        // Exact match, no change output is wasted
+        assert!(matches!(branch_and_bound.excess, Excess::NoChange { .. }));
+        assert!(matches!(largest_first.excess, Excess::Change { .. }));
+    }
+}
@@ -0,0 +1,387 @@
+//! Syncs detached from the account, meant to run in another thread than the
+//! one owning the account (e.g. a Web Worker).
+//!
+//! The account exports a serializable sync handle, holding only public data.
+//! The handle is sent to the worker, which fetches chain data with a
+//! [`BlockchainClient`] and sends back a serializable [`SyncUpdate`], to be
+//! applied to the account with [`Account::apply_sync_update`].
+//!
+//! A partial sync can also be advanced in chunks with a serializable
+//! [`PartialSyncSnapshot`], e.g. within the short background fetch windows
+//! of mobile apps, and resumed later without restarting it.
+//!
+//! [`Account::apply_sync_update`]: crate::account::Account::apply_sync_update
+
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+use bdk_chain::{
+    spk_client::{FullScanRequest, SyncRequest},
+    BlockId, CheckPoint, ConfirmationBlockTime, SpkIterator, TxUpdate,
+};
+use bdk_wallet::{
+    bitcoin::{OutPoint, ScriptBuf, Transaction, TxOut, Txid},
+    chain::spk_client::{FullScanResult, SyncResult},
+    KeychainKind, Update,
+};
+use miniscript::{Descriptor, DescriptorPublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::Account,
+    blockchain_client::{BlockchainClient, DEFAULT_STOP_GAP},
+    error::Error,
+    storage::{WalletPersister, WalletPersisterConnector},
+};
+
+/// Data needed to run a full sync of an account: its public descriptors and
+/// local chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullSyncHandle {
+    pub external_descriptor: String,
+    pub internal_descriptor: String,
+    pub chain: Vec<BlockId>,
+    pub stop_gap: Option<usize>,
+}
+
+/// Data needed to run a partial sync of an account: the revealed script pub
+/// keys, the unspent outpoints and the unconfirmed transactions to track
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialSyncHandle {
+    pub spks: Vec<ScriptBuf>,
+    pub outpoints: Vec<OutPoint>,
+    pub txids: Vec<Txid>,
+    pub chain: Vec<BlockId>,
+}
+
+impl PartialSyncHandle {
+    pub fn len(&self) -> usize {
+        self.spks.len() + self.txids.len() + self.outpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes at most `size` items from the handle, script pub keys first,
+    /// and returns them in a handle sharing the same chain
+    fn take_chunk(&mut self, size: usize) -> PartialSyncHandle {
+        fn take<T>(items: &mut Vec<T>, size: &mut usize) -> Vec<T> {
+            let taken = items.drain(..(*size).min(items.len())).collect::<Vec<_>>();
+            *size -= taken.len();
+            taken
+        }
+
+        let mut size = size;
+        PartialSyncHandle {
+            spks: take(&mut self.spks, &mut size),
+            txids: take(&mut self.txids, &mut size),
+            outpoints: take(&mut self.outpoints, &mut size),
+            chain: self.chain.clone(),
+        }
+    }
+}
+
+/// State of a partial sync advanced in chunks with
+/// [`BlockchainClient::partial_sync_chunk`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialSyncSnapshot {
+    /// Items left to sync
+    pub remaining: PartialSyncHandle,
+    /// Update accumulated over the synced chunks, to be applied once the
+    /// snapshot is done
+    pub update: SyncUpdate,
+}
+
+impl PartialSyncSnapshot {
+    pub fn new(handle: PartialSyncHandle) -> Self {
+        PartialSyncSnapshot {
+            remaining: handle,
+            update: SyncUpdate::default(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Serializable form of a wallet [`Update`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncUpdate {
+    pub last_active_indices: Vec<(KeychainKind, u32)>,
+    pub txs: Vec<Transaction>,
+    pub txouts: Vec<(OutPoint, TxOut)>,
+    pub anchors: Vec<(ConfirmationBlockTime, Txid)>,
+    pub chain: Option<Vec<BlockId>>,
+}
+
+fn to_block_ids(checkpoint: &CheckPoint) -> Vec<BlockId> {
+    let mut block_ids = checkpoint.iter().map(|cp| cp.block_id()).collect::<Vec<_>>();
+    // Checkpoints are iterated from the tip, but are rebuilt from the lowest one
+    block_ids.reverse();
+
+    block_ids
+}
+
+fn to_checkpoint(block_ids: Vec<BlockId>) -> Result<CheckPoint, Error> {
+    CheckPoint::from_block_ids(block_ids)
+        .map_err(|_| Error::Other(anyhow::anyhow!("Sync chain is empty or not sorted by height")))
+}
+
+fn parse_descriptor(descriptor: &str) -> Result<Descriptor<DescriptorPublicKey>, Error> {
+    Descriptor::<DescriptorPublicKey>::from_str(descriptor).map_err(|e| Error::Other(e.into()))
+}
+
+impl SyncUpdate {
+    fn new(
+        tx_update: TxUpdate<ConfirmationBlockTime>,
+        chain_update: Option<CheckPoint>,
+        last_active_indices: BTreeMap<KeychainKind, u32>,
+    ) -> Self {
+        SyncUpdate {
+            last_active_indices: last_active_indices.into_iter().collect(),
+            txs: tx_update.txs.iter().map(|tx| tx.as_ref().clone()).collect(),
+            txouts: tx_update.txouts.into_iter().collect(),
+            anchors: tx_update.anchors.into_iter().collect(),
+            chain: chain_update.as_ref().map(to_block_ids),
+        }
+    }
+
+    /// Merges the update of a later sync into this one. The chains are
+    /// merged by height, the later update's blocks taking precedence
+    pub fn merge(&mut self, other: SyncUpdate) {
+        self.last_active_indices.extend(other.last_active_indices);
+        self.txs.extend(other.txs);
+        self.txouts.extend(other.txouts);
+        self.anchors.extend(other.anchors);
+
+        self.chain = match (self.chain.take(), other.chain) {
+            (Some(chain), Some(other_chain)) => Some(
+                chain
+                    .into_iter()
+                    .chain(other_chain)
+                    .map(|block_id| (block_id.height, block_id))
+                    .collect::<BTreeMap<_, _>>()
+                    .into_values()
+                    .collect(),
+            ),
+            (chain, other_chain) => other_chain.or(chain),
+        };
+    }
+
+    pub fn into_update(self) -> Result<Update, Error> {
+        let mut tx_update = TxUpdate::<ConfirmationBlockTime>::default();
+        tx_update.txs = self.txs.into_iter().map(Arc::new).collect();
+        tx_update.txouts = self.txouts.into_iter().collect();
+        tx_update.anchors = self.anchors.into_iter().collect();
+
+        Ok(Update {
+            last_active_indices: self.last_active_indices.into_iter().collect(),
+            tx_update,
+            chain: self.chain.map(to_checkpoint).transpose()?,
+        })
+    }
+}
+
+impl From<FullScanResult<KeychainKind>> for SyncUpdate {
+    fn from(value: FullScanResult<KeychainKind>) -> Self {
+        SyncUpdate::new(value.tx_update, value.chain_update, value.last_active_indices)
+    }
+}
+
+impl From<SyncResult> for SyncUpdate {
+    fn from(value: SyncResult) -> Self {
+        SyncUpdate::new(value.tx_update, value.chain_update, BTreeMap::new())
+    }
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
+    pub async fn get_full_sync_handle(&self, stop_gap: Option<usize>) -> FullSyncHandle {
+        let wallet_lock = self.get_wallet().await;
+
+        FullSyncHandle {
+            external_descriptor: wallet_lock.public_descriptor(KeychainKind::External).to_string(),
+            internal_descriptor: wallet_lock.public_descriptor(KeychainKind::Internal).to_string(),
+            chain: to_block_ids(&wallet_lock.local_chain().tip()),
+            stop_gap,
+        }
+    }
+
+    /// Returns the handle of a partial sync, tracking the same items as
+    /// [`BlockchainClient::partial_sync`]
+    pub async fn get_partial_sync_handle(&self) -> PartialSyncHandle {
+        let wallet_lock = self.get_wallet().await;
+        let chain = wallet_lock.local_chain();
+        let chain_tip = chain.tip().block_id();
+
+        let txids = wallet_lock
+            .tx_graph()
+            .list_canonical_txs(chain, chain_tip)
+            .filter(|canonical_tx| !canonical_tx.chain_position.is_confirmed())
+            .map(|canonical_tx| canonical_tx.tx_node.txid)
+            .collect();
+
+        PartialSyncHandle {
+            spks: wallet_lock.spk_index().revealed_spks(..).map(|(_, spk)| spk).collect(),
+            outpoints: wallet_lock.list_unspent().map(|utxo| utxo.outpoint).collect(),
+            txids,
+            chain: to_block_ids(&chain.tip()),
+        }
+    }
+
+    /// Applies an update produced by a detached sync and persists it
+    pub async fn apply_sync_update(&self, update: SyncUpdate) -> Result<(), Error> {
+        self.apply_update(update.into_update()?).await
+    }
+}
+
+impl BlockchainClient {
+    /// Runs a full sync from a handle, without access to the account
+    pub async fn full_sync_detached(&self, handle: FullSyncHandle) -> Result<SyncUpdate, Error> {
+        let external_descriptor = parse_descriptor(&handle.external_descriptor)?;
+        let internal_descriptor = parse_descriptor(&handle.internal_descriptor)?;
+
+        let request = FullScanRequest::builder()
+            .chain_tip(to_checkpoint(handle.chain)?)
+            .spks_for_keychain(KeychainKind::External, SpkIterator::new(external_descriptor))
+            .spks_for_keychain(KeychainKind::Internal, SpkIterator::new(internal_descriptor));
+
+        let update = self
+            .full_scan(request, handle.stop_gap.unwrap_or(DEFAULT_STOP_GAP))
+            .await?;
+
+        Ok(update.into())
+    }
+
+    /// Runs a partial sync from a handle, without access to the account
+    pub async fn partial_sync_detached(&self, handle: PartialSyncHandle) -> Result<SyncUpdate, Error> {
+        let request = SyncRequest::builder()
+            .chain_tip(to_checkpoint(handle.chain)?)
+            .spks(handle.spks)
+            .outpoints(handle.outpoints)
+            .txids(handle.txids);
+
+        let update = self.sync(request).await?;
+
+        Ok(update.into())
+    }
+
+    /// Syncs at most `chunk_size` items of the snapshot and returns it
+    /// advanced. Once the snapshot is done, its update is applied with
+    /// [`Account::apply_sync_update`].
+    ///
+    /// On error, the snapshot passed in is still valid and can be retried.
+    pub async fn partial_sync_chunk(
+        &self,
+        mut snapshot: PartialSyncSnapshot,
+        chunk_size: usize,
+    ) -> Result<PartialSyncSnapshot, Error> {
+        let chunk = snapshot.remaining.take_chunk(chunk_size.max(1));
+        let update = self.partial_sync_detached(chunk).await?;
+        snapshot.update.merge(update);
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk_chain::{BlockId, ConfirmationBlockTime};
+    use bdk_wallet::{
+        bitcoin::{hashes::Hash, BlockHash, OutPoint, ScriptBuf, Txid},
+        serde_json, KeychainKind,
+    };
+
+    use super::{PartialSyncHandle, PartialSyncSnapshot, SyncUpdate};
+
+    fn block_id(height: u32, byte: u8) -> BlockId {
+        BlockId {
+            height,
+            hash: BlockHash::from_byte_array([byte; 32]),
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_sync_update() {
+        let block_ids = (0..3)
+            .map(|height| BlockId {
+                height,
+                hash: BlockHash::from_byte_array([height as u8; 32]),
+            })
+            .collect::<Vec<_>>();
+        let txid = Txid::from_byte_array([1; 32]);
+
+        let sync_update = SyncUpdate {
+            last_active_indices: vec![(KeychainKind::External, 4)],
+            anchors: vec![(
+                ConfirmationBlockTime {
+                    block_id: block_ids[2],
+                    confirmation_time: 1714116130,
+                },
+                txid,
+            )],
+            chain: Some(block_ids.clone()),
+            ..Default::default()
+        };
+
+        let serialized = serde_json::to_string(&sync_update).unwrap();
+        let update = serde_json::from_str::<SyncUpdate>(&serialized)
+            .unwrap()
+            .into_update()
+            .unwrap();
+
+        assert_eq!(update.last_active_indices.get(&KeychainKind::External), Some(&4));
+        assert_eq!(update.tx_update.anchors.len(), 1);
+        assert_eq!(update.chain.unwrap().block_id(), block_ids[2]);
+    }
+
+    #[test]
+    fn should_reject_unsorted_chain() {
+        let sync_update = SyncUpdate {
+            chain: Some(vec![]),
+            ..Default::default()
+        };
+
+        assert!(sync_update.into_update().is_err());
+    }
+
+    #[test]
+    fn should_take_partial_sync_chunks() {
+        let handle = PartialSyncHandle {
+            spks: vec![ScriptBuf::new(); 3],
+            outpoints: vec![OutPoint::null(); 2],
+            txids: vec![Txid::from_byte_array([1; 32])],
+            chain: vec![block_id(0, 0)],
+        };
+        let mut snapshot = PartialSyncSnapshot::new(handle);
+        assert_eq!(snapshot.remaining.len(), 6);
+
+        let chunk = snapshot.remaining.take_chunk(4);
+        assert_eq!((chunk.spks.len(), chunk.txids.len(), chunk.outpoints.len()), (3, 1, 0));
+        assert_eq!(chunk.chain, vec![block_id(0, 0)]);
+        assert!(!snapshot.is_done());
+
+        let chunk = snapshot.remaining.take_chunk(4);
+        assert_eq!(chunk.outpoints.len(), 2);
+        assert!(snapshot.is_done());
+    }
+
+    #[test]
+    fn should_merge_sync_updates() {
+        let mut update = SyncUpdate {
+            chain: Some(vec![block_id(0, 0), block_id(5, 5)]),
+            ..Default::default()
+        };
+
+        update.merge(SyncUpdate {
+            last_active_indices: vec![(KeychainKind::Internal, 2)],
+            chain: Some(vec![block_id(0, 0), block_id(5, 6), block_id(7, 7)]),
+            ..Default::default()
+        });
+        assert_eq!(update.last_active_indices, vec![(KeychainKind::Internal, 2)]);
+        assert_eq!(update.chain, Some(vec![block_id(0, 0), block_id(5, 6), block_id(7, 7)]));
+
+        update.merge(SyncUpdate::default());
+        assert_eq!(update.chain.map(|chain| chain.len()), Some(3));
+    }
+}
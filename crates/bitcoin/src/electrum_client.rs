@@ -0,0 +1,105 @@
+//! Sync backend talking to an Electrum server, for users running their own
+//! server instead of going through the Proton blockchain proxy.
+//!
+//! # Notes
+//!
+//! The Electrum client is blocking: its calls hold the executor thread until
+//! the server responds. Apps driving syncs from a single-threaded runtime
+//! should run them on a dedicated thread.
+
+use std::collections::HashMap;
+
+use bdk_chain::spk_client::{FullScanRequest, SyncRequest};
+use bdk_electrum::{
+    electrum_client::{Client, ElectrumApi},
+    BdkElectrumClient,
+};
+use bdk_wallet::{
+    bitcoin::{BlockHash, ScriptBuf, Transaction, Txid},
+    chain::spk_client::{FullScanResult, SyncResult},
+};
+
+use crate::error::Error;
+
+/// Number of script pub keys, transactions or outpoints requested at once
+pub const BATCH_SIZE: usize = 10;
+
+/// Confirmation targets, in blocks, for which fees are estimated. They match
+/// the ones returned by esplora's `fee-estimates` endpoint
+const FEE_ESTIMATION_TARGETS: [usize; 28] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 144, 504, 1008,
+];
+
+pub struct ElectrumClient(BdkElectrumClient<Client>);
+
+impl ElectrumClient {
+    /// Connects to the Electrum server at `url`, e.g.
+    /// `ssl://electrum.blockstream.info:50002`
+    pub fn new(url: &str) -> Result<Self, Error> {
+        let client = Client::new(url)?;
+
+        Ok(ElectrumClient(BdkElectrumClient::new(client)))
+    }
+
+    /// Scans the keychains' script pub keys until `stop_gap` consecutive ones
+    /// without history are found
+    pub fn full_scan<K: Ord + Clone>(
+        &self,
+        request: impl Into<FullScanRequest<K>>,
+        stop_gap: usize,
+    ) -> Result<FullScanResult<K>, Error> {
+        Ok(self.0.full_scan(request, stop_gap, BATCH_SIZE, true)?)
+    }
+
+    /// Syncs the script pub keys, transactions and outpoints of the request
+    pub fn sync<I: 'static>(&self, request: impl Into<SyncRequest<I>>) -> Result<SyncResult, Error> {
+        Ok(self.0.sync(request, BATCH_SIZE, true)?)
+    }
+
+    /// Returns whether any of the script pub keys has a transaction history
+    pub fn has_history(&self, spks: Vec<ScriptBuf>) -> Result<bool, Error> {
+        let histories = self
+            .0
+            .inner
+            .batch_script_get_history(spks.iter().map(|spk| spk.as_script()))?;
+
+        Ok(histories.iter().any(|history| !history.is_empty()))
+    }
+
+    /// Returns the hash of the current chain tip
+    pub fn get_tip_hash(&self) -> Result<BlockHash, Error> {
+        let notification = self.0.inner.block_headers_subscribe()?;
+
+        Ok(notification.header.block_hash())
+    }
+
+    /// Returns the height of the current chain tip
+    pub fn get_height(&self) -> Result<u32, Error> {
+        let notification = self.0.inner.block_headers_subscribe()?;
+
+        Ok(notification.height as u32)
+    }
+
+    /// Returns the server's minimum relay fee in BTC/kB
+    pub fn relay_fee(&self) -> Result<f64, Error> {
+        Ok(self.0.inner.relay_fee()?)
+    }
+
+    /// Returns fee estimations in sat/vB, keyed by confirmation target.
+    /// Targets the server can't estimate are left out
+    pub fn get_fee_estimates(&self) -> Result<HashMap<String, f64>, Error> {
+        let estimates = self.0.inner.batch_estimate_fee(FEE_ESTIMATION_TARGETS)?;
+
+        Ok(FEE_ESTIMATION_TARGETS
+            .iter()
+            .zip(estimates)
+            // Server returns -1 when it doesn't have enough data
+            .filter(|(_, btc_per_kb)| *btc_per_kb > 0.0)
+            .map(|(target, btc_per_kb)| (target.to_string(), btc_per_kb * 100000.0))
+            .collect())
+    }
+
+    pub fn broadcast(&self, transaction: &Transaction) -> Result<Txid, Error> {
+        Ok(self.0.transaction_broadcast(transaction)?)
+    }
+}
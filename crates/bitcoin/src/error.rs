@@ -1,11 +1,16 @@
 use std::fmt::Debug;
 
+use andromeda_common::cancellation::Cancelled;
+pub use andromeda_common::error::ErrorCategory;
+use andromeda_common::ScriptType;
 use andromeda_esplora::error::Error as EsploraClientError;
 use bdk_wallet::{
     bitcoin::{
         address::ParseError as BitcoinAddressParseError,
         bip32::Error as Bip32Error,
-        psbt::{Error as PsbtError, ExtractTxError},
+        key::FromWifError,
+        psbt::{Error as PsbtError, ExtractTxError, PsbtParseError},
+        sign_message::MessageSignatureError,
         OutPoint,
     },
     chain::local_chain::CannotConnectError,
@@ -46,6 +51,14 @@ pub enum Error {
     Bip39(#[from] Bip39Error),
     #[error("An error occured in esplora client: \n\t{0}")]
     EsploraClient(#[from] EsploraClientError),
+    #[error("An error occured in the API client: \n\t{0}")]
+    Api(#[from] andromeda_api::error::Error),
+    #[error("An error occured in electrum client: \n\t{0}")]
+    Electrum(String),
+    #[error("An error occured in bitcoind RPC client: \n\t{0}")]
+    BitcoindRpc(String),
+    #[error("Operation is not supported by the {0} backend")]
+    UnsupportedByBackend(&'static str),
     #[error("Invalid Hex data returned: \n\t{0}")]
     HexToArray(#[from] bitcoin::hashes::hex::HexToArrayError),
     #[error("Invalid Hex data returned: \n\t{0}")]
@@ -60,6 +73,16 @@ pub enum Error {
     ExtractTx(#[from] ExtractTxError),
     #[error("An error occured when interacting with PSBT: \n\t{0}")]
     Psbt(#[from] PsbtError),
+    #[error("An error occured when parsing base64 PSBT: \n\t{0}")]
+    PsbtParse(#[from] PsbtParseError),
+    #[error("Private key is invalid: \n\t{0}")]
+    PrivateKey(#[from] FromWifError),
+    #[error("BIP38 encrypted private key is invalid")]
+    InvalidBip38Key,
+    #[error("BIP38 passphrase is invalid")]
+    InvalidBip38Passphrase,
+    #[error("An error occured when signing or verifying a message: \n\t{0}")]
+    MessageSignature(#[from] MessageSignatureError),
     #[error("Address is invalid: {0}")]
     InvalidAddress(String),
     #[error("Data is invalid: {0:?}")]
@@ -68,6 +91,89 @@ pub enum Error {
     TransactionNotFound,
     #[error("UTXO was not found: {0:?}")]
     UtxoNotFound(OutPoint),
+    #[error("Operation was cancelled")]
+    Cancelled,
+    #[error("Script type {0:?} is not supported for this operation")]
+    UnsupportedScriptType(ScriptType),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl From<Cancelled> for Error {
+    fn from(_: Cancelled) -> Self {
+        Error::Cancelled
+    }
+}
+
+#[cfg(feature = "electrum")]
+impl From<bdk_electrum::electrum_client::Error> for Error {
+    fn from(error: bdk_electrum::electrum_client::Error) -> Self {
+        Error::Electrum(error.to_string())
+    }
+}
+
+#[cfg(feature = "bitcoind-rpc")]
+impl From<bdk_bitcoind_rpc::bitcoincore_rpc::Error> for Error {
+    fn from(error: bdk_bitcoind_rpc::bitcoincore_rpc::Error) -> Self {
+        Error::BitcoindRpc(error.to_string())
+    }
+}
+
+impl Error {
+    /// Category of the error, in the taxonomy shared with the API and
+    /// esplora errors
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::AccountNotFound | Error::TransactionNotFound | Error::UtxoNotFound(_) => ErrorCategory::NotFound,
+            Error::CreateWithPersistError | Error::LoadWithPersistError | Error::PersistError => ErrorCategory::Storage,
+            Error::CreateTx(CreateTxError::CoinSelection(_)) => ErrorCategory::InsufficientFunds,
+            Error::CreateTx(_) | Error::BuildFeeBump(_) | Error::AddUtxo(_) => ErrorCategory::InvalidInput,
+            Error::MiniscriptPsbt(_) | Error::Signer(_) => ErrorCategory::Signing,
+            Error::ExtractTx(_) => ErrorCategory::Consensus,
+            Error::EsploraClient(error) => error.category(),
+            Error::Api(error) => error.category(),
+            Error::CannotConnect(_) | Error::Electrum(_) | Error::BitcoindRpc(_) => ErrorCategory::Network,
+            Error::BitcoinAddressParse(_) | Error::FromScript(_) | Error::InvalidAddress(_) => {
+                ErrorCategory::InvalidAddress
+            }
+            Error::Bip32(_)
+            | Error::Bip39(_)
+            | Error::HexToArray(_)
+            | Error::HexToBytes(_)
+            | Error::Descriptor(_)
+            | Error::Psbt(_)
+            | Error::PsbtParse(_)
+            | Error::PrivateKey(_)
+            | Error::InvalidBip38Key
+            | Error::InvalidBip38Passphrase
+            | Error::MessageSignature(_)
+            | Error::InvalidData(_)
+            | Error::UnsupportedScriptType(_)
+            | Error::UnsupportedByBackend(_) => ErrorCategory::InvalidInput,
+            Error::Cancelled => ErrorCategory::Cancelled,
+            Error::Other(_) => ErrorCategory::Internal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk_wallet::coin_selection::InsufficientFunds;
+
+    use super::{CreateTxError, Error, ErrorCategory};
+
+    #[test]
+    fn should_categorise_errors() {
+        let insufficient_funds = Error::CreateTx(CreateTxError::CoinSelection(InsufficientFunds {
+            needed: 2000,
+            available: 1000,
+        }));
+        assert_eq!(insufficient_funds.category(), ErrorCategory::InsufficientFunds);
+
+        assert_eq!(
+            Error::InvalidAddress("bc1...".to_string()).category(),
+            ErrorCategory::InvalidAddress
+        );
+        assert_eq!(Error::Cancelled.category().as_str(), "cancelled");
+    }
+}
@@ -0,0 +1,103 @@
+//! Signing of PSBT inputs by keys held outside of the wallet, e.g. on a
+//! hardware wallet connected over USB, NFC or Bluetooth.
+
+use std::{fmt::Debug, sync::Arc};
+
+use bdk_wallet::{
+    bitcoin::{bip32::Fingerprint, psbt::Psbt as BdkPsbt},
+    signer::{SignerCommon, SignerError, SignerId, SignerOrdering, TransactionSigner},
+    SignOptions,
+};
+use bitcoin::secp256k1::{All, Secp256k1};
+
+/// Ordering of the external signers, after the wallet's own ones so that the
+/// PSBT sent to the device already holds the local signatures
+pub const EXTERNAL_SIGNER_ORDERING: SignerOrdering = SignerOrdering(200);
+
+/// Signer holding keys outside of the wallet, implemented by the host app
+pub trait ExternalSigner: Debug + Send + Sync {
+    /// Fingerprint of the signer's master key
+    fn fingerprint(&self) -> Fingerprint;
+
+    /// Signs the inputs of the PSBT that the signer owns and returns the
+    /// signed PSBT. Errors are messages to be shown to the user, e.g. when
+    /// the device got disconnected or the signing was rejected on it
+    fn sign_psbt(&self, psbt: BdkPsbt) -> Result<BdkPsbt, String>;
+}
+
+/// Adapter of an [`ExternalSigner`] to the signers of a BDK wallet
+#[derive(Debug, Clone)]
+pub struct ExternalSignerWrapper(pub Arc<dyn ExternalSigner>);
+
+impl SignerCommon for ExternalSignerWrapper {
+    fn id(&self, _secp: &Secp256k1<All>) -> SignerId {
+        SignerId::Fingerprint(self.0.fingerprint())
+    }
+}
+
+impl TransactionSigner for ExternalSignerWrapper {
+    fn sign_transaction(
+        &self,
+        psbt: &mut BdkPsbt,
+        _sign_options: &SignOptions,
+        _secp: &Secp256k1<All>,
+    ) -> Result<(), SignerError> {
+        let signed = self.0.sign_psbt(psbt.clone()).map_err(SignerError::External)?;
+
+        // Only the signatures are taken from the signed PSBT, which is
+        // rejected if the signer changed the transaction
+        psbt.combine(signed).map_err(|e| SignerError::External(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime, bip32::Fingerprint, psbt::Psbt as BdkPsbt, secp256k1::Secp256k1, transaction::Version,
+            Amount, ScriptBuf, Transaction, TxOut,
+        },
+        signer::{SignerError, TransactionSigner},
+        SignOptions,
+    };
+
+    use super::{ExternalSigner, ExternalSignerWrapper};
+
+    #[derive(Debug)]
+    struct TamperingSigner;
+
+    impl ExternalSigner for TamperingSigner {
+        fn fingerprint(&self) -> Fingerprint {
+            Fingerprint::from([1, 2, 3, 4])
+        }
+
+        fn sign_psbt(&self, mut psbt: BdkPsbt) -> Result<BdkPsbt, String> {
+            psbt.unsigned_tx.output[0].value = Amount::from_sat(1);
+            Ok(psbt)
+        }
+    }
+
+    #[test]
+    fn should_reject_tampered_psbt() {
+        let mut psbt = BdkPsbt::from_unsigned_tx(Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(1000),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        })
+        .unwrap();
+
+        let signer = ExternalSignerWrapper(Arc::new(TamperingSigner));
+        let result = signer.sign_transaction(&mut psbt, &SignOptions::default(), &Secp256k1::new());
+
+        assert!(matches!(result, Err(SignerError::External(_))));
+        assert_eq!(psbt.unsigned_tx.output[0].value, Amount::from_sat(1000));
+    }
+}
@@ -1,9 +1,21 @@
 pub mod account;
+pub mod account_sweeper;
 pub mod address;
+pub mod andromeda_wallet;
 pub mod bdk_wallet_ext;
+pub mod bip38;
+#[cfg(feature = "bitcoind-rpc")]
+pub mod bitcoind_rpc_client;
 pub mod blockchain_client;
+pub mod coin_selection;
+pub mod detached_sync;
+#[cfg(feature = "electrum")]
+pub mod electrum_client;
 pub mod error;
+pub mod external_signer;
+pub mod message_signer;
 pub mod mnemonic;
+pub mod paper_account;
 pub mod payment_link;
 pub mod psbt;
 pub mod storage;
@@ -20,15 +32,16 @@ pub use bdk_wallet::rusqlite::Connection;
 #[doc(hidden)]
 pub use bdk_wallet::{
     bitcoin::{
-        bip32::{ChildNumber, DerivationPath, Xpriv},
+        bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
         block::Header as BlockHeader,
         blockdata::{
             constants::genesis_block,
             locktime::absolute::{Height, LockTime, Time},
         },
         consensus::Params as ConsensusParams,
-        Address, Amount, BlockHash, Network as BdkNetwork, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
-        Witness,
+        psbt::Psbt as BdkPsbt,
+        Address, Amount, BlockHash, FeeRate, Network as BdkNetwork, OutPoint, ScriptBuf, Sequence, Transaction, TxIn,
+        TxOut, Witness,
     },
     chain::{ConfirmationBlockTime, ConfirmationTime},
     keys::{
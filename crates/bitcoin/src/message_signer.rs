@@ -0,0 +1,256 @@
+use std::{str::FromStr, sync::Arc};
+
+use bdk_wallet::{
+    bitcoin::{
+        base64::{prelude::BASE64_STANDARD, Engine},
+        bip32::ChildNumber,
+        hashes::Hash,
+        secp256k1::{Message, Secp256k1},
+        sign_message::{signed_msg_hash, MessageSignature, MessageSignatureError},
+        Address, AddressType, PrivateKey, PublicKey, ScriptBuf,
+    },
+    WalletPersister,
+};
+use miniscript::descriptor::DescriptorSecretKey;
+
+use crate::{account::Account, error::Error, storage::WalletPersisterConnector};
+
+/// Header offsets added by BIP137 to the recovery id, on top of the
+/// compressed key one, depending on the address type
+const BIP137_P2SH_P2WPKH_OFFSET: u8 = 4;
+const BIP137_P2WPKH_OFFSET: u8 = 8;
+
+/// Format of the message signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningType {
+    /// Header doesn't depend on the address type, as done by Electrum and
+    /// Bitcoin Core. Supported by most verifiers
+    Electrum,
+    /// Header encodes the address type, as specified by https://bips.dev/137/
+    Bip137,
+}
+
+/// Signs messages with the keys of an account's addresses, to prove their
+/// ownership, and verifies such signatures.
+///
+/// # Notes
+///
+/// Only single key addresses (P2PKH, P2SH-P2WPKH and P2WPKH) are supported,
+/// taproot ones would need BIP322 signatures.
+#[derive(Debug, Clone)]
+pub struct MessageSigner<C: WalletPersisterConnector<P>, P: WalletPersister> {
+    account: Arc<Account<C, P>>,
+}
+
+fn header_offset(address_type: AddressType, signing_type: SigningType) -> Result<u8, Error> {
+    match (address_type, signing_type) {
+        (AddressType::P2pkh, _) | (AddressType::P2sh | AddressType::P2wpkh, SigningType::Electrum) => Ok(0),
+        (AddressType::P2sh, SigningType::Bip137) => Ok(BIP137_P2SH_P2WPKH_OFFSET),
+        (AddressType::P2wpkh, SigningType::Bip137) => Ok(BIP137_P2WPKH_OFFSET),
+        (address_type, _) => Err(MessageSignatureError::UnsupportedAddressType(address_type).into()),
+    }
+}
+
+/// Returns the script pub key of the given type locked by the public key
+fn script_pubkey_of(public_key: &PublicKey, address_type: AddressType) -> Option<ScriptBuf> {
+    match address_type {
+        AddressType::P2pkh => Some(ScriptBuf::new_p2pkh(&public_key.pubkey_hash())),
+        AddressType::P2sh => {
+            let redeem_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash().ok()?);
+            Some(ScriptBuf::new_p2sh(&redeem_script.script_hash()))
+        }
+        AddressType::P2wpkh => Some(ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash().ok()?)),
+        _ => None,
+    }
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> MessageSigner<C, P> {
+    pub fn new(account: Arc<Account<C, P>>) -> Self {
+        Self { account }
+    }
+
+    async fn parse_address(&self, btc_address: &str) -> Result<Address, Error> {
+        let network = self.account.get_wallet().await.network();
+
+        Ok(Address::from_str(btc_address)?.require_network(network)?)
+    }
+
+    /// Returns the private key of an address owned by the account
+    async fn get_private_key(&self, address: &Address) -> Result<PrivateKey, Error> {
+        let wallet_lock = self.account.get_wallet().await;
+        let (keychain, index) = wallet_lock
+            .derivation_of_spk(address.script_pubkey())
+            .ok_or_else(|| Error::InvalidAddress(format!("{} is not owned by the account", address)))?;
+
+        let secp = Secp256k1::new();
+        wallet_lock
+            .get_signers(keychain)
+            .as_key_map(&secp)
+            .into_values()
+            .find_map(|secret_key| match secret_key {
+                DescriptorSecretKey::XPrv(xkey) => {
+                    let path = xkey.derivation_path.child(ChildNumber::Normal { index });
+                    xkey.xkey.derive_priv(&secp, &path).ok().map(|xprv| xprv.to_priv())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| Error::InvalidAddress(format!("No private key found for {}", address)))
+    }
+
+    /// Signs a message with the key of one of the account's addresses and
+    /// returns the base64 encoded signature
+    pub async fn sign_message(
+        &self,
+        message: &str,
+        signing_type: SigningType,
+        btc_address: &str,
+    ) -> Result<String, Error> {
+        let address = self.parse_address(btc_address).await?;
+        let address_type = address
+            .address_type()
+            .ok_or_else(|| Error::InvalidAddress(address.to_string()))?;
+        let header_offset = header_offset(address_type, signing_type)?;
+
+        let private_key = self.get_private_key(&address).await?;
+
+        let secp = Secp256k1::new();
+        let message_hash = Message::from_digest(signed_msg_hash(message).to_byte_array());
+        let signature = secp.sign_ecdsa_recoverable(&message_hash, &private_key.inner);
+
+        let mut serialized = MessageSignature::new(signature, private_key.compressed).serialize();
+        serialized[0] += header_offset;
+
+        Ok(BASE64_STANDARD.encode(serialized))
+    }
+
+    /// Verifies that a base64 encoded signature of the message was produced
+    /// by the key of the given address. Both signature formats are accepted.
+    pub async fn verify_message(&self, message: &str, signature: &str, btc_address: &str) -> Result<bool, Error> {
+        let address = self.parse_address(btc_address).await?;
+        let Some(address_type) = address.address_type() else {
+            return Ok(false);
+        };
+
+        let mut serialized = BASE64_STANDARD
+            .decode(signature)
+            .map_err(|_| MessageSignatureError::InvalidBase64)?;
+        // Strips the BIP137 offset so that the header is back to the Electrum one
+        if let Some(header) = serialized.first_mut() {
+            if *header >= 27 + 4 + BIP137_P2WPKH_OFFSET {
+                *header -= BIP137_P2WPKH_OFFSET;
+            } else if *header >= 27 + 4 + BIP137_P2SH_P2WPKH_OFFSET {
+                *header -= BIP137_P2SH_P2WPKH_OFFSET;
+            }
+        }
+
+        let signature = MessageSignature::from_slice(&serialized)?;
+        let public_key = signature.recover_pubkey(&Secp256k1::verification_only(), signed_msg_hash(message))?;
+
+        Ok(script_pubkey_of(&public_key, address_type) == Some(address.script_pubkey()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use andromeda_common::{Network, ScriptType};
+    use bdk_wallet::bitcoin::{
+        bip32::{DerivationPath, Xpriv},
+        NetworkKind,
+    };
+
+    use super::{MessageSigner, SigningType};
+    use crate::{account::Account, error::Error, mnemonic::Mnemonic, storage::MemoryPersisted};
+
+    fn set_test_signer(
+        script_type: ScriptType,
+        derivation_path: &str,
+    ) -> (
+        MessageSigner<MemoryPersisted, MemoryPersisted>,
+        Arc<Account<MemoryPersisted, MemoryPersisted>>,
+    ) {
+        let mnemonic = Mnemonic::from_string(
+            "desk prevent enhance husband hungry idle member vessel room moment simple behave".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+
+        let account = Arc::new(
+            Account::new(
+                master_secret_key,
+                Network::Testnet,
+                script_type,
+                DerivationPath::from_str(derivation_path).unwrap(),
+                MemoryPersisted {},
+            )
+            .unwrap(),
+        );
+
+        (MessageSigner::new(account.clone()), account)
+    }
+
+    #[tokio::test]
+    async fn should_sign_and_verify_message() {
+        let accounts = [
+            (ScriptType::Legacy, "m/44'/1'/0'"),
+            (ScriptType::NestedSegwit, "m/49'/1'/0'"),
+            (ScriptType::NativeSegwit, "m/84'/1'/0'"),
+        ];
+
+        for (script_type, derivation_path) in accounts {
+            let (signer, account) = set_test_signer(script_type, derivation_path);
+            let address = account.peek_receive_address(2).await.unwrap().address.to_string();
+            let other_address = account.peek_receive_address(3).await.unwrap().address.to_string();
+
+            for signing_type in [SigningType::Electrum, SigningType::Bip137] {
+                let signature = signer
+                    .sign_message("Proton Wallet", signing_type, &address)
+                    .await
+                    .unwrap();
+
+                assert!(signer
+                    .verify_message("Proton Wallet", &signature, &address)
+                    .await
+                    .unwrap());
+                assert!(!signer
+                    .verify_message("Proton Mail", &signature, &address)
+                    .await
+                    .unwrap());
+                assert!(!signer
+                    .verify_message("Proton Wallet", &signature, &other_address)
+                    .await
+                    .unwrap());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_not_sign_with_taproot_address() {
+        let (signer, account) = set_test_signer(ScriptType::Taproot, "m/86'/1'/0'");
+        let address = account.peek_receive_address(0).await.unwrap().address.to_string();
+
+        assert!(matches!(
+            signer
+                .sign_message("Proton Wallet", SigningType::Electrum, &address)
+                .await,
+            Err(Error::MessageSignature(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_not_sign_with_foreign_address() {
+        let (signer, _) = set_test_signer(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        assert!(matches!(
+            signer
+                .sign_message(
+                    "Proton Wallet",
+                    SigningType::Electrum,
+                    "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+                )
+                .await,
+            Err(Error::InvalidAddress(_))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{str::FromStr, sync::Arc};
+
+use andromeda_common::{utils::now, Network, ScriptType};
+use async_std::sync::RwLock;
+use bdk_wallet::{
+    bitcoin::{Address, FeeRate, PrivateKey, ScriptBuf},
+    descriptor, Balance as BdkBalance, KeychainKind, SignOptions, Wallet as BdkWallet,
+};
+
+use crate::{bip38::decrypt_bip38, blockchain_client::BlockchainClient, error::Error, psbt::Psbt};
+
+/// A paper account holds a single private key, usually imported from a paper
+/// wallet in WIF format, for the sole purpose of sweeping its funds into a
+/// Proton Wallet account.
+///
+/// Unlike [`Account`](crate::account::Account), it is not derived from the
+/// wallet's master key and is never persisted: chain data is fetched again
+/// each time the key is imported.
+#[derive(Debug, Clone)]
+pub struct PaperAccount {
+    script_type: ScriptType,
+    wallet: Arc<RwLock<BdkWallet>>,
+}
+
+impl PaperAccount {
+    /// Builds a paper account from a WIF encoded private key.
+    ///
+    /// # Notes
+    ///
+    /// Uncompressed keys can only be used with legacy scripts, other script
+    /// types will return a descriptor error.
+    pub fn new(private_key: &str, script_type: ScriptType, network: Network) -> Result<Self, Error> {
+        Self::from_private_key(PrivateKey::from_str(private_key)?, script_type, network)
+    }
+
+    /// Builds a paper account from a BIP38 encrypted private key and its
+    /// passphrase
+    pub fn new_bip38(
+        encrypted_key: &str,
+        passphrase: &str,
+        script_type: ScriptType,
+        network: Network,
+    ) -> Result<Self, Error> {
+        Self::from_private_key(decrypt_bip38(encrypted_key, passphrase, network)?, script_type, network)
+    }
+
+    pub fn from_private_key(private_key: PrivateKey, script_type: ScriptType, network: Network) -> Result<Self, Error> {
+        let descriptor = match script_type {
+            ScriptType::Legacy => descriptor!(pkh(private_key))?,
+            ScriptType::NestedSegwit => descriptor!(sh(wpkh(private_key)))?,
+            ScriptType::NativeSegwit => descriptor!(wpkh(private_key))?,
+            ScriptType::Taproot => descriptor!(tr(private_key))?,
+            ScriptType::NativeSegwitMultisig => return Err(Error::UnsupportedScriptType(script_type)),
+        };
+
+        let wallet = BdkWallet::create_single(descriptor)
+            .network(network.into())
+            .create_wallet_no_persist()?;
+
+        Ok(Self {
+            script_type,
+            wallet: Arc::new(RwLock::new(wallet)),
+        })
+    }
+
+    pub fn get_script_type(&self) -> ScriptType {
+        self.script_type
+    }
+
+    /// Returns the only address controlled by the private key for the
+    /// account's script type
+    pub async fn get_address(&self) -> Address {
+        self.wallet.read().await.peek_address(KeychainKind::External, 0).address
+    }
+
+    /// Returns the balance of the key, as of the last sync
+    pub async fn get_balance(&self) -> BdkBalance {
+        self.wallet.read().await.balance()
+    }
+
+    /// Fetches the transactions of the key's address and applies them to the
+    /// account
+    pub async fn sync(&self, client: &BlockchainClient) -> Result<(), Error> {
+        let request = self.wallet.read().await.start_full_scan();
+        // The descriptor has no wildcard, so there is a single script pub key to scan
+        let update = client.full_scan(request, 1).await?;
+
+        self.wallet
+            .write()
+            .await
+            .apply_update_at(update, Some(now().as_secs()))?;
+
+        Ok(())
+    }
+
+    /// Builds and signs a PSBT sending the whole balance of the key to the
+    /// given script pub key
+    pub async fn create_sweep_psbt(&self, recipient: ScriptBuf, fee_rate: FeeRate) -> Result<Psbt, Error> {
+        let mut wallet_lock = self.wallet.write().await;
+
+        let mut builder = wallet_lock.build_tx();
+        builder.drain_wallet().drain_to(recipient).fee_rate(fee_rate);
+        let mut psbt = builder.finish()?;
+
+        wallet_lock.sign(&mut psbt, SignOptions::default())?;
+
+        Ok(psbt.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use andromeda_common::{Network, ScriptType};
+
+    use super::PaperAccount;
+    use crate::error::Error;
+
+    const COMPRESSED_WIF: &str = "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA";
+    const UNCOMPRESSED_WIF: &str = "91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjJoQFacbgwmaKkrx";
+
+    #[tokio::test]
+    async fn should_derive_address_for_each_script_type() {
+        let addresses = [
+            (ScriptType::Legacy, "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"),
+            (ScriptType::NestedSegwit, "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"),
+            (ScriptType::NativeSegwit, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"),
+        ];
+
+        for (script_type, expected) in addresses {
+            let account = PaperAccount::new(COMPRESSED_WIF, script_type, Network::Testnet).unwrap();
+            assert_eq!(account.get_script_type(), script_type);
+            assert_eq!(account.get_address().await.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn should_reject_segwit_with_uncompressed_key() {
+        assert!(PaperAccount::new(UNCOMPRESSED_WIF, ScriptType::Legacy, Network::Testnet).is_ok());
+        assert!(matches!(
+            PaperAccount::new(UNCOMPRESSED_WIF, ScriptType::NativeSegwit, Network::Testnet),
+            Err(Error::Descriptor(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_import_bip38_encrypted_key() {
+        // Test vector from BIP38
+        let account = PaperAccount::new_bip38(
+            "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            "TestingOneTwoThree",
+            ScriptType::Legacy,
+            Network::Bitcoin,
+        )
+        .unwrap();
+
+        assert_eq!(
+            account.get_address().await.to_string(),
+            "164MQi977u9GUteHr4EPH27VkkdxmfCvGW"
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_private_key() {
+        assert!(matches!(
+            PaperAccount::new("not a key", ScriptType::NativeSegwit, Network::Testnet),
+            Err(Error::PrivateKey(_))
+        ));
+    }
+}
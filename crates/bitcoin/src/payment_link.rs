@@ -59,6 +59,10 @@ const AMOUNT_KEY: &str = "amount";
 const LABEL_KEY: &str = "label";
 const MESSAGE_KEY: &str = "message";
 
+// Human readable prefixes of BOLT11 invoices, for mainnet, testnet, signet and
+// regtest.
+const BOLT11_PREFIXES: [&str; 4] = ["lnbc", "lntb", "lntbs", "lnbcrt"];
+
 impl PaymentLink {
     /// Helper function to generate a query string from optional BIP-21
     /// parameters.
@@ -114,11 +118,19 @@ impl PaymentLink {
         Ok(address)
     }
 
+    /// Returns whether the string is a BOLT11 invoice, without `lightning:`
+    /// prefix
+    fn is_bolt11_invoice(payment_link_str: &str) -> bool {
+        let lowercased = payment_link_str.to_lowercase();
+        BOLT11_PREFIXES.iter().any(|prefix| lowercased.starts_with(prefix))
+    }
+
     /// Attempts to parse a `PaymentLink` from a string.
-    /// Supports Bitcoin addresses, BIP-21 URIs, and Lightning URIs.
+    /// Supports Bitcoin addresses, BIP-21 URIs, Lightning URIs and bare BOLT11
+    /// invoices.
     pub fn try_parse(payment_link_str: String, network: Network) -> Result<PaymentLink> {
-        // Check if URI is a Lightning URI.
-        if payment_link_str.starts_with("lightning") {
+        // Check if URI is a Lightning URI or invoice.
+        if payment_link_str.starts_with("lightning") || Self::is_bolt11_invoice(&payment_link_str) {
             return Ok(PaymentLink::LightningURI { uri: payment_link_str });
         }
 
@@ -233,6 +245,17 @@ mod tests {
         assert_eq!(payment_link.to_string(), "tb1qnmsyczn68t628m4uct5nqgjr7vf3w6mc0lvkfn");
     }
 
+    #[test]
+    fn parse_bare_bolt11_invoice_into_lightning_uri() {
+        let invoice = "LNBC2500N1P0YX2ZP2PP5AJH5UZ8MM0LPRVGFZJCH5YRLZE7YX9SHCNFQHVX9Y0WNN3CD5SRQ".to_string();
+        let result = PaymentLink::try_parse(invoice.clone(), Network::Bitcoin).unwrap();
+        assert_eq!(result, PaymentLink::LightningURI { uri: invoice });
+
+        let invoice = "lntb2500n1p0yx2zp2pp5ajh5uz8mm0lprvgfzjch5yrlze7yx9shcnfqhvx9y0wnn3cd5srq".to_string();
+        let result = PaymentLink::try_parse(invoice.clone(), Network::Testnet).unwrap();
+        assert_eq!(result, PaymentLink::LightningURI { uri: invoice });
+    }
+
     #[test]
     fn to_bitcoin_address_string_with_all_type() {
         let test_lightning_url = "lightning:lnbc2500n1p0yx2zp2pp5ajh5uz8mm0lprvgfzjch5yrlze7yx9shcnfqhvx9y0wnn3cd5srqdqqcqzysxqzfvsp5jc7uzwksah3t5kc04z3dh0g6aelw8p4x9n4vj4k5r6jjjufryxl2rq9qyyssqf7lnsx3wn9asjzj4u5q7uzg9xv7ss4srrtygwjt0hfzd9jvkhxygxmpds0p5ezyf34ynzzc3afddzfdgsak7awwtlcpczy7q2".to_string();
@@ -1,13 +1,41 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, str::FromStr};
 
-use bdk_wallet::bitcoin::psbt::Psbt as BdkPsbt;
-use bitcoin::{Amount, Transaction};
+use bdk_wallet::{bitcoin::psbt::Psbt as BdkPsbt, KeychainKind, Wallet as BdkWallet};
+use bitcoin::{Address, Amount, FeeRate, OutPoint, Transaction, TxOut};
 
 use crate::error::Error;
 
 #[derive(Clone, Debug)]
 pub struct Psbt(BdkPsbt);
 
+/// An input of a PSBT, with the output it spends when it is known
+#[derive(Clone, Debug)]
+pub struct PsbtInputDetails {
+    pub previous_output: OutPoint,
+    pub value: Option<Amount>,
+    pub address: Option<Address>,
+    pub is_mine: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct PsbtOutputDetails {
+    pub value: Amount,
+    pub address: Option<Address>,
+    pub is_mine: bool,
+    /// Whether the output pays to the internal keychain of the wallet
+    pub is_change: bool,
+}
+
+/// Summary of what a PSBT spends and where it sends the funds, to be reviewed
+/// before signing or broadcasting it
+#[derive(Clone, Debug)]
+pub struct PsbtDetails {
+    pub inputs: Vec<PsbtInputDetails>,
+    pub outputs: Vec<PsbtOutputDetails>,
+    pub fee: Amount,
+    pub fee_rate: FeeRate,
+}
+
 impl From<BdkPsbt> for Psbt {
     fn from(value: BdkPsbt) -> Self {
         Psbt(value)
@@ -23,6 +51,40 @@ impl Psbt {
         self.0.clone()
     }
 
+    /// Parses a base64 encoded PSBT, as exported by hardware wallet software
+    /// (Sparrow, Electrum...), see [`Psbt::from_bytes`]
+    pub fn from_base64(base64: &str) -> Result<Self, Error> {
+        let psbt = BdkPsbt::from_str(base64.trim())?;
+
+        Self::validate_round_trip(psbt)
+    }
+
+    pub fn to_base64(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Parses a binary PSBT. It is rejected if serialising it back wouldn't
+    /// yield the same PSBT, so that nothing is lost when it is exported again
+    /// after signing
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let psbt = BdkPsbt::deserialize(bytes)?;
+
+        Self::validate_round_trip(psbt)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.serialize()
+    }
+
+    fn validate_round_trip(psbt: BdkPsbt) -> Result<Self, Error> {
+        let bytes = psbt.serialize();
+        if BdkPsbt::deserialize(&bytes)? != psbt {
+            return Err(Error::InvalidData(bytes));
+        }
+
+        Ok(Psbt(psbt))
+    }
+
     pub fn extract_tx(&self) -> Result<Transaction, Error> {
         Ok(self.0.clone().extract_tx()?)
     }
@@ -34,4 +96,211 @@ impl Psbt {
     pub fn compute_tx_vbytes(&self) -> Result<u64, Error> {
         Ok(self.extract_tx()?.weight().to_vbytes_ceil())
     }
+
+    /// Returns the details of the PSBT's inputs and outputs, flagged with
+    /// their ownership by the given wallet.
+    ///
+    /// # Notes
+    ///
+    /// The fee rate is computed from the weight of the extracted transaction,
+    /// hence is only exact once the PSBT is signed.
+    pub fn inspect(&self, wallet: &BdkWallet) -> Result<PsbtDetails, Error> {
+        let address_of = |txout: &TxOut| Address::from_script(&txout.script_pubkey, wallet.network()).ok();
+
+        let inputs = self
+            .0
+            .unsigned_tx
+            .input
+            .iter()
+            .zip(self.0.inputs.iter())
+            .map(|(txin, input)| {
+                let previous_output = txin.previous_output;
+                let txout = input
+                    .witness_utxo
+                    .clone()
+                    .or_else(|| {
+                        input
+                            .non_witness_utxo
+                            .as_ref()
+                            .and_then(|tx| tx.output.get(previous_output.vout as usize).cloned())
+                    })
+                    .or_else(|| wallet.get_utxo(previous_output).map(|utxo| utxo.txout));
+
+                PsbtInputDetails {
+                    previous_output,
+                    value: txout.as_ref().map(|txout| txout.value),
+                    address: txout.as_ref().and_then(address_of),
+                    is_mine: txout.is_some_and(|txout| wallet.is_mine(txout.script_pubkey)),
+                }
+            })
+            .collect();
+
+        let outputs = self
+            .0
+            .unsigned_tx
+            .output
+            .iter()
+            .map(|txout| {
+                let derivation = wallet.derivation_of_spk(txout.script_pubkey.clone());
+
+                PsbtOutputDetails {
+                    value: txout.value,
+                    address: address_of(txout),
+                    is_mine: derivation.is_some(),
+                    is_change: matches!(derivation, Some((KeychainKind::Internal, _))),
+                }
+            })
+            .collect();
+
+        let fee = self.fee()?;
+        let weight = self.extract_tx()?.weight();
+
+        Ok(PsbtDetails {
+            inputs,
+            outputs,
+            fee,
+            fee_rate: FeeRate::from_sat_per_kwu(fee.to_sat() * 1000 / weight.to_wu().max(1)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use andromeda_common::{Network, ScriptType};
+    use bdk_chain::{BlockId, ConfirmationBlockTime};
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime,
+            bip32::{DerivationPath, Xpriv},
+            constants::genesis_block,
+            hashes::Hash,
+            transaction::Version,
+            Address, Amount, BlockHash, FeeRate, NetworkKind, OutPoint, Transaction, TxIn, TxOut, Txid,
+        },
+        SignOptions,
+    };
+
+    use crate::{
+        account::Account, detached_sync::SyncUpdate, mnemonic::Mnemonic, psbt::Psbt, storage::MemoryPersisted,
+    };
+
+    const FOREIGN_ADDRESS: &str = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+
+    async fn set_funded_test_account() -> Account<MemoryPersisted, MemoryPersisted> {
+        let mnemonic = Mnemonic::from_string(
+            "desk prevent enhance husband hungry idle member vessel room moment simple behave".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+
+        let account = Account::new(
+            master_secret_key,
+            Network::Testnet,
+            ScriptType::NativeSegwit,
+            DerivationPath::from_str("m/84'/1'/0'").unwrap(),
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        let address = account.get_next_receive_address().await.unwrap();
+        let funding_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_byte_array([1; 32]), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+
+        let genesis = BlockId {
+            height: 0,
+            hash: genesis_block(bdk_wallet::bitcoin::Network::Testnet).block_hash(),
+        };
+        let block = BlockId {
+            height: 1,
+            hash: BlockHash::from_byte_array([1; 32]),
+        };
+
+        account
+            .apply_sync_update(SyncUpdate {
+                anchors: vec![(
+                    ConfirmationBlockTime {
+                        block_id: block,
+                        confirmation_time: 1714116130,
+                    },
+                    funding_tx.compute_txid(),
+                )],
+                txs: vec![funding_tx],
+                chain: Some(vec![genesis, block]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        account
+    }
+
+    #[tokio::test]
+    async fn should_inspect_psbt() {
+        let account = set_funded_test_account().await;
+        let recipient = Address::from_str(FOREIGN_ADDRESS).unwrap().assume_checked();
+
+        let psbt: Psbt = {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let mut builder = wallet_lock.build_tx();
+            builder
+                .add_recipient(recipient.script_pubkey(), Amount::from_sat(50_000))
+                .fee_rate(FeeRate::from_sat_per_vb(2).unwrap());
+            let mut psbt = builder.finish().unwrap();
+            wallet_lock.sign(&mut psbt, SignOptions::default()).unwrap();
+
+            psbt.into()
+        };
+
+        let details = psbt.inspect(&account.get_wallet().await).unwrap();
+
+        assert_eq!(details.inputs.len(), 1);
+        assert!(details.inputs[0].is_mine);
+        assert_eq!(details.inputs[0].value, Some(Amount::from_sat(100_000)));
+
+        let (change, payment): (Vec<_>, Vec<_>) = details.outputs.iter().partition(|output| output.is_change);
+        assert_eq!(payment.len(), 1);
+        assert_eq!(payment[0].value, Amount::from_sat(50_000));
+        assert_eq!(payment[0].address, Some(recipient));
+        assert!(!payment[0].is_mine);
+        assert_eq!(change.len(), 1);
+        assert!(change[0].is_mine);
+
+        assert_eq!(details.fee, Amount::from_sat(100_000 - 50_000) - change[0].value);
+        assert!((1..=3).contains(&details.fee_rate.to_sat_per_vb_ceil()));
+    }
+
+    #[tokio::test]
+    async fn should_round_trip_psbt() {
+        let account = set_funded_test_account().await;
+        let recipient = Address::from_str(FOREIGN_ADDRESS).unwrap().assume_checked();
+
+        let psbt: Psbt = {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let mut builder = wallet_lock.build_tx();
+            builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(50_000));
+
+            builder.finish().unwrap().into()
+        };
+
+        let from_base64 = Psbt::from_base64(&psbt.to_base64()).unwrap();
+        assert_eq!(from_base64.inner(), psbt.inner());
+
+        let from_bytes = Psbt::from_bytes(&psbt.to_bytes()).unwrap();
+        assert_eq!(from_bytes.inner(), psbt.inner());
+
+        assert!(Psbt::from_base64("cHNidP8=").is_err());
+        assert!(Psbt::from_bytes(&psbt.to_bytes()[..10]).is_err());
+    }
 }
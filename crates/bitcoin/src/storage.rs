@@ -1,7 +1,14 @@
-use std::{convert::Infallible, fmt::Debug};
+#[cfg(feature = "sqlite")]
+use std::path::PathBuf;
+use std::{convert::Infallible, fmt::Debug, sync::Arc};
 
+use bdk_wallet::serde_json;
 pub use bdk_wallet::{chain::Merge, ChangeSet, WalletPersister};
 
+use crate::error::Error;
+
+const CHANGESET_KEY_BASE: &str = "CHANGESET";
+
 pub trait WalletConnectorFactory<C, P>: Clone + Debug
 where
     C: WalletPersisterConnector<P>,
@@ -43,3 +50,155 @@ impl WalletPersister for MemoryPersisted {
         Ok(())
     }
 }
+
+/// Key-value storage implemented by the host app, e.g. over a callback
+/// interface backed by its own database. Changesets are handed over JSON
+/// serialised, so that the storage doesn't need to know about BDK types.
+pub trait ForeignStorage: Debug + Send + Sync {
+    fn read(&self, key: String) -> Result<Option<String>, Error>;
+
+    fn write(&self, key: String, value: String) -> Result<(), Error>;
+}
+
+/// Persists the wallet changeset in a [`ForeignStorage`], merged with the
+/// previously persisted one
+#[derive(Clone, Debug)]
+pub struct ForeignPersister {
+    storage: Arc<dyn ForeignStorage>,
+    changeset_key: String,
+}
+
+impl ForeignPersister {
+    pub fn new(storage: Arc<dyn ForeignStorage>, key: String) -> Self {
+        Self {
+            storage,
+            changeset_key: format!("{}_{}", CHANGESET_KEY_BASE, key),
+        }
+    }
+
+    fn get(&self) -> Result<ChangeSet, Error> {
+        let changeset = match self.storage.read(self.changeset_key.clone())? {
+            Some(serialized) => serde_json::from_str(&serialized).map_err(|e| Error::Other(e.into()))?,
+            None => ChangeSet::default(),
+        };
+
+        Ok(changeset)
+    }
+}
+
+impl WalletPersister for ForeignPersister {
+    type Error = Error;
+
+    fn initialize(persister: &mut Self) -> Result<ChangeSet, Error> {
+        persister.get()
+    }
+
+    fn persist(persister: &mut Self, new_changeset: &ChangeSet) -> Result<(), Error> {
+        let mut changeset = persister.get()?;
+        changeset.merge(new_changeset.clone());
+
+        let serialized = serde_json::to_string(&changeset).map_err(|e| Error::Other(e.into()))?;
+        persister.storage.write(persister.changeset_key.clone(), serialized)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ForeignConnector {
+    storage: Arc<dyn ForeignStorage>,
+    key: String,
+}
+
+impl WalletPersisterConnector<ForeignPersister> for ForeignConnector {
+    fn connect(&self) -> ForeignPersister {
+        ForeignPersister::new(self.storage.clone(), self.key.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ForeignPersisterFactory(pub Arc<dyn ForeignStorage>);
+
+impl WalletConnectorFactory<ForeignConnector, ForeignPersister> for ForeignPersisterFactory {
+    fn build(self, key: String) -> ForeignConnector {
+        ForeignConnector { storage: self.0, key }
+    }
+}
+
+/// Opens the SQLite database of a single account, the schema being the one
+/// of BDK's own rusqlite persister
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Debug)]
+pub struct SqliteConnector {
+    path: PathBuf,
+}
+
+#[cfg(feature = "sqlite")]
+impl WalletPersisterConnector<bdk_wallet::rusqlite::Connection> for SqliteConnector {
+    fn connect(&self) -> bdk_wallet::rusqlite::Connection {
+        bdk_wallet::rusqlite::Connection::open(&self.path).expect("database should be openable")
+    }
+}
+
+/// Stores each account in its own SQLite file under the given directory
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Debug)]
+pub struct SqlitePersisterFactory(pub PathBuf);
+
+#[cfg(feature = "sqlite")]
+impl WalletConnectorFactory<SqliteConnector, bdk_wallet::rusqlite::Connection> for SqlitePersisterFactory {
+    fn build(self, key: String) -> SqliteConnector {
+        // Keys contain the derivation path, which isn't a valid file name
+        let file_name = key.replace('/', "_").replace('\'', "h");
+
+        SqliteConnector {
+            path: self.0.join(format!("{}.sqlite", file_name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use bdk_wallet::{bitcoin::Network, ChangeSet, WalletPersister};
+
+    use super::{ForeignPersister, ForeignStorage};
+    use crate::error::Error;
+
+    #[derive(Debug, Default)]
+    struct InMemoryStorage(Mutex<HashMap<String, String>>);
+
+    impl ForeignStorage for InMemoryStorage {
+        fn read(&self, key: String) -> Result<Option<String>, Error> {
+            Ok(self.0.lock().unwrap().get(&key).cloned())
+        }
+
+        fn write(&self, key: String, value: String) -> Result<(), Error> {
+            self.0.lock().unwrap().insert(key, value);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_persist_in_foreign_storage() {
+        let storage = Arc::new(InMemoryStorage::default());
+
+        let mut persister = ForeignPersister::new(storage.clone(), "account".to_string());
+        assert_eq!(
+            WalletPersister::initialize(&mut persister).unwrap(),
+            ChangeSet::default()
+        );
+
+        let changeset = ChangeSet {
+            network: Some(Network::Testnet),
+            ..Default::default()
+        };
+        WalletPersister::persist(&mut persister, &changeset).unwrap();
+        assert!(storage.0.lock().unwrap().contains_key("CHANGESET_account"));
+
+        let mut persister = ForeignPersister::new(storage, "account".to_string());
+        assert_eq!(WalletPersister::initialize(&mut persister).unwrap(), changeset);
+    }
+}
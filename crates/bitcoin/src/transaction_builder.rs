@@ -8,28 +8,32 @@ use bdk_wallet::{
     },
     error::CreateTxError,
     tx_builder::{ChangeSpendPolicy, TxBuilder as BdkTxBuilder},
-    WalletPersister,
+    LocalOutput, WalletPersister,
 };
 use bitcoin::key::rand::RngCore;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::account::Account;
 use crate::{
+    coin_selection::PrivacyPreservingCoinSelection,
     error::Error,
     psbt::Psbt,
     storage::{MemoryPersisted, WalletPersisterConnector},
 };
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum CoinSelection {
     BranchAndBound,
     LargestFirst,
     OldestFirst,
+    /// See [`PrivacyPreservingCoinSelection`]
+    PrivacyPreserving,
     Manual,
 }
 
-struct FixedRng(pub u32);
+pub(crate) struct FixedRng(pub u32);
 
 impl RngCore for FixedRng {
     fn next_u32(&mut self) -> u32 {
@@ -58,6 +62,47 @@ impl RngCore for FixedRng {
 #[derive(Clone, Debug, PartialEq)]
 pub struct TmpRecipient(pub String, pub String, pub Amount);
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DraftRecipient {
+    pub id: String,
+    pub address: String,
+    pub amount: u64,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Output of a batched transaction, matched back to the recipient it pays
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchOutput {
+    pub vout: u32,
+    pub address: String,
+    pub amount: Amount,
+    pub label: Option<String>,
+}
+
+/// PSBT paying many recipients at once, along with the outputs paying each of
+/// them, so that a wallet transaction can be created for each output with
+/// its label
+#[derive(Clone, Debug)]
+pub struct BatchPsbt {
+    pub psbt: Psbt,
+    pub outputs: Vec<BatchOutput>,
+}
+
+/// Serialisable state of an in-progress [`TxBuilder`], so that it can be
+/// persisted and restored, e.g. across app restarts. The account isn't part
+/// of the draft and needs to be set again on the restored builder.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TxBuilderDraft {
+    pub recipients: Vec<DraftRecipient>,
+    pub utxos_to_spend: Vec<OutPoint>,
+    pub manually_selected_only: bool,
+    /// Fee rate in sat/vb
+    pub fee_rate: Option<u64>,
+    pub coin_selection: CoinSelection,
+    pub rbf_enabled: bool,
+}
+
 /// BDK's implementation of Transaction builder is quite complete, but we need a
 /// struct that enables stateful transaction creation, so we just added a layer
 /// on top of it.
@@ -86,6 +131,10 @@ pub struct TxBuilder<C: WalletPersisterConnector<P>, P: WalletPersister = Memory
     /// A set of unspent transaction outputs (UTXOs) that are selected to be
     /// spent in the transaction.
     pub utxos_to_spend: HashSet<OutPoint>,
+    /// A flag indicating whether only the UTXOs in `utxos_to_spend` can be
+    /// spent with manual coin selection. Otherwise, other UTXOs get added when
+    /// the selected ones are not enough to fund the transaction.
+    pub manually_selected_only: bool,
     /// The policy dictating how change from the transaction should be handled.
     pub change_policy: ChangeSpendPolicy,
     /// The fee rate to be used for the transaction, if specified.
@@ -103,6 +152,8 @@ pub struct TxBuilder<C: WalletPersisterConnector<P>, P: WalletPersister = Memory
     pub data: Vec<u8>,
     /// The coin selection strategy to use for choosing UTXOs.
     pub coin_selection: CoinSelection,
+    /// Labels of the recipients, keyed by their uuid
+    pub recipient_labels: HashMap<String, String>,
     /// The locktime (block height or timestamp) at which this transaction can
     /// be included in a block, if specified.
     pub locktime: Option<LockTime>,
@@ -115,6 +166,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Clone for TxBuilder<C,
             random_number: self.random_number,
             recipients: self.recipients.clone(),
             utxos_to_spend: self.utxos_to_spend.clone(),
+            manually_selected_only: self.manually_selected_only,
             change_policy: self.change_policy,
             fee_rate: self.fee_rate,
             drain_wallet: self.drain_wallet,
@@ -122,6 +174,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Clone for TxBuilder<C,
             rbf_enabled: self.rbf_enabled,
             data: self.data.clone(),
             coin_selection: self.coin_selection.clone(),
+            recipient_labels: self.recipient_labels.clone(),
             locktime: self.locktime,
         }
     }
@@ -181,6 +234,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
             random_number: bitcoin::key::rand::thread_rng().next_u32(),
             recipients: vec![TmpRecipient(Uuid::new_v4().to_string(), String::new(), Amount::ZERO)],
             utxos_to_spend: HashSet::new(),
+            manually_selected_only: false,
             change_policy: ChangeSpendPolicy::ChangeAllowed,
             fee_rate: None,
             drain_wallet: false,
@@ -188,6 +242,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
             rbf_enabled: true,
             locktime: None,
             coin_selection: CoinSelection::BranchAndBound,
+            recipient_labels: HashMap::new(),
             data: Vec::new(),
         }
     }
@@ -227,6 +282,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
     pub fn clear_recipients(&self) -> Self {
         TxBuilder {
             recipients: Vec::new(),
+            recipient_labels: HashMap::new(),
             ..self.clone()
         }
     }
@@ -256,6 +312,29 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
+    /// Adds recipients, given as address, amount in sats and label, to the
+    /// internal list, e.g. for batched payouts finished with
+    /// [`TxBuilder::create_batch_psbt`]
+    pub fn add_labelled_recipients(&self, batch: Vec<(String, u64, Option<String>)>) -> Self {
+        let mut recipients = self.recipients.clone();
+        let mut recipient_labels = self.recipient_labels.clone();
+
+        for (address, amount, label) in batch {
+            let id = Uuid::new_v4().to_string();
+            if let Some(label) = label {
+                recipient_labels.insert(id.clone(), label);
+            }
+
+            recipients.push(TmpRecipient(id, address, Amount::from_sat(amount)));
+        }
+
+        TxBuilder {
+            recipients,
+            recipient_labels,
+            ..self.clone()
+        }
+    }
+
     /// Remove a recipient from the internal list.
     ///     
     /// ```rust, ignore
@@ -266,12 +345,16 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
     pub fn remove_recipient(&self, index: usize) -> Self {
         let mut recipients = self.recipients.clone();
 
+        let mut recipient_labels = self.recipient_labels.clone();
+
         if index < recipients.len() {
-            recipients.remove(index);
+            let TmpRecipient(uuid, _, _) = recipients.remove(index);
+            recipient_labels.remove(&uuid);
         }
 
         TxBuilder {
             recipients,
+            recipient_labels,
             ..self.clone()
         }
     }
@@ -367,6 +450,30 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
+    /// Restricts manual coin selection to the outpoints to spend
+    pub fn set_manually_selected_only(&self, manually_selected_only: bool) -> Self {
+        TxBuilder {
+            manually_selected_only,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the account's UTXOs along with whether they are in the list
+    /// of outpoints to spend, for coin control pickers
+    pub async fn get_utxos_with_selection(&self) -> Result<Vec<(LocalOutput, bool)>, Error> {
+        let account = self.account.clone().ok_or(Error::AccountNotFound)?;
+
+        Ok(account
+            .get_utxos()
+            .await
+            .into_iter()
+            .map(|utxo| {
+                let selected = self.utxos_to_spend.contains(&utxo.outpoint);
+                (utxo, selected)
+            })
+            .collect())
+    }
+
     /// Sets the selected coin selection algorithm
     pub fn set_coin_selection(&self, coin_selection: CoinSelection) -> Self {
         TxBuilder {
@@ -428,6 +535,55 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
+    /// Returns the recipients, selected coins and fee options of the builder
+    pub fn to_draft(&self) -> TxBuilderDraft {
+        TxBuilderDraft {
+            recipients: self
+                .recipients
+                .iter()
+                .map(|recipient| DraftRecipient {
+                    id: recipient.0.clone(),
+                    address: recipient.1.clone(),
+                    amount: recipient.2.to_sat(),
+                    label: self.recipient_labels.get(&recipient.0).cloned(),
+                })
+                .collect(),
+            utxos_to_spend: self.utxos_to_spend.iter().copied().collect(),
+            manually_selected_only: self.manually_selected_only,
+            fee_rate: self.fee_rate.map(|fee_rate| fee_rate.to_sat_per_vb_ceil()),
+            coin_selection: self.coin_selection.clone(),
+            rbf_enabled: self.rbf_enabled,
+        }
+    }
+
+    /// Restores the state of a draft returned by [`TxBuilder::to_draft`],
+    /// keeping the builder's account.
+    ///
+    /// # Notes
+    ///
+    /// The draft's coins are not checked against the account, those spent
+    /// since the draft was saved make the PSBT creation fail.
+    pub fn apply_draft(&self, draft: TxBuilderDraft) -> Self {
+        TxBuilder {
+            recipient_labels: draft
+                .recipients
+                .iter()
+                .filter_map(|recipient| Some((recipient.id.clone(), recipient.label.clone()?)))
+                .collect(),
+            recipients: draft
+                .recipients
+                .into_iter()
+                .map(|recipient| TmpRecipient(recipient.id, recipient.address, Amount::from_sat(recipient.amount)))
+                .collect(),
+            utxos_to_spend: draft.utxos_to_spend.into_iter().collect(),
+            manually_selected_only: draft.manually_selected_only,
+            fee_rate: draft.fee_rate.and_then(FeeRate::from_sat_per_vb),
+            coin_selection: draft.coin_selection,
+            rbf_enabled: draft.rbf_enabled,
+            ..self.clone()
+        }
+    }
+
     fn commit_utxos<'a, Cs: CoinSelectionAlgorithm>(
         &self,
         mut tx_builder: BdkTxBuilder<'a, Cs>,
@@ -436,6 +592,10 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
             let bdk_utxos: Vec<OutPoint> = self.utxos_to_spend.iter().copied().collect();
             let utxos: &[OutPoint] = &bdk_utxos;
             tx_builder.add_utxos(utxos)?;
+
+            if self.manually_selected_only {
+                tx_builder.manually_selected_only();
+            }
         }
 
         Ok(tx_builder)
@@ -498,6 +658,9 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
                 CoinSelection::OldestFirst => {
                     self.finish_tx(tx_builder.coin_selection(OldestFirstCoinSelection), allow_dust)
                 }
+                CoinSelection::PrivacyPreserving => {
+                    self.finish_tx(tx_builder.coin_selection(PrivacyPreservingCoinSelection), allow_dust)
+                }
                 CoinSelection::Manual => self.finish_tx(self.commit_utxos(tx_builder)?, allow_dust),
             }
         }?;
@@ -516,6 +679,48 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         let psbt = self.create_psbt(allow_dust, true).await?;
         Ok(psbt)
     }
+
+    /// Creates a PSBT paying all the recipients, and returns it along with
+    /// the output paying each recipient and its label.
+    ///
+    /// Outputs are shuffled in the transaction, recipients paying the same
+    /// amount to the same address are matched to the outputs in order.
+    pub async fn create_batch_psbt(&self, allow_dust: bool) -> Result<BatchPsbt, Error> {
+        let psbt = self.create_psbt(allow_dust, false).await?;
+
+        let transaction = psbt.inner().unsigned_tx;
+
+        let mut matched_vouts = HashSet::new();
+        let outputs = self
+            .recipients
+            .iter()
+            .map(|TmpRecipient(uuid, address, amount)| {
+                let script_pubkey = Address::from_str(address)?.assume_checked().script_pubkey();
+
+                let vout = transaction
+                    .output
+                    .iter()
+                    .enumerate()
+                    .find_map(|(vout, output)| {
+                        (output.script_pubkey == script_pubkey
+                            && output.value == *amount
+                            && !matched_vouts.contains(&vout))
+                        .then_some(vout)
+                    })
+                    .ok_or_else(|| anyhow::anyhow!("No output pays {} to {}", amount, address))?;
+                matched_vouts.insert(vout);
+
+                Ok(BatchOutput {
+                    vout: vout as u32,
+                    address: address.clone(),
+                    amount: *amount,
+                    label: self.recipient_labels.get(uuid).cloned(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(BatchPsbt { psbt, outputs })
+    }
 }
 
 #[cfg(test)]
@@ -537,7 +742,7 @@ mod tests {
         bitcoin::{
             absolute::LockTime,
             bip32::{DerivationPath, Xpriv},
-            Address, Amount, FeeRate, NetworkKind,
+            Address, Amount, FeeRate, NetworkKind, OutPoint,
         },
         serde_json,
         tx_builder::ChangeSpendPolicy,
@@ -631,10 +836,25 @@ mod tests {
         let updated = tx_builder.set_coin_selection(CoinSelection::LargestFirst);
         assert_eq!(updated.coin_selection, CoinSelection::LargestFirst);
 
+        let updated = tx_builder.set_coin_selection(CoinSelection::PrivacyPreserving);
+        assert_eq!(updated.coin_selection, CoinSelection::PrivacyPreserving);
+
         let updated = tx_builder.set_coin_selection(CoinSelection::Manual);
         assert_eq!(updated.coin_selection, CoinSelection::Manual);
     }
 
+    #[test]
+    fn should_set_manually_selected_only() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new();
+        assert!(!tx_builder.manually_selected_only);
+
+        let updated = tx_builder.set_manually_selected_only(true);
+        assert!(updated.manually_selected_only);
+
+        let updated = updated.set_manually_selected_only(false);
+        assert!(!updated.manually_selected_only);
+    }
+
     #[test]
     fn should_set_change_policy() {
         let tx_builder = TxBuilder::<MemoryPersisted>::new();
@@ -673,6 +893,29 @@ mod tests {
         assert_eq!(tx_builder.recipients.len(), 1);
     }
 
+    #[test]
+    fn should_add_labelled_recipients() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new().clear_recipients();
+
+        let updated = tx_builder.add_labelled_recipients(vec![
+            ("tb1...abc".to_string(), 1000, Some("Alice's payout".to_string())),
+            ("tb1...xyz".to_string(), 2000, None),
+        ]);
+        assert_eq!(updated.recipients.len(), 2);
+        assert_eq!(
+            updated.recipient_labels.get(&updated.recipients[0].0),
+            Some(&"Alice's payout".to_string())
+        );
+        assert_eq!(updated.recipient_labels.get(&updated.recipients[1].0), None);
+
+        let serialized = serde_json::to_string(&updated.to_draft()).unwrap();
+        let restored = TxBuilder::<MemoryPersisted>::new().apply_draft(serde_json::from_str(&serialized).unwrap());
+        assert_eq!(restored.recipient_labels, updated.recipient_labels);
+
+        let removed = updated.remove_recipient(0);
+        assert!(removed.recipient_labels.is_empty());
+    }
+
     #[tokio::test]
     async fn should_update_recipient() {
         let tx_builder = TxBuilder::<MemoryPersisted>::new();
@@ -686,6 +929,28 @@ mod tests {
         assert_eq!(updated.recipients[0].2, Amount::from_sat(668932));
     }
 
+    #[test]
+    fn should_restore_draft() {
+        let outpoint =
+            OutPoint::from_str("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90:1").unwrap();
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .update_recipient(0, (Some("tb1...xyz".to_string()), Some(15837)))
+            .add_recipient(Some((Some("tb1...abc".to_string()), Some(1000))))
+            .add_utxo_to_spend(&outpoint)
+            .set_coin_selection(CoinSelection::Manual)
+            .set_fee_rate(12)
+            .disable_rbf();
+
+        let serialized = serde_json::to_string(&tx_builder.to_draft()).unwrap();
+        let restored = TxBuilder::<MemoryPersisted>::new().apply_draft(serde_json::from_str(&serialized).unwrap());
+
+        assert_eq!(restored.recipients, tx_builder.recipients);
+        assert_eq!(restored.utxos_to_spend, tx_builder.utxos_to_spend);
+        assert_eq!(restored.coin_selection, CoinSelection::Manual);
+        assert_eq!(restored.fee_rate, FeeRate::from_sat_per_vb(12));
+        assert!(!restored.rbf_enabled);
+    }
+
     fn set_test_account_regtest(
         script_type: ScriptType,
         derivation_path: &str,
@@ -288,3 +288,67 @@ impl Default for Pagination {
         Pagination::new(0, usize::MAX)
     }
 }
+
+/// Transactions kept when listing them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionFilter {
+    #[default]
+    All,
+    /// Transactions increasing the balance
+    Received,
+    /// Transactions decreasing the balance, including transfers between
+    /// accounts which only spend fees
+    Sent,
+    Unconfirmed,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, transaction: &TransactionDetails) -> bool {
+        match self {
+            TransactionFilter::All => true,
+            TransactionFilter::Received => transaction.received > transaction.sent,
+            TransactionFilter::Sent => transaction.sent > transaction.received,
+            TransactionFilter::Unconfirmed => matches!(transaction.time, TransactionTime::Unconfirmed { .. }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bdk_wallet::bitcoin::{bip32::DerivationPath, hashes::Hash, Txid};
+
+    use super::{TransactionDetails, TransactionFilter, TransactionTime};
+
+    fn transaction(received: u64, sent: u64, time: TransactionTime) -> TransactionDetails {
+        TransactionDetails {
+            txid: Txid::all_zeros(),
+            received,
+            sent,
+            fees: Some(200),
+            vbytes_size: 141,
+            time,
+            inputs: vec![],
+            outputs: vec![],
+            account_derivation_path: DerivationPath::from_str("m/84'/1'/0'").unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_filter_transactions() {
+        let received = transaction(
+            1000,
+            0,
+            TransactionTime::Confirmed {
+                confirmation_time: 1714116130,
+            },
+        );
+        let sent = transaction(300, 1500, TransactionTime::Unconfirmed { last_seen: 1714116130 });
+
+        assert!(TransactionFilter::All.matches(&received) && TransactionFilter::All.matches(&sent));
+        assert!(TransactionFilter::Received.matches(&received) && !TransactionFilter::Received.matches(&sent));
+        assert!(TransactionFilter::Sent.matches(&sent) && !TransactionFilter::Sent.matches(&received));
+        assert!(TransactionFilter::Unconfirmed.matches(&sent) && !TransactionFilter::Unconfirmed.matches(&received));
+    }
+}
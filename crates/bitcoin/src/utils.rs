@@ -1,9 +1,9 @@
-use andromeda_common::{BitcoinUnit, BITCOIN, MILLI_BITCOIN, SATOSHI};
+use andromeda_common::BitcoinUnit;
 
 use super::transactions::Pagination;
 use crate::transactions::TransactionDetails;
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum SortOrder {
     Asc,
     Desc,
@@ -27,6 +27,10 @@ where
 }
 
 pub fn convert_amount(value: f64, from: BitcoinUnit, to: BitcoinUnit) -> f64 {
+    const BITCOIN: u64 = BitcoinUnit::BTC.sats();
+    const MILLI_BITCOIN: u64 = BitcoinUnit::MBTC.sats();
+    const SATOSHI: u64 = BitcoinUnit::SATS.sats();
+
     match from {
         BitcoinUnit::BTC => match to {
             BitcoinUnit::BTC => value,
@@ -13,7 +13,11 @@ use bdk_wallet::{
 };
 use futures::future::try_join_all;
 
-use super::{account::Account, transactions::Pagination, utils::sort_and_paginate_txs};
+use super::{
+    account::Account,
+    transactions::{Pagination, TransactionFilter},
+    utils::sort_and_paginate_txs,
+};
 use crate::{
     blockchain_client::BlockchainClient,
     error::Error,
@@ -26,6 +30,13 @@ use crate::{
 const ACCOUNT_DISCOVERY_STOP_GAP: u32 = 2;
 const ADDRESS_DISCOVERY_STOP_GAP: usize = 10;
 
+/// Balance of a wallet, with the balance of each of its accounts
+#[derive(Debug, Clone)]
+pub struct AggregatedBalance {
+    pub total: Balance,
+    pub accounts: Vec<(DerivationPath, Balance)>,
+}
+
 #[derive(Debug)]
 pub struct Wallet<C: WalletPersisterConnector<P>, P: WalletPersister> {
     mprv: Xpriv,
@@ -91,10 +102,14 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
     }
 
     pub async fn get_balance(&self) -> Result<Balance, Error> {
-        let async_iter = self.accounts.keys().map(|account_key| async move {
-            let account = self.accounts.get(account_key).ok_or(Error::AccountNotFound)?;
+        Ok(self.get_aggregated_balance().await?.total)
+    }
 
-            Ok::<Balance, Error>(account.get_balance().await)
+    /// Returns the balance of the wallet along with the balance of each of
+    /// its accounts
+    pub async fn get_aggregated_balance(&self) -> Result<AggregatedBalance, Error> {
+        let async_iter = self.accounts.iter().map(|(account_key, account)| async move {
+            Ok::<(DerivationPath, Balance), Error>((account_key.clone(), account.get_balance().await))
         });
 
         let account_balances = try_join_all(async_iter).await?;
@@ -106,11 +121,14 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
             trusted_pending: Amount::from_sat(0),
         };
 
-        let balance = account_balances
-            .into_iter()
-            .fold(init, |acc: Balance, account_balance| acc + account_balance);
+        let total = account_balances
+            .iter()
+            .fold(init, |acc: Balance, (_, account_balance)| acc + account_balance.clone());
 
-        Ok(balance)
+        Ok(AggregatedBalance {
+            total,
+            accounts: account_balances,
+        })
     }
 
     pub async fn discover_accounts<F>(
@@ -172,6 +190,23 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         &self,
         pagination: Option<Pagination>,
         sort: Option<SortOrder>,
+    ) -> Result<Vec<TransactionDetails>, Error> {
+        self.get_aggregated_transactions(pagination, sort, TransactionFilter::All)
+            .await
+    }
+
+    /// Returns the transactions of all the accounts matching the filter,
+    /// sorted and paginated together. Each transaction carries the derivation
+    /// path of its account.
+    ///
+    /// # Notes
+    ///
+    /// Transfers between accounts of the wallet are returned once per account.
+    pub async fn get_aggregated_transactions(
+        &self,
+        pagination: Option<Pagination>,
+        sort: Option<SortOrder>,
+        filter: TransactionFilter,
     ) -> Result<Vec<TransactionDetails>, Error> {
         let pagination = pagination.unwrap_or_default();
 
@@ -191,6 +226,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
             .await?
             .into_iter()
             .flatten()
+            .filter(|tx| filter.matches(tx))
             .collect::<Vec<_>>();
 
         Ok(sort_and_paginate_txs(txs, pagination, sort))
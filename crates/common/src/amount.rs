@@ -0,0 +1,218 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, BitcoinUnit};
+
+impl BitcoinUnit {
+    /// Number of satoshis in one unit
+    pub const fn sats(&self) -> u64 {
+        match self {
+            BitcoinUnit::BTC => 100_000_000,
+            BitcoinUnit::MBTC => 100_000,
+            BitcoinUnit::SATS => 1,
+        }
+    }
+
+    /// Number of decimals needed to express any amount in the unit
+    pub const fn decimals(&self) -> usize {
+        match self {
+            BitcoinUnit::BTC => 8,
+            BitcoinUnit::MBTC => 5,
+            BitcoinUnit::SATS => 0,
+        }
+    }
+
+    /// Converts a value expressed in this unit to another one, going through
+    /// [`Amount`] hence rounded to the nearest satoshi
+    pub fn convert(self, value: f64, to: BitcoinUnit) -> Result<f64, Error> {
+        Ok(Amount::from_unit(value, self)?.to_unit(to))
+    }
+}
+
+/// Parses the unit as sent by the API, e.g. `SATS`. Parsing is case
+/// insensitive, so that `mBTC` or `sats` typed by users are accepted too
+impl FromStr for BitcoinUnit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "BTC" => Ok(BitcoinUnit::BTC),
+            "MBTC" => Ok(BitcoinUnit::MBTC),
+            "SATS" | "SAT" => Ok(BitcoinUnit::SATS),
+            _ => Err(Error::InvalidBitcoinUnit(s.to_string())),
+        }
+    }
+}
+
+/// Amount of bitcoin, stored in satoshis.
+///
+/// Arithmetic is checked, the `Add` and `Sub` operators panicking on overflow
+/// like integers do in debug builds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Amount(u64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+    pub const ONE_SAT: Amount = Amount(1);
+    pub const ONE_BTC: Amount = Amount(100_000_000);
+    /// Total supply of bitcoin, 21 million BTC
+    pub const MAX_MONEY: Amount = Amount(21_000_000 * 100_000_000);
+
+    pub const fn from_sat(sats: u64) -> Self {
+        Amount(sats)
+    }
+
+    pub const fn to_sat(self) -> u64 {
+        self.0
+    }
+
+    /// Converts a value expressed in the given unit, rounded to the nearest
+    /// satoshi. Negative, non finite and out of range values are rejected
+    pub fn from_unit(value: f64, unit: BitcoinUnit) -> Result<Self, Error> {
+        let sats = (value * unit.sats() as f64).round();
+        if !sats.is_finite() || sats < 0.0 || sats > u64::MAX as f64 {
+            return Err(Error::InvalidAmount(value.to_string()));
+        }
+
+        Ok(Amount(sats as u64))
+    }
+
+    /// Value of the amount in the given unit, e.g. for charts. Use
+    /// [`Amount::to_string_in`] to display it
+    pub fn to_unit(self, unit: BitcoinUnit) -> f64 {
+        self.0 as f64 / unit.sats() as f64
+    }
+
+    /// Formats the amount in the given unit with all its decimals, without
+    /// the unit, e.g. `0.00012345` in BTC
+    pub fn to_string_in(self, unit: BitcoinUnit) -> String {
+        let sats_per_unit = unit.sats();
+        let integer = self.0 / sats_per_unit;
+
+        match unit.decimals() {
+            0 => integer.to_string(),
+            decimals => format!("{}.{:0width$}", integer, self.0 % sats_per_unit, width = decimals),
+        }
+    }
+
+    pub fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_add(rhs.0).map(Amount)
+    }
+
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_sub(rhs.0).map(Amount)
+    }
+
+    pub fn checked_mul(self, rhs: u64) -> Option<Amount> {
+        self.0.checked_mul(rhs).map(Amount)
+    }
+
+    pub fn checked_div(self, rhs: u64) -> Option<Amount> {
+        self.0.checked_div(rhs).map(Amount)
+    }
+
+    pub fn saturating_sub(self, rhs: Amount) -> Amount {
+        Amount(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, rhs: Amount) -> Amount {
+        self.checked_add(rhs).expect("Amount addition overflowed")
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, rhs: Amount) -> Amount {
+        self.checked_sub(rhs).expect("Amount subtraction underflowed")
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Formats the amount in BTC, e.g. `0.00012345 BTC`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_string_in(BitcoinUnit::BTC), BitcoinUnit::BTC)
+    }
+}
+
+impl From<bitcoin::Amount> for Amount {
+    fn from(value: bitcoin::Amount) -> Self {
+        Amount(value.to_sat())
+    }
+}
+
+impl From<Amount> for bitcoin::Amount {
+    fn from(value: Amount) -> Self {
+        bitcoin::Amount::from_sat(value.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Amount;
+    use crate::BitcoinUnit;
+
+    #[test]
+    fn should_parse_bitcoin_units() {
+        for unit in [BitcoinUnit::BTC, BitcoinUnit::MBTC, BitcoinUnit::SATS] {
+            assert_eq!(BitcoinUnit::from_str(&unit.to_string()).unwrap(), unit);
+
+            let json = serde_json::to_string(&unit).unwrap();
+            assert_eq!(json, format!("\"{}\"", unit));
+            assert_eq!(serde_json::from_str::<BitcoinUnit>(&json).unwrap(), unit);
+        }
+
+        assert_eq!(BitcoinUnit::from_str("mBTC").unwrap(), BitcoinUnit::MBTC);
+        assert!(BitcoinUnit::from_str("ETH").is_err());
+    }
+
+    #[test]
+    fn should_convert_values_between_units() {
+        assert_eq!(BitcoinUnit::BTC.convert(0.5, BitcoinUnit::SATS).unwrap(), 50_000_000.0);
+        assert_eq!(BitcoinUnit::SATS.convert(150_000.0, BitcoinUnit::MBTC).unwrap(), 1.5);
+        assert!(BitcoinUnit::BTC.convert(-1.0, BitcoinUnit::SATS).is_err());
+    }
+
+    #[test]
+    fn should_convert_between_units() {
+        let amount = Amount::from_unit(1.5, BitcoinUnit::MBTC).unwrap();
+        assert_eq!(amount.to_sat(), 150_000);
+        assert_eq!(amount.to_unit(BitcoinUnit::BTC), 0.0015);
+
+        assert_eq!(
+            Amount::from_unit(0.00000001, BitcoinUnit::BTC).unwrap(),
+            Amount::ONE_SAT
+        );
+        assert!(Amount::from_unit(-1.0, BitcoinUnit::SATS).is_err());
+        assert!(Amount::from_unit(f64::NAN, BitcoinUnit::BTC).is_err());
+    }
+
+    #[test]
+    fn should_format_amounts() {
+        let amount = Amount::from_sat(12_345);
+        assert_eq!(amount.to_string_in(BitcoinUnit::BTC), "0.00012345");
+        assert_eq!(amount.to_string_in(BitcoinUnit::MBTC), "0.12345");
+        assert_eq!(amount.to_string_in(BitcoinUnit::SATS), "12345");
+        assert_eq!(Amount::ONE_BTC.to_string(), "1.00000000 BTC");
+    }
+
+    #[test]
+    fn should_check_arithmetic() {
+        assert_eq!(Amount::ONE_SAT.checked_sub(Amount::ONE_BTC), None);
+        assert_eq!(Amount::from_sat(u64::MAX).checked_add(Amount::ONE_SAT), None);
+        assert_eq!(Amount::ONE_BTC.saturating_sub(Amount::MAX_MONEY), Amount::ZERO);
+        assert_eq!(Amount::ONE_SAT + Amount::ONE_SAT, Amount::from_sat(2));
+    }
+}
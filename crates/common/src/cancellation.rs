@@ -0,0 +1,179 @@
+//! Cancellation of long running operations, such as syncs, account discovery
+//! or API requests, e.g. when the screen that started them is dismissed.
+//!
+//! The errors of every crate convert from [`Cancelled`], so that the same
+//! token can cancel work across them.
+
+use std::{
+    collections::HashMap,
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+};
+
+/// Error of the operations cancelled with a [`CancellationToken`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Operation was cancelled")]
+pub struct Cancelled;
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    next_run_id: AtomicU64,
+    /// Wakers of the pending operations, keyed by the id of their `run` call
+    wakers: Mutex<HashMap<u64, Waker>>,
+}
+
+/// Unregisters the waker of a `run` call once it completes or gets dropped
+struct Registration<'a> {
+    state: &'a CancellationState,
+    id: u64,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.state.wakers.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Handle shared between an operation and its caller, the latter cancelling
+/// the operation through it. Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the operations run with the token. Those started afterwards
+    /// are cancelled right away
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+
+        let wakers = std::mem::take(&mut *self.0.wakers.lock().unwrap());
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn register(&self, id: u64, waker: &Waker) {
+        let mut wakers = self.0.wakers.lock().unwrap();
+        if !wakers.get(&id).is_some_and(|registered| registered.will_wake(waker)) {
+            wakers.insert(id, waker.clone());
+        }
+    }
+
+    /// Runs the future until it completes or the token is cancelled.
+    ///
+    /// On cancellation, the future is dropped, which cancels the requests it
+    /// has in flight, and [`Cancelled`] is returned as the future's error.
+    pub async fn run<T, E: From<Cancelled>>(&self, future: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+        let mut future = pin!(future);
+        let registration = Registration {
+            state: &self.0,
+            id: self.0.next_run_id.fetch_add(1, Ordering::Relaxed),
+        };
+
+        poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(Err(Cancelled.into()));
+            }
+
+            if let Poll::Ready(result) = future.as_mut().poll(cx) {
+                return Poll::Ready(result);
+            }
+
+            self.register(registration.id, cx.waker());
+
+            // The token might have been cancelled while registering the waker
+            if self.is_cancelled() {
+                Poll::Ready(Err(Cancelled.into()))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{self, Future},
+        pin::pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    use super::{CancellationToken, Cancelled};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    #[test]
+    fn should_cancel_pending_operation() {
+        let token = CancellationToken::new();
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut operation = pin!(token.run(future::pending::<Result<(), Cancelled>>()));
+        assert!(operation.as_mut().poll(&mut cx).is_pending());
+
+        token.cancel();
+        assert_eq!(operation.as_mut().poll(&mut cx), Poll::Ready(Err(Cancelled)));
+
+        // Operations run after the cancellation don't start
+        let mut operation = pin!(token.run(future::ready(Ok::<_, Cancelled>(42))));
+        assert_eq!(operation.as_mut().poll(&mut cx), Poll::Ready(Err(Cancelled)));
+    }
+
+    #[test]
+    fn should_complete_operation() {
+        let token = CancellationToken::new();
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut operation = pin!(token.run(future::ready(Ok::<_, Cancelled>(42))));
+        assert_eq!(operation.as_mut().poll(&mut cx), Poll::Ready(Ok(42)));
+    }
+
+    #[test]
+    fn should_unregister_waker_once_operation_ends() {
+        let token = CancellationToken::new();
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        {
+            let mut operation = pin!(token.run(future::pending::<Result<(), Cancelled>>()));
+            assert!(operation.as_mut().poll(&mut cx).is_pending());
+            assert!(operation.as_mut().poll(&mut cx).is_pending());
+            assert_eq!(token.0.wakers.lock().unwrap().len(), 1);
+        }
+        assert!(token.0.wakers.lock().unwrap().is_empty());
+
+        let mut is_ready = false;
+        let mut operation = pin!(token.run(future::poll_fn(|_| {
+            if is_ready {
+                Poll::Ready(Ok::<_, Cancelled>(42))
+            } else {
+                is_ready = true;
+                Poll::Pending
+            }
+        })));
+        assert!(operation.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(operation.as_mut().poll(&mut cx), Poll::Ready(Ok(42)));
+        assert!(token.0.wakers.lock().unwrap().is_empty());
+    }
+}
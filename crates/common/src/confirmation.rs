@@ -0,0 +1,117 @@
+//! Conversions between confirmation targets, in blocks, and durations, for
+//! the fee estimation screens.
+//!
+//! Blocks are found on average every 10 minutes, as a Poisson process, hence
+//! a target is only met in its estimated duration about half of the time.
+//! [`ConfirmationConfidence`] gives the odds of meeting it in a given time.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Average time between two blocks
+pub const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Estimated duration for a transaction to confirm within `target_blocks`
+pub fn target_to_duration(target_blocks: u32) -> Duration {
+    AVERAGE_BLOCK_INTERVAL * target_blocks.max(1)
+}
+
+/// Confirmation target, in blocks, of a transaction expected to confirm
+/// within the duration. Durations shorter than a block map to the next block
+pub fn duration_to_target(duration: Duration) -> u32 {
+    let target = duration.as_secs() / AVERAGE_BLOCK_INTERVAL.as_secs();
+    target.clamp(1, u32::MAX as u64) as u32
+}
+
+/// Probability that at least `target_blocks` blocks are found within the
+/// duration, i.e. that a transaction paying the fee rate estimated for the
+/// target confirms in time
+pub fn confirmation_probability(target_blocks: u32, within: Duration) -> f64 {
+    let expected_blocks = within.as_secs_f64() / AVERAGE_BLOCK_INTERVAL.as_secs_f64();
+
+    // Probability of finding fewer blocks than the target, summing the
+    // Poisson terms iteratively to avoid computing factorials
+    let mut term = (-expected_blocks).exp();
+    let mut fewer_blocks = 0.0;
+    for k in 0..target_blocks.max(1) {
+        fewer_blocks += term;
+        term *= expected_blocks / (k + 1) as f64;
+    }
+
+    (1.0 - fewer_blocks).clamp(0.0, 1.0)
+}
+
+/// Odds of a confirmation target being met in a given time, to label the
+/// fee options. Like the error kinds, these are part of the public contract
+/// with the clients and must not be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationConfidence {
+    /// At least 90%
+    High,
+    /// At least 50%
+    Medium,
+    Low,
+}
+
+impl ConfirmationConfidence {
+    pub fn from_probability(probability: f64) -> Self {
+        if probability >= 0.9 {
+            ConfirmationConfidence::High
+        } else if probability >= 0.5 {
+            ConfirmationConfidence::Medium
+        } else {
+            ConfirmationConfidence::Low
+        }
+    }
+
+    /// Confidence of a transaction confirming within `target_blocks` in the
+    /// given time
+    pub fn for_target(target_blocks: u32, within: Duration) -> Self {
+        Self::from_probability(confirmation_probability(target_blocks, within))
+    }
+
+    /// Machine-readable identifier of the confidence, e.g. `high`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfirmationConfidence::High => "high",
+            ConfirmationConfidence::Medium => "medium",
+            ConfirmationConfidence::Low => "low",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{confirmation_probability, duration_to_target, target_to_duration, ConfirmationConfidence};
+
+    #[test]
+    fn should_convert_targets_and_durations() {
+        assert_eq!(target_to_duration(6), Duration::from_secs(60 * 60));
+        assert_eq!(target_to_duration(0), Duration::from_secs(10 * 60));
+        assert_eq!(duration_to_target(Duration::from_secs(24 * 60 * 60)), 144);
+        assert_eq!(duration_to_target(Duration::from_secs(60)), 1);
+    }
+
+    #[test]
+    fn should_compute_confirmation_confidence() {
+        let probability = confirmation_probability(1, Duration::from_secs(10 * 60));
+        assert!((probability - 0.632).abs() < 0.001);
+
+        assert_eq!(
+            ConfirmationConfidence::for_target(1, Duration::from_secs(30 * 60)),
+            ConfirmationConfidence::High
+        );
+        assert_eq!(
+            ConfirmationConfidence::for_target(6, target_to_duration(6)),
+            ConfirmationConfidence::Medium
+        );
+        assert_eq!(
+            ConfirmationConfidence::for_target(6, Duration::from_secs(20 * 60)),
+            ConfirmationConfidence::Low
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use serde::Serialize;
+
 // errors in common layer
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -7,4 +9,63 @@ pub enum Error {
     InvalidScriptType(String),
     #[error("Invalid network: {0}")]
     InvalidNetwork(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+    #[error("Invalid bitcoin unit: {0}")]
+    InvalidBitcoinUnit(String),
+}
+
+impl Error {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::InvalidScriptType(_)
+            | Error::InvalidNetwork(_)
+            | Error::InvalidAmount(_)
+            | Error::InvalidDerivationPath(_)
+            | Error::InvalidBitcoinUnit(_) => ErrorCategory::InvalidInput,
+        }
+    }
+}
+
+/// Broad category of an error, shared by the errors of every crate so that
+/// the bindings expose a single error model, for the apps to branch on and
+/// display a translated message without matching every variant. These are
+/// part of the public contract with the clients and must not be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Network,
+    Auth,
+    /// Invalid bitcoin data, e.g. a transaction that can't be decoded or
+    /// would be rejected by the network
+    Consensus,
+    InsufficientFunds,
+    InvalidAddress,
+    InvalidInput,
+    NotFound,
+    Storage,
+    Signing,
+    Cancelled,
+    Internal,
+}
+
+impl ErrorCategory {
+    /// Machine-readable identifier of the category, e.g. `insufficient_funds`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Network => "network",
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::Consensus => "consensus",
+            ErrorCategory::InsufficientFunds => "insufficient_funds",
+            ErrorCategory::InvalidAddress => "invalid_address",
+            ErrorCategory::InvalidInput => "invalid_input",
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::Storage => "storage",
+            ErrorCategory::Signing => "signing",
+            ErrorCategory::Cancelled => "cancelled",
+            ErrorCategory::Internal => "internal",
+        }
+    }
 }
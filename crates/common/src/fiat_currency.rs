@@ -0,0 +1,280 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Fiat currencies supported by the wallet, named after their ISO 4217 code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum FiatCurrency {
+    ALL,
+    DZD,
+    ARS,
+    AMD,
+    AUD,
+    AZN,
+    BHD,
+    BDT,
+    BYN,
+    BMD,
+    BOB,
+    BAM,
+    BRL,
+    BGN,
+    KHR,
+    CAD,
+    CLP,
+    CNY,
+    COP,
+    CRC,
+    HRK,
+    CUP,
+    CZK,
+    DKK,
+    DOP,
+    EGP,
+    EUR,
+    GEL,
+    GHS,
+    GTQ,
+    HNL,
+    HKD,
+    HUF,
+    ISK,
+    INR,
+    IDR,
+    IRR,
+    IQD,
+    ILS,
+    JMD,
+    JPY,
+    JOD,
+    KZT,
+    KES,
+    KWD,
+    KGS,
+    LBP,
+    MKD,
+    MYR,
+    MUR,
+    MXN,
+    MDL,
+    MNT,
+    MAD,
+    MMK,
+    NAD,
+    NPR,
+    TWD,
+    NZD,
+    NIO,
+    NGN,
+    NOK,
+    OMR,
+    PKR,
+    PAB,
+    PEN,
+    PHP,
+    PLN,
+    GBP,
+    QAR,
+    RON,
+    RUB,
+    SAR,
+    RSD,
+    SGD,
+    ZAR,
+    KRW,
+    SSP,
+    VES,
+    LKR,
+    SEK,
+    CHF,
+    THB,
+    TTD,
+    TND,
+    TRY,
+    UGX,
+    UAH,
+    AED,
+    #[default]
+    USD,
+    UYU,
+    UZS,
+    VND,
+}
+
+impl fmt::Display for FiatCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FiatCurrency {
+    /// ISO 4217 code of the currency, e.g. `EUR`
+    pub fn code(&self) -> String {
+        self.to_string()
+    }
+
+    /// Symbol commonly used for the currency, e.g. `€`. Symbols aren't unique
+    /// across currencies, the code is to be preferred when it matters
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            FiatCurrency::ALL => "L",
+            FiatCurrency::DZD => "DA",
+            FiatCurrency::ARS => "$",
+            FiatCurrency::AMD => "֏",
+            FiatCurrency::AUD => "A$",
+            FiatCurrency::AZN => "₼",
+            FiatCurrency::BHD => "BD",
+            FiatCurrency::BDT => "৳",
+            FiatCurrency::BYN => "Br",
+            FiatCurrency::BMD => "$",
+            FiatCurrency::BOB => "Bs",
+            FiatCurrency::BAM => "KM",
+            FiatCurrency::BRL => "R$",
+            FiatCurrency::BGN => "лв",
+            FiatCurrency::KHR => "៛",
+            FiatCurrency::CAD => "CA$",
+            FiatCurrency::CLP => "$",
+            FiatCurrency::CNY => "¥",
+            FiatCurrency::COP => "$",
+            FiatCurrency::CRC => "₡",
+            FiatCurrency::HRK => "kn",
+            FiatCurrency::CUP => "$",
+            FiatCurrency::CZK => "Kč",
+            FiatCurrency::DKK => "kr",
+            FiatCurrency::DOP => "RD$",
+            FiatCurrency::EGP => "E£",
+            FiatCurrency::EUR => "€",
+            FiatCurrency::GEL => "₾",
+            FiatCurrency::GHS => "GH₵",
+            FiatCurrency::GTQ => "Q",
+            FiatCurrency::HNL => "L",
+            FiatCurrency::HKD => "HK$",
+            FiatCurrency::HUF => "Ft",
+            FiatCurrency::ISK => "kr",
+            FiatCurrency::INR => "₹",
+            FiatCurrency::IDR => "Rp",
+            FiatCurrency::IRR => "﷼",
+            FiatCurrency::IQD => "ع.د",
+            FiatCurrency::ILS => "₪",
+            FiatCurrency::JMD => "J$",
+            FiatCurrency::JPY => "¥",
+            FiatCurrency::JOD => "JD",
+            FiatCurrency::KZT => "₸",
+            FiatCurrency::KES => "KSh",
+            FiatCurrency::KWD => "KD",
+            FiatCurrency::KGS => "сом",
+            FiatCurrency::LBP => "L£",
+            FiatCurrency::MKD => "ден",
+            FiatCurrency::MYR => "RM",
+            FiatCurrency::MUR => "₨",
+            FiatCurrency::MXN => "MX$",
+            FiatCurrency::MDL => "L",
+            FiatCurrency::MNT => "₮",
+            FiatCurrency::MAD => "DH",
+            FiatCurrency::MMK => "K",
+            FiatCurrency::NAD => "N$",
+            FiatCurrency::NPR => "₨",
+            FiatCurrency::TWD => "NT$",
+            FiatCurrency::NZD => "NZ$",
+            FiatCurrency::NIO => "C$",
+            FiatCurrency::NGN => "₦",
+            FiatCurrency::NOK => "kr",
+            FiatCurrency::OMR => "RO",
+            FiatCurrency::PKR => "₨",
+            FiatCurrency::PAB => "B/.",
+            FiatCurrency::PEN => "S/",
+            FiatCurrency::PHP => "₱",
+            FiatCurrency::PLN => "zł",
+            FiatCurrency::GBP => "£",
+            FiatCurrency::QAR => "QR",
+            FiatCurrency::RON => "lei",
+            FiatCurrency::RUB => "₽",
+            FiatCurrency::SAR => "SR",
+            FiatCurrency::RSD => "din",
+            FiatCurrency::SGD => "S$",
+            FiatCurrency::ZAR => "R",
+            FiatCurrency::KRW => "₩",
+            FiatCurrency::SSP => "SS£",
+            FiatCurrency::VES => "Bs.",
+            FiatCurrency::LKR => "Rs",
+            FiatCurrency::SEK => "kr",
+            FiatCurrency::CHF => "CHF",
+            FiatCurrency::THB => "฿",
+            FiatCurrency::TTD => "TT$",
+            FiatCurrency::TND => "DT",
+            FiatCurrency::TRY => "₺",
+            FiatCurrency::UGX => "USh",
+            FiatCurrency::UAH => "₴",
+            FiatCurrency::AED => "AED",
+            FiatCurrency::USD => "$",
+            FiatCurrency::UYU => "$U",
+            FiatCurrency::UZS => "soʻm",
+            FiatCurrency::VND => "₫",
+        }
+    }
+
+    /// Number of decimals of the currency's minor unit, as defined by ISO 4217
+    pub fn decimals(&self) -> u32 {
+        match self {
+            FiatCurrency::CLP
+            | FiatCurrency::ISK
+            | FiatCurrency::JPY
+            | FiatCurrency::KRW
+            | FiatCurrency::UGX
+            | FiatCurrency::VND => 0,
+            FiatCurrency::BHD
+            | FiatCurrency::IQD
+            | FiatCurrency::JOD
+            | FiatCurrency::KWD
+            | FiatCurrency::OMR
+            | FiatCurrency::TND => 3,
+            _ => 2,
+        }
+    }
+
+    /// Number of minor units in a major one, e.g. 100 cents in a dollar
+    pub fn minor_units(&self) -> u64 {
+        10u64.pow(self.decimals())
+    }
+
+    /// Formats an amount of minor units in the major unit with the currency's
+    /// decimals, without symbol, e.g. `1234` cents as `12.34`
+    pub fn format_minor(&self, minor: u64) -> String {
+        let minor_units = self.minor_units();
+        let major = minor / minor_units;
+
+        match self.decimals() {
+            0 => major.to_string(),
+            decimals => format!("{}.{:0width$}", major, minor % minor_units, width = decimals as usize),
+        }
+    }
+
+    /// Formats an amount of minor units followed by the currency code, e.g.
+    /// `12.34 EUR`, for contexts where the locale isn't known
+    pub fn format_minor_with_code(&self, minor: u64) -> String {
+        format!("{} {}", self.format_minor(minor), self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FiatCurrency;
+
+    #[test]
+    fn should_format_with_currency_decimals() {
+        assert_eq!(FiatCurrency::EUR.format_minor(1234), "12.34");
+        assert_eq!(FiatCurrency::USD.format_minor(5), "0.05");
+        assert_eq!(FiatCurrency::JPY.format_minor(1234), "1234");
+        assert_eq!(FiatCurrency::KWD.format_minor(1234), "1.234");
+        assert_eq!(FiatCurrency::CHF.format_minor_with_code(100), "1.00 CHF");
+    }
+
+    #[test]
+    fn should_expose_currency_metadata() {
+        assert_eq!(FiatCurrency::default(), FiatCurrency::USD);
+        assert_eq!(FiatCurrency::GBP.code(), "GBP");
+        assert_eq!(FiatCurrency::EUR.symbol(), "€");
+        assert_eq!(FiatCurrency::JPY.minor_units(), 1);
+    }
+}
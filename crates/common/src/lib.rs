@@ -7,13 +7,18 @@ use bitcoin::{
 use error::Error;
 use serde::{Deserialize, Serialize};
 
-pub const SATOSHI: u64 = 1;
-pub const BITCOIN: u64 = 100_000_000 * SATOSHI;
-pub const MILLI_BITCOIN: u64 = BITCOIN / 1000;
-
+pub mod amount;
+pub mod cancellation;
+pub mod confirmation;
 pub mod error;
+pub mod fiat_currency;
+pub mod number_format;
 pub mod utils;
 
+pub use amount::Amount;
+pub use fiat_currency::FiatCurrency;
+pub use number_format::NumberFormat;
+
 /// Reimpl of BDK's Network enum to have exhaustive enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Network {
@@ -50,14 +55,18 @@ impl From<Network> for BdkNetwork {
     }
 }
 
-impl From<BdkNetwork> for Network {
-    fn from(network: BdkNetwork) -> Self {
+impl TryFrom<BdkNetwork> for Network {
+    type Error = Error;
+
+    /// BDK's network is non exhaustive, networks added upstream are rejected
+    /// until they are supported here
+    fn try_from(network: BdkNetwork) -> Result<Network, Error> {
         match network {
-            BdkNetwork::Bitcoin => Network::Bitcoin,
-            BdkNetwork::Testnet => Network::Testnet,
-            BdkNetwork::Signet => Network::Signet,
-            BdkNetwork::Regtest => Network::Regtest,
-            _ => panic!("Network {} not supported", network),
+            BdkNetwork::Bitcoin => Ok(Network::Bitcoin),
+            BdkNetwork::Testnet => Ok(Network::Testnet),
+            BdkNetwork::Signet => Ok(Network::Signet),
+            BdkNetwork::Regtest => Ok(Network::Regtest),
+            _ => Err(Error::InvalidNetwork(network.to_string())),
         }
     }
 }
@@ -101,8 +110,87 @@ impl fmt::Display for BitcoinUnit {
 
 pub trait FromParts {
     fn from_parts(purpose: ScriptType, network: Network, account_index: u32) -> Self;
+
+    /// Builds the path of an address: the account-level path followed by the
+    /// change and address index levels
+    fn from_address_parts(
+        purpose: ScriptType,
+        network: Network,
+        account_index: u32,
+        is_change: bool,
+        address_index: u32,
+    ) -> Self;
+}
+
+/// Parts of an account or address derivation path, parsed back from a
+/// [`DerivationPath`] built with [`FromParts`].
+///
+/// # Notes
+///
+/// Coin type 1 is shared by all the test networks, which are parsed as
+/// [`Network::Testnet`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DerivationParts {
+    pub script_type: ScriptType,
+    pub network: Network,
+    pub account_index: u32,
+    /// Change and address index levels, for address-level paths
+    pub address: Option<(bool, u32)>,
+}
+
+impl TryFrom<&DerivationPath> for DerivationParts {
+    type Error = Error;
+
+    fn try_from(derivation_path: &DerivationPath) -> Result<Self, Error> {
+        let invalid = || Error::InvalidDerivationPath(derivation_path.to_string());
+        let hardened = |level: Option<&ChildNumber>| match level {
+            Some(ChildNumber::Hardened { index }) => Ok(*index),
+            _ => Err(invalid()),
+        };
+
+        let mut levels = derivation_path.as_ref().iter();
+
+        let script_type = match hardened(levels.next())? {
+            44 => ScriptType::Legacy,
+            49 => ScriptType::NestedSegwit,
+            84 => ScriptType::NativeSegwit,
+            86 => ScriptType::Taproot,
+            48 => ScriptType::NativeSegwitMultisig,
+            _ => return Err(invalid()),
+        };
+        let network = match hardened(levels.next())? {
+            0 => Network::Bitcoin,
+            1 => Network::Testnet,
+            _ => return Err(invalid()),
+        };
+        let account_index = hardened(levels.next())?;
+
+        if script_type == ScriptType::NativeSegwitMultisig && hardened(levels.next())? != BIP48_P2WSH_SCRIPT_TYPE {
+            return Err(invalid());
+        }
+
+        let address = match (levels.next(), levels.next(), levels.next()) {
+            (None, None, None) => None,
+            (Some(ChildNumber::Normal { index: change }), Some(ChildNumber::Normal { index: address_index }), None)
+                if *change <= 1 =>
+            {
+                Some((*change == 1, *address_index))
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(DerivationParts {
+            script_type,
+            network,
+            account_index,
+            address,
+        })
+    }
 }
 
+/// Script type level of BIP48 multisig paths, for P2WSH
+const BIP48_P2WSH_SCRIPT_TYPE: u32 = 2;
+
 impl FromParts for DerivationPath {
     /// Builds a `DerivationPath` from different parts.
     /// Given BIP32: purpose is used as first index, then network to infer
@@ -127,7 +215,26 @@ impl FromParts for DerivationPath {
 
         let account_level = ChildNumber::from_hardened_idx(account).unwrap();
 
-        DerivationPath::from(vec![purpose_level, cointype_level, account_level])
+        let mut levels = vec![purpose_level, cointype_level, account_level];
+        // BIP48 adds a script type level after the account one
+        if script_type == ScriptType::NativeSegwitMultisig {
+            levels.push(ChildNumber::from_hardened_idx(BIP48_P2WSH_SCRIPT_TYPE).unwrap());
+        }
+
+        DerivationPath::from(levels)
+    }
+
+    fn from_address_parts(
+        script_type: ScriptType,
+        network: Network,
+        account: u32,
+        is_change: bool,
+        address_index: u32,
+    ) -> Self {
+        DerivationPath::from_parts(script_type, network, account).extend([
+            ChildNumber::from_normal_idx(is_change as u32).unwrap(),
+            ChildNumber::from_normal_idx(address_index).unwrap(),
+        ])
     }
 }
 
@@ -141,9 +248,14 @@ pub enum ScriptType {
     NativeSegwit = 3,
     /// Taproot scripts : https://bips.dev/341/
     Taproot = 4,
+    /// Native segwit multisig scripts (P2WSH), derived as per
+    /// https://bips.dev/48/
+    NativeSegwitMultisig = 5,
 }
 
 impl ScriptType {
+    /// Single signature script types, the ones accounts can be discovered or
+    /// swept for from a single key
     pub fn values() -> [ScriptType; 4] {
         [
             ScriptType::Legacy,
@@ -161,6 +273,7 @@ impl From<ScriptType> for u8 {
             ScriptType::NestedSegwit => 2u8,
             ScriptType::NativeSegwit => 3u8,
             ScriptType::Taproot => 4u8,
+            ScriptType::NativeSegwitMultisig => 5u8,
         }
     }
 }
@@ -174,6 +287,7 @@ impl TryFrom<u8> for ScriptType {
             2 => Ok(ScriptType::NestedSegwit),
             3 => Ok(ScriptType::NativeSegwit),
             4 => Ok(ScriptType::Taproot),
+            5 => Ok(ScriptType::NativeSegwitMultisig),
             _ => Err(Error::InvalidScriptType(value.to_string())),
         }
     }
@@ -186,6 +300,60 @@ impl From<ScriptType> for ChildNumber {
             ScriptType::NestedSegwit => ChildNumber::Hardened { index: 49 },
             ScriptType::NativeSegwit => ChildNumber::Hardened { index: 84 },
             ScriptType::Taproot => ChildNumber::Hardened { index: 86 },
+            ScriptType::NativeSegwitMultisig => ChildNumber::Hardened { index: 48 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::{bip32::DerivationPath, Network as BdkNetwork};
+
+    use super::{DerivationParts, FromParts, Network, ScriptType};
+
+    #[test]
+    fn should_build_multisig_derivation_path() {
+        let derivation_path = DerivationPath::from_parts(ScriptType::NativeSegwitMultisig, Network::Testnet, 3);
+        assert_eq!(derivation_path, DerivationPath::from_str("m/48'/1'/3'/2'").unwrap());
+        assert_eq!(ScriptType::try_from(5).unwrap(), ScriptType::NativeSegwitMultisig);
+    }
+
+    #[test]
+    fn should_round_trip_address_derivation_paths() {
+        let derivation_path = DerivationPath::from_address_parts(ScriptType::Taproot, Network::Bitcoin, 1, true, 7);
+        assert_eq!(derivation_path, DerivationPath::from_str("m/86'/0'/1'/1/7").unwrap());
+
+        let parts = DerivationParts::try_from(&derivation_path).unwrap();
+        assert_eq!(parts.script_type, ScriptType::Taproot);
+        assert_eq!(parts.network, Network::Bitcoin);
+        assert_eq!(parts.account_index, 1);
+        assert_eq!(parts.address, Some((true, 7)));
+
+        let multisig_path = DerivationPath::from_str("m/48'/1'/0'/2'/0/3").unwrap();
+        let parts = DerivationParts::try_from(&multisig_path).unwrap();
+        assert_eq!(parts.script_type, ScriptType::NativeSegwitMultisig);
+        assert_eq!(parts.address, Some((false, 3)));
+
+        let account_path = DerivationPath::from_str("m/84'/1'/2'").unwrap();
+        assert_eq!(DerivationParts::try_from(&account_path).unwrap().address, None);
+
+        for invalid_path in [
+            "m/84'/1'",
+            "m/84'/1'/0'/2/0",
+            "m/84'/1'/0'/0",
+            "m/84/1'/0'",
+            "m/48'/1'/0'/0/0",
+        ] {
+            assert!(DerivationParts::try_from(&DerivationPath::from_str(invalid_path).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn should_convert_bdk_networks() {
+        for network in [Network::Bitcoin, Network::Testnet, Network::Signet, Network::Regtest] {
+            assert_eq!(Network::try_from(BdkNetwork::from(network)).unwrap(), network);
         }
     }
 }
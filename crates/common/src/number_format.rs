@@ -0,0 +1,210 @@
+//! Locale-aware formatting and parsing of bitcoin amounts, so that every app
+//! displays them the same way.
+//!
+//! Only the separators depend on the locale: digits are always ASCII and
+//! grouped by thousands.
+
+use crate::{error::Error, Amount, BitcoinUnit};
+
+/// Separators used to format numbers in a locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Separator between thousands, none when digits aren't grouped
+    pub grouping_separator: Option<char>,
+    pub decimal_separator: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::EN
+    }
+}
+
+impl NumberFormat {
+    /// `1,234.5`
+    pub const EN: NumberFormat = NumberFormat {
+        grouping_separator: Some(','),
+        decimal_separator: '.',
+    };
+    /// `1.234,5`
+    pub const DE: NumberFormat = NumberFormat {
+        grouping_separator: Some('.'),
+        decimal_separator: ',',
+    };
+    /// `1 234,5`, with a narrow no-break space
+    pub const FR: NumberFormat = NumberFormat {
+        grouping_separator: Some('\u{202F}'),
+        decimal_separator: ',',
+    };
+    /// `1 234,5`, with a no-break space
+    pub const SPACE_COMMA: NumberFormat = NumberFormat {
+        grouping_separator: Some('\u{A0}'),
+        decimal_separator: ',',
+    };
+    /// `1’234.5`
+    pub const CH: NumberFormat = NumberFormat {
+        grouping_separator: Some('’'),
+        decimal_separator: '.',
+    };
+
+    /// Separators of a BCP 47 locale, e.g. `fr-CH`, falling back to the
+    /// english ones for unknown locales
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale.replace('_', "-").to_lowercase();
+        let language = locale.split('-').next().unwrap_or_default();
+
+        match (language, locale.as_str()) {
+            (_, "de-ch" | "fr-ch" | "it-ch" | "rm-ch") => NumberFormat::CH,
+            (_, "pt-pt") => NumberFormat::SPACE_COMMA,
+            ("fr", _) => NumberFormat::FR,
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl" | "sr" | "vi", _) => {
+                NumberFormat::DE
+            }
+            ("ru" | "uk" | "pl" | "cs" | "sk" | "fi" | "sv" | "nb" | "no" | "hu" | "bg" | "lt" | "lv" | "et", _) => {
+                NumberFormat::SPACE_COMMA
+            }
+            _ => NumberFormat::EN,
+        }
+    }
+
+    fn group(&self, digits: &str) -> String {
+        let Some(separator) = self.grouping_separator else {
+            return digits.to_string();
+        };
+
+        let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+
+        grouped
+    }
+}
+
+impl Amount {
+    /// Formats the amount in the given unit, without the unit. Trailing zero
+    /// decimals are omitted, e.g. `1,234.5` for 1234.5 BTC in english
+    pub fn format(self, unit: BitcoinUnit, number_format: &NumberFormat) -> String {
+        let sats_per_unit = unit.sats();
+        let integer = number_format.group(&(self.to_sat() / sats_per_unit).to_string());
+
+        let decimals = format!("{:0width$}", self.to_sat() % sats_per_unit, width = unit.decimals());
+        let decimals = decimals.trim_end_matches('0');
+
+        if decimals.is_empty() {
+            integer
+        } else {
+            format!("{}{}{}", integer, number_format.decimal_separator, decimals)
+        }
+    }
+
+    /// Parses an amount formatted in the given unit, as typed by the user or
+    /// returned by [`Amount::format`]. Grouping separators and spaces are
+    /// ignored, while decimals finer than a satoshi are rejected
+    pub fn parse(value: &str, unit: BitcoinUnit, number_format: &NumberFormat) -> Result<Amount, Error> {
+        let invalid = || Error::InvalidAmount(value.to_string());
+
+        let normalised = value
+            .trim()
+            .chars()
+            .filter(|c| Some(*c) != number_format.grouping_separator && !c.is_whitespace())
+            .collect::<String>();
+
+        let (integer, decimals) = normalised
+            .split_once(number_format.decimal_separator)
+            .unwrap_or((normalised.as_str(), ""));
+
+        let is_number = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && decimals.is_empty())
+            || !is_number(integer)
+            || !is_number(decimals)
+            || decimals.len() > unit.decimals()
+        {
+            return Err(invalid());
+        }
+
+        let integer = if integer.is_empty() {
+            0
+        } else {
+            integer.parse::<u64>().map_err(|_| invalid())?
+        };
+        let decimals = if decimals.is_empty() {
+            0
+        } else {
+            format!("{:0<width$}", decimals, width = unit.decimals())
+                .parse::<u64>()
+                .map_err(|_| invalid())?
+        };
+
+        integer
+            .checked_mul(unit.sats())
+            .and_then(|sats| sats.checked_add(decimals))
+            .map(Amount::from_sat)
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+    use crate::{Amount, BitcoinUnit};
+
+    #[test]
+    fn should_format_amounts_per_locale() {
+        let amount = Amount::from_sat(123_456_780_000);
+
+        assert_eq!(amount.format(BitcoinUnit::BTC, &NumberFormat::EN), "1,234.5678");
+        assert_eq!(
+            amount.format(BitcoinUnit::BTC, &NumberFormat::from_locale("de-DE")),
+            "1.234,5678"
+        );
+        assert_eq!(
+            amount.format(BitcoinUnit::BTC, &NumberFormat::from_locale("fr_FR")),
+            "1\u{202F}234,5678"
+        );
+        assert_eq!(
+            amount.format(BitcoinUnit::BTC, &NumberFormat::from_locale("de-CH")),
+            "1’234.5678"
+        );
+        assert_eq!(
+            Amount::from_sat(1_000).format(BitcoinUnit::SATS, &NumberFormat::EN),
+            "1,000"
+        );
+        assert_eq!(Amount::ZERO.format(BitcoinUnit::MBTC, &NumberFormat::EN), "0");
+    }
+
+    #[test]
+    fn should_round_trip_formatted_amounts() {
+        for locale in ["en-US", "de-DE", "fr-FR", "de-CH", "pl-PL"] {
+            let number_format = NumberFormat::from_locale(locale);
+            for unit in [BitcoinUnit::BTC, BitcoinUnit::MBTC, BitcoinUnit::SATS] {
+                let amount = Amount::from_sat(2_100_000_012_345);
+                let formatted = amount.format(unit, &number_format);
+
+                assert_eq!(Amount::parse(&formatted, unit, &number_format).unwrap(), amount);
+            }
+        }
+    }
+
+    #[test]
+    fn should_parse_user_input() {
+        let en = NumberFormat::EN;
+
+        assert_eq!(
+            Amount::parse(" .5 ", BitcoinUnit::BTC, &en).unwrap(),
+            Amount::from_sat(50_000_000)
+        );
+        assert_eq!(
+            Amount::parse("1 000", BitcoinUnit::SATS, &en).unwrap(),
+            Amount::from_sat(1_000)
+        );
+        assert!(Amount::parse("0.000000001", BitcoinUnit::BTC, &en).is_err());
+        assert!(Amount::parse("1.5", BitcoinUnit::SATS, &en).is_err());
+        assert!(Amount::parse("-1", BitcoinUnit::BTC, &en).is_err());
+        assert!(Amount::parse("", BitcoinUnit::BTC, &en).is_err());
+        assert!(Amount::parse("184467440737.1", BitcoinUnit::BTC, &en).is_err());
+    }
+}
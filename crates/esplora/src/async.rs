@@ -25,6 +25,7 @@ use andromeda_api::{
     transaction::{BroadcastMessage, ExchangeRateOrTransactionTime, MempoolInfo, TransactionClient},
     ProtonWalletApiClient,
 };
+use andromeda_common::cancellation::CancellationToken;
 use bitcoin::{
     block::Header as BlockHeader,
     consensus::{deserialize, serialize},
@@ -37,6 +38,7 @@ use futures::lock::Mutex;
 #[derive(Clone)]
 
 pub struct AsyncClient {
+    api_client: ProtonWalletApiClient,
     transaction: TransactionClient,
     address: AddressClient,
     block: BlockClient,
@@ -50,6 +52,10 @@ pub struct AsyncClient {
 
 const TRANSACTIONS_PER_PAGE: u32 = 25;
 
+/// Counter of transactions refused by the API on broadcast
+#[cfg(feature = "metrics")]
+pub const BROADCAST_FAILURES_TOTAL: &str = "andromeda_esplora_broadcast_failures_total";
+
 fn hash_spk(spk: &ScriptBuf) -> String {
     sha256::Hash::hash(spk.as_bytes()).to_string()
 }
@@ -64,6 +70,7 @@ impl AsyncClient {
         let block = clients.block;
 
         AsyncClient {
+            api_client,
             transaction,
             address,
             block,
@@ -72,6 +79,18 @@ impl AsyncClient {
         }
     }
 
+    /// Returns a client whose requests fail with a
+    /// [cancelled](andromeda_common::error::ErrorCategory::Cancelled) error
+    /// once `token` is cancelled, see
+    /// [`ProtonWalletApiClient::with_cancellation`]. The set of fetched spks
+    /// is shared with this client.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        AsyncClient {
+            fetched_spks: self.fetched_spks.clone(),
+            ..AsyncClient::from_client(self.api_client.with_cancellation(token))
+        }
+    }
+
     /// Returns an iterator we only spks that haven't been fetched yet
     pub async fn filter_already_fetched(&self, spks: Vec<ScriptBuf>) -> Vec<ScriptBuf> {
         let fetched_spks = self.fetched_spks.lock().await;
@@ -179,6 +198,10 @@ impl AsyncClient {
 
     /// Broadcast a [`Transaction`] to Esplora
     #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(
+        feature = "instrumentation",
+        tracing::instrument(name = "esplora.broadcast", skip_all, fields(txid = %transaction.compute_txid()))
+    )]
     pub async fn broadcast(
         &self,
         transaction: &Transaction,
@@ -192,7 +215,8 @@ impl AsyncClient {
         recipients: Option<HashMap<String, String>>,
         is_anonymous: Option<u8>,
     ) -> Result<(), Error> {
-        self.transaction
+        let result = self
+            .transaction
             .broadcast_raw_transaction(
                 serialize(transaction).to_lower_hex_string(),
                 wallet_id,
@@ -205,7 +229,14 @@ impl AsyncClient {
                 recipients,
                 is_anonymous,
             )
-            .await?;
+            .await;
+
+        #[cfg(feature = "metrics")]
+        if result.is_err() {
+            metrics::counter!(BROADCAST_FAILURES_TOTAL).increment(1);
+        }
+
+        result?;
 
         Ok(())
     }
@@ -11,6 +11,10 @@ use crate::{error::Error, insert_anchor_from_status, insert_prevouts, r#async::A
 
 pub const MAX_SPKS_PER_REQUESTS: usize = 50;
 
+/// Counter of transactions fetched by scans, labelled with `scan`
+#[cfg(feature = "metrics")]
+pub const TXS_DISCOVERED_TOTAL: &str = "andromeda_esplora_txs_discovered_total";
+
 /// Trait to extend the functionality of [`AsyncClient`].
 ///
 /// Refer to [crate-level documentation](crate) for more.
@@ -50,6 +54,7 @@ pub trait EsploraAsyncExt {
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl EsploraAsyncExt for AsyncClient {
+    #[cfg_attr(feature = "instrumentation", tracing::instrument(name = "esplora.full_scan", skip_all, fields(stop_gap = stop_gap)))]
     async fn full_scan<K: Ord + Clone + Send, R: Into<FullScanRequest<K>> + Send>(
         &self,
         request: R,
@@ -85,6 +90,17 @@ impl EsploraAsyncExt for AsyncClient {
             _ => None,
         };
 
+        #[cfg(feature = "instrumentation")]
+        tracing::debug!(
+            txs = tx_update.txs.len(),
+            anchors = tx_update.anchors.len(),
+            ?last_active_indices,
+            "full scan fetched"
+        );
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!(TXS_DISCOVERED_TOTAL, "scan" => "full_scan").increment(tx_update.txs.len() as u64);
+
         Ok(FullScanResult {
             chain_update,
             tx_update,
@@ -92,6 +108,7 @@ impl EsploraAsyncExt for AsyncClient {
         })
     }
 
+    #[cfg_attr(feature = "instrumentation", tracing::instrument(name = "esplora.sync", skip_all, fields(parallel_requests = parallel_requests)))]
     async fn sync<I: Send, R: Into<SyncRequest<I>> + Send>(
         &self,
         request: R,
@@ -121,6 +138,16 @@ impl EsploraAsyncExt for AsyncClient {
             _ => None,
         };
 
+        #[cfg(feature = "instrumentation")]
+        tracing::debug!(
+            txs = tx_update.txs.len(),
+            anchors = tx_update.anchors.len(),
+            "sync fetched"
+        );
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!(TXS_DISCOVERED_TOTAL, "scan" => "sync").increment(tx_update.txs.len() as u64);
+
         Ok(SyncResult {
             chain_update,
             tx_update,
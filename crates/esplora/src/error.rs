@@ -1,5 +1,6 @@
 use std::{fmt::Debug, io};
 
+use andromeda_common::{cancellation::Cancelled, error::ErrorCategory};
 use bitcoin::{BlockHash, Txid};
 
 /// Errors that can happen during a sync with `Esplora`
@@ -29,4 +30,22 @@ pub enum Error {
     HeaderHeightNotFound(u32),
     #[error("Header hash not found: \n\t{0}")]
     HeaderHashNotFound(BlockHash),
+    #[error(transparent)]
+    Cancelled(#[from] Cancelled),
+}
+
+impl Error {
+    /// Category of the error in the taxonomy shared across crates
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::ApiError(error) => error.category(),
+            Error::MuonError(_) | Error::HttpResponse(_) | Error::Io(_) | Error::NoHeader => ErrorCategory::Network,
+            Error::BitcoinEncoding(_) => ErrorCategory::Consensus,
+            Error::Parsing(_) | Error::HexToArrayError(_) | Error::HexToBytesError(_) => ErrorCategory::Internal,
+            Error::TransactionNotFound(_) | Error::HeaderHeightNotFound(_) | Error::HeaderHashNotFound(_) => {
+                ErrorCategory::NotFound
+            }
+            Error::Cancelled(_) => ErrorCategory::Cancelled,
+        }
+    }
 }
@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, Error, Expr, Fields, ItemStruct};
+
+#[derive(Default)]
+pub struct ApiClientArgs {
+    base: Option<Expr>,
+}
+
+impl ApiClientArgs {
+    pub fn parse(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+        if meta.path.is_ident("base") {
+            self.base = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported api_client attribute, expected `base`"))
+        }
+    }
+}
+
+pub fn expand(args: ApiClientArgs, input: ItemStruct) -> Result<TokenStream, Error> {
+    let Some(base) = args.base else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "api_client requires a base path, e.g. #[api_client(base = \"wallet/v1\")]",
+        ));
+    };
+
+    let ItemStruct {
+        attrs,
+        vis,
+        ident,
+        generics,
+        fields,
+        ..
+    } = input;
+
+    let fields = match fields {
+        Fields::Named(fields) => fields.named.into_iter().collect(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(fields) => {
+            return Err(Error::new_spanned(
+                fields,
+                "api_client requires a unit struct or named fields",
+            ))
+        }
+    };
+
+    let field_idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #ident #generics #where_clause {
+            api_client: ::std::sync::Arc<crate::ProtonWalletApiClient>,
+            #(#fields,)*
+        }
+
+        impl #impl_generics crate::core::ApiClient for #ident #ty_generics #where_clause {
+            fn new(api_client: ::std::sync::Arc<crate::ProtonWalletApiClient>) -> Self {
+                Self {
+                    api_client,
+                    #(#field_idents: ::core::default::Default::default(),)*
+                }
+            }
+
+            fn api_client(&self) -> &::std::sync::Arc<crate::ProtonWalletApiClient> {
+                &self.api_client
+            }
+
+            fn base_url(&self) -> &str {
+                #base
+            }
+        }
+    })
+}
@@ -0,0 +1,283 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, GenericArgument, Ident, LitStr, Path, PathArguments, Type};
+
+struct BuilderField {
+    ident: Ident,
+    setter: Ident,
+    ty: Type,
+    /// Inner type of `Option` fields, which are left unset by default
+    optional: Option<Type>,
+    /// Whether the field falls back to its `Default` value when unset
+    default: bool,
+}
+
+/// Converts a field name to snake case, keeping acronyms together, e.g.
+/// `WalletAccountIDs` to `wallet_account_ids`
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next = chars.get(i + 1);
+            // A trailing `s` after an acronym is a plural, not a new word
+            let is_plural = next == Some(&'s') && chars.get(i + 2).is_none_or(|c| c.is_uppercase());
+            let starts_word = previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()) && !is_plural);
+
+            if starts_word && previous != '_' {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// Setter of a field, named after the field in snake case. Keywords, e.g.
+/// `Type`, get a raw identifier setter, except for the path keywords which
+/// can't be raw and get a trailing underscore instead
+fn setter_ident(field: &Ident) -> Ident {
+    let setter_name = to_snake_case(&field.to_string());
+    match setter_name.as_str() {
+        "self" | "super" | "crate" => format_ident!("{}_", setter_name),
+        _ if syn::parse_str::<Ident>(&setter_name).is_ok() => Ident::new(&setter_name, Span::call_site()),
+        _ => Ident::new_raw(&setter_name, Span::call_site()),
+    }
+}
+
+fn option_inner(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_validate(input: &DeriveInput) -> Result<Option<Path>, Error> {
+    let mut validate = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("validate") {
+                validate = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported builder attribute, expected `validate`"))
+            }
+        })?;
+    }
+
+    Ok(validate)
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<BuilderField>, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "Builder requires named fields")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Builder can only be derived for structs",
+            ))
+        }
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            let mut default = false;
+
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("default") {
+                        default = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported builder attribute, expected `default`"))
+                    }
+                })?;
+            }
+
+            Ok(BuilderField {
+                setter: setter_ident(&ident),
+                ident,
+                ty: field.ty.clone(),
+                optional: option_inner(&field.ty),
+                default,
+            })
+        })
+        .collect()
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "Builder doesn't support generics"));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("{}Builder", name);
+
+    let validate = parse_validate(&input)?;
+    let fields = parse_fields(&input)?;
+
+    let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+
+    let storage = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.optional {
+            Some(_) => {
+                let ty = &field.ty;
+                quote!(#ident: #ty)
+            }
+            None => {
+                let ty = &field.ty;
+                quote!(#ident: ::core::option::Option<#ty>)
+            }
+        }
+    });
+
+    let setters = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let setter = &field.setter;
+        let doc = format!("Sets `{}`", ident);
+
+        match &field.optional {
+            // Accepts both a value and an option, to forward optional inputs
+            Some(inner) => quote! {
+                #[doc = #doc]
+                pub fn #setter(mut self, value: impl ::core::convert::Into<::core::option::Option<#inner>>) -> Self {
+                    self.#ident = value.into();
+                    self
+                }
+            },
+            None => {
+                let ty = &field.ty;
+                quote! {
+                    #[doc = #doc]
+                    pub fn #setter(mut self, value: impl ::core::convert::Into<#ty>) -> Self {
+                        self.#ident = ::core::option::Option::Some(value.into());
+                        self
+                    }
+                }
+            }
+        }
+    });
+
+    let values = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = ident.to_string();
+
+        if field.optional.is_some() {
+            quote!(#ident: self.#ident)
+        } else if field.default {
+            quote!(#ident: self.#ident.unwrap_or_default())
+        } else {
+            quote!(#ident: self.#ident.ok_or(crate::error::Error::MissingField(#name))?)
+        }
+    });
+
+    let validation = validate.map(|validate| quote!(#validate(&value)?;));
+
+    let builder_doc = format!("Builder of [`{}`], see [`{}::builder`]", name, name);
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #[derive(Debug, Clone)]
+        #[allow(non_snake_case)]
+        #vis struct #builder {
+            #(#storage,)*
+        }
+
+        impl #name {
+            /// Returns a builder of the request body, checking on build that
+            /// the required fields are set
+            #vis fn builder() -> #builder {
+                #builder {
+                    #(#idents: ::core::option::Option::None,)*
+                }
+            }
+        }
+
+        impl #builder {
+            #(#setters)*
+
+            /// Builds the request body, failing if a required field is missing
+            /// or if the validation hook rejects it
+            #vis fn build(self) -> ::core::result::Result<#name, crate::error::Error> {
+                let value = #name {
+                    #(#values,)*
+                };
+
+                #validation
+
+                ::core::result::Result::Ok(value)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::Ident;
+
+    use super::{setter_ident, to_snake_case};
+
+    #[test]
+    fn test_to_snake_case() {
+        let cases = [
+            ("ID", "id"),
+            ("Label", "label"),
+            ("WalletID", "wallet_id"),
+            ("CreateTime", "create_time"),
+            ("IDs", "ids"),
+            ("WalletAccountIDs", "wallet_account_ids"),
+            ("BitcoinAddressIDs", "bitcoin_address_ids"),
+            ("HTTPResponse", "http_response"),
+            ("UserIDsList", "user_ids_list"),
+            ("FiatCurrency", "fiat_currency"),
+            ("Address2", "address2"),
+            ("Bip44Path", "bip44_path"),
+            ("already_snake", "already_snake"),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(to_snake_case(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_setter_ident() {
+        let cases = [
+            ("WalletID", "wallet_id"),
+            ("Type", "r#type"),
+            ("Self", "self_"),
+            ("Crate", "crate_"),
+            ("Ref", "r#ref"),
+        ];
+
+        for (field, expected) in cases {
+            let setter = setter_ident(&Ident::new(field, Span::call_site()));
+            assert_eq!(setter.to_string(), expected, "{}", field);
+        }
+    }
+}
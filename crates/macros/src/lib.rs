@@ -0,0 +1,138 @@
+//! Derive macros removing the boilerplate shared by the API clients.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemStruct};
+
+mod api_client;
+mod builder;
+mod mirror;
+mod response_body;
+
+/// Derives `andromeda_api::core::ResponseBody` for an API response body.
+///
+/// The struct must have a `Code: u16` field, holding the Proton response
+/// code. A single field can be marked with `#[response_body(data)]` to be
+/// returned by `ProtonResponseExt::parse_body`, otherwise nothing is
+/// returned.
+///
+/// ```ignore
+/// #[derive(Debug, Deserialize, ResponseBody)]
+/// #[allow(non_snake_case)]
+/// struct GetWalletAccountsResponseBody {
+///     pub Code: u16,
+///     #[response_body(data)]
+///     pub Accounts: Vec<ApiWalletAccount>,
+/// }
+/// ```
+///
+/// Only usable within `andromeda-api`, as the generated impl refers to the
+/// trait through `crate::core`.
+#[proc_macro_derive(ResponseBody, attributes(response_body))]
+pub fn derive_response_body(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    response_body::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives conversions between a struct and its mirror in another crate, e.g.
+/// a core type and the struct exposing it to the bindings.
+///
+/// Fields are matched by name and converted with `Into`. The struct-level
+/// attributes choose the generated impls:
+/// - `from = "Type"`: `From<Type> for Self`
+/// - `into = "Type"`: `From<Self> for Type`
+/// - `try_from = "Type", error = "Error"`: `TryFrom<Type> for Self`, in
+///   which fields marked `try` are converted with `TryInto`
+///
+/// Field-level attributes:
+/// - `rename = "Field"`: name of the field in the mirror
+/// - `vec`: converts each item of a collection
+/// - `option`: converts the value of an option
+/// - `try`: converts with `TryInto`, the error converting into `error`
+///
+/// ```ignore
+/// #[derive(Tsify, Serialize, Deserialize, Mirror)]
+/// #[mirror(from = "ApiContact")]
+/// #[allow(non_snake_case)]
+/// pub struct WasmApiContact {
+///     pub ID: String,
+///     #[mirror(vec)]
+///     pub Cards: Vec<WasmApiContactCard>,
+/// }
+/// ```
+#[proc_macro_derive(Mirror, attributes(mirror))]
+pub fn derive_mirror(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    mirror::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turns a struct into an API client: adds the shared
+/// `Arc<ProtonWalletApiClient>` and implements `andromeda_api::core::ApiClient`
+/// with the given base path, either a string or a constant.
+///
+/// The struct may declare extra fields, which `ApiClient::new` initialises
+/// with their `Default` value.
+///
+/// ```ignore
+/// #[api_client(base = BASE_WALLET_API_V1)]
+/// #[derive(Clone)]
+/// pub struct BlockClient;
+/// ```
+///
+/// Only usable within `andromeda-api`, as the generated code refers to its
+/// types through `crate`.
+#[proc_macro_attribute]
+pub fn api_client(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut parsed_args = api_client::ApiClientArgs::default();
+    let args_parser = syn::meta::parser(|meta| parsed_args.parse(meta));
+    parse_macro_input!(args with args_parser);
+
+    let input = parse_macro_input!(input as ItemStruct);
+
+    api_client::expand(parsed_args, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives a builder for an API request body, as `Type::builder()`.
+///
+/// Setters are named after the fields in snake case, as raw identifiers for
+/// keywords, e.g. `r#type` (`self_` for `Self`), and accept anything
+/// converting into the field type. `Option` fields are left unset by default and their setters accept
+/// either a value or an option. The other fields are required unless marked
+/// with
+/// `#[builder(default)]`, and a missing one fails `build` with
+/// `Error::MissingField`. A validation hook, returning `Result<(), Error>`,
+/// can be run on build with `#[builder(validate = "path::to::fn")]`.
+///
+/// ```ignore
+/// #[derive(Serialize, Builder)]
+/// #[allow(non_snake_case)]
+/// pub struct CreateWalletAccountRequestBody {
+///     pub DerivationPath: String,
+///     pub Label: String,
+///     pub ScriptType: u8,
+/// }
+///
+/// let body = CreateWalletAccountRequestBody::builder()
+///     .derivation_path("m/84'/0'/0'")
+///     .label("Primary account")
+///     .script_type(3)
+///     .build()?;
+/// ```
+///
+/// Only usable within `andromeda-api`, as the generated code refers to its
+/// error through `crate`.
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    builder::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
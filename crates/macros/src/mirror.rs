@@ -0,0 +1,210 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Path};
+
+/// How a field value is converted to the other struct's field type
+enum FieldConversion {
+    /// `Into::into` on the value
+    Into,
+    /// `Into::into` on each item of a collection
+    Vec,
+    /// `Into::into` on the value of an option
+    Option,
+    /// `TryInto::try_into` on the value, only for `try_from` conversions
+    Try,
+}
+
+struct MirrorField {
+    ident: Ident,
+    /// Name of the field in the other struct
+    other: Ident,
+    conversion: FieldConversion,
+}
+
+#[derive(Default)]
+struct MirrorOptions {
+    from: Vec<Path>,
+    into: Vec<Path>,
+    try_from: Option<Path>,
+    error: Option<Path>,
+}
+
+fn parse_options(input: &DeriveInput) -> Result<MirrorOptions, Error> {
+    let mut options = MirrorOptions::default();
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("mirror")) {
+        attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<LitStr>()?.parse::<Path>()?;
+
+            if meta.path.is_ident("from") {
+                options.from.push(value);
+            } else if meta.path.is_ident("into") {
+                options.into.push(value);
+            } else if meta.path.is_ident("try_from") {
+                options.try_from = Some(value);
+            } else if meta.path.is_ident("error") {
+                options.error = Some(value);
+            } else {
+                return Err(meta.error("unsupported mirror attribute, expected `from`, `into`, `try_from` or `error`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    if options.try_from.is_some() != options.error.is_some() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`try_from` and `error` must be set together",
+        ));
+    }
+
+    Ok(options)
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<MirrorField>, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "Mirror requires named fields")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Mirror can only be derived for structs",
+            ))
+        }
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            let mut other = ident.clone();
+            let mut conversion = FieldConversion::Into;
+
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("mirror")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        other = meta.value()?.parse::<LitStr>()?.parse::<Ident>()?;
+                    } else if meta.path.is_ident("vec") {
+                        conversion = FieldConversion::Vec;
+                    } else if meta.path.is_ident("option") {
+                        conversion = FieldConversion::Option;
+                    } else if meta.path.is_ident("try") {
+                        conversion = FieldConversion::Try;
+                    } else {
+                        return Err(
+                            meta.error("unsupported mirror attribute, expected `rename`, `vec`, `option` or `try`")
+                        );
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            Ok(MirrorField {
+                ident,
+                other,
+                conversion,
+            })
+        })
+        .collect()
+}
+
+/// Expression converting `value.<source>`, for infallible conversions
+fn convert(source: &Ident, conversion: &FieldConversion) -> Result<TokenStream, Error> {
+    Ok(match conversion {
+        FieldConversion::Into => quote!(::core::convert::Into::into(value.#source)),
+        FieldConversion::Vec => quote!(value.#source.into_iter().map(::core::convert::Into::into).collect()),
+        FieldConversion::Option => quote!(value.#source.map(::core::convert::Into::into)),
+        FieldConversion::Try => {
+            return Err(Error::new_spanned(
+                source,
+                "`try` fields can only be converted with `try_from`",
+            ))
+        }
+    })
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let options = parse_options(&input)?;
+    let fields = parse_fields(&input)?;
+
+    let mut impls = Vec::new();
+
+    for other in &options.from {
+        let assignments = fields
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                let value = convert(&field.other, &field.conversion)?;
+                Ok(quote!(#ident: #value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        impls.push(quote! {
+            impl #impl_generics ::core::convert::From<#other> for #name #ty_generics #where_clause {
+                fn from(value: #other) -> Self {
+                    Self { #(#assignments,)* }
+                }
+            }
+        });
+    }
+
+    for other in &options.into {
+        let assignments = fields
+            .iter()
+            .map(|field| {
+                let other_ident = &field.other;
+                let value = convert(&field.ident, &field.conversion)?;
+                Ok(quote!(#other_ident: #value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        impls.push(quote! {
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for #other #where_clause {
+                fn from(value: #name #ty_generics) -> Self {
+                    Self { #(#assignments,)* }
+                }
+            }
+        });
+    }
+
+    if let (Some(other), Some(error)) = (&options.try_from, &options.error) {
+        let assignments = fields
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                let source = &field.other;
+                let value = match field.conversion {
+                    FieldConversion::Try => quote!(::core::convert::TryInto::try_into(value.#source)?),
+                    ref conversion => convert(source, conversion)?,
+                };
+                Ok(quote!(#ident: #value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        impls.push(quote! {
+            impl #impl_generics ::core::convert::TryFrom<#other> for #name #ty_generics #where_clause {
+                type Error = #error;
+
+                fn try_from(value: #other) -> ::core::result::Result<Self, Self::Error> {
+                    ::core::result::Result::Ok(Self { #(#assignments,)* })
+                }
+            }
+        });
+    }
+
+    if impls.is_empty() {
+        return Err(Error::new_spanned(
+            name,
+            "Mirror requires at least one of `from`, `into` or `try_from`",
+        ));
+    }
+
+    Ok(quote!(#(#impls)*))
+}
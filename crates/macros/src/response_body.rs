@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Field, Fields};
+
+const CODE_FIELD: &str = "Code";
+
+/// Returns whether the field is marked with `#[response_body(data)]`
+fn is_data_field(field: &Field) -> Result<bool, Error> {
+    let mut is_data = false;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("response_body")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("data") {
+                is_data = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported response_body attribute, expected `data`"))
+            }
+        })?;
+    }
+
+    Ok(is_data)
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(name, "ResponseBody requires named fields")),
+        },
+        _ => return Err(Error::new_spanned(name, "ResponseBody can only be derived for structs")),
+    };
+
+    if !fields
+        .iter()
+        .any(|field| field.ident.as_ref().is_some_and(|ident| ident == CODE_FIELD))
+    {
+        return Err(Error::new_spanned(name, "ResponseBody requires a `Code: u16` field"));
+    }
+
+    let mut data_fields = Vec::new();
+    for field in fields {
+        if is_data_field(field)? {
+            data_fields.push(field);
+        }
+    }
+
+    let (data_type, into_data) = match data_fields.as_slice() {
+        [] => (quote!(()), quote!(())),
+        [field] => {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            (quote!(#ty), quote!(self.#ident))
+        }
+        [_, field, ..] => {
+            return Err(Error::new_spanned(
+                field,
+                "only one field can be marked with #[response_body(data)]",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics crate::core::ResponseBody for #name #ty_generics #where_clause {
+            type Data = #data_type;
+
+            fn code(&self) -> u16 {
+                self.Code
+            }
+
+            fn into_data(self) -> Self::Data {
+                #into_data
+            }
+        }
+    })
+}
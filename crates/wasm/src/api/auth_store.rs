@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use andromeda_api::{Auth, EnvId, Store, StoreFailure, StoredAuth, WalletAuthStore};
+
+const AUTH_KEY_BASE: &str = "AUTH";
+
+fn get_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Auth store persisting the session in the browser's local storage, so that
+/// it survives page reloads.
+///
+/// # Notes
+///
+/// muon's [`Store`] is synchronous, which rules out IndexedDB and WebCrypto,
+/// and the local storage is readable by any script running on the page.
+/// Hence only the uid of external sessions, whose tokens are held by the
+/// browser's cookies, gets persisted: the tokens of internal sessions are
+/// only kept in memory. Persistence is best effort: when no local storage is
+/// available, e.g. in a Web Worker, the session is only kept in memory.
+#[derive(Debug, Clone)]
+pub struct WebAuthStore {
+    env: EnvId,
+    storage_key: String,
+    auth: Arc<Mutex<Auth>>,
+}
+
+impl WebAuthStore {
+    /// Creates a store for the given environment, restoring the session
+    /// persisted under `key` if any
+    pub fn new(env: String, key: String) -> Self {
+        let storage_key = format!("{}_{}", AUTH_KEY_BASE, key);
+        let stored_auth = get_storage().and_then(|storage| {
+            let stored_auth = storage
+                .get_item(&storage_key)
+                .ok()
+                .flatten()
+                .and_then(|serialized| serde_json::from_str::<StoredAuth>(&serialized).ok());
+
+            // Tokens persisted by a previous version are wiped out
+            if stored_auth
+                .as_ref()
+                .is_some_and(|stored_auth| !is_external(stored_auth))
+            {
+                let _ = storage.remove_item(&storage_key);
+                return None;
+            }
+
+            stored_auth
+        });
+        let auth = stored_auth.map_or(Auth::None, StoredAuth::into_auth);
+
+        Self {
+            env: WalletAuthStore::from_env_str(env, Arc::new(Mutex::new(Auth::None))).env,
+            storage_key,
+            auth: Arc::new(Mutex::new(auth)),
+        }
+    }
+
+    fn persist(&self, auth: &Auth) {
+        let Some(storage) = get_storage() else {
+            return;
+        };
+
+        match StoredAuth::from_auth(auth) {
+            Some(stored_auth) if is_external(&stored_auth) => {
+                if let Ok(serialized) = serde_json::to_string(&stored_auth) {
+                    let _ = storage.set_item(&self.storage_key, &serialized);
+                }
+            }
+            // Session was logged out, or is an internal one whose tokens must
+            // not be written in clear
+            _ => {
+                let _ = storage.remove_item(&self.storage_key);
+            }
+        }
+    }
+}
+
+/// External sessions only have a uid, their tokens being held by the browser
+fn is_external(stored_auth: &StoredAuth) -> bool {
+    stored_auth.access_token.is_none() && stored_auth.refresh_token.is_none()
+}
+
+impl Store for WebAuthStore {
+    fn env(&self) -> EnvId {
+        self.env.clone()
+    }
+
+    fn get_auth(&self) -> Auth {
+        self.auth.lock().unwrap().clone()
+    }
+
+    fn set_auth(&mut self, auth: Auth) -> Result<Auth, StoreFailure> {
+        self.persist(&auth);
+        *self.auth.lock().unwrap() = auth.clone();
+
+        Ok(auth)
+    }
+}
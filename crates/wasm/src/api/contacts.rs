@@ -0,0 +1,150 @@
+use andromeda_api::contacts::{ApiContact, ApiContactCard, ApiContactEmails, ContactsClient};
+use andromeda_macros::Mirror;
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use crate::common::error::ErrorExt;
+
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiContactEmails")]
+#[allow(non_snake_case)]
+pub struct WasmApiContactEmails {
+    pub ID: String,
+    pub Name: String,
+    pub Email: String,
+    pub CanonicalEmail: String,
+    pub IsProton: u32,
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiContactCard", into = "ApiContactCard")]
+#[allow(non_snake_case)]
+pub struct WasmApiContactCard {
+    pub Type: u8,
+    pub Data: String,
+    pub Signature: Option<String>,
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiContact")]
+#[allow(non_snake_case)]
+pub struct WasmApiContact {
+    pub ID: String,
+    pub Name: String,
+    #[mirror(vec)]
+    pub Cards: Vec<WasmApiContactCard>,
+    #[mirror(vec)]
+    pub ContactEmails: Vec<WasmApiContactEmails>,
+}
+
+// We need this wrapper because unfortunately, tsify doesn't support
+// VectoIntoWasmAbi yet
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmApiContactEmailsData {
+    pub Data: WasmApiContactEmails,
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmApiContactData {
+    pub Data: WasmApiContact,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmApiContactsEmails(pub Vec<WasmApiContactEmailsData>);
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmApiContactCards(pub Vec<WasmApiContactCard>);
+
+#[wasm_bindgen]
+impl WasmApiContactCards {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    pub fn push(&mut self, card: WasmApiContactCard) {
+        self.0.push(card)
+    }
+}
+
+impl From<WasmApiContactCards> for Vec<ApiContactCard> {
+    fn from(value: WasmApiContactCards) -> Self {
+        value.0.into_iter().map(|card| card.into()).collect()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmContactsClient(ContactsClient);
+
+impl From<ContactsClient> for WasmContactsClient {
+    fn from(value: ContactsClient) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmContactsClient {
+    #[wasm_bindgen(js_name = "getContacts")]
+    pub async fn get_contacts(
+        &self,
+        page_size: Option<u64>,
+        page: Option<u64>,
+    ) -> Result<WasmApiContactsEmails, JsValue> {
+        let contacts = self
+            .0
+            .get_contacts(page_size, page)
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|contact| WasmApiContactEmailsData { Data: contact.into() })
+            .collect::<Vec<_>>();
+
+        Ok(WasmApiContactsEmails(contacts))
+    }
+
+    #[wasm_bindgen(js_name = "getContact")]
+    pub async fn get_contact(&self, contact_id: String) -> Result<WasmApiContactData, JsValue> {
+        self.0
+            .get_contact(contact_id)
+            .await
+            .map(|contact| WasmApiContactData { Data: contact.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "createContact")]
+    pub async fn create_contact(&self, cards: WasmApiContactCards) -> Result<WasmApiContactData, JsValue> {
+        self.0
+            .create_contact(cards.into())
+            .await
+            .map(|contact| WasmApiContactData { Data: contact.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "updateContact")]
+    pub async fn update_contact(
+        &self,
+        contact_id: String,
+        cards: WasmApiContactCards,
+    ) -> Result<WasmApiContactData, JsValue> {
+        self.0
+            .update_contact(contact_id, cards.into())
+            .await
+            .map(|contact| WasmApiContactData { Data: contact.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "deleteContacts")]
+    pub async fn delete_contacts(&self, contact_ids: Vec<String>) -> Result<(), JsValue> {
+        self.0.delete_contacts(contact_ids).await.map_err(|e| e.to_js_error())
+    }
+}
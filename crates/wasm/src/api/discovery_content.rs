@@ -0,0 +1,88 @@
+use andromeda_api::discovery_content::{Content, DiscoverContentClient};
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use crate::common::{bigint, error::ErrorExt};
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmDiscoverContent {
+    pub Title: String,
+    pub Link: String,
+    pub Description: String,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
+    pub PubDate: i64,
+    pub Author: String,
+    pub Category: String,
+}
+
+impl From<Content> for WasmDiscoverContent {
+    fn from(value: Content) -> Self {
+        Self {
+            Title: value.Title,
+            Link: value.Link,
+            Description: value.Description,
+            PubDate: value.PubDate,
+            Author: value.Author,
+            Category: value.Category,
+        }
+    }
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmDiscoverContentData {
+    pub Data: WasmDiscoverContent,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmDiscoverContents(pub Vec<WasmDiscoverContentData>);
+
+impl From<Vec<Content>> for WasmDiscoverContents {
+    fn from(value: Vec<Content>) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|content| WasmDiscoverContentData { Data: content.into() })
+                .collect(),
+        )
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmDiscoverContentClient(DiscoverContentClient);
+
+impl From<DiscoverContentClient> for WasmDiscoverContentClient {
+    fn from(value: DiscoverContentClient) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmDiscoverContentClient {
+    /// Returns the discover content for the given locale (e.g. `fr_FR`).
+    /// Results are cached, use `refreshDiscoveryContents` to bypass the cache
+    #[wasm_bindgen(js_name = "getDiscoveryContents")]
+    pub async fn get_discovery_contents(&self, locale: Option<String>) -> Result<WasmDiscoverContents, JsValue> {
+        self.0
+            .get_localized_discovery_contents(locale)
+            .await
+            .map(|contents| contents.into())
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "refreshDiscoveryContents")]
+    pub async fn refresh_discovery_contents(&self, locale: Option<String>) -> Result<WasmDiscoverContents, JsValue> {
+        self.0
+            .refresh_discovery_contents(locale)
+            .await
+            .map(|contents| contents.into())
+            .map_err(|e| e.to_js_error())
+    }
+}
@@ -62,4 +62,20 @@ impl WasmEmailIntegrationClient {
             .await
             .map_err(|e| e.to_js_error())
     }
+
+    #[wasm_bindgen(js_name = "sendBveSetupReminder")]
+    pub async fn send_bve_setup_reminder(&self, email: String, inviter_address_id: String) -> Result<(), JsValue> {
+        self.0
+            .send_bve_setup_reminder(email, inviter_address_id)
+            .await
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "cancelBveSetupReminder")]
+    pub async fn cancel_bve_setup_reminder(&self, email: String) -> Result<(), JsValue> {
+        self.0
+            .cancel_bve_setup_reminder(email)
+            .await
+            .map_err(|e| e.to_js_error())
+    }
 }
@@ -0,0 +1,423 @@
+use std::{pin::pin, sync::Arc, time::Duration};
+
+use andromeda_api::{
+    error::Error as ApiError,
+    event::{
+        ApiContactsEmailEvent, ApiWalletAccountEvent, ApiWalletEvent, ApiWalletKeyEvent, ApiWalletSettingsEvent,
+        ApiWalletTransactionsEvent, EventClient, EventListener, EventLoop,
+    },
+    settings::UserSettings,
+};
+use futures::{
+    channel::mpsc,
+    future::{self, Either},
+    lock::Mutex,
+    StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use super::{
+    contacts::WasmApiContactEmails,
+    settings::WasmUserSettings,
+    wallet::{WasmApiWallet, WasmApiWalletAccount, WasmApiWalletKey, WasmApiWalletSettings, WasmApiWalletTransaction},
+};
+use crate::common::error::ErrorExt;
+
+pub const DEFAULT_EVENT_LOOP_INTERVAL_MS: u32 = 30_000;
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiWalletEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub Wallet: Option<WasmApiWallet>,
+}
+
+impl From<ApiWalletEvent> for WasmApiWalletEvent {
+    fn from(value: ApiWalletEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            Wallet: value.Wallet.map(|wallet| wallet.into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiWalletAccountEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub WalletAccount: Option<WasmApiWalletAccount>,
+}
+
+impl From<ApiWalletAccountEvent> for WasmApiWalletAccountEvent {
+    fn from(value: ApiWalletAccountEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            WalletAccount: value.WalletAccount.map(|account| account.into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiWalletKeyEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub WalletKey: Option<WasmApiWalletKey>,
+}
+
+impl From<ApiWalletKeyEvent> for WasmApiWalletKeyEvent {
+    fn from(value: ApiWalletKeyEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            WalletKey: value.WalletKey.map(|key| key.into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiWalletSettingsEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub WalletSettings: Option<WasmApiWalletSettings>,
+}
+
+impl From<ApiWalletSettingsEvent> for WasmApiWalletSettingsEvent {
+    fn from(value: ApiWalletSettingsEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            WalletSettings: value.WalletSettings.map(|settings| settings.into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiWalletTransactionEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub WalletTransaction: Option<WasmApiWalletTransaction>,
+}
+
+impl From<ApiWalletTransactionsEvent> for WasmApiWalletTransactionEvent {
+    fn from(value: ApiWalletTransactionsEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            WalletTransaction: value.WalletTransaction.map(|transaction| transaction.into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiContactEmailEvent {
+    pub ID: String,
+    pub Action: u32,
+    pub ContactEmail: Option<WasmApiContactEmails>,
+}
+
+impl From<ApiContactsEmailEvent> for WasmApiContactEmailEvent {
+    fn from(value: ApiContactsEmailEvent) -> Self {
+        Self {
+            ID: value.ID,
+            Action: value.Action,
+            ContactEmail: value.ContactEmail.map(|email| email.into()),
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmEventClient(EventClient);
+
+impl From<EventClient> for WasmEventClient {
+    fn from(value: EventClient) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmEventClient {
+    #[wasm_bindgen(js_name = "getLatestEventId")]
+    pub async fn get_latest_event_id(&self) -> Result<String, JsValue> {
+        self.0.get_latest_event_id().await.map_err(|e| e.to_js_error())
+    }
+
+    /// Returns an event loop dispatching the events polled with this client to
+    /// the registered callbacks
+    #[wasm_bindgen(js_name = "eventLoop")]
+    pub fn event_loop(&self) -> WasmEventLoop {
+        WasmEventLoop::new(self.0.clone())
+    }
+}
+
+/// Events pushed by the core [`EventLoop`], forwarded to the JS callbacks
+enum EventMessage {
+    Wallet(ApiWalletEvent),
+    WalletAccount(ApiWalletAccountEvent),
+    WalletKey(ApiWalletKeyEvent),
+    WalletSettings(ApiWalletSettingsEvent),
+    WalletTransaction(ApiWalletTransactionsEvent),
+    ContactEmail(ApiContactsEmailEvent),
+    UserSettings(UserSettings),
+    Refresh(u32),
+    Error(ApiError),
+}
+
+/// [`EventListener`] sending the events to the [`WasmEventLoop`]. JS functions
+/// can't be shared across threads, so they are called on the receiving end
+struct ChannelEventListener(mpsc::UnboundedSender<EventMessage>);
+
+impl ChannelEventListener {
+    fn send(&self, message: EventMessage) {
+        let _ = self.0.unbounded_send(message);
+    }
+}
+
+impl EventListener for ChannelEventListener {
+    fn on_wallet_event(&self, event: ApiWalletEvent) {
+        self.send(EventMessage::Wallet(event));
+    }
+
+    fn on_wallet_account_event(&self, event: ApiWalletAccountEvent) {
+        self.send(EventMessage::WalletAccount(event));
+    }
+
+    fn on_wallet_key_event(&self, event: ApiWalletKeyEvent) {
+        self.send(EventMessage::WalletKey(event));
+    }
+
+    fn on_wallet_settings_event(&self, event: ApiWalletSettingsEvent) {
+        self.send(EventMessage::WalletSettings(event));
+    }
+
+    fn on_wallet_transaction_event(&self, event: ApiWalletTransactionsEvent) {
+        self.send(EventMessage::WalletTransaction(event));
+    }
+
+    fn on_contact_email_event(&self, event: ApiContactsEmailEvent) {
+        self.send(EventMessage::ContactEmail(event));
+    }
+
+    fn on_user_settings_event(&self, user_settings: UserSettings) {
+        self.send(EventMessage::UserSettings(user_settings));
+    }
+
+    fn on_refresh(&self, refresh: u32) {
+        self.send(EventMessage::Refresh(refresh));
+    }
+
+    fn on_error(&self, error: ApiError) {
+        self.send(EventMessage::Error(error));
+    }
+}
+
+/// Polls the events of the user and dispatches them, typed, to the callbacks
+/// registered for each kind of event.
+///
+/// Callbacks need to be registered before the loop is started.
+#[wasm_bindgen]
+pub struct WasmEventLoop {
+    inner: EventLoop,
+    receiver: Mutex<mpsc::UnboundedReceiver<EventMessage>>,
+    on_wallet: Option<js_sys::Function>,
+    on_wallet_account: Option<js_sys::Function>,
+    on_wallet_key: Option<js_sys::Function>,
+    on_wallet_settings: Option<js_sys::Function>,
+    on_wallet_transaction: Option<js_sys::Function>,
+    on_contact_email: Option<js_sys::Function>,
+    on_user_settings: Option<js_sys::Function>,
+    on_refresh: Option<js_sys::Function>,
+    on_error: Option<js_sys::Function>,
+}
+
+fn dispatch<T: Serialize>(callback: &Option<js_sys::Function>, value: T) -> Result<(), JsValue> {
+    if let Some(callback) = callback {
+        let value = serde_wasm_bindgen::to_value(&value)?;
+        callback.call1(&JsValue::NULL, &value)?;
+    }
+
+    Ok(())
+}
+
+impl WasmEventLoop {
+    fn new(client: EventClient) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        Self {
+            inner: EventLoop::new(client, Arc::new(ChannelEventListener(sender))),
+            receiver: Mutex::new(receiver),
+            on_wallet: None,
+            on_wallet_account: None,
+            on_wallet_key: None,
+            on_wallet_settings: None,
+            on_wallet_transaction: None,
+            on_contact_email: None,
+            on_user_settings: None,
+            on_refresh: None,
+            on_error: None,
+        }
+    }
+
+    fn dispatch_message(&self, message: EventMessage) -> Result<(), JsValue> {
+        match message {
+            EventMessage::Wallet(event) => dispatch(&self.on_wallet, WasmApiWalletEvent::from(event)),
+            EventMessage::WalletAccount(event) => {
+                dispatch(&self.on_wallet_account, WasmApiWalletAccountEvent::from(event))
+            }
+            EventMessage::WalletKey(event) => dispatch(&self.on_wallet_key, WasmApiWalletKeyEvent::from(event)),
+            EventMessage::WalletSettings(event) => {
+                dispatch(&self.on_wallet_settings, WasmApiWalletSettingsEvent::from(event))
+            }
+            EventMessage::WalletTransaction(event) => {
+                dispatch(&self.on_wallet_transaction, WasmApiWalletTransactionEvent::from(event))
+            }
+            EventMessage::ContactEmail(event) => {
+                dispatch(&self.on_contact_email, WasmApiContactEmailEvent::from(event))
+            }
+            EventMessage::UserSettings(user_settings) => {
+                dispatch(&self.on_user_settings, WasmUserSettings::from(user_settings))
+            }
+            EventMessage::Refresh(refresh) => dispatch(&self.on_refresh, refresh),
+            // Without error callback, the loop stops on the first error
+            EventMessage::Error(error) => match &self.on_error {
+                Some(on_error) => on_error.call1(&JsValue::NULL, &error.to_js_error()).map(|_| ()),
+                None => Err(error.to_js_error()),
+            },
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl WasmEventLoop {
+    /// Called with each `WasmApiWalletEvent`
+    #[wasm_bindgen(js_name = "onWalletEvent")]
+    pub fn on_wallet_event(&mut self, callback: js_sys::Function) {
+        self.on_wallet = Some(callback);
+    }
+
+    /// Called with each `WasmApiWalletAccountEvent`
+    #[wasm_bindgen(js_name = "onWalletAccountEvent")]
+    pub fn on_wallet_account_event(&mut self, callback: js_sys::Function) {
+        self.on_wallet_account = Some(callback);
+    }
+
+    /// Called with each `WasmApiWalletKeyEvent`
+    #[wasm_bindgen(js_name = "onWalletKeyEvent")]
+    pub fn on_wallet_key_event(&mut self, callback: js_sys::Function) {
+        self.on_wallet_key = Some(callback);
+    }
+
+    /// Called with each `WasmApiWalletSettingsEvent`
+    #[wasm_bindgen(js_name = "onWalletSettingsEvent")]
+    pub fn on_wallet_settings_event(&mut self, callback: js_sys::Function) {
+        self.on_wallet_settings = Some(callback);
+    }
+
+    /// Called with each `WasmApiWalletTransactionEvent`
+    #[wasm_bindgen(js_name = "onWalletTransactionEvent")]
+    pub fn on_wallet_transaction_event(&mut self, callback: js_sys::Function) {
+        self.on_wallet_transaction = Some(callback);
+    }
+
+    /// Called with each `WasmApiContactEmailEvent`
+    #[wasm_bindgen(js_name = "onContactEmailEvent")]
+    pub fn on_contact_email_event(&mut self, callback: js_sys::Function) {
+        self.on_contact_email = Some(callback);
+    }
+
+    /// Called with the updated `WasmUserSettings`
+    #[wasm_bindgen(js_name = "onUserSettingsEvent")]
+    pub fn on_user_settings_event(&mut self, callback: js_sys::Function) {
+        self.on_user_settings = Some(callback);
+    }
+
+    /// Called with the refresh flags when the server asks for a full refetch
+    /// of the data
+    #[wasm_bindgen(js_name = "onRefresh")]
+    pub fn on_refresh(&mut self, callback: js_sys::Function) {
+        self.on_refresh = Some(callback);
+    }
+
+    /// Called with the polling errors while the loop is running. Without it,
+    /// the loop stops on the first error
+    #[wasm_bindgen(js_name = "onError")]
+    pub fn on_error(&mut self, callback: js_sys::Function) {
+        self.on_error = Some(callback);
+    }
+
+    /// Returns the id of the last dispatched event
+    #[wasm_bindgen(js_name = "getCursor")]
+    pub fn get_cursor(&self) -> Option<String> {
+        self.inner.client().cursor()
+    }
+
+    /// Resumes the loop from the given event id, e.g. one persisted by the app
+    #[wasm_bindgen(js_name = "setCursor")]
+    pub fn set_cursor(&self, event_id: String) {
+        self.inner.client().set_cursor(event_id);
+    }
+
+    /// Polls the events once and dispatches them. Returns the number of
+    /// dispatched events.
+    ///
+    /// On the first poll without cursor, only the cursor is initialised.
+    #[wasm_bindgen]
+    pub async fn poll(&self) -> Result<usize, JsValue> {
+        let count = self.inner.poll().await.map_err(|e| e.to_js_error())?;
+
+        // While the loop is started, it dispatches the events itself
+        if let Some(mut receiver) = self.receiver.try_lock() {
+            while let Ok(Some(message)) = receiver.try_next() {
+                self.dispatch_message(message)?;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Polls the events every `interval_ms` (30 seconds by default) until
+    /// `stop` is called
+    #[wasm_bindgen]
+    pub async fn start(&self, interval_ms: Option<u32>) -> Result<(), JsValue> {
+        let mut receiver = self.receiver.lock().await;
+        let interval = Duration::from_millis(interval_ms.unwrap_or(DEFAULT_EVENT_LOOP_INTERVAL_MS).into());
+
+        let forward = async {
+            while let Some(message) = receiver.next().await {
+                self.dispatch_message(message)?;
+            }
+
+            Ok(())
+        };
+
+        match future::select(pin!(self.inner.start(interval)), pin!(forward)).await {
+            Either::Left(((), _)) => Ok(()),
+            Either::Right((result, _)) => {
+                self.inner.stop();
+                result
+            }
+        }
+    }
+
+    /// Stops the loop after the current iteration
+    #[wasm_bindgen]
+    pub fn stop(&self) {
+        self.inner.stop();
+    }
+}
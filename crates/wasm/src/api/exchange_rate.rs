@@ -1,4 +1,7 @@
-use andromeda_api::exchange_rate::{ApiExchangeRate, ApiFiatCurrency, ExchangeRateClient};
+use andromeda_api::{
+    exchange_rate::{ApiExchangeRate, ApiFiatCurrency, ExchangeRateClient},
+    fiat_conversion,
+};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -33,6 +36,48 @@ impl From<ApiExchangeRate> for WasmApiExchangeRate {
     }
 }
 
+impl From<WasmApiExchangeRate> for ApiExchangeRate {
+    fn from(value: WasmApiExchangeRate) -> Self {
+        Self {
+            ID: value.ID,
+            BitcoinUnit: value.BitcoinUnit.into(),
+            FiatCurrency: value.FiatCurrency.into(),
+            Sign: value.Sign,
+            ExchangeRateTime: value.ExchangeRateTime,
+            ExchangeRate: value.ExchangeRate,
+            Cents: value.Cents,
+        }
+    }
+}
+
+/// Converts a satoshi amount to fiat minor units (e.g. cents for USD)
+#[wasm_bindgen(js_name = satsToFiatMinor)]
+pub fn sats_to_fiat_minor(exchange_rate: WasmApiExchangeRate, sats: u64) -> u64 {
+    fiat_conversion::sats_to_fiat_minor(&exchange_rate.into(), sats)
+}
+
+/// Converts a satoshi amount to a fiat value, rounded to the currency's
+/// precision
+#[wasm_bindgen(js_name = satsToFiat)]
+pub fn sats_to_fiat(exchange_rate: WasmApiExchangeRate, sats: u64) -> f64 {
+    fiat_conversion::sats_to_fiat(&exchange_rate.into(), sats)
+}
+
+/// Converts fiat minor units to a satoshi amount. Returns `undefined` when the
+/// exchange rate is zero
+#[wasm_bindgen(js_name = fiatMinorToSats)]
+pub fn fiat_minor_to_sats(exchange_rate: WasmApiExchangeRate, fiat_minor: u64) -> Option<u64> {
+    fiat_conversion::fiat_minor_to_sats(&exchange_rate.into(), fiat_minor)
+}
+
+/// Converts a fiat value to a satoshi amount, after rounding the value to the
+/// currency's precision. Returns `undefined` for a zero exchange rate or an
+/// invalid fiat value
+#[wasm_bindgen(js_name = fiatToSats)]
+pub fn fiat_to_sats(exchange_rate: WasmApiExchangeRate, fiat: f64) -> Option<u64> {
+    fiat_conversion::fiat_to_sats(&exchange_rate.into(), fiat)
+}
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[allow(non_snake_case)]
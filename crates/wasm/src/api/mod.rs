@@ -1,26 +1,42 @@
-use andromeda_api::{self, ApiConfig, Auth, ProtonWalletApiClient};
+use andromeda_api::{self, ApiConfig, Auth, DynStore, ProtonWalletApiClient, Store};
+use auth_store::WebAuthStore;
 use bitcoin_address::WasmBitcoinAddressClient;
+use contacts::WasmContactsClient;
+use discovery_content::WasmDiscoverContentClient;
 use email_integration::WasmEmailIntegrationClient;
+use event::WasmEventClient;
 use exchange_rate::WasmExchangeRateClient;
 use invite::WasmInviteClient;
 use network::WasmNetworkClient;
+#[cfg(feature = "gateway")]
 use payment_gateway::WasmPaymentGatewayClient;
+#[cfg(feature = "pricegraph")]
 use price_graph::WasmPriceGraphClient;
+use proton_users::WasmProtonUsersClient;
 use settings::WasmSettingsClient;
+use unleash::WasmUnleashClient;
 use wallet::WasmWalletClient;
 use wasm_bindgen::prelude::*;
 
 use crate::common::error::ErrorExt;
 
+mod auth_store;
 mod bitcoin_address;
+mod contacts;
+mod discovery_content;
 mod email_integration;
 mod env;
+mod event;
 mod exchange_rate;
 mod invite;
 mod network;
+#[cfg(feature = "gateway")]
 mod payment_gateway;
+#[cfg(feature = "pricegraph")]
 mod price_graph;
+mod proton_users;
 mod settings;
+mod unleash;
 mod wallet;
 
 #[wasm_bindgen(getter_with_clone)]
@@ -46,16 +62,47 @@ pub struct WasmApiClients {
     pub exchange_rate: WasmExchangeRateClient,
     pub email_integration: WasmEmailIntegrationClient,
     pub bitcoin_address: WasmBitcoinAddressClient,
-    pub payment_gateway: WasmPaymentGatewayClient,
-    pub price_graph: WasmPriceGraphClient,
     pub settings: WasmSettingsClient,
     pub network: WasmNetworkClient,
     pub invite: WasmInviteClient,
     pub wallet: WasmWalletClient,
+    pub contacts: WasmContactsClient,
+    pub event: WasmEventClient,
+    pub discover_content: WasmDiscoverContentClient,
+    pub proton_users: WasmProtonUsersClient,
+    pub unleash: WasmUnleashClient,
+    // Clients behind features are exposed through the getters below, as
+    // wasm_bindgen doesn't support `cfg` on exported fields
+    #[cfg(feature = "gateway")]
+    payment_gateway: WasmPaymentGatewayClient,
+    #[cfg(feature = "pricegraph")]
+    price_graph: WasmPriceGraphClient,
+}
+
+#[cfg(feature = "gateway")]
+#[wasm_bindgen]
+impl WasmApiClients {
+    #[wasm_bindgen(getter)]
+    pub fn payment_gateway(&self) -> WasmPaymentGatewayClient {
+        self.payment_gateway.clone()
+    }
+}
+
+#[cfg(feature = "pricegraph")]
+#[wasm_bindgen]
+impl WasmApiClients {
+    #[wasm_bindgen(getter)]
+    pub fn price_graph(&self) -> WasmPriceGraphClient {
+        self.price_graph.clone()
+    }
 }
 
 #[wasm_bindgen]
 impl WasmProtonWalletApiClient {
+    /// Builds the api client. When `auth_storage_key` is provided, the uid of
+    /// the external session is persisted in the local storage under this key
+    /// and restored on the next page load, `uid_str` then overriding the
+    /// persisted one. Tokens of internal sessions are never persisted
     #[wasm_bindgen(constructor)]
     pub fn new(
         app_version: String,
@@ -63,13 +110,25 @@ impl WasmProtonWalletApiClient {
         uid_str: Option<String>,
         origin: Option<String>,
         url_prefix: Option<String>,
+        auth_storage_key: Option<String>,
     ) -> Result<WasmProtonWalletApiClient, js_sys::Error> {
+        let store = auth_storage_key.map(|key| {
+            let env = origin.clone().unwrap_or("atlas".to_string());
+            let mut store = WebAuthStore::new(env, key);
+            if let Some(uid) = uid_str.clone() {
+                let _ = store.set_auth(Auth::external(uid));
+            }
+
+            Box::new(store) as DynStore
+        });
+
         let config = ApiConfig {
             spec: (app_version, user_agent),
             auth: uid_str.map(|u| Auth::external(u)),
             env: origin,
             url_prefix,
-            store: None,
+            store,
+            ..Default::default()
         };
 
         let client = ProtonWalletApiClient::from_config(config).map_err(|e| e.to_js_error())?;
@@ -84,12 +143,19 @@ impl WasmProtonWalletApiClient {
             exchange_rate: WasmExchangeRateClient::from(clients.exchange_rate),
             email_integration: WasmEmailIntegrationClient::from(clients.email_integration),
             bitcoin_address: WasmBitcoinAddressClient::from(clients.bitcoin_address),
-            payment_gateway: WasmPaymentGatewayClient::from(clients.payment_gateway),
-            price_graph: WasmPriceGraphClient::from(clients.price_graph),
             settings: WasmSettingsClient::from(clients.settings),
             network: WasmNetworkClient::from(clients.network),
             invite: WasmInviteClient::from(clients.invite),
             wallet: WasmWalletClient::from(clients.wallet),
+            contacts: WasmContactsClient::from(clients.contacts),
+            event: WasmEventClient::from(clients.event),
+            discover_content: WasmDiscoverContentClient::from(clients.discover_content),
+            proton_users: WasmProtonUsersClient::from(clients.proton_users),
+            unleash: WasmUnleashClient::from(clients.unleash),
+            #[cfg(feature = "gateway")]
+            payment_gateway: WasmPaymentGatewayClient::from(clients.payment_gateway),
+            #[cfg(feature = "pricegraph")]
+            price_graph: WasmPriceGraphClient::from(clients.price_graph),
         }
     }
 }
@@ -105,7 +171,7 @@ mod tests {
     #[ignore]
     #[allow(dead_code)]
     async fn should_create_pw_api_client() {
-        let client = WasmProtonWalletApiClient::new(test_spec().0, test_spec().1, None, None, None).unwrap();
+        let client = WasmProtonWalletApiClient::new(test_spec().0, test_spec().1, None, None, None, None).unwrap();
         client.0.login("pro", "pro").await.unwrap();
     }
 }
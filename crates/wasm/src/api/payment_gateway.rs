@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
-use crate::common::error::ErrorExt;
+use crate::common::{abort::abortable, error::ErrorExt};
 
 #[wasm_bindgen]
 #[derive(Clone)]
@@ -352,17 +352,22 @@ impl WasmPaymentGatewayClient {
         fiat_currency: String,
         payment_method: Option<WasmPaymentMethod>,
         provider: Option<WasmGatewayProvider>,
+        signal: Option<web_sys::AbortSignal>,
     ) -> Result<WasmQuotesByProvider, JsValue> {
-        self.0
-            .get_quotes(
-                amount,
-                fiat_currency,
-                payment_method.map(|p| p.into()),
-                provider.map(|o| o.into()),
-            )
-            .await
-            .map(|c| c.into())
-            .map_err(|e| e.to_js_error())
+        let quotes = async {
+            self.0
+                .get_quotes(
+                    amount,
+                    fiat_currency,
+                    payment_method.map(|p| p.into()),
+                    provider.map(|o| o.into()),
+                )
+                .await
+                .map(|c| c.into())
+                .map_err(|e| e.to_js_error())
+        };
+
+        abortable(quotes, signal).await
     }
 
     #[wasm_bindgen(js_name = "createOnRampCheckout")]
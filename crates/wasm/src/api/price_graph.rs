@@ -4,7 +4,7 @@ use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use super::settings::WasmFiatCurrencySymbol;
-use crate::common::{error::ErrorExt, types::WasmBitcoinUnit};
+use crate::common::{bigint, error::ErrorExt, types::WasmBitcoinUnit};
 
 #[wasm_bindgen]
 #[derive(Clone)]
@@ -42,6 +42,8 @@ impl From<WasmTimeframe> for Timeframe {
 pub struct WasmDataPoint {
     pub ExchangeRate: u64,
     pub Cents: u8,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub Timestamp: u64,
 }
 
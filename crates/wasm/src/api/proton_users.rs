@@ -0,0 +1,188 @@
+use andromeda_api::{
+    proton_users::{ApiKeySalt, ProtonUser, ProtonUserKey, ProtonUsersClientExt},
+    ProtonUsersClient,
+};
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use crate::common::{bigint, error::ErrorExt};
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmProtonUserKey {
+    pub ID: String,
+    pub Version: u32,
+    pub PrivateKey: String,
+    pub RecoverySecret: Option<String>,
+    pub RecoverySecretSignature: Option<String>,
+    pub Token: Option<String>,
+    pub Fingerprint: String,
+    pub Primary: u32,
+    pub Active: u32,
+}
+
+impl From<ProtonUserKey> for WasmProtonUserKey {
+    fn from(value: ProtonUserKey) -> Self {
+        Self {
+            ID: value.ID,
+            Version: value.Version,
+            PrivateKey: value.PrivateKey,
+            RecoverySecret: value.RecoverySecret,
+            RecoverySecretSignature: value.RecoverySecretSignature,
+            Token: value.Token,
+            Fingerprint: value.Fingerprint,
+            Primary: value.Primary,
+            Active: value.Active,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmProtonUser {
+    pub ID: String,
+    pub Name: Option<String>,
+    pub UsedSpace: u64,
+    pub Currency: String,
+    pub Credit: u32,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
+    pub CreateTime: u64,
+    pub MaxSpace: u64,
+    pub MaxUpload: u64,
+    pub Role: u32,
+    pub Private: u32,
+    pub Subscribed: u32,
+    pub Services: u32,
+    pub Delinquent: u32,
+    pub OrganizationPrivateKey: Option<String>,
+    pub Email: String,
+    pub DisplayName: Option<String>,
+    pub Keys: Option<Vec<WasmProtonUserKey>>,
+    pub MnemonicStatus: u32,
+}
+
+impl From<ProtonUser> for WasmProtonUser {
+    fn from(value: ProtonUser) -> Self {
+        Self {
+            ID: value.ID,
+            Name: value.Name,
+            UsedSpace: value.UsedSpace,
+            Currency: value.Currency,
+            Credit: value.Credit,
+            CreateTime: value.CreateTime,
+            MaxSpace: value.MaxSpace,
+            MaxUpload: value.MaxUpload,
+            Role: value.Role,
+            Private: value.Private,
+            Subscribed: value.Subscribed,
+            Services: value.Services,
+            Delinquent: value.Delinquent,
+            OrganizationPrivateKey: value.OrganizationPrivateKey,
+            Email: value.Email,
+            DisplayName: value.DisplayName,
+            Keys: value.Keys.map(|keys| keys.into_iter().map(|key| key.into()).collect()),
+            MnemonicStatus: value.MnemonicStatus,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmApiKeySalt {
+    pub ID: String,
+    pub KeySalt: Option<String>,
+}
+
+impl From<ApiKeySalt> for WasmApiKeySalt {
+    fn from(value: ApiKeySalt) -> Self {
+        Self {
+            ID: value.ID,
+            KeySalt: value.KeySalt,
+        }
+    }
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmProtonUserData {
+    pub Data: WasmProtonUser,
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmProtonUserKeyData {
+    pub Data: WasmProtonUserKey,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmProtonUserKeys(pub Vec<WasmProtonUserKeyData>);
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmApiKeySaltData {
+    pub Data: WasmApiKeySalt,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmApiKeySalts(pub Vec<WasmApiKeySaltData>);
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmProtonUsersClient(ProtonUsersClient);
+
+impl From<ProtonUsersClient> for WasmProtonUsersClient {
+    fn from(value: ProtonUsersClient) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmProtonUsersClient {
+    #[wasm_bindgen(js_name = "getUserInfo")]
+    pub async fn get_user_info(&self) -> Result<WasmProtonUserData, JsValue> {
+        self.0
+            .get_user_info()
+            .await
+            .map(|user| WasmProtonUserData { Data: user.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "getUserKeys")]
+    pub async fn get_user_keys(&self) -> Result<WasmProtonUserKeys, JsValue> {
+        let keys = self
+            .0
+            .get_user_keys()
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|key| WasmProtonUserKeyData { Data: key.into() })
+            .collect::<Vec<_>>();
+
+        Ok(WasmProtonUserKeys(keys))
+    }
+
+    #[wasm_bindgen(js_name = "getKeySalts")]
+    pub async fn get_key_salts(&self) -> Result<WasmApiKeySalts, JsValue> {
+        let key_salts = self
+            .0
+            .get_key_salts()
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|key_salt| WasmApiKeySaltData { Data: key_salt.into() })
+            .collect::<Vec<_>>();
+
+        Ok(WasmApiKeySalts(key_salts))
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use andromeda_api::unleash::{UnleashClient, UnleashToggle, UnleashVariant};
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use crate::common::{error::ErrorExt, timer::sleep};
+
+pub const DEFAULT_FEATURE_FLAGS_INTERVAL_MS: u32 = 10 * 60 * 1000;
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmUnleashVariantPayload {
+    #[serde(rename = "type")]
+    pub payload_type: String,
+    pub value: String,
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmUnleashVariant {
+    pub name: String,
+    pub enabled: bool,
+    pub payload: Option<WasmUnleashVariantPayload>,
+}
+
+impl From<UnleashVariant> for WasmUnleashVariant {
+    fn from(value: UnleashVariant) -> Self {
+        Self {
+            name: value.name,
+            enabled: value.enabled,
+            payload: value.payload.map(|payload| WasmUnleashVariantPayload {
+                payload_type: payload.payload_type,
+                value: payload.value,
+            }),
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmUnleashClient(UnleashClient);
+
+impl From<UnleashClient> for WasmUnleashClient {
+    fn from(value: UnleashClient) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmUnleashClient {
+    /// Returns a new set of feature flags, to be fetched with `refresh` or
+    /// `start`
+    #[wasm_bindgen(js_name = "featureFlags")]
+    pub fn feature_flags(&self) -> WasmFeatureFlags {
+        WasmFeatureFlags::new(self.0.clone())
+    }
+}
+
+/// Feature flags of the user, kept up to date by polling the backend.
+///
+/// Accessors read the last fetched flags, every flag being disabled until
+/// the first fetch.
+#[wasm_bindgen]
+pub struct WasmFeatureFlags {
+    client: UnleashClient,
+    toggles: RefCell<HashMap<String, UnleashToggle>>,
+    running: Cell<bool>,
+    on_change: Option<js_sys::Function>,
+    on_error: Option<js_sys::Function>,
+}
+
+impl WasmFeatureFlags {
+    fn new(client: UnleashClient) -> Self {
+        Self {
+            client,
+            toggles: RefCell::new(HashMap::new()),
+            running: Cell::new(false),
+            on_change: None,
+            on_error: None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl WasmFeatureFlags {
+    #[wasm_bindgen(js_name = "isEnabled")]
+    pub fn is_enabled(&self, name: String) -> bool {
+        self.toggles.borrow().get(&name).is_some_and(|toggle| toggle.enabled)
+    }
+
+    /// Returns the variant of the flag, if it is enabled and has one
+    #[wasm_bindgen(js_name = "getVariant")]
+    pub fn get_variant(&self, name: String) -> Option<WasmUnleashVariant> {
+        self.toggles
+            .borrow()
+            .get(&name)
+            .filter(|toggle| toggle.enabled)
+            .and_then(|toggle| toggle.variant.clone())
+            .map(|variant| variant.into())
+    }
+
+    /// Returns the names of the enabled flags
+    #[wasm_bindgen(js_name = "getEnabledFlags")]
+    pub fn get_enabled_flags(&self) -> Vec<String> {
+        self.toggles
+            .borrow()
+            .values()
+            .filter(|toggle| toggle.enabled)
+            .map(|toggle| toggle.name.clone())
+            .collect()
+    }
+
+    /// Called with the names of the flags that changed, each time a fetch
+    /// changes them
+    #[wasm_bindgen(js_name = "onChange")]
+    pub fn on_change(&mut self, callback: js_sys::Function) {
+        self.on_change = Some(callback);
+    }
+
+    /// Called with the fetching errors while polling. Without it, polling
+    /// stops on the first error
+    #[wasm_bindgen(js_name = "onError")]
+    pub fn on_error(&mut self, callback: js_sys::Function) {
+        self.on_error = Some(callback);
+    }
+
+    /// Fetches the flags once and returns the names of the ones that changed
+    #[wasm_bindgen]
+    pub async fn refresh(&self) -> Result<Vec<String>, JsValue> {
+        let toggles = self
+            .client
+            .get_toggles()
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|toggle| (toggle.name.clone(), toggle))
+            .collect::<HashMap<_, _>>();
+
+        let changed = {
+            let previous = self.toggles.borrow();
+            let mut changed = toggles
+                .keys()
+                .chain(previous.keys())
+                .filter(|name| toggles.get(*name) != previous.get(*name))
+                .cloned()
+                .collect::<Vec<_>>();
+            changed.sort();
+            changed.dedup();
+            changed
+        };
+
+        *self.toggles.borrow_mut() = toggles;
+
+        if let Some(on_change) = &self.on_change {
+            if !changed.is_empty() {
+                let names = changed.iter().map(JsValue::from).collect::<js_sys::Array>();
+                on_change.call1(&JsValue::NULL, &names)?;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Fetches the flags every `interval_ms` (10 minutes by default) until
+    /// `stop` is called
+    #[wasm_bindgen]
+    pub async fn start(&self, interval_ms: Option<u32>) -> Result<(), JsValue> {
+        self.running.set(true);
+
+        while self.running.get() {
+            if let Err(error) = self.refresh().await {
+                match &self.on_error {
+                    Some(on_error) => {
+                        on_error.call1(&JsValue::NULL, &error)?;
+                    }
+                    None => {
+                        self.running.set(false);
+                        return Err(error);
+                    }
+                }
+            }
+
+            sleep(interval_ms.unwrap_or(DEFAULT_FEATURE_FLAGS_INTERVAL_MS)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops polling after the current iteration
+    #[wasm_bindgen]
+    pub fn stop(&self) {
+        self.running.set(false);
+    }
+}
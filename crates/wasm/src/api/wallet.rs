@@ -1,11 +1,13 @@
 use andromeda_api::{
     wallet::{
-        ApiEmailAddress, ApiWalletAccount, ApiWalletData, ApiWalletTransaction, CreateWalletAccountRequestBody,
-        CreateWalletRequestBody, CreateWalletTransactionRequestBody, MigratedWallet, MigratedWalletAccount,
-        MigratedWalletTransaction, TransactionType, WalletClient, WalletMigrateRequestBody, WalletTransactionFlag,
+        ApiEmailAddress, ApiWallet, ApiWalletAccount, ApiWalletData, ApiWalletKey, ApiWalletSettings,
+        ApiWalletTransaction, CreateWalletAccountRequestBody, CreateWalletRequestBody,
+        CreateWalletTransactionRequestBody, MigratedWallet, MigratedWalletAccount, MigratedWalletTransaction,
+        TransactionType, WalletClient, WalletMigrateRequestBody, WalletTransactionFlag,
     },
     wallet_ext::WalletClientExt,
 };
+use andromeda_macros::Mirror;
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -14,6 +16,7 @@ use super::{exchange_rate::WasmApiExchangeRate, settings::WasmFiatCurrencySymbol
 use crate::{
     bitcoin::types::derivation_path::WasmDerivationPath,
     common::{
+        bigint,
         error::ErrorExt,
         types::{FromBool, WasmScriptType},
     },
@@ -29,8 +32,9 @@ impl From<WalletClient> for WasmWalletClient {
     }
 }
 
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiWallet")]
 #[allow(non_snake_case)]
 pub struct WasmApiWallet {
     pub ID: String,
@@ -47,8 +51,9 @@ pub struct WasmApiWallet {
     pub Legacy: Option<u8>,
 }
 
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiWalletKey")]
 #[allow(non_snake_case)]
 pub struct WasmApiWalletKey {
     pub WalletID: String,
@@ -57,20 +62,24 @@ pub struct WasmApiWalletKey {
     pub WalletKeySignature: String,
 }
 
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiWalletSettings")]
 #[allow(non_snake_case)]
 pub struct WasmApiWalletSettings {
     pub WalletID: String,
     pub HideAccounts: u8,
     pub InvoiceDefaultDescription: Option<String>,
     pub InvoiceExpirationTime: u64,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub MaxChannelOpeningFee: u64,
     pub ShowWalletRecovery: Option<bool>,
 }
 
 #[wasm_bindgen(getter_with_clone)]
-#[derive(Clone)]
+#[derive(Clone, Mirror)]
+#[mirror(from = "ApiWalletData")]
 #[allow(non_snake_case)]
 pub struct WasmApiWalletData {
     pub Wallet: WasmApiWallet,
@@ -78,41 +87,6 @@ pub struct WasmApiWalletData {
     pub WalletSettings: WasmApiWalletSettings,
 }
 
-impl From<ApiWalletData> for WasmApiWalletData {
-    fn from(value: ApiWalletData) -> Self {
-        WasmApiWalletData {
-            Wallet: WasmApiWallet {
-                ID: value.Wallet.ID,
-                Name: value.Wallet.Name,
-                IsImported: value.Wallet.IsImported,
-                Priority: value.Wallet.Priority,
-                Type: value.Wallet.Type,
-                HasPassphrase: value.Wallet.HasPassphrase,
-                Status: value.Wallet.Status,
-                Mnemonic: value.Wallet.Mnemonic,
-                PublicKey: value.Wallet.PublicKey,
-                Fingerprint: value.Wallet.Fingerprint,
-                MigrationRequired: value.Wallet.MigrationRequired,
-                Legacy: value.Wallet.Legacy,
-            },
-            WalletKey: WasmApiWalletKey {
-                WalletID: value.WalletKey.WalletID,
-                UserKeyID: value.WalletKey.UserKeyID,
-                WalletKey: value.WalletKey.WalletKey,
-                WalletKeySignature: value.WalletKey.WalletKeySignature,
-            },
-            WalletSettings: WasmApiWalletSettings {
-                WalletID: value.WalletSettings.WalletID,
-                HideAccounts: value.WalletSettings.HideAccounts,
-                InvoiceDefaultDescription: value.WalletSettings.InvoiceDefaultDescription,
-                InvoiceExpirationTime: value.WalletSettings.InvoiceExpirationTime,
-                MaxChannelOpeningFee: value.WalletSettings.MaxChannelOpeningFee,
-                ShowWalletRecovery: value.WalletSettings.ShowWalletRecovery,
-            },
-        }
-    }
-}
-
 #[wasm_bindgen]
 impl WasmApiWalletData {
     #[wasm_bindgen]
@@ -125,25 +99,18 @@ impl WasmApiWalletData {
     }
 }
 
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiEmailAddress")]
 #[allow(non_snake_case)]
 pub struct WasmApiEmailAddress {
     pub ID: String,
     pub Email: String,
 }
 
-impl From<ApiEmailAddress> for WasmApiEmailAddress {
-    fn from(value: ApiEmailAddress) -> Self {
-        WasmApiEmailAddress {
-            ID: value.ID,
-            Email: value.Email,
-        }
-    }
-}
-
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiWalletAccount")]
 #[allow(non_snake_case)]
 pub struct WasmApiWalletAccount {
     pub WalletID: String,
@@ -155,7 +122,11 @@ pub struct WasmApiWalletAccount {
     pub PoolSize: u32,
     pub Priority: u32,
     pub ScriptType: u8,
+    #[mirror(vec)]
     pub Addresses: Vec<WasmApiEmailAddress>,
+    /// 1 if the account has been hidden (archived) by the user
+    pub Hidden: Option<u8>,
+    pub StopGap: Option<u32>,
 }
 
 // We need this wrapper because unfortunately, tsify doesn't support
@@ -174,23 +145,6 @@ pub struct WasmWalletAccountAddressData {
     pub Data: WasmApiEmailAddress,
 }
 
-impl From<ApiWalletAccount> for WasmApiWalletAccount {
-    fn from(value: ApiWalletAccount) -> Self {
-        WasmApiWalletAccount {
-            WalletID: value.WalletID,
-            FiatCurrency: value.FiatCurrency.into(),
-            ID: value.ID,
-            Label: value.Label,
-            LastUsedIndex: value.LastUsedIndex,
-            PoolSize: value.PoolSize,
-            Priority: value.Priority,
-            DerivationPath: value.DerivationPath,
-            ScriptType: value.ScriptType,
-            Addresses: value.Addresses.into_iter().map(|a| a.into()).collect::<Vec<_>>(),
-        }
-    }
-}
-
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 pub enum WasmTransactionType {
@@ -215,11 +169,13 @@ impl From<TransactionType> for WasmTransactionType {
     }
 }
 
-#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[derive(Tsify, Serialize, Deserialize, Clone, Mirror)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
+#[mirror(from = "ApiWalletTransaction")]
 #[allow(non_snake_case)]
 pub struct WasmApiWalletTransaction {
     pub ID: String,
+    #[mirror(option)]
     pub Type: Option<WasmTransactionType>,
     pub WalletID: String,
     pub WalletAccountID: Option<String>,
@@ -229,6 +185,7 @@ pub struct WasmApiWalletTransaction {
     pub IsSuspicious: u8,
     pub IsPrivate: u8,
     pub IsAnonymous: Option<u8>,
+    #[mirror(option)]
     pub ExchangeRate: Option<WasmApiExchangeRate>,
     pub HashedTransactionID: Option<String>,
     pub Subject: Option<String>,
@@ -237,29 +194,6 @@ pub struct WasmApiWalletTransaction {
     pub Sender: Option<String>,
 }
 
-impl From<ApiWalletTransaction> for WasmApiWalletTransaction {
-    fn from(value: ApiWalletTransaction) -> Self {
-        WasmApiWalletTransaction {
-            ID: value.ID,
-            Type: value.Type.map(|t| t.into()),
-            WalletID: value.WalletID,
-            WalletAccountID: value.WalletAccountID,
-            Label: value.Label,
-            TransactionID: value.TransactionID,
-            TransactionTime: value.TransactionTime,
-            IsSuspicious: value.IsSuspicious,
-            IsPrivate: value.IsPrivate,
-            IsAnonymous: value.IsAnonymous,
-            ExchangeRate: value.ExchangeRate.map(|r| r.into()),
-            HashedTransactionID: value.HashedTransactionID,
-            Subject: value.Subject,
-            Body: value.Body,
-            ToList: value.ToList,
-            Sender: value.Sender,
-        }
-    }
-}
-
 #[wasm_bindgen]
 pub enum WasmWalletTransactionFlag {
     Suspicious,
@@ -466,19 +400,20 @@ impl WasmWalletClient {
         public_key: Option<String>,
         is_auto_created: Option<bool>,
     ) -> Result<WasmApiWalletData, JsValue> {
-        let payload = CreateWalletRequestBody {
-            Name: name,
-            IsImported: u8::from_bool(is_imported),
-            Type: wallet_type,
-            HasPassphrase: u8::from_bool(has_passphrase),
-            UserKeyID: user_key_id,
-            WalletKey: wallet_key,
-            WalletKeySignature: wallet_key_signature,
-            Mnemonic: mnemonic,
-            Fingerprint: fingerprint,
-            PublicKey: public_key,
-            IsAutoCreated: is_auto_created.map(u8::from_bool).unwrap_or(0),
-        };
+        let payload = CreateWalletRequestBody::builder()
+            .name(name)
+            .is_imported(u8::from_bool(is_imported))
+            .r#type(wallet_type)
+            .has_passphrase(u8::from_bool(has_passphrase))
+            .user_key_id(user_key_id)
+            .wallet_key(wallet_key)
+            .wallet_key_signature(wallet_key_signature)
+            .mnemonic(mnemonic)
+            .fingerprint(fingerprint)
+            .public_key(public_key)
+            .is_auto_created(is_auto_created.map(u8::from_bool).unwrap_or(0))
+            .build()
+            .map_err(|e| e.to_js_error())?;
 
         self.0
             .create_wallet(payload)
@@ -1,12 +1,15 @@
 use std::sync::Arc;
 
-use andromeda_bitcoin::account::Account;
+use andromeda_bitcoin::{account::Account, error::Error as BitcoinError};
 use wasm_bindgen::prelude::*;
 
 use super::{
     blockchain_client::WasmBlockchainClient,
     psbt::WasmPsbt,
     storage::{WalletWebConnector, WalletWebPersister, WalletWebPersisterFactory},
+    transaction_iterator::{
+        WasmTransactionDetailsAsyncIterator, WasmTransactionIterator, DEFAULT_TRANSACTIONS_PAGE_SIZE,
+    },
     types::{
         address::{WasmAddress, WasmAddressDetailsArray, WasmAddressDetailsData},
         address_info::WasmAddressInfo,
@@ -19,6 +22,7 @@ use super::{
     wallet::WasmWallet,
 };
 use crate::common::{
+    abort::abortable,
     error::ErrorExt,
     types::{WasmKeychainKind, WasmNetwork, WasmScriptType},
 };
@@ -154,17 +158,21 @@ impl WasmAccount {
         client: &WasmBlockchainClient,
         keychain: WasmKeychainKind,
         force_sync: Option<bool>,
+        signal: Option<web_sys::AbortSignal>,
     ) -> Result<WasmAddressDetailsArray, js_sys::Error> {
-        let address_details = self
-            .inner
-            .get_addresses(
-                pagination.into(),
-                client.into(),
-                keychain.into(),
-                force_sync.unwrap_or(false),
-            )
-            .await
-            .map_err(|e| e.to_js_error())?
+        let addresses = async {
+            self.inner
+                .get_addresses(
+                    pagination.into(),
+                    client.into(),
+                    keychain.into(),
+                    force_sync.unwrap_or(false),
+                )
+                .await
+                .map_err(|e| e.to_js_error())
+        };
+        let address_details = abortable(addresses, signal)
+            .await?
             .into_iter()
             .map(|address| WasmAddressDetailsData { Data: address.into() })
             .collect::<Vec<_>>();
@@ -190,6 +198,22 @@ impl WasmAccount {
         Ok(WasmTransactionDetailsArray(transactions))
     }
 
+    /// Returns an async iterator over the account's transactions, fetched
+    /// `page_size` at a time
+    #[wasm_bindgen(js_name = getTransactionsIterator)]
+    pub fn get_transactions_iterator(
+        &self,
+        page_size: Option<usize>,
+        sort: Option<WasmSortOrder>,
+    ) -> WasmTransactionDetailsAsyncIterator {
+        WasmTransactionIterator::new(
+            self.inner.clone(),
+            page_size.unwrap_or(DEFAULT_TRANSACTIONS_PAGE_SIZE),
+            sort.map(|s| s.into()),
+        )
+        .into_async_iterator()
+    }
+
     #[wasm_bindgen(js_name = getTransaction)]
     pub async fn get_transaction(&self, txid: String) -> Result<WasmTransactionDetailsData, js_sys::Error> {
         let transaction = self.inner.get_transaction(txid).await.map_err(|e| e.to_js_error())?;
@@ -234,6 +258,77 @@ impl WasmAccount {
         Ok(wasm_psbt)
     }
 
+    /// Returns a PSBT replacing the unconfirmed transaction with one sending
+    /// its inputs back to the account, at `new_fee_rate` in sat/vB
+    #[wasm_bindgen(js_name = cancelTransaction)]
+    pub async fn cancel_transaction(
+        &self,
+        network: WasmNetwork,
+        txid: String,
+        new_fee_rate: u64,
+    ) -> Result<WasmPsbt, js_sys::Error> {
+        let psbt = self
+            .inner
+            .cancel_transaction(txid, new_fee_rate)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        let wasm_psbt = WasmPsbt::from_psbt(&psbt, network.into())?;
+
+        Ok(wasm_psbt)
+    }
+
+    /// Returns a PSBT spending the account's outputs of an unconfirmed
+    /// incoming transaction, so that both confirm at `target_fee_rate` in
+    /// sat/vB
+    #[wasm_bindgen(js_name = createCpfp)]
+    pub async fn create_cpfp(
+        &self,
+        network: WasmNetwork,
+        parent_txid: String,
+        target_fee_rate: u64,
+    ) -> Result<WasmPsbt, js_sys::Error> {
+        let psbt = self
+            .inner
+            .create_cpfp(parent_txid, target_fee_rate)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        let wasm_psbt = WasmPsbt::from_psbt(&psbt, network.into())?;
+
+        Ok(wasm_psbt)
+    }
+
+    /// Returns the JSON serialized handle of a full sync, to be run in a
+    /// worker with `WasmBlockchainClient.fullSyncDetached`
+    #[wasm_bindgen(js_name = getFullSyncHandle)]
+    pub async fn get_full_sync_handle(&self, stop_gap: Option<usize>) -> Result<String, js_sys::Error> {
+        let handle = self.inner.get_full_sync_handle(stop_gap).await;
+
+        Ok(serde_json::to_string(&handle).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?)
+    }
+
+    /// Returns the JSON serialized handle of a partial sync, to be run in a
+    /// worker with `WasmBlockchainClient.partialSyncDetached`
+    #[wasm_bindgen(js_name = getPartialSyncHandle)]
+    pub async fn get_partial_sync_handle(&self) -> Result<String, js_sys::Error> {
+        let handle = self.inner.get_partial_sync_handle().await;
+
+        Ok(serde_json::to_string(&handle).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?)
+    }
+
+    /// Applies the JSON serialized update returned by a detached sync
+    #[wasm_bindgen(js_name = applySyncUpdate)]
+    pub async fn apply_sync_update(&self, update: String) -> Result<(), js_sys::Error> {
+        let update = serde_json::from_str(&update).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?;
+        self.inner
+            .apply_sync_update(update)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(())
+    }
+
     #[wasm_bindgen(js_name = clearStore)]
     pub async fn clear_store(&self) -> Result<(), js_sys::Error> {
         self.inner.clear_store().map_err(|e| e.to_js_error())?;
@@ -0,0 +1,87 @@
+use andromeda_bitcoin::{account_sweeper::AccountSweeper, FeeRate};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    account::WasmAccount,
+    blockchain_client::WasmBlockchainClient,
+    paper_account::{WasmPaperAccount, WasmPaperAccountArray},
+    psbt::WasmPsbt,
+    storage::{WalletWebConnector, WalletWebPersister},
+};
+use crate::common::{error::ErrorExt, types::WasmNetwork};
+
+#[wasm_bindgen]
+pub struct WasmAccountSweeper {
+    inner: AccountSweeper<WalletWebConnector, WalletWebPersister>,
+}
+
+#[wasm_bindgen]
+impl WasmAccountSweeper {
+    #[wasm_bindgen(constructor)]
+    pub fn new(client: &WasmBlockchainClient, account: &WasmAccount) -> WasmAccountSweeper {
+        WasmAccountSweeper {
+            inner: AccountSweeper::new(client.into(), account.get_inner()),
+        }
+    }
+
+    /// Returns the paper accounts holding funds for the given private key, one
+    /// per script type. The key is WIF encoded, or BIP38 encrypted when
+    /// `bip38_passphrase` is provided
+    #[wasm_bindgen(js_name = getFundedPaperAccounts)]
+    pub async fn get_funded_paper_accounts(
+        &self,
+        private_key: String,
+        network: WasmNetwork,
+        bip38_passphrase: Option<String>,
+    ) -> Result<WasmPaperAccountArray, js_sys::Error> {
+        let paper_accounts = self
+            .inner
+            .get_funded_paper_accounts(&private_key, bip38_passphrase.as_deref(), network.into())
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(WasmPaperAccount::from)
+            .collect::<Vec<_>>();
+
+        Ok(WasmPaperAccountArray(paper_accounts))
+    }
+
+    /// Returns the balance, in sats, held by the private key across all
+    /// script types
+    #[wasm_bindgen(js_name = getSweepBalance)]
+    pub async fn get_sweep_balance(
+        &self,
+        private_key: String,
+        network: WasmNetwork,
+        bip38_passphrase: Option<String>,
+    ) -> Result<u64, js_sys::Error> {
+        let balance = self
+            .inner
+            .get_sweep_balance(&private_key, bip38_passphrase.as_deref(), network.into())
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(balance.to_sat())
+    }
+
+    /// Builds a signed PSBT sweeping the paper account to the next receive
+    /// address of the account
+    #[wasm_bindgen(js_name = getSweepPsbt)]
+    pub async fn get_sweep_psbt(
+        &self,
+        paper_account: &WasmPaperAccount,
+        sat_per_vb: u64,
+        network: WasmNetwork,
+    ) -> Result<WasmPsbt, JsValue> {
+        let psbt = self
+            .inner
+            .get_sweep_psbt(
+                paper_account.get_inner(),
+                FeeRate::from_sat_per_vb_unchecked(sat_per_vb),
+            )
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        WasmPsbt::from_psbt(&psbt, network.into())
+    }
+}
@@ -1,9 +1,16 @@
 use std::{collections::HashMap, sync::Arc};
 
 use super::{account::WasmAccount, psbt::WasmPsbt};
-use crate::{api::WasmProtonWalletApiClient, common::error::ErrorExt};
+use crate::{
+    api::WasmProtonWalletApiClient,
+    common::{abort::abortable, error::ErrorExt},
+};
 use andromeda_api::transaction::{BroadcastMessage, ExchangeRateOrTransactionTime, RecommendedFees};
-use andromeda_bitcoin::blockchain_client::{self, BlockchainClient, MinimumFees};
+use andromeda_bitcoin::{
+    blockchain_client::{self, BlockchainClient, MinimumFees, ScanProgress, ScanStats},
+    error::Error as BitcoinError,
+};
+use futures::{channel::mpsc, future, StreamExt};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -53,11 +60,11 @@ pub struct WasmRecommendedFees {
 impl From<RecommendedFees> for WasmRecommendedFees {
     fn from(value: RecommendedFees) -> Self {
         WasmRecommendedFees {
-            FastestFee: value.FastestFee,
-            HalfHourFee: value.HalfHourFee,
-            HourFee: value.HourFee,
-            EconomyFee: value.EconomyFee,
-            MinimumFee: value.MinimumFee,
+            FastestFee: value.fastest_fee.to_sat_per_vb_ceil() as u8,
+            HalfHourFee: value.half_hour_fee.to_sat_per_vb_ceil() as u8,
+            HourFee: value.hour_fee.to_sat_per_vb_ceil() as u8,
+            EconomyFee: value.economy_fee.to_sat_per_vb_ceil() as u8,
+            MinimumFee: value.minimum_fee.to_sat_per_vb_ceil() as u8,
         }
     }
 }
@@ -102,6 +109,65 @@ pub struct WasmTransactionData {
     exchange_rate_or_transaction_time: WasmExchangeRateOrTransactionTime,
 }
 
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmScanProgress {
+    pub scanned: usize,
+    pub remaining: usize,
+}
+
+impl From<ScanProgress> for WasmScanProgress {
+    fn from(value: ScanProgress) -> Self {
+        WasmScanProgress {
+            scanned: value.scanned,
+            remaining: value.remaining,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct WasmScanStats {
+    /// Number of addresses checked against the chain
+    pub scanned: usize,
+    /// Number of transactions found
+    pub transactions: usize,
+    pub last_active_external_index: Option<u32>,
+    pub last_active_internal_index: Option<u32>,
+    /// Time spent scanning, in milliseconds
+    pub duration_ms: f64,
+}
+
+impl WasmScanStats {
+    fn new(stats: ScanStats, duration_ms: f64) -> Self {
+        WasmScanStats {
+            scanned: stats.scanned,
+            transactions: stats.transactions,
+            last_active_external_index: stats.last_active_external_index,
+            last_active_internal_index: stats.last_active_internal_index,
+            duration_ms,
+        }
+    }
+}
+
+/// Calls the JS progress callback with each progress sent on the channel,
+/// until the sync drops the sender. Returns the number of items scanned
+async fn forward_progress(
+    receiver: mpsc::UnboundedReceiver<ScanProgress>,
+    on_progress: Option<js_sys::Function>,
+) -> usize {
+    receiver
+        .fold(0, |_, progress| {
+            if let Some(on_progress) = &on_progress {
+                let value = serde_wasm_bindgen::to_value(&WasmScanProgress::from(progress)).unwrap();
+                // Errors thrown by the callback must not abort the sync
+                let _ = on_progress.call1(&JsValue::NULL, &value);
+            }
+            future::ready(progress.scanned)
+        })
+        .await
+}
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 pub struct WasmBroadcastMessage {
@@ -159,35 +225,102 @@ impl WasmBlockchainClient {
         Ok(WasmRecommendedFees::from(recommended_fees))
     }
 
+    /// Runs a full sync of the account and returns stats about the scan.
+    /// `stop_gap` defaults to `getDefaultStopGap()`, it should be set to the
+    /// account's `StopGap` when there is one. `on_progress`, when provided,
+    /// is called with a `WasmScanProgress` each time an address is scanned.
+    /// Aborting `signal` stops the scan, leaving the account untouched
     #[wasm_bindgen(js_name = fullSync)]
-    pub async fn full_sync(&self, account: &WasmAccount, stop_gap: Option<usize>) -> Result<(), JsValue> {
+    pub async fn full_sync(
+        &self,
+        account: &WasmAccount,
+        stop_gap: Option<usize>,
+        on_progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
+    ) -> Result<WasmScanStats, JsValue> {
         let account_inner = account.get_inner();
+        let started_at = js_sys::Date::now();
+
+        let (sender, receiver) = mpsc::unbounded();
+        let scan = async {
+            let (update, scanned) = future::join(
+                self.inner
+                    .full_sync_with_progress(&account_inner, stop_gap, move |progress| {
+                        let _ = sender.unbounded_send(progress);
+                    }),
+                forward_progress(receiver, on_progress),
+            )
+            .await;
+            update.map(|update| (update, scanned)).map_err(|e| e.to_js_error())
+        };
+        let (update, scanned) = abortable(scan, signal).await?;
 
-        let update = self
-            .inner
-            .full_sync(&account_inner, stop_gap)
-            .await
-            .map_err(|e| e.to_js_error())?;
-
+        let stats = ScanStats::new(&update, scanned);
         account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
 
-        Ok(())
+        Ok(WasmScanStats::new(stats, js_sys::Date::now() - started_at))
     }
 
+    /// Runs a partial sync of the account. `on_progress`, when provided, is
+    /// called with a `WasmScanProgress` each time an address, transaction or
+    /// outpoint is checked. Aborting `signal` stops the sync, leaving the
+    /// account untouched
     #[wasm_bindgen(js_name = partialSync)]
-    pub async fn partial_sync(&self, account: &WasmAccount) -> Result<(), JsValue> {
+    pub async fn partial_sync(
+        &self,
+        account: &WasmAccount,
+        on_progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
+    ) -> Result<(), JsValue> {
         let account_inner = account.get_inner();
 
         let wallet_lock = account_inner.get_wallet().await;
+        let (sender, receiver) = mpsc::unbounded();
+        let sync = async {
+            let (update, _) = future::join(
+                self.inner.partial_sync_with_progress(wallet_lock, move |progress| {
+                    let _ = sender.unbounded_send(progress);
+                }),
+                forward_progress(receiver, on_progress),
+            )
+            .await;
+            update.map_err(|e| e.to_js_error())
+        };
+        let update = abortable(sync, signal).await?;
+
+        account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+
+        Ok(())
+    }
+
+    /// Runs a full sync from the JSON serialized handle returned by
+    /// `WasmAccount.getFullSyncHandle`, without access to the account. Meant
+    /// to be called from a Web Worker, the returned JSON serialized update
+    /// is then applied with `WasmAccount.applySyncUpdate`
+    #[wasm_bindgen(js_name = fullSyncDetached)]
+    pub async fn full_sync_detached(&self, handle: String) -> Result<String, JsValue> {
+        let handle = serde_json::from_str(&handle).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?;
         let update = self
             .inner
-            .partial_sync(wallet_lock)
+            .full_sync_detached(handle)
             .await
             .map_err(|e| e.to_js_error())?;
 
-        account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+        serde_json::to_string(&update).map_err(|e| BitcoinError::Other(e.into()).to_js_error())
+    }
 
-        Ok(())
+    /// Runs a partial sync from the JSON serialized handle returned by
+    /// `WasmAccount.getPartialSyncHandle`, see `fullSyncDetached`
+    #[wasm_bindgen(js_name = partialSyncDetached)]
+    pub async fn partial_sync_detached(&self, handle: String) -> Result<String, JsValue> {
+        let handle = serde_json::from_str(&handle).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?;
+        let update = self
+            .inner
+            .partial_sync_detached(handle)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        serde_json::to_string(&update).map_err(|e| BitcoinError::Other(e.into()).to_js_error())
     }
 
     #[wasm_bindgen(js_name = shouldSync)]
@@ -0,0 +1,67 @@
+use andromeda_bitcoin::message_signer::{MessageSigner, SigningType};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    account::WasmAccount,
+    storage::{WalletWebConnector, WalletWebPersister},
+};
+use crate::common::error::ErrorExt;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum WasmSigningType {
+    Electrum,
+    Bip137,
+}
+
+impl From<WasmSigningType> for SigningType {
+    fn from(value: WasmSigningType) -> Self {
+        match value {
+            WasmSigningType::Electrum => SigningType::Electrum,
+            WasmSigningType::Bip137 => SigningType::Bip137,
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub struct WasmMessageSigner {
+    inner: MessageSigner<WalletWebConnector, WalletWebPersister>,
+}
+
+#[wasm_bindgen]
+impl WasmMessageSigner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(account: &WasmAccount) -> WasmMessageSigner {
+        WasmMessageSigner {
+            inner: MessageSigner::new(account.get_inner()),
+        }
+    }
+
+    /// Signs the message with the key of the given account address and
+    /// returns the base64 encoded signature
+    #[wasm_bindgen(js_name = signMessage)]
+    pub async fn sign_message(
+        &self,
+        message: String,
+        signing_type: WasmSigningType,
+        btc_address: String,
+    ) -> Result<String, JsValue> {
+        self.inner
+            .sign_message(&message, signing_type.into(), &btc_address)
+            .await
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = verifyMessage)]
+    pub async fn verify_message(
+        &self,
+        message: String,
+        signature: String,
+        btc_address: String,
+    ) -> Result<bool, JsValue> {
+        self.inner
+            .verify_message(&message, &signature, &btc_address)
+            .await
+            .map_err(|e| e.to_js_error())
+    }
+}
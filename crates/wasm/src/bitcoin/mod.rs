@@ -1,9 +1,15 @@
 pub mod account;
+#[cfg(feature = "sweeper")]
+pub mod account_sweeper;
 pub mod blockchain_client;
+pub mod message_signer;
 pub mod mnemonic;
+#[cfg(feature = "sweeper")]
+pub mod paper_account;
 pub mod payment_link;
 pub mod psbt;
 pub mod storage;
 pub mod transaction_builder;
+pub mod transaction_iterator;
 pub mod types;
 pub mod wallet;
@@ -0,0 +1,89 @@
+use andromeda_bitcoin::{bip38, paper_account::PaperAccount};
+use wasm_bindgen::prelude::*;
+
+use super::{blockchain_client::WasmBlockchainClient, types::balance::WasmBalanceWrapper};
+use crate::common::{
+    error::ErrorExt,
+    types::{WasmNetwork, WasmScriptType},
+};
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct WasmPaperAccount {
+    inner: PaperAccount,
+}
+
+impl WasmPaperAccount {
+    pub fn get_inner(&self) -> &PaperAccount {
+        &self.inner
+    }
+}
+
+impl From<PaperAccount> for WasmPaperAccount {
+    fn from(value: PaperAccount) -> Self {
+        WasmPaperAccount { inner: value }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmPaperAccountArray(pub Vec<WasmPaperAccount>);
+
+/// Returns whether the private key is BIP38 encrypted, i.e. needs a
+/// passphrase to be imported
+#[wasm_bindgen(js_name = isBip38Encrypted)]
+pub fn is_bip38_encrypted(private_key: String) -> bool {
+    bip38::is_bip38_encrypted(&private_key)
+}
+
+#[wasm_bindgen]
+impl WasmPaperAccount {
+    /// Imports a WIF encoded private key with the given script type
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        private_key: String,
+        script_type: WasmScriptType,
+        network: WasmNetwork,
+    ) -> Result<WasmPaperAccount, js_sys::Error> {
+        let inner = PaperAccount::new(&private_key, script_type.into(), network.into()).map_err(|e| e.to_js_error())?;
+
+        Ok(inner.into())
+    }
+
+    /// Imports a BIP38 encrypted private key with the given script type
+    #[wasm_bindgen(js_name = fromBip38)]
+    pub fn from_bip38(
+        encrypted_key: String,
+        passphrase: String,
+        script_type: WasmScriptType,
+        network: WasmNetwork,
+    ) -> Result<WasmPaperAccount, js_sys::Error> {
+        let inner = PaperAccount::new_bip38(&encrypted_key, &passphrase, script_type.into(), network.into())
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(inner.into())
+    }
+
+    #[wasm_bindgen(js_name = getScriptType)]
+    pub fn get_script_type(&self) -> WasmScriptType {
+        self.inner.get_script_type().into()
+    }
+
+    #[wasm_bindgen(js_name = getAddress)]
+    pub async fn get_address(&self) -> String {
+        self.inner.get_address().await.to_string()
+    }
+
+    #[wasm_bindgen(js_name = getBalance)]
+    pub async fn get_balance(&self) -> WasmBalanceWrapper {
+        WasmBalanceWrapper {
+            data: self.inner.get_balance().await.into(),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub async fn sync(&self, client: &WasmBlockchainClient) -> Result<(), JsValue> {
+        let client: std::sync::Arc<_> = client.into();
+
+        self.inner.sync(&client).await.map_err(|e| e.to_js_error())
+    }
+}
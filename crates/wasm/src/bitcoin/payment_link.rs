@@ -1,4 +1,6 @@
 use andromeda_bitcoin::payment_link::PaymentLink;
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use crate::common::{error::ErrorExt, types::WasmNetwork};
@@ -25,6 +27,60 @@ pub struct WasmOnchainPaymentLink {
     pub label: Option<String>,
 }
 
+/// Parsed payment link, discriminated on `kind`
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind")]
+pub enum WasmParsedPaymentLink {
+    BitcoinAddress {
+        address: String,
+    },
+    BitcoinURI {
+        address: String,
+        amount: Option<u64>,
+        label: Option<String>,
+        message: Option<String>,
+    },
+    LightningURI {
+        uri: String,
+    },
+    UnifiedURI {
+        uri: String,
+    },
+}
+
+impl From<PaymentLink> for WasmParsedPaymentLink {
+    fn from(value: PaymentLink) -> Self {
+        match value {
+            PaymentLink::BitcoinAddress(address) => WasmParsedPaymentLink::BitcoinAddress {
+                address: address.to_string(),
+            },
+            PaymentLink::BitcoinURI {
+                address,
+                amount,
+                label,
+                message,
+            } => WasmParsedPaymentLink::BitcoinURI {
+                address: address.to_string(),
+                amount,
+                label,
+                message,
+            },
+            PaymentLink::LightningURI { uri } => WasmParsedPaymentLink::LightningURI { uri },
+            PaymentLink::UnifiedURI { uri } => WasmParsedPaymentLink::UnifiedURI { uri },
+        }
+    }
+}
+
+/// Detects the kind of a pasted payment string (address, BIP21 URI, lightning
+/// URI or invoice) and returns its parsed content
+#[wasm_bindgen(js_name = parsePaymentLink)]
+pub fn parse_payment_link(str: String, network: WasmNetwork) -> Result<WasmParsedPaymentLink, js_sys::Error> {
+    let payment_link = PaymentLink::try_parse(str, network.into()).map_err(|e| e.to_js_error())?;
+
+    Ok(payment_link.into())
+}
+
 impl Into<WasmPaymentLink> for PaymentLink {
     fn into(self) -> WasmPaymentLink {
         WasmPaymentLink { inner: self }
@@ -56,6 +112,11 @@ impl WasmPaymentLink {
         Ok(WasmPaymentLink { inner })
     }
 
+    #[wasm_bindgen(js_name = toParsed)]
+    pub fn to_parsed(&self) -> WasmParsedPaymentLink {
+        self.inner.clone().into()
+    }
+
     #[wasm_bindgen(js_name = getKind)]
     pub fn get_kind(&self) -> WasmPaymentLinkKind {
         match self.inner {
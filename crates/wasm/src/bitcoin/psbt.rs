@@ -1,9 +1,15 @@
-use andromeda_bitcoin::{error::Error as BitcoinError, psbt::Psbt, Address, ConsensusParams, SignOptions};
+use andromeda_bitcoin::{
+    error::Error as BitcoinError,
+    psbt::{Psbt, PsbtDetails, PsbtInputDetails, PsbtOutputDetails},
+    Address, ConsensusParams, SignOptions,
+};
 use andromeda_common::Network;
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use super::account::WasmAccount;
-use crate::common::{error::ErrorExt, types::WasmNetwork};
+use crate::common::{bigint, error::ErrorExt, types::WasmNetwork};
 
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone)]
@@ -18,6 +24,83 @@ pub struct WasmPsbt {
     pub total_fees: u64,
 }
 
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmPsbtInput {
+    pub txid: String,
+    pub vout: u32,
+    #[serde(with = "bigint::option")]
+    #[tsify(type = "bigint | null")]
+    pub value: Option<u64>,
+    pub address: Option<String>,
+    pub is_mine: bool,
+}
+
+impl From<PsbtInputDetails> for WasmPsbtInput {
+    fn from(value: PsbtInputDetails) -> Self {
+        WasmPsbtInput {
+            txid: value.previous_output.txid.to_string(),
+            vout: value.previous_output.vout,
+            value: value.value.map(|value| value.to_sat()),
+            address: value.address.map(|address| address.to_string()),
+            is_mine: value.is_mine,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmPsbtOutput {
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
+    pub value: u64,
+    pub address: Option<String>,
+    pub is_mine: bool,
+    pub is_change: bool,
+}
+
+impl From<PsbtOutputDetails> for WasmPsbtOutput {
+    fn from(value: PsbtOutputDetails) -> Self {
+        WasmPsbtOutput {
+            value: value.value.to_sat(),
+            address: value.address.map(|address| address.to_string()),
+            is_mine: value.is_mine,
+            is_change: value.is_change,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmPsbtDetails {
+    pub inputs: Vec<WasmPsbtInput>,
+    pub outputs: Vec<WasmPsbtOutput>,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
+    pub total_fees: u64,
+    /// Fee rate in sat/vb
+    pub fee_rate: f64,
+}
+
+impl From<PsbtDetails> for WasmPsbtDetails {
+    fn from(value: PsbtDetails) -> Self {
+        WasmPsbtDetails {
+            inputs: value.inputs.into_iter().map(|input| input.into()).collect(),
+            outputs: value.outputs.into_iter().map(|output| output.into()).collect(),
+            total_fees: value.fee.to_sat(),
+            fee_rate: value.fee_rate.to_sat_per_kwu() as f64 * 4.0 / 1000.0,
+        }
+    }
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmPsbtDetailsData {
+    pub Data: WasmPsbtDetails,
+}
+
 impl WasmPsbt {
     pub fn get_inner(&self) -> Psbt {
         self.inner.clone()
@@ -66,6 +149,45 @@ impl WasmPsbt {
         WasmPsbt::from_psbt(&mutable_psbt.into(), network.into())
     }
 
+    /// Returns the inputs and outputs of the PSBT, flagged with their
+    /// ownership by the account, along with its fees
+    #[wasm_bindgen]
+    pub async fn inspect(&self, wasm_account: &WasmAccount) -> Result<WasmPsbtDetailsData, JsValue> {
+        let account = wasm_account.get_inner();
+        let wallet_lock = account.get_wallet().await;
+
+        self.inner
+            .inspect(&wallet_lock)
+            .map(|details| WasmPsbtDetailsData { Data: details.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
+    /// Parses a base64 encoded PSBT, e.g. exported from a hardware wallet
+    /// software
+    #[wasm_bindgen(js_name = fromBase64)]
+    pub fn from_base64(base64: String, network: WasmNetwork) -> Result<WasmPsbt, JsValue> {
+        let psbt = Psbt::from_base64(&base64).map_err(|e| e.to_js_error())?;
+
+        WasmPsbt::from_psbt(&psbt, network.into())
+    }
+
+    #[wasm_bindgen(js_name = toBase64)]
+    pub fn to_base64(&self) -> String {
+        self.inner.to_base64()
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: Vec<u8>, network: WasmNetwork) -> Result<WasmPsbt, JsValue> {
+        let psbt = Psbt::from_bytes(&bytes).map_err(|e| e.to_js_error())?;
+
+        WasmPsbt::from_psbt(&psbt, network.into())
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
     #[wasm_bindgen(js_name = computeTxVbytes)]
     pub fn compute_tx_vbytes(&self) -> Result<u64, JsValue> {
         self.inner.compute_tx_vbytes().map_err(|e| e.to_js_error())
@@ -1,5 +1,6 @@
 use andromeda_bitcoin::{
-    transaction_builder::{CoinSelection, TmpRecipient, TxBuilder},
+    error::Error as BitcoinError,
+    transaction_builder::{BatchOutput, CoinSelection, TmpRecipient, TxBuilder, TxBuilderDraft},
     ChangeSpendPolicy, OutPoint,
 };
 use wasm_bindgen::prelude::*;
@@ -8,7 +9,11 @@ use super::{
     account::WasmAccount,
     psbt::WasmPsbt,
     storage::{WalletWebConnector, WalletWebPersister},
-    types::{locktime::WasmLockTime, transaction::WasmOutPoint},
+    types::{
+        locktime::WasmLockTime,
+        transaction::WasmOutPoint,
+        utxo::{WasmSelectableUtxo, WasmSelectableUtxoArray},
+    },
 };
 use crate::common::{error::ErrorExt, types::WasmNetwork};
 
@@ -18,6 +23,33 @@ pub struct WasmTxBuilder {
     inner: TxBuilder<WalletWebConnector, WalletWebPersister>,
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmBatchOutput {
+    pub vout: u32,
+    pub address: String,
+    pub amount: u64,
+    pub label: Option<String>,
+}
+
+impl From<BatchOutput> for WasmBatchOutput {
+    fn from(value: BatchOutput) -> Self {
+        WasmBatchOutput {
+            vout: value.vout,
+            address: value.address,
+            amount: value.amount.to_sat(),
+            label: value.label,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmBatchPsbt {
+    pub psbt: WasmPsbt,
+    pub outputs: Vec<WasmBatchOutput>,
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Copy)]
 pub enum WasmCoinSelection {
@@ -25,6 +57,8 @@ pub enum WasmCoinSelection {
     LargestFirst,
     OldestFirst,
     Manual,
+    /// Appended after `Manual` to keep the values already used from JS
+    PrivacyPreserving,
 }
 
 impl Into<CoinSelection> for WasmCoinSelection {
@@ -34,6 +68,7 @@ impl Into<CoinSelection> for WasmCoinSelection {
             WasmCoinSelection::LargestFirst => CoinSelection::LargestFirst,
             WasmCoinSelection::OldestFirst => CoinSelection::OldestFirst,
             WasmCoinSelection::Manual => CoinSelection::Manual,
+            WasmCoinSelection::PrivacyPreserving => CoinSelection::PrivacyPreserving,
         }
     }
 }
@@ -45,6 +80,7 @@ impl Into<WasmCoinSelection> for CoinSelection {
             CoinSelection::LargestFirst => WasmCoinSelection::LargestFirst,
             CoinSelection::OldestFirst => WasmCoinSelection::OldestFirst,
             CoinSelection::Manual => WasmCoinSelection::Manual,
+            CoinSelection::PrivacyPreserving => WasmCoinSelection::PrivacyPreserving,
         }
     }
 }
@@ -116,6 +152,14 @@ impl WasmTxBuilder {
         WasmTxBuilder { inner }
     }
 
+    /// Adds a recipient whose label is returned along with its output by
+    /// `createBatchPsbt`
+    #[wasm_bindgen(js_name = addLabelledRecipient)]
+    pub fn add_labelled_recipient(&self, address_str: String, amount: u64, label: Option<String>) -> WasmTxBuilder {
+        let inner = self.inner.add_labelled_recipients(vec![(address_str, amount, label)]);
+        WasmTxBuilder { inner }
+    }
+
     #[wasm_bindgen(js_name = removeRecipient)]
     pub fn remove_recipient(&self, index: usize) -> WasmTxBuilder {
         let inner = self.inner.remove_recipient(index);
@@ -196,6 +240,35 @@ impl WasmTxBuilder {
             .collect()
     }
 
+    #[wasm_bindgen(js_name = setManuallySelectedOnly)]
+    pub fn set_manually_selected_only(&self, manually_selected_only: bool) -> WasmTxBuilder {
+        let inner = self.inner.set_manually_selected_only(manually_selected_only);
+        WasmTxBuilder { inner }
+    }
+
+    #[wasm_bindgen(js_name = getManuallySelectedOnly)]
+    pub fn get_manually_selected_only(&self) -> bool {
+        self.inner.manually_selected_only
+    }
+
+    /// Lists the account's UTXOs, flagged when selected to be spent
+    #[wasm_bindgen(js_name = getUtxosWithSelection)]
+    pub async fn get_utxos_with_selection(&self) -> Result<WasmSelectableUtxoArray, JsValue> {
+        let utxos = self
+            .inner
+            .get_utxos_with_selection()
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|(utxo, selected)| WasmSelectableUtxo {
+                utxo: utxo.into(),
+                selected,
+            })
+            .collect();
+
+        Ok(WasmSelectableUtxoArray(utxos))
+    }
+
     /**
      * Coin selection enforcement
      */
@@ -287,6 +360,31 @@ impl WasmTxBuilder {
         self.inner.locktime.map(|l| l.into())
     }
 
+    /**
+     * Draft
+     */
+
+    /// Returns the recipients, selected coins and fee options as a JSON blob,
+    /// to be persisted by the app and restored with `applyDraft`
+    #[wasm_bindgen(js_name = toDraft)]
+    pub fn to_draft(&self) -> Result<String, js_sys::Error> {
+        let draft =
+            serde_json::to_string(&self.inner.to_draft()).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?;
+
+        Ok(draft)
+    }
+
+    /// Restores a draft returned by `toDraft`. The account isn't part of the
+    /// draft, it is kept from the current builder
+    #[wasm_bindgen(js_name = applyDraft)]
+    pub fn apply_draft(&self, draft: String) -> Result<WasmTxBuilder, js_sys::Error> {
+        let draft: TxBuilderDraft =
+            serde_json::from_str(&draft).map_err(|e| BitcoinError::Other(e.into()).to_js_error())?;
+        let inner = self.inner.apply_draft(draft);
+
+        Ok(WasmTxBuilder { inner })
+    }
+
     /**
      * Final
      */
@@ -302,6 +400,16 @@ impl WasmTxBuilder {
         WasmPsbt::from_psbt(&psbt, network.into())
     }
 
+    #[wasm_bindgen(js_name = createBatchPsbt)]
+    pub async fn create_batch_psbt(&self, network: WasmNetwork) -> Result<WasmBatchPsbt, JsValue> {
+        let batch = self.inner.create_batch_psbt(false).await.map_err(|e| e.to_js_error())?;
+
+        Ok(WasmBatchPsbt {
+            psbt: WasmPsbt::from_psbt(&batch.psbt, network.into())?,
+            outputs: batch.outputs.into_iter().map(|output| output.into()).collect(),
+        })
+    }
+
     #[wasm_bindgen(js_name = createDraftPsbt)]
     pub async fn create_draft_psbt(&self, network: WasmNetwork, allow_dust: Option<bool>) -> Result<WasmPsbt, JsValue> {
         let psbt = self
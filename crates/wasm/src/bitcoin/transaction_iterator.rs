@@ -0,0 +1,104 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use andromeda_bitcoin::{account::Account, transactions::Pagination, utils::SortOrder};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    storage::{WalletWebConnector, WalletWebPersister},
+    types::transaction::WasmTransactionDetailsData,
+};
+use crate::common::error::ErrorExt;
+
+pub const DEFAULT_TRANSACTIONS_PAGE_SIZE: usize = 50;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "AsyncIterableIterator<WasmTransactionDetailsData>")]
+    pub type WasmTransactionDetailsAsyncIterator;
+}
+
+/// Iterates over an account's transactions, fetching them page by page so
+/// that only one page at a time is marshalled to JS.
+///
+/// Implements the async iterator protocol, so it can be consumed with
+/// `for await (const tx of account.getTransactionsIterator())`.
+#[wasm_bindgen]
+pub struct WasmTransactionIterator {
+    account: Arc<Account<WalletWebConnector, WalletWebPersister>>,
+    sort: Option<SortOrder>,
+    page_size: usize,
+    skip: usize,
+    page: VecDeque<WasmTransactionDetailsData>,
+    exhausted: bool,
+}
+
+impl WasmTransactionIterator {
+    pub fn new(
+        account: Arc<Account<WalletWebConnector, WalletWebPersister>>,
+        page_size: usize,
+        sort: Option<SortOrder>,
+    ) -> Self {
+        WasmTransactionIterator {
+            account,
+            sort,
+            page_size: page_size.max(1),
+            skip: 0,
+            page: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+
+    /// Wraps the iterator in a JS value which also is an async iterable
+    pub fn into_async_iterator(self) -> WasmTransactionDetailsAsyncIterator {
+        let iterator = JsValue::from(self);
+        // `for await` gets the iterator from `[Symbol.asyncIterator]()`, which
+        // wasm-bindgen classes can't define
+        let _ = js_sys::Reflect::set(
+            &iterator,
+            &js_sys::Symbol::async_iterator(),
+            &js_sys::Function::new_no_args("return this"),
+        );
+
+        iterator.unchecked_into()
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<(), js_sys::Error> {
+        let transactions = self
+            .account
+            .get_transactions(Pagination::new(self.skip, self.page_size), self.sort)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        self.skip += transactions.len();
+        self.exhausted = transactions.len() < self.page_size;
+        self.page.extend(
+            transactions
+                .into_iter()
+                .map(|tx| WasmTransactionDetailsData { Data: tx.into() }),
+        );
+
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl WasmTransactionIterator {
+    /// Returns the next transaction as an `IteratorResult`
+    #[wasm_bindgen]
+    pub async fn next(&mut self) -> Result<js_sys::Object, js_sys::Error> {
+        if self.page.is_empty() && !self.exhausted {
+            self.fetch_next_page().await?;
+        }
+
+        let result = js_sys::Object::new();
+        let (done, value) = match self.page.pop_front() {
+            Some(transaction) => (false, JsValue::from(transaction)),
+            None => (true, JsValue::UNDEFINED),
+        };
+        // Setting properties of a plain object can't fail
+        let _ = js_sys::Reflect::set(&result, &"done".into(), &done.into());
+        let _ = js_sys::Reflect::set(&result, &"value".into(), &value);
+
+        Ok(result)
+    }
+}
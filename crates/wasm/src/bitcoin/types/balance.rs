@@ -3,17 +3,27 @@ use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
+use crate::common::bigint;
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[allow(non_snake_case)]
 pub struct WasmBalance {
     /// All coinbase outputs not yet matured
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub immature: u64,
     /// Unconfirmed UTXOs generated by a wallet tx
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub trusted_pending: u64,
     /// Unconfirmed UTXOs received from an external wallet
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub untrusted_pending: u64,
     /// Confirmed and immediately spendable balance
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub confirmed: u64,
 }
 
@@ -33,3 +43,39 @@ impl Into<WasmBalance> for Balance {
 pub struct WasmBalanceWrapper {
     pub data: WasmBalance,
 }
+
+// Getters read the fields without converting the whole balance to a JS
+// object, as `data` does on each access
+#[wasm_bindgen]
+impl WasmBalanceWrapper {
+    #[wasm_bindgen(getter)]
+    pub fn immature(&self) -> u64 {
+        self.data.immature
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn trusted_pending(&self) -> u64 {
+        self.data.trusted_pending
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn untrusted_pending(&self) -> u64 {
+        self.data.untrusted_pending
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn confirmed(&self) -> u64 {
+        self.data.confirmed
+    }
+
+    /// Confirmed and trusted pending balance
+    #[wasm_bindgen(getter)]
+    pub fn trusted_spendable(&self) -> u64 {
+        self.data.confirmed + self.data.trusted_pending
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> u64 {
+        self.data.immature + self.data.trusted_pending + self.data.untrusted_pending + self.data.confirmed
+    }
+}
@@ -1,4 +1,7 @@
-use andromeda_bitcoin::{transactions::Pagination, utils::SortOrder};
+use andromeda_bitcoin::{
+    transactions::{Pagination, TransactionFilter},
+    utils::SortOrder,
+};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -30,3 +33,22 @@ impl Into<SortOrder> for WasmSortOrder {
         }
     }
 }
+
+#[wasm_bindgen]
+pub enum WasmTransactionFilter {
+    All,
+    Received,
+    Sent,
+    Unconfirmed,
+}
+
+impl Into<TransactionFilter> for WasmTransactionFilter {
+    fn into(self) -> TransactionFilter {
+        match self {
+            WasmTransactionFilter::All => TransactionFilter::All,
+            WasmTransactionFilter::Received => TransactionFilter::Received,
+            WasmTransactionFilter::Sent => TransactionFilter::Sent,
+            WasmTransactionFilter::Unconfirmed => TransactionFilter::Unconfirmed,
+        }
+    }
+}
@@ -14,7 +14,7 @@ use super::{
     super::{account::WasmAccount, psbt::WasmPsbt},
     address::WasmAddress,
 };
-use crate::common::{error::ErrorExt, types::WasmNetwork};
+use crate::common::{bigint, error::ErrorExt, types::WasmNetwork};
 
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone, Serialize, Deserialize)]
@@ -130,6 +130,8 @@ impl Into<WasmDetailledTxIn> for DetailledTxIn {
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Tsify, Clone, Serialize, Deserialize)]
 pub struct WasmTxOut {
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub value: u64,
     pub script_pubkey: WasmScript,
     pub is_mine: bool,
@@ -152,8 +154,14 @@ impl Into<WasmTxOut> for DetailledTxOutput {
 #[allow(non_snake_case)]
 pub struct WasmTransactionDetails {
     pub txid: String,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub received: u64,
+    #[serde(with = "bigint")]
+    #[tsify(type = "bigint")]
     pub sent: u64,
+    #[serde(with = "bigint::option")]
+    #[tsify(type = "bigint | null")]
     pub fee: Option<u64>,
     pub size: u64,
     pub time: WasmTransactionTime,
@@ -171,9 +179,73 @@ pub struct WasmTransactionDetailsData {
     pub Data: WasmTransactionDetails,
 }
 
+// Getters read the fields without converting the whole transaction, with its
+// inputs and outputs, to a JS object as `Data` does on each access
+#[wasm_bindgen]
+impl WasmTransactionDetailsData {
+    #[wasm_bindgen(getter)]
+    pub fn txid(&self) -> String {
+        self.Data.txid.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn received(&self) -> u64 {
+        self.Data.received
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn sent(&self) -> u64 {
+        self.Data.sent
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fee(&self) -> Option<u64> {
+        self.Data.fee
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> u64 {
+        self.Data.size
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn confirmed(&self) -> bool {
+        self.Data.time.confirmed
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn confirmation_time(&self) -> Option<u64> {
+        self.Data.time.confirmation_time
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn last_seen(&self) -> Option<u64> {
+        self.Data.time.last_seen
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn account_derivation_path(&self) -> String {
+        self.Data.account_derivation_path.clone()
+    }
+}
+
 #[wasm_bindgen(getter_with_clone)]
 pub struct WasmTransactionDetailsArray(pub Vec<WasmTransactionDetailsData>);
 
+#[wasm_bindgen]
+impl WasmTransactionDetailsArray {
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the transaction at the given index, without copying the whole
+    /// list to JS
+    pub fn get(&self, index: usize) -> Option<WasmTransactionDetailsData> {
+        self.0.get(index).cloned()
+    }
+}
+
 impl Into<WasmTransactionDetails> for TransactionDetails {
     fn into(self) -> WasmTransactionDetails {
         WasmTransactionDetails {
@@ -209,7 +281,11 @@ pub async fn create_transaction_from_psbt(
 #[allow(non_snake_case)]
 pub struct WasmTransactionTime {
     pub confirmed: bool,
+    #[serde(with = "bigint::option")]
+    #[tsify(type = "bigint | null")]
     pub confirmation_time: Option<u64>,
+    #[serde(with = "bigint::option")]
+    #[tsify(type = "bigint | null")]
     pub last_seen: Option<u64>,
 }
 
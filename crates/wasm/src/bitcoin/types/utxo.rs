@@ -29,3 +29,29 @@ impl Into<WasmUtxo> for LocalOutput {
 
 #[wasm_bindgen(getter_with_clone)]
 pub struct WasmUtxoArray(pub Vec<WasmUtxo>);
+
+#[wasm_bindgen]
+impl WasmUtxoArray {
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the UTXO at the given index, without copying the whole list
+    /// to JS
+    pub fn get(&self, index: usize) -> Option<WasmUtxo> {
+        self.0.get(index).cloned()
+    }
+}
+
+/// Account UTXO along with whether it is selected to be spent in a
+/// transaction builder
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Serialize)]
+pub struct WasmSelectableUtxo {
+    pub utxo: WasmUtxo,
+    pub selected: bool,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmSelectableUtxoArray(pub Vec<WasmSelectableUtxo>);
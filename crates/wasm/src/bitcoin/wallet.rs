@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
-use andromeda_bitcoin::{error::Error as BitcoinError, wallet::Wallet, DerivationPath};
+use andromeda_bitcoin::{
+    error::Error as BitcoinError, transactions::TransactionFilter, wallet::Wallet, DerivationPath,
+};
 use andromeda_common::error::Error;
 use wasm_bindgen::prelude::*;
 
@@ -10,7 +12,7 @@ use super::{
     types::{
         balance::WasmBalanceWrapper,
         derivation_path::WasmDerivationPath,
-        pagination::{WasmPagination, WasmSortOrder},
+        pagination::{WasmPagination, WasmSortOrder, WasmTransactionFilter},
         transaction::{WasmTransactionDetailsArray, WasmTransactionDetailsData},
     },
 };
@@ -29,7 +31,7 @@ pub struct WasmWallet {
 
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(typescript_type = "[u8, String]")]
+    #[wasm_bindgen(typescript_type = "[number, string]")]
     pub type AccountConfigTupple;
 }
 
@@ -128,10 +130,15 @@ impl WasmWallet {
         &self,
         pagination: Option<WasmPagination>,
         sort: Option<WasmSortOrder>,
+        filter: Option<WasmTransactionFilter>,
     ) -> Result<WasmTransactionDetailsArray, js_sys::Error> {
         let transactions = self
             .inner
-            .get_transactions(pagination.map(|pa| pa.into()), sort.map(|s| s.into()))
+            .get_aggregated_transactions(
+                pagination.map(|pa| pa.into()),
+                sort.map(|s| s.into()),
+                filter.map_or(TransactionFilter::All, |f| f.into()),
+            )
             .await
             .map_err(|e| e.to_js_error())?
             .into_iter()
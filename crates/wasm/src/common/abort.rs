@@ -0,0 +1,47 @@
+use std::{future::Future, pin::pin};
+
+use futures::{
+    channel::oneshot,
+    future::{self, Either},
+};
+use wasm_bindgen::prelude::*;
+use web_sys::AbortSignal;
+
+use super::error::WasmError;
+
+fn aborted_error() -> JsValue {
+    WasmError::new("Aborted", "The operation was aborted").into()
+}
+
+/// Runs the future until it completes or the signal is aborted.
+///
+/// On abort, the future is dropped, which cancels the requests it has in
+/// flight, and an `Aborted` error is returned.
+pub async fn abortable<T>(
+    future: impl Future<Output = Result<T, JsValue>>,
+    signal: Option<AbortSignal>,
+) -> Result<T, JsValue> {
+    let Some(signal) = signal else {
+        return future.await;
+    };
+
+    if signal.aborted() {
+        return Err(aborted_error());
+    }
+
+    let (sender, receiver) = oneshot::channel::<()>();
+    let on_abort = Closure::once(move || {
+        let _ = sender.send(());
+    });
+    signal.add_event_listener_with_callback("abort", on_abort.as_ref().unchecked_ref())?;
+
+    let result = match future::select(pin!(future), receiver).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(aborted_error()),
+    };
+
+    // The closure is dropped on return, so it must not be called anymore
+    let _ = signal.remove_event_listener_with_callback("abort", on_abort.as_ref().unchecked_ref());
+
+    result
+}
@@ -0,0 +1,122 @@
+//! Serde helpers exposing 64-bit integers (sats amounts, timestamps) to JS as
+//! `bigint`, which, unlike `number`, holds them without losing precision.
+//!
+//! ```rust, ignore
+//! #[derive(Tsify, Serialize, Deserialize)]
+//! pub struct WasmUtxo {
+//!     #[serde(with = "bigint")]
+//!     #[tsify(type = "bigint")]
+//!     pub value: u64,
+//! }
+//! ```
+//!
+//! # Notes
+//!
+//! serde-wasm-bindgen converts 128-bit integers to `BigInt`, values are
+//! serialized as such. Deserialization accepts either a `bigint` or a safe
+//! integer `number`, so that JS callers passing a literal keep working.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<i128>,
+    S: Serializer,
+{
+    serializer.serialize_i128((*value).into())
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer)
+}
+
+/// Same as the parent module, for optional values
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Copy + Into<i128>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => {
+                let value: i128 = (*value).into();
+                serializer.serialize_some(&value)
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use js_sys::{BigInt, Map, Object, Reflect};
+    use tsify::Tsify;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::bitcoin::{blockchain_client::WasmBroadcastMessage, types::transaction::WasmTransactionTime};
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        Reflect::get(value, &key.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_expose_values_above_max_safe_integer_as_bigint() {
+        let time = WasmTransactionTime {
+            confirmed: true,
+            confirmation_time: Some(u64::MAX),
+            last_seen: None,
+        };
+
+        let value: JsValue = time.into_js().unwrap().into();
+        let confirmation_time = get(&value, "confirmation_time");
+
+        assert!(confirmation_time.is_bigint());
+        assert_eq!(confirmation_time, BigInt::from(u64::MAX).into());
+        assert!(get(&value, "last_seen").is_null());
+
+        let time = WasmTransactionTime::from_js(value).unwrap();
+        assert_eq!(time.confirmation_time, Some(u64::MAX));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_accept_safe_integer_numbers() {
+        let value = Object::new();
+        Reflect::set(&value, &"confirmed".into(), &false.into()).unwrap();
+        Reflect::set(&value, &"confirmation_time".into(), &JsValue::NULL).unwrap();
+        Reflect::set(&value, &"last_seen".into(), &1_700_000_000.into()).unwrap();
+
+        let time = WasmTransactionTime::from_js(value).unwrap();
+        assert_eq!(time.last_seen, Some(1_700_000_000));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_serialize_maps_as_plain_objects() {
+        let message = WasmBroadcastMessage {
+            data_packet: "data".to_string(),
+            key_packets: HashMap::from([("alice@proton.me".to_string(), "key".to_string())]),
+        };
+
+        let value: JsValue = message.into_js().unwrap().into();
+        let key_packets = get(&value, "key_packets");
+
+        assert!(!key_packets.is_instance_of::<Map>());
+        assert_eq!(get(&key_packets, "alice@proton.me"), JsValue::from("key"));
+    }
+}
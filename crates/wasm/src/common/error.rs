@@ -1,109 +1,258 @@
 use std::error::Error;
 
-use andromeda_api::error::Error as ApiError;
+use andromeda_api::{device_verification::DeviceVerificationChallenge, error::Error as ApiError};
 use andromeda_bitcoin::error::{CreateTxError, Error as BitcoinError, InsufficientFundsError};
 use andromeda_common::error::Error as CommonError;
 use andromeda_esplora::error::Error as EsploraError;
 use serde::Serialize;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use serde_wasm_bindgen::Serializer;
+use tsify::Tsify;
 use wasm_bindgen::JsValue;
 
 pub trait ErrorExt {
     fn to_js_error(self) -> JsValue;
 }
 
-pub fn json_to_jsvalue(json: Value) -> JsValue {
-    // Serialize the Rust struct into a JsValue
-    let serializer = Serializer::new().serialize_maps_as_objects(true);
-    json.serialize(&serializer).unwrap()
+/// Error thrown to JS.
+///
+/// `kind` is the name of the error variant (e.g. `HTTPError`,
+/// `InsufficientFunds`), `code` the API error code when the error comes from
+/// the API, and `message` a human readable description, not meant to be
+/// displayed as is. Some kinds carry additional fields, flattened in the same
+/// object.
+///
+/// The `WasmError` TypeScript type is generated from this struct, so that JS
+/// callers can narrow caught errors on `kind`.
+#[derive(Debug, Serialize, Tsify)]
+pub struct WasmError {
+    pub kind: String,
+    #[tsify(optional)]
+    pub code: Option<u16>,
+    pub message: String,
+    #[serde(flatten)]
+    #[tsify(type = "Record<string, unknown>")]
+    pub fields: Map<String, Value>,
+}
+
+impl WasmError {
+    pub fn new(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        WasmError {
+            kind: kind.into(),
+            code: None,
+            message: message.into(),
+            fields: Map::new(),
+        }
+    }
+
+    pub fn with_code(mut self, code: u16) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Adds the fields of the given JSON object to the error
+    pub fn with_fields(mut self, fields: Value) -> Self {
+        if let Value::Object(fields) = fields {
+            self.fields.extend(fields);
+        }
+        self
+    }
+}
+
+impl From<WasmError> for JsValue {
+    fn from(value: WasmError) -> Self {
+        // Serialize the Rust struct into a JsValue
+        let serializer = Serializer::new().serialize_maps_as_objects(true);
+        value.serialize(&serializer).unwrap()
+    }
 }
 
 impl ErrorExt for ApiError {
     fn to_js_error(self) -> JsValue {
-        match self {
-            ApiError::AuthSession(kind) => JsValue::from(&format!(
-                "AuthSession: A muon {kind} error was caused by a non-existent auth session",
-            )),
-            ApiError::AuthRefresh(kind) => JsValue::from(&format!(
-                "AuthRefresh: A muon {kind} error was caused by a failed auth refresh"
-            )),
-            ApiError::ForkAuthSession => JsValue::from(
-                &"ForkAuthSession: A muon error was caused by a failed auth via forked session".to_string(),
+        let error_kind = self.kind();
+        let category = self.category();
+        let message = self.to_string();
+
+        let error = match self {
+            ApiError::AuthSession(_) => WasmError::new("AuthSession", message),
+            ApiError::AuthRefresh(_) => WasmError::new("AuthRefresh", message),
+            ApiError::ForkAuthSession => WasmError::new("ForkAuthSession", message),
+            ApiError::ForkSession => WasmError::new("ForkSession", message),
+            ApiError::LoginError => WasmError::new("LoginError", message),
+            ApiError::UnsupportedTwoFactor => WasmError::new("UnsupportedTwoFactor", message),
+            ApiError::MuonError(me) => WasmError::new(
+                "MuonError",
+                format!("{message} (caused by: {source:?})", source = me.source()),
             ),
-            ApiError::ForkSession => {
-                JsValue::from(&"ForkSession: A muon error was caused by a failed fork session".to_string())
-            }
-            ApiError::LoginError => JsValue::from(&"LoginError: A muon error was caused by a failed login".to_string()),
-            ApiError::UnsupportedTwoFactor => {
-                JsValue::from(&"UnsupportedTwoFactor: A muon error was caused by unsupported TwoFactor".to_string())
-            }
-            ApiError::MuonError(me) => JsValue::from(&format!(
-                "MuonError: {me} (caused by: {source:?})",
-                source = me.source(),
-            )),
-            ApiError::BitcoinDeserialize(bde) => {
-                JsValue::from(&format!("BitcoinDeserializeError occurred: {:?}", bde.source()))
-            }
-            ApiError::HexToArrayDecoding(hde) => {
-                JsValue::from(&format!("HexToArrayDecoding error occurred: {:?}", hde.source()))
-            }
-            ApiError::HexToBytesErrorDecoding(hde) => {
-                JsValue::from(&format!("HexToBytesErrorDecoding error occurred: {:?}", hde.source()))
+            ApiError::BitcoinDeserialize(_) => WasmError::new("BitcoinDeserialize", message),
+            ApiError::HexToArrayDecoding(_) => WasmError::new("HexToArrayDecoding", message),
+            ApiError::HexToBytesErrorDecoding(_) => WasmError::new("HexToBytesErrorDecoding", message),
+            ApiError::Http => WasmError::new("Http", message),
+            ApiError::ErrorCode(status, error) => WasmError::new("HTTPError", error.Error.clone())
+                .with_code(error.Code)
+                .with_fields(json!({
+                    "status": status.as_u16(),
+                    "error": error.Error,
+                    "details": error.Details,
+                })),
+            ApiError::Deserialize(err) => WasmError::new("Deserialize", err),
+            ApiError::MuonAppVersion(_) => WasmError::new("MuonAppVersion", message),
+            ApiError::MuonStatus(_) => WasmError::new("MuonStatus", message),
+            ApiError::Utf8Error(_) => WasmError::new("Utf8Error", message),
+            ApiError::Serialize(_) => WasmError::new("Serialize", message),
+            ApiError::Compression(_) => WasmError::new("Compression", message),
+            ApiError::Unavailable(endpoint, retry_after) => WasmError::new("Unavailable", message).with_fields(json!({
+                "endpoint": endpoint,
+                "retryAfterMs": retry_after.as_millis() as u64,
+            })),
+            ApiError::RateLimited(retry_after) => WasmError::new("RateLimited", message).with_fields(json!({
+                "retryAfterMs": retry_after.map(|retry_after| retry_after.as_millis() as u64),
+            })),
+            ApiError::DeviceVerificationRequired(challenge) => WasmError::new("DeviceVerificationRequired", message)
+                .with_fields(match challenge {
+                    DeviceVerificationChallenge::ProofOfWork {
+                        token,
+                        seed,
+                        difficulty,
+                    } => json!({
+                        "type": "ProofOfWork",
+                        "token": token,
+                        "seed": seed,
+                        "difficulty": difficulty,
+                    }),
+                    DeviceVerificationChallenge::Code { token, destination } => json!({
+                        "type": "Code",
+                        "token": token,
+                        "destination": destination,
+                    }),
+                }),
+            ApiError::DeviceVerificationCodeMissing => WasmError::new("DeviceVerificationCodeMissing", message),
+            ApiError::DeviceVerificationUnsolvable(difficulty) => {
+                WasmError::new("DeviceVerificationUnsolvable", message).with_fields(json!({
+                    "difficulty": difficulty,
+                }))
             }
-            ApiError::Http => JsValue::from("HTTP error occurred"),
-            ApiError::ErrorCode(status, error) => json_to_jsvalue(json!({
-                "kind": "HTTPError",
-                "status": status.as_u16(),
-                "code": error.Code,
-                "error": error.Error,
-                "details": error.Details
+            ApiError::Cancelled(_) => WasmError::new("Cancelled", message),
+            ApiError::MissingField(field) => WasmError::new("MissingField", message).with_fields(json!({
+                "field": field,
             })),
-            ApiError::Deserialize(err) => JsValue::from(&err),
-            ApiError::MuonAppVersion(err) => JsValue::from(&format!("MuonAppVersion occurred: {:?}", err.source())),
-            ApiError::MuonStatus(err) => JsValue::from(&format!("MuonStatusError occurred: {:?}", err.source())),
-            ApiError::Utf8Error(err) => JsValue::from(&format!("Utf8Error occurred: {:?}", err.source())),
-        }
+            ApiError::InvalidRequestBody(_) => WasmError::new("InvalidRequestBody", message),
+        };
+
+        error
+            .with_fields(json!({
+                "errorKind": error_kind,
+                "localizationKey": error_kind.localization_key(),
+                "category": category.as_str(),
+            }))
+            .into()
+    }
+}
+
+/// Name of the bitcoin error variant, used as the JS error kind
+fn bitcoin_error_kind(error: &BitcoinError) -> &'static str {
+    match error {
+        BitcoinError::AccountNotFound => "AccountNotFound",
+        BitcoinError::CreateWithPersistError => "CreateWithPersistError",
+        BitcoinError::LoadWithPersistError => "LoadWithPersistError",
+        BitcoinError::PersistError => "PersistError",
+        BitcoinError::MiniscriptPsbt(_) => "MiniscriptPsbt",
+        BitcoinError::CreateTx(_) => "CreateTx",
+        BitcoinError::BuildFeeBump(_) => "BuildFeeBump",
+        BitcoinError::AddUtxo(_) => "AddUtxo",
+        BitcoinError::Signer(_) => "Signer",
+        BitcoinError::CannotConnect(_) => "CannotConnect",
+        BitcoinError::Bip32(_) => "Bip32",
+        BitcoinError::Bip39(_) => "Bip39",
+        BitcoinError::EsploraClient(_) => "EsploraClient",
+        BitcoinError::Api(_) => "Api",
+        BitcoinError::Electrum(_) => "Electrum",
+        BitcoinError::BitcoindRpc(_) => "BitcoindRpc",
+        BitcoinError::UnsupportedByBackend(_) => "UnsupportedByBackend",
+        BitcoinError::HexToArray(_) => "HexToArray",
+        BitcoinError::HexToBytes(_) => "HexToBytes",
+        BitcoinError::BitcoinAddressParse(_) => "BitcoinAddressParse",
+        BitcoinError::FromScript(_) => "FromScript",
+        BitcoinError::Descriptor(_) => "Descriptor",
+        BitcoinError::ExtractTx(_) => "ExtractTx",
+        BitcoinError::Psbt(_) => "Psbt",
+        BitcoinError::PsbtParse(_) => "PsbtParse",
+        BitcoinError::PrivateKey(_) => "PrivateKey",
+        BitcoinError::InvalidBip38Key => "InvalidBip38Key",
+        BitcoinError::InvalidBip38Passphrase => "InvalidBip38Passphrase",
+        BitcoinError::MessageSignature(_) => "MessageSignature",
+        BitcoinError::InvalidAddress(_) => "InvalidAddress",
+        BitcoinError::InvalidData(_) => "InvalidData",
+        BitcoinError::TransactionNotFound => "TransactionNotFound",
+        BitcoinError::UtxoNotFound(_) => "UtxoNotFound",
+        BitcoinError::Cancelled => "Cancelled",
+        BitcoinError::UnsupportedScriptType(_) => "UnsupportedScriptType",
+        BitcoinError::Other(_) => "Other",
     }
 }
 
 impl ErrorExt for BitcoinError {
     fn to_js_error(self) -> JsValue {
-        let common_error = JsValue::from(
-            &format!("Wasm error occured in Bitcoin: {}", self),
-            // json!(self), TODO: fix this to have more detailled errors
-        );
+        let message = format!("Wasm error occured in Bitcoin: {}", self);
+        let category = self.category();
+        let common_error = WasmError::new(bitcoin_error_kind(&self), message.clone());
 
-        match self {
+        let error = match self {
             BitcoinError::CreateTx(error) => match error {
-                CreateTxError::CoinSelection(InsufficientFundsError { needed, available }) => json_to_jsvalue(json!({
-                    "kind": "InsufficientFunds",
-                    "needed": needed,
-                    "available": available,
-                })),
-                CreateTxError::OutputBelowDustLimit(limit) => json_to_jsvalue(json!({
-                    "kind": "OutputBelowDustLimit",
-                    "limit": limit,
-                })),
+                CreateTxError::CoinSelection(InsufficientFundsError { needed, available }) => {
+                    WasmError::new("InsufficientFunds", message).with_fields(json!({
+                        "needed": needed,
+                        "available": available,
+                    }))
+                }
+                CreateTxError::OutputBelowDustLimit(limit) => WasmError::new("OutputBelowDustLimit", message)
+                    .with_fields(json!({
+                        "limit": limit,
+                    })),
                 _ => common_error,
             },
-            BitcoinError::EsploraClient(EsploraError::ApiError(error)) => error.to_js_error(),
+            BitcoinError::EsploraClient(EsploraError::ApiError(error)) => return error.to_js_error(),
             _ => common_error,
-        }
+        };
+
+        error
+            .with_fields(json!({
+                "category": category.as_str(),
+            }))
+            .into()
     }
 }
 
 impl ErrorExt for CommonError {
     fn to_js_error(self) -> JsValue {
-        match self {
-            CommonError::InvalidNetwork(network) => json_to_jsvalue(json!({
-                "kind": "InvalidNetwork",
+        let message = self.to_string();
+        let category = self.category();
+
+        let error = match self {
+            CommonError::InvalidNetwork(network) => WasmError::new("InvalidNetwork", message).with_fields(json!({
                 "network": network,
             })),
-            CommonError::InvalidScriptType(script_type) => json_to_jsvalue(json!({
-                "kind":"InvalidScriptType",
-                "scriptType": script_type,
+            CommonError::InvalidAmount(amount) => WasmError::new("InvalidAmount", message).with_fields(json!({
+                "amount": amount,
             })),
-        }
+            CommonError::InvalidDerivationPath(derivation_path) => WasmError::new("InvalidDerivationPath", message)
+                .with_fields(json!({
+                    "derivationPath": derivation_path,
+                })),
+            CommonError::InvalidBitcoinUnit(unit) => WasmError::new("InvalidBitcoinUnit", message).with_fields(json!({
+                "unit": unit,
+            })),
+            CommonError::InvalidScriptType(script_type) => {
+                WasmError::new("InvalidScriptType", message).with_fields(json!({
+                    "scriptType": script_type,
+                }))
+            }
+        };
+
+        error
+            .with_fields(json!({
+                "category": category.as_str(),
+            }))
+            .into()
     }
 }
@@ -1,2 +1,5 @@
+pub mod abort;
+pub mod bigint;
 pub mod error;
+pub mod timer;
 pub mod types;
@@ -0,0 +1,13 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+/// Resolves after the given delay. `setTimeout` is read from the global scope
+/// so that polling loops also run in Web Workers, where there is no `window`
+pub async fn sleep(delay_ms: u32) -> Result<(), JsValue> {
+    let set_timeout: js_sys::Function = js_sys::Reflect::get(&js_sys::global(), &"setTimeout".into())?.dyn_into()?;
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = set_timeout.call2(&JsValue::NULL, &resolve, &delay_ms.into());
+    });
+
+    JsFuture::from(promise).await.map(|_| ())
+}
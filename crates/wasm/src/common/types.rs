@@ -123,6 +123,7 @@ pub enum WasmScriptType {
     NestedSegwit = 2,
     NativeSegwit = 3,
     Taproot = 4,
+    NativeSegwitMultisig = 5,
 }
 
 impl From<ScriptType> for WasmScriptType {
@@ -132,6 +133,7 @@ impl From<ScriptType> for WasmScriptType {
             ScriptType::NestedSegwit => WasmScriptType::NestedSegwit,
             ScriptType::NativeSegwit => WasmScriptType::NativeSegwit,
             ScriptType::Taproot => WasmScriptType::Taproot,
+            ScriptType::NativeSegwitMultisig => WasmScriptType::NativeSegwitMultisig,
         }
     }
 }
@@ -143,6 +145,7 @@ impl Into<ScriptType> for WasmScriptType {
             WasmScriptType::NestedSegwit => ScriptType::NestedSegwit,
             WasmScriptType::NativeSegwit => ScriptType::NativeSegwit,
             WasmScriptType::Taproot => ScriptType::Taproot,
+            WasmScriptType::NativeSegwitMultisig => ScriptType::NativeSegwitMultisig,
         }
     }
 }
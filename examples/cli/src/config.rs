@@ -0,0 +1,71 @@
+use std::{fs, path::PathBuf};
+
+use andromeda_common::ScriptType;
+use serde::Deserialize;
+
+/// Wallets and accounts to open at startup, loaded from a TOML file given with
+/// `--config=`.
+///
+/// ```toml
+/// data_dir = ".andromeda-cli"
+///
+/// [[wallets]]
+/// name = "main"
+/// network = "testnet"
+/// mnemonic_env = "MAIN_MNEMONIC"
+///
+/// [[wallets.accounts]]
+/// script_type = "NativeSegwit"
+/// derivation_path = "m/84'/1'/0'"
+///
+/// [[watch_only]]
+/// xpub = "tpub..."
+/// script_type = "Taproot"
+/// derivation_path = "m/86'/1'/0'"
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    /// Directory of the accounts' databases, `--data-dir` takes precedence
+    pub data_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub wallets: Vec<WalletConfig>,
+    #[serde(default)]
+    pub watch_only: Vec<WatchOnlyConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WalletConfig {
+    /// Name used to select the wallet with `onchain:wallet:use`
+    pub name: String,
+    /// Defaults to testnet
+    pub network: Option<String>,
+    /// Mnemonics are read from the environment so that they aren't written
+    /// in the config file
+    pub mnemonic_env: String,
+    pub passphrase_env: Option<String>,
+    #[serde(default)]
+    pub accounts: Vec<AccountConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountConfig {
+    pub script_type: ScriptType,
+    pub derivation_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WatchOnlyConfig {
+    /// Defaults to testnet
+    pub network: Option<String>,
+    pub xpub: String,
+    pub script_type: ScriptType,
+    pub derivation_path: String,
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("could not read config file: {}", e))?;
+
+        toml::from_str(&content).map_err(|e| format!("invalid config file: {}", e))
+    }
+}
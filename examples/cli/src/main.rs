@@ -1,23 +1,238 @@
 use std::{
+    collections::HashMap,
+    env, fs,
     io::{self, Write},
+    path::{Path, PathBuf},
     str::{FromStr, SplitWhitespace},
     sync::{Arc, Mutex},
 };
 
-use andromeda_api::{ApiConfig, ProtonWalletApiClient};
+use andromeda_api::{transaction::ExchangeRateOrTransactionTime, ApiConfig, Auth, ProtonWalletApiClient, Store};
 use andromeda_bitcoin::{
     account::Account,
+    account_sweeper::AccountSweeper,
+    bip38::is_bip38_encrypted,
     blockchain_client::BlockchainClient,
-    storage::MemoryPersisted,
+    psbt::Psbt,
+    storage::{SqliteConnector, SqlitePersisterFactory},
+    transaction_builder::TxBuilder,
     transactions::{Pagination, TransactionTime},
     wallet::Wallet,
-    DerivationPath,
+    Address, Balance, BdkNetwork, BdkPsbt, Connection, DerivationPath, FeeRate, KeychainKind, Transaction, Xpub,
 };
-use andromeda_common::{Network, ScriptType};
+use andromeda_common::{utils::now, Network, ScriptType};
+use config::Config;
+use serde_json::{json, Value};
+use session::FileAuthStore;
 
-fn create_wallet(
-    words: &mut SplitWhitespace<'_>,
-) -> Result<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>, &'static str> {
+mod config;
+mod session;
+
+const DEFAULT_DATA_DIR: &str = ".andromeda-cli";
+const API_ENV: &str = "atlas";
+const SESSION_FILE: &str = "session.json";
+const WATCHED_ACCOUNTS_FILE: &str = "watched.accounts";
+const ADDRESS_LABELS_FILE: &str = "address.labels";
+const DEFAULT_ADDRESSES_PAGE_SIZE: usize = 20;
+
+type CliWallet = Wallet<SqliteConnector, Connection>;
+type CliAccount = Account<SqliteConnector, Connection>;
+
+struct CliState {
+    data_dir: PathBuf,
+    /// Whether outputs are printed as JSON, one object per line, instead of
+    /// human readable text
+    json: bool,
+    wallet: Option<Arc<Mutex<CliWallet>>>,
+    /// Wallets declared in the config file, by name
+    wallets: HashMap<String, Arc<Mutex<CliWallet>>>,
+    /// Watch-only accounts, which don't belong to the wallet as they are
+    /// created from an xpub
+    watched_accounts: HashMap<DerivationPath, Arc<CliAccount>>,
+}
+
+impl CliState {
+    fn new(data_dir: PathBuf, json: bool) -> Self {
+        let content = fs::read_to_string(data_dir.join(WATCHED_ACCOUNTS_FILE)).unwrap_or_default();
+        let watched_accounts = content
+            .lines()
+            .filter_map(|line| {
+                let mut words = line.split_whitespace();
+                let network = Network::try_from(words.next()?.to_string()).ok()?;
+                let script_type = words.next()?.parse::<u8>().ok()?.try_into().ok()?;
+                let derivation_path = DerivationPath::from_str(words.next()?).ok()?;
+                let xpub = Xpub::from_str(words.next()?).ok()?;
+
+                let account = Account::new_with_xpub(
+                    xpub,
+                    network,
+                    script_type,
+                    derivation_path.clone(),
+                    SqlitePersisterFactory(data_dir.clone()),
+                )
+                .ok()?;
+
+                Some((derivation_path, Arc::new(account)))
+            })
+            .collect();
+
+        Self {
+            data_dir,
+            json,
+            wallet: None,
+            wallets: HashMap::new(),
+            watched_accounts,
+        }
+    }
+
+    /// Opens the wallets and watch-only accounts declared in the config, the
+    /// first wallet becoming the current one
+    fn load_config(&mut self, config: Config) -> Result<(), String> {
+        for wallet_config in config.wallets {
+            let mnemonic = env::var(&wallet_config.mnemonic_env)
+                .map_err(|_| format!("{} is not set", wallet_config.mnemonic_env))?;
+            let passphrase = wallet_config
+                .passphrase_env
+                .map(|var| env::var(&var).map_err(|_| format!("{} is not set", var)))
+                .transpose()?;
+            let network = parse_network(wallet_config.network.as_deref())?;
+
+            let mut wallet = open_wallet(network, mnemonic, passphrase, &self.data_dir)?;
+            for account in wallet_config.accounts {
+                let derivation_path = DerivationPath::from_str(&account.derivation_path).map_err(|_| {
+                    format!(
+                        "invalid derivation path {} in wallet {}",
+                        account.derivation_path, wallet_config.name
+                    )
+                })?;
+
+                if wallet.get_account(&derivation_path).is_none() {
+                    wallet
+                        .add_account(
+                            account.script_type,
+                            derivation_path,
+                            SqlitePersisterFactory(self.data_dir.clone()),
+                        )
+                        .map_err(|e| format!("could not add account to wallet {}: {}", wallet_config.name, e))?;
+                }
+            }
+
+            let wallet = Arc::new(Mutex::new(wallet));
+            self.wallet.get_or_insert_with(|| wallet.clone());
+            self.wallets.insert(wallet_config.name, wallet);
+        }
+
+        for account_config in config.watch_only {
+            let xpub =
+                Xpub::from_str(&account_config.xpub).map_err(|_| format!("invalid xpub {}", account_config.xpub))?;
+            let derivation_path = DerivationPath::from_str(&account_config.derivation_path)
+                .map_err(|_| format!("invalid derivation path {}", account_config.derivation_path))?;
+
+            let account = Account::new_with_xpub(
+                xpub,
+                parse_network(account_config.network.as_deref())?,
+                account_config.script_type,
+                derivation_path.clone(),
+                SqlitePersisterFactory(self.data_dir.clone()),
+            )
+            .map_err(|e| format!("could not create watch-only account {}: {}", derivation_path, e))?;
+
+            self.watched_accounts.insert(derivation_path, Arc::new(account));
+        }
+
+        Ok(())
+    }
+
+    /// Prints the value in JSON mode, the human readable text otherwise
+    fn print(&self, value: Value, text: impl FnOnce() -> String) {
+        if self.json {
+            println!("{}", value);
+        } else {
+            println!("{}", text());
+        }
+    }
+
+    fn print_error(&self, error: &str) {
+        self.print(json!({ "error": error }), || format!("{:?}", error));
+    }
+}
+
+/// Path of the file listing the accounts added to a wallet, so that they can
+/// be reopened when the wallet is created again with the same mnemonic
+fn accounts_file(data_dir: &Path, fingerprint: &str) -> PathBuf {
+    data_dir.join(format!("{}.accounts", fingerprint))
+}
+
+/// Returns the accounts previously added to the wallet, as (script type,
+/// derivation path) pairs
+fn read_saved_accounts(data_dir: &Path, fingerprint: &str) -> Vec<(ScriptType, DerivationPath)> {
+    let content = fs::read_to_string(accounts_file(data_dir, fingerprint)).unwrap_or_default();
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let (script_type, derivation_path) = line.split_once(' ')?;
+            let script_type = script_type.parse::<u8>().ok()?.try_into().ok()?;
+            let derivation_path = DerivationPath::from_str(derivation_path).ok()?;
+
+            Some((script_type, derivation_path))
+        })
+        .collect()
+}
+
+fn save_account(
+    data_dir: &Path,
+    fingerprint: &str,
+    script_type: ScriptType,
+    derivation_path: &DerivationPath,
+) -> Result<(), &'static str> {
+    let mut accounts = read_saved_accounts(data_dir, fingerprint);
+    if accounts.iter().any(|(_, saved)| saved == derivation_path) {
+        return Ok(());
+    }
+    accounts.push((script_type, derivation_path.clone()));
+
+    let content = accounts
+        .into_iter()
+        .map(|(script_type, derivation_path)| format!("{} {}\n", u8::from(script_type), derivation_path))
+        .collect::<String>();
+
+    fs::write(accounts_file(data_dir, fingerprint), content).map_err(|_| "ERROR: could not save account")
+}
+
+fn parse_network(network: Option<&str>) -> Result<Network, &'static str> {
+    network
+        .map_or(Ok(Network::Testnet), |str| Network::try_from(str.to_string()))
+        .map_err(|_| "ERROR: invalid network")
+}
+
+/// Creates the wallet, reopening the accounts added to it in previous runs
+/// with their synced state
+fn open_wallet(
+    network: Network,
+    bip39: String,
+    bip38: Option<String>,
+    data_dir: &Path,
+) -> Result<CliWallet, &'static str> {
+    let mut wallet = Wallet::new(network, bip39, bip38).map_err(|e| {
+        println!("ERROR: could not create wallet {}", e);
+        "ERROR: could not create wallet"
+    })?;
+
+    for (script_type, derivation_path) in read_saved_accounts(data_dir, &wallet.get_fingerprint()) {
+        wallet
+            .add_account(
+                script_type,
+                derivation_path,
+                SqlitePersisterFactory(data_dir.to_path_buf()),
+            )
+            .map_err(|_| "ERROR: could not reopen account")?;
+    }
+
+    Ok(wallet)
+}
+
+fn create_wallet(words: &mut SplitWhitespace<'_>, data_dir: &Path) -> Result<Arc<Mutex<CliWallet>>, &'static str> {
     let (bip39, bip38, network) = words.fold((None, None, None), |acc, word| {
         let bip39 = if acc.0.is_none() {
             word.strip_prefix("--bip39=")
@@ -42,22 +257,26 @@ fn create_wallet(
     });
 
     let bip39 = bip39.ok_or("ERROR: createwallet requires BIP39 mnemonic")?;
+    let network = parse_network(network)?;
 
-    let network = network
-        .map_or(Ok(Network::Testnet), |str| Network::try_from(str.to_string()))
-        .map_err(|_| "ERROR: invalid network")?;
+    let wallet = open_wallet(network, bip39, bip38, data_dir)?;
 
-    let wallet = Wallet::new(network, bip39, bip38);
+    Ok(Arc::new(Mutex::new(wallet)))
+}
 
-    wallet.map(|wallet| Arc::new(Mutex::new(wallet))).map_err(|e| {
-        println!("ERROR: could not create wallet {}", e);
-        "ERROR: could not create wallet"
-    })
+fn use_wallet(words: &mut SplitWhitespace<'_>, state: &mut CliState) -> Result<String, &'static str> {
+    let name = get_arg(words, "--name=").ok_or("ERROR: wallet name is required")?;
+    let wallet = state
+        .wallets
+        .get(name)
+        .ok_or("ERROR: no wallet with this name in the config file")?;
+
+    state.wallet = Some(wallet.clone());
+
+    Ok(name.to_string())
 }
 
-fn require_wallet(
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>, &'static str> {
+fn require_wallet(wallet: Option<Arc<Mutex<CliWallet>>>) -> Result<Arc<Mutex<CliWallet>>, &'static str> {
     wallet.ok_or("ERROR: you need to create a wallet first. use onchain:wallet command")
 }
 
@@ -80,36 +299,68 @@ fn require_derivation_arg(words: &SplitWhitespace<'_>) -> Result<DerivationPath,
     Ok(derivation_path)
 }
 
-fn require_account_lock(
-    wallet: Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>,
-    derivation_path: &DerivationPath,
-) -> Result<Arc<Account<MemoryPersisted, MemoryPersisted>>, &'static str> {
+/// Returns the value of a `--name=value` argument
+fn get_arg<'a>(words: &SplitWhitespace<'a>, prefix: &str) -> Option<&'a str> {
+    words.clone().find_map(|word| word.strip_prefix(prefix))
+}
+
+fn require_account(state: &CliState, derivation_path: &DerivationPath) -> Result<Arc<CliAccount>, &'static str> {
+    if let Some(account) = state.watched_accounts.get(derivation_path) {
+        return Ok(account.clone());
+    }
+
+    let wallet = require_wallet(state.wallet.clone())?;
     let lock = wallet.lock().unwrap();
     let account = lock.get_account(derivation_path).ok_or("ERROR: account not found")?;
 
     Ok(account.clone())
 }
 
-async fn get_wallet_balance(
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<(), &'static str> {
-    let wallet = require_wallet(wallet)?;
+fn print_balance(state: &CliState, balance: Balance) {
+    state.print(
+        json!({
+            "confirmed": balance.confirmed.to_sat(),
+            "trustedSpendable": balance.trusted_spendable().to_sat(),
+            "trustedPending": balance.trusted_pending.to_sat(),
+            "untrustedPending": balance.untrusted_pending.to_sat(),
+        }),
+        || {
+            format!(
+                "\nBALANCE\nconfirmed: {}\ntrusted_spendable: {}\ntrusted_pending: {}\nuntrusted_pending: {}",
+                balance.confirmed,
+                balance.trusted_spendable(),
+                balance.trusted_pending,
+                balance.untrusted_pending
+            )
+        },
+    );
+}
+
+async fn get_wallet_balance(state: &CliState) -> Result<(), &'static str> {
+    let wallet = require_wallet(state.wallet.clone())?;
 
     let lock = wallet.lock().unwrap();
     let balance = lock.get_balance().await.unwrap();
-
-    println!("\nBALANCE");
-    println!("confirmed: {}", balance.confirmed);
-    println!("trusted_spendable: {}", balance.trusted_spendable());
-    println!("trusted_pending: {}", balance.trusted_pending);
-    println!("untrusted_pending: {}", balance.untrusted_pending);
+    print_balance(state, balance);
 
     Ok(())
 }
 
+fn parse_script_type(script_type: Option<&str>) -> Result<ScriptType, &'static str> {
+    match script_type {
+        None => Ok(ScriptType::NativeSegwit),
+        Some(str) => str
+            .parse::<u8>()
+            .map_err(|_| "ERROR:invalid script type")?
+            .try_into()
+            .map_err(|_| "ERROR:invalid script type"),
+    }
+}
+
 fn add_account(
     words: &mut SplitWhitespace<'_>,
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
+    wallet: Option<Arc<Mutex<CliWallet>>>,
+    data_dir: &Path,
 ) -> Result<DerivationPath, &'static str> {
     let wallet = require_wallet(wallet)?;
 
@@ -130,14 +381,7 @@ fn add_account(
         (script_type, derivation_path)
     });
 
-    let script_type = match script_type {
-        None => Ok(ScriptType::NativeSegwit),
-        Some(str) => str
-            .parse::<u8>()
-            .map_err(|_| "ERROR:invalid script type")?
-            .try_into()
-            .map_err(|_| "ERROR:invalid script type"),
-    }?;
+    let script_type = parse_script_type(script_type.as_deref())?;
 
     let derivation_path = match derivation_path {
         None => Ok(DerivationPath::from_str("m/84'/1'/0'").unwrap()),
@@ -146,38 +390,241 @@ fn add_account(
 
     let mut lock = wallet.lock().unwrap();
 
-    lock.add_account(script_type, derivation_path.clone(), MemoryPersisted)
-        .map_err(|_| "ERROR: could not add account")?;
+    lock.add_account(
+        script_type,
+        derivation_path.clone(),
+        SqlitePersisterFactory(data_dir.to_path_buf()),
+    )
+    .map_err(|_| "ERROR: could not add account")?;
+    save_account(data_dir, &lock.get_fingerprint(), script_type, &derivation_path)?;
 
     Ok(derivation_path)
 }
 
-async fn sync_account(
-    words: &mut SplitWhitespace<'_>,
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<DerivationPath, &'static str> {
-    println!("in sync_account");
-    let wallet = require_wallet(wallet)?;
-
+fn watch_account(words: &mut SplitWhitespace<'_>, state: &mut CliState) -> Result<DerivationPath, &'static str> {
+    let xpub = get_arg(words, "--xpub=").ok_or("ERROR: xpub is required")?;
+    let xpub = Xpub::from_str(xpub).map_err(|_| "ERROR: invalid xpub")?;
+    let script_type = parse_script_type(get_arg(words, "--scriptType="))?;
     let derivation_path = require_derivation_arg(words)?;
-    let account = require_account_lock(wallet, &derivation_path)?;
+    let network = parse_network(get_arg(words, "--network="))?;
+
+    let account = Account::new_with_xpub(
+        xpub,
+        network,
+        script_type,
+        derivation_path.clone(),
+        SqlitePersisterFactory(state.data_dir.clone()),
+    )
+    .map_err(|_| "ERROR: could not create watch-only account")?;
+
+    if state
+        .watched_accounts
+        .insert(derivation_path.clone(), Arc::new(account))
+        .is_none()
+    {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(state.data_dir.join(WATCHED_ACCOUNTS_FILE))
+            .map_err(|_| "ERROR: could not save watch-only account")?;
+        writeln!(
+            file,
+            "{} {} {} {}",
+            network,
+            u8::from(script_type),
+            derivation_path,
+            xpub
+        )
+        .map_err(|_| "ERROR: could not save watch-only account")?;
+    }
+
+    Ok(derivation_path)
+}
+
+/// Returns the labels set on addresses, stored locally as one tab separated
+/// address and label per line
+fn read_address_labels(data_dir: &Path) -> HashMap<String, String> {
+    let content = fs::read_to_string(data_dir.join(ADDRESS_LABELS_FILE)).unwrap_or_default();
 
+    content
+        .lines()
+        .filter_map(|line| {
+            let (address, label) = line.split_once('\t')?;
+            Some((address.to_string(), label.to_string()))
+        })
+        .collect()
+}
+
+fn label_address(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<(String, String), &'static str> {
+    let address = get_arg(words, "--address=").ok_or("ERROR: address is required")?;
+    let address = Address::from_str(address)
+        .map_err(|_| "ERROR: invalid address")?
+        .assume_checked()
+        .to_string();
+    // Like mnemonics, labels are given with underscores instead of spaces
+    let label = get_arg(words, "--label=")
+        .ok_or("ERROR: label is required")?
+        .replace('_', " ");
+
+    let mut labels = read_address_labels(&state.data_dir);
+    if label.is_empty() {
+        labels.remove(&address);
+    } else {
+        labels.insert(address.clone(), label.clone());
+    }
+
+    let content = labels
+        .iter()
+        .map(|(address, label)| format!("{}\t{}\n", address, label))
+        .collect::<String>();
+    fs::write(state.data_dir.join(ADDRESS_LABELS_FILE), content).map_err(|_| "ERROR: could not save label")?;
+
+    Ok((address, label))
+}
+
+fn auth_store(state: &CliState) -> FileAuthStore {
+    FileAuthStore::new(API_ENV.to_string(), state.data_dir.join(SESSION_FILE))
+}
+
+fn build_api_client(store: FileAuthStore) -> Result<ProtonWalletApiClient, &'static str> {
     let config = ApiConfig {
         spec: (
             String::from("cli-wallet@0.0.1"),
             String::from("ProtonWallet/plus-agent-details"),
         ),
-        auth: None,
-        env: Some("atlas".to_string()),
-        url_prefix: None,
-        store: None,
+        env: Some(API_ENV.to_string()),
+        store: Some(Box::new(store)),
+        ..Default::default()
     };
 
-    let proton_api_client = ProtonWalletApiClient::from_config(config).unwrap();
+    ProtonWalletApiClient::from_config(config).map_err(|_| "ERROR: could not build api client")
+}
+
+/// Logs in with `--username`, prompting for the password and the 2FA code if
+/// enabled. The session is persisted in the data directory
+async fn login(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<String, &'static str> {
+    let username = get_arg(words, "--username=").ok_or("ERROR: username is required")?;
+    let password = rpassword::prompt_password("Password: ").map_err(|_| "ERROR: could not read password")?;
+
+    let proton_api_client = build_api_client(auth_store(state))?;
+    proton_api_client
+        .login_with_two_factor(username, &password, || prompt("2FA code: "))
+        .await
+        .map_err(|_| "ERROR: login failed")?;
+
+    Ok(username.to_string())
+}
+
+fn logout(state: &CliState) -> Result<(), &'static str> {
+    auth_store(state)
+        .set_auth(Auth::None)
+        .map(|_| ())
+        .map_err(|_| "ERROR: could not logout")
+}
+
+async fn connect_blockchain_client(state: &CliState) -> Result<BlockchainClient, &'static str> {
+    let store = auth_store(state);
+    if !store.is_logged_in() {
+        return Err("ERROR: you need to login first. use login command");
+    }
+
+    Ok(BlockchainClient::new(build_api_client(store)?))
+}
+
+async fn get_chain_fees(state: &CliState) -> Result<(), &'static str> {
+    let chain = connect_blockchain_client(state).await?;
+
+    let recommended = chain
+        .get_recommended_fees()
+        .await
+        .map_err(|_| "ERROR: could not get recommended fees")?;
+    let minimum = chain
+        .get_minimum_fees()
+        .await
+        .map_err(|_| "ERROR: could not get minimum fees")?;
+
+    let rates = [
+        ("fastest", recommended.fastest_fee),
+        ("halfHour", recommended.half_hour_fee),
+        ("hour", recommended.hour_fee),
+        ("economy", recommended.economy_fee),
+        ("minimum", recommended.minimum_fee),
+    ];
 
-    proton_api_client.login("pro", "pro").await.unwrap();
+    state.print(
+        json!({
+            "recommended": rates
+                .iter()
+                .map(|(name, rate)| (name.to_string(), json!(rate.to_sat_per_vb_ceil())))
+                .collect::<serde_json::Map<_, _>>(),
+            "minimumBroadcastFee": minimum.MinimumBroadcastFee,
+            "minimumIncrementalFee": minimum.MinimumIncrementalFee,
+        }),
+        || {
+            let mut text = "\nFEES (sat/vB)".to_string();
+            rates
+                .iter()
+                .for_each(|(name, rate)| text.push_str(&format!("\n{}: {}", name, rate.to_sat_per_vb_ceil())));
+            text.push_str(&format!(
+                "\nminimum broadcast fee: {}\nminimum incremental fee: {}",
+                minimum.MinimumBroadcastFee, minimum.MinimumIncrementalFee
+            ));
+
+            text
+        },
+    );
+
+    Ok(())
+}
+
+async fn get_chain_tip(state: &CliState) -> Result<(), &'static str> {
+    let chain = connect_blockchain_client(state).await?;
+    let client = chain.inner().ok_or("ERROR: chain tip is only available from esplora")?;
+
+    let height = client
+        .get_height()
+        .await
+        .map_err(|_| "ERROR: could not get tip height")?;
+    let hash = client
+        .get_tip_hash()
+        .await
+        .map_err(|_| "ERROR: could not get tip hash")?;
+    let header = client
+        .get_header_by_hash(&hash)
+        .await
+        .map_err(|_| "ERROR: could not get tip header")?;
+    let mempool = client
+        .get_mempool_info()
+        .await
+        .map_err(|_| "ERROR: could not get mempool info")?;
+
+    let age = now().as_secs().saturating_sub(header.time as u64);
+
+    state.print(
+        json!({
+            "height": height,
+            "hash": hash.to_string(),
+            "time": header.time,
+            "age": age,
+            "mempoolTxs": mempool.Size,
+            "mempoolBytes": mempool.Bytes,
+        }),
+        || {
+            format!(
+                "\nTIP\nheight: {}\nhash: {}\ntime: {} ({}s ago)\nmempool: {} txs | {} bytes",
+                height, hash, header.time, age, mempool.Size, mempool.Bytes
+            )
+        },
+    );
+
+    Ok(())
+}
+
+async fn sync_account(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<DerivationPath, &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
 
-    let chain = BlockchainClient::new(proton_api_client);
+    let chain = connect_blockchain_client(state).await?;
 
     let update = chain.full_sync(&account, None).await.unwrap();
     account
@@ -188,92 +635,528 @@ async fn sync_account(
     Ok(derivation_path)
 }
 
-async fn get_account_balance(
-    words: &mut SplitWhitespace<'_>,
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<(), &'static str> {
-    let wallet = require_wallet(wallet)?;
-
+async fn get_account_balance(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<(), &'static str> {
     let derivation_path = require_derivation_arg(words)?;
-    let account = require_account_lock(wallet, &derivation_path)?;
+    let account = require_account(state, &derivation_path)?;
 
     let balance = account.get_balance().await;
+    print_balance(state, balance);
+
+    Ok(())
+}
+
+async fn get_account_transactions(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<(), &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
 
-    println!("\nBALANCE");
-    println!("confirmed: {}", balance.confirmed);
-    println!("trusted_spendable: {}", balance.trusted_spendable());
-    println!("trusted_pending: {}", balance.trusted_pending);
-    println!("untrusted_pending: {}", balance.untrusted_pending);
+    let transactions = account
+        .get_transactions(Pagination::default(), None)
+        .await
+        .map_err(|_| "Cannot get transactions")?;
+
+    let time = |time: &TransactionTime| match time {
+        TransactionTime::Confirmed { confirmation_time } => (true, *confirmation_time),
+        TransactionTime::Unconfirmed { last_seen } => (false, *last_seen),
+    };
+
+    state.print(
+        transactions
+            .iter()
+            .map(|simple_tx| {
+                let (confirmed, time) = time(&simple_tx.time);
+                json!({
+                    "txid": simple_tx.txid.to_string(),
+                    "confirmed": confirmed,
+                    "time": time,
+                    "sent": simple_tx.sent,
+                    "received": simple_tx.received,
+                    "fees": simple_tx.fees,
+                })
+            })
+            .collect(),
+        || {
+            let lines = transactions.iter().map(|simple_tx| {
+                format!(
+                    "txid: {:?} | time {} | sent: {} sats | received: {} sats | fees: {} ",
+                    simple_tx.txid,
+                    time(&simple_tx.time).1,
+                    simple_tx.sent,
+                    simple_tx.received,
+                    match simple_tx.fees {
+                        Some(fees) => format!("{} sats", fees),
+                        None => "None".to_string(),
+                    },
+                )
+            });
+
+            std::iter::once("\nTRANSACTIONS".to_string())
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+    );
 
     Ok(())
 }
 
-async fn get_account_transactions(
-    words: &mut SplitWhitespace<'_>,
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<(), &'static str> {
-    let wallet = require_wallet(wallet)?;
+async fn get_account_utxos(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<(), &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
+
+    let utxos = account.get_utxos().await;
+
+    state.print(
+        utxos
+            .iter()
+            .map(|utxo| {
+                json!({
+                    "outpoint": utxo.outpoint.to_string(),
+                    "keychain": format!("{:?}", utxo.keychain),
+                    "value": utxo.txout.value.to_sat(),
+                    "spent": utxo.is_spent,
+                })
+            })
+            .collect(),
+        || {
+            let lines = utxos.iter().map(|utxo| {
+                format!(
+                    "outpoint {} | keychain {:?} | value {} | spent {}",
+                    utxo.outpoint, utxo.keychain, utxo.txout.value, utxo.is_spent
+                )
+            });
 
+            std::iter::once("\nUTXOs".to_string())
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+    );
+
+    Ok(())
+}
+
+async fn get_account_addresses(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<(), &'static str> {
     let derivation_path = require_derivation_arg(words)?;
-    let account = require_account_lock(wallet, &derivation_path)?;
+    let account = require_account(state, &derivation_path)?;
 
-    println!("\nTRANSACTIONS");
-    account
-        .get_transactions(Pagination::default(), None)
+    let page = get_arg(words, "--page=")
+        .map_or(Ok(0), str::parse::<usize>)
+        .map_err(|_| "ERROR: invalid page")?;
+    let page_size = get_arg(words, "--pageSize=")
+        .map_or(Ok(DEFAULT_ADDRESSES_PAGE_SIZE), str::parse::<usize>)
+        .map_err(|_| "ERROR: invalid page size")?;
+    let keychain = match get_arg(words, "--keychain=") {
+        None | Some("external") => KeychainKind::External,
+        Some("internal") => KeychainKind::Internal,
+        Some(_) => return Err("ERROR: keychain should be either external or internal"),
+    };
+    let force_sync = words.clone().any(|word| word == "--sync");
+
+    let chain = connect_blockchain_client(state).await?;
+    let addresses = account
+        .get_addresses(
+            Pagination::new(page * page_size, page_size),
+            Arc::new(chain),
+            keychain,
+            force_sync,
+        )
         .await
-        .map_err(|_| "Cannot get transactions")?
-        .into_iter()
-        .for_each(|simple_tx| {
-            println!(
-                "txid: {:?} | time {} | sent: {} sats | received: {} sats | fees: {} ",
-                simple_tx.txid,
-                match simple_tx.time {
-                    TransactionTime::Confirmed { confirmation_time } => confirmation_time.to_string(),
-                    TransactionTime::Unconfirmed { last_seen } => last_seen.to_string(),
-                },
-                simple_tx.sent,
-                simple_tx.received,
-                match simple_tx.fees {
-                    Some(fees) => format!("{} sats", fees),
-                    None => "None".to_string(),
-                },
-            );
-        });
+        .map_err(|_| "ERROR: could not get addresses")?;
+    let labels = read_address_labels(&state.data_dir);
+
+    state.print(
+        addresses
+            .iter()
+            .map(|address| {
+                json!({
+                    "index": address.index,
+                    "address": address.address,
+                    "label": labels.get(&address.address),
+                    "balance": address.balance.total().to_sat(),
+                    "transactions": address.transactions.len(),
+                })
+            })
+            .collect(),
+        || {
+            let lines = addresses.iter().map(|address| {
+                format!(
+                    "index {} | {} | balance {} | txs {}{}",
+                    address.index,
+                    address.address,
+                    address.balance.total(),
+                    address.transactions.len(),
+                    labels
+                        .get(&address.address)
+                        .map(|label| format!(" | label: {}", label))
+                        .unwrap_or_default()
+                )
+            });
+
+            std::iter::once(format!("\nADDRESSES (page {})", page))
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+    );
 
     Ok(())
 }
 
-async fn get_account_utxos(
-    words: &mut SplitWhitespace<'_>,
-    wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>>,
-) -> Result<(), &'static str> {
-    let wallet = require_wallet(wallet)?;
+/// Asks the user for an input. The question is printed on stderr so that it
+/// doesn't mix with JSON outputs
+fn prompt(question: &str) -> String {
+    eprint!("{}", question);
+    io::stderr().flush().unwrap();
+
+    let mut answer = String::new();
+    let _ = io::stdin().read_line(&mut answer);
+
+    answer.trim().to_string()
+}
+
+/// Asks the user to confirm an action, defaulting to no
+fn confirm(question: &str) -> bool {
+    prompt(&format!("{} [y/N] ", question)).eq_ignore_ascii_case("y")
+}
+
+/// Returns a transaction builder paying `--amount` sats to `--to` at
+/// `--feeRate` sat/vB from the account
+fn tx_builder_from_args<'a>(
+    words: &SplitWhitespace<'a>,
+    account: Arc<CliAccount>,
+) -> Result<(TxBuilder<SqliteConnector, Connection>, &'a str, u64), &'static str> {
+    let to = get_arg(words, "--to=").ok_or("ERROR: recipient address is required")?;
+    let amount = get_arg(words, "--amount=")
+        .ok_or("ERROR: amount in sats is required")?
+        .parse::<u64>()
+        .map_err(|_| "ERROR: invalid amount")?;
+    let fee_rate = get_arg(words, "--feeRate=")
+        .ok_or("ERROR: fee rate in sat/vB is required")?
+        .parse::<u64>()
+        .map_err(|_| "ERROR: invalid fee rate")?;
+
+    let tx_builder = TxBuilder::<SqliteConnector, Connection>::new()
+        .set_account(account)
+        .update_recipient(0, (Some(to.to_string()), Some(amount)))
+        .set_fee_rate(fee_rate);
 
+    Ok((tx_builder, to, amount))
+}
+
+async fn broadcast_transaction(
+    words: &SplitWhitespace<'_>,
+    state: &CliState,
+    transaction: Transaction,
+) -> Result<String, &'static str> {
+    // The Proton backend indexes broadcasted transactions by wallet account
+    let wallet_id = get_arg(words, "--walletId=").ok_or("ERROR: wallet id is required")?;
+    let wallet_account_id = get_arg(words, "--walletAccountId=").ok_or("ERROR: wallet account id is required")?;
+
+    let txid = transaction.compute_txid().to_string();
+
+    let chain = connect_blockchain_client(state).await?;
+    chain
+        .broadcast(
+            transaction,
+            wallet_id.to_string(),
+            wallet_account_id.to_string(),
+            None,
+            ExchangeRateOrTransactionTime::TransactionTime(now().as_secs().to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .map_err(|_| "ERROR: could not broadcast transaction")?;
+
+    Ok(txid)
+}
+
+async fn send(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<Option<String>, &'static str> {
     let derivation_path = require_derivation_arg(words)?;
-    let account = require_account_lock(wallet, &derivation_path)?;
+    let account = require_account(state, &derivation_path)?;
+
+    let (tx_builder, to, amount) = tx_builder_from_args(words, account.clone())?;
+    // Checked before asking for confirmation
+    get_arg(words, "--walletId=").ok_or("ERROR: wallet id is required")?;
+    get_arg(words, "--walletAccountId=").ok_or("ERROR: wallet account id is required")?;
+
+    let draft = tx_builder
+        .create_draft_psbt(false)
+        .await
+        .map_err(|_| "ERROR: could not build transaction")?;
+    let details = draft
+        .inspect(&account.get_wallet().await)
+        .map_err(|_| "ERROR: could not inspect transaction")?;
+
+    let change = details
+        .outputs
+        .iter()
+        .filter(|output| output.is_change)
+        .map(|output| output.value.to_sat())
+        .collect::<Vec<_>>();
+
+    state.print(
+        json!({
+            "to": to,
+            "amount": amount,
+            "fee": details.fee.to_sat(),
+            "feeRate": details.fee_rate.to_sat_per_vb_ceil(),
+            "change": change,
+        }),
+        || {
+            let mut preview = format!(
+                "\nTRANSACTION PREVIEW\nto: {} | amount: {} sats\nfee: {} sats (~{} sat/vB)",
+                to,
+                amount,
+                details.fee.to_sat(),
+                details.fee_rate.to_sat_per_vb_ceil()
+            );
+            change
+                .iter()
+                .for_each(|value| preview.push_str(&format!("\nchange: {} sats", value)));
+
+            preview
+        },
+    );
+
+    if !confirm("Sign and broadcast?") {
+        return Ok(None);
+    }
+
+    let mut psbt = tx_builder
+        .create_psbt(false, false)
+        .await
+        .map_err(|_| "ERROR: could not build transaction")?
+        .inner();
+    account
+        .sign(&mut psbt, None)
+        .await
+        .map_err(|_| "ERROR: could not sign transaction")?;
+
+    let transaction = Psbt::from(psbt)
+        .extract_tx()
+        .map_err(|_| "ERROR: could not finalize transaction")?;
+
+    broadcast_transaction(words, state, transaction).await.map(Some)
+}
+
+fn read_psbt(words: &SplitWhitespace<'_>) -> Result<(PathBuf, BdkPsbt), &'static str> {
+    let file = PathBuf::from(get_arg(words, "--file=").ok_or("ERROR: PSBT file is required")?);
+    let content = fs::read_to_string(&file).map_err(|_| "ERROR: could not read PSBT file")?;
+    let psbt = BdkPsbt::from_str(content.trim()).map_err(|_| "ERROR: invalid PSBT")?;
+
+    Ok((file, psbt))
+}
 
-    println!("\nUTXOs");
-    account.get_utxos().await.into_iter().for_each(|utxo| {
-        println!(
-            "outpoint {} | keychain {:?} | value {} | spent {}",
-            utxo.outpoint, utxo.keychain, utxo.txout.value, utxo.is_spent
+/// Writes the PSBT base64 encoded, the format most wallets import
+fn write_psbt(file: &Path, psbt: &BdkPsbt) -> Result<(), &'static str> {
+    fs::write(file, psbt.to_string()).map_err(|_| "ERROR: could not write PSBT file")
+}
+
+/// Creates an unsigned PSBT and writes it to `--file`, e.g. to be signed by a
+/// hardware wallet or an air-gapped machine
+async fn create_psbt(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<PathBuf, &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
+    let file = PathBuf::from(get_arg(words, "--file=").ok_or("ERROR: PSBT file is required")?);
+
+    let (tx_builder, _, _) = tx_builder_from_args(words, account)?;
+    let psbt = tx_builder
+        .create_psbt(false, false)
+        .await
+        .map_err(|_| "ERROR: could not build transaction")?;
+
+    write_psbt(&file, &psbt.inner())?;
+
+    Ok(file)
+}
+
+/// Signs the PSBT in `--file` with the account's keys, finalizing it when all
+/// signatures are there. A watch-only account only finalizes it
+async fn sign_psbt(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<Option<bool>, &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
+    let (file, mut psbt) = read_psbt(words)?;
+
+    let details = Psbt::from(psbt.clone())
+        .inspect(&account.get_wallet().await)
+        .map_err(|_| "ERROR: could not inspect PSBT")?;
+
+    state.print(
+        json!({
+            "outputs": details
+                .outputs
+                .iter()
+                .map(|output| json!({
+                    "address": output.address.as_ref().map(|address| address.to_string()),
+                    "value": output.value.to_sat(),
+                    "isMine": output.is_mine,
+                    "isChange": output.is_change,
+                }))
+                .collect::<Vec<_>>(),
+            "fee": details.fee.to_sat(),
+        }),
+        || {
+            let mut preview = "\nPSBT OUTPUTS".to_string();
+            details.outputs.iter().for_each(|output| {
+                preview.push_str(&format!(
+                    "\n{} | {} sats{}",
+                    output
+                        .address
+                        .as_ref()
+                        .map_or("unknown script".to_string(), |address| address.to_string()),
+                    output.value.to_sat(),
+                    if output.is_change { " | change" } else { "" }
+                ))
+            });
+            preview.push_str(&format!("\nfee: {} sats", details.fee.to_sat()));
+
+            preview
+        },
+    );
+
+    if !confirm("Sign PSBT?") {
+        return Ok(None);
+    }
+
+    account
+        .sign(&mut psbt, None)
+        .await
+        .map_err(|_| "ERROR: could not sign PSBT")?;
+    write_psbt(&file, &psbt)?;
+
+    let finalized = psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_sig.is_some() || input.final_script_witness.is_some());
+
+    Ok(Some(finalized))
+}
+
+async fn broadcast_psbt(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<String, &'static str> {
+    let (_, psbt) = read_psbt(words)?;
+
+    let transaction = Psbt::from(psbt)
+        .extract_tx()
+        .map_err(|_| "ERROR: PSBT is not finalized, sign it first")?;
+
+    broadcast_transaction(words, state, transaction).await
+}
+
+/// Sweeps the funds of a paper wallet key given with `--wif` into the account,
+/// or to `--to` when provided. BIP38 encrypted keys prompt for their
+/// passphrase. Each funded script type of the key is swept in its own
+/// transaction, returning their txids
+async fn sweep(words: &mut SplitWhitespace<'_>, state: &CliState) -> Result<Vec<String>, &'static str> {
+    let derivation_path = require_derivation_arg(words)?;
+    let account = require_account(state, &derivation_path)?;
+
+    let private_key = get_arg(words, "--wif=").ok_or("ERROR: private key is required")?;
+    let fee_rate = get_arg(words, "--feeRate=")
+        .ok_or("ERROR: fee rate in sat/vB is required")?
+        .parse::<u64>()
+        .ok()
+        .and_then(FeeRate::from_sat_per_vb)
+        .ok_or("ERROR: invalid fee rate")?;
+    let recipient = get_arg(words, "--to=")
+        .map(|to| Address::from_str(to).map_err(|_| "ERROR: invalid recipient address"))
+        .transpose()?;
+    get_arg(words, "--walletId=").ok_or("ERROR: wallet id is required")?;
+    get_arg(words, "--walletAccountId=").ok_or("ERROR: wallet account id is required")?;
+
+    let bip38_passphrase = if words.clone().any(|word| word == "--bip38") || is_bip38_encrypted(private_key) {
+        Some(rpassword::prompt_password("BIP38 passphrase: ").map_err(|_| "ERROR: could not read passphrase")?)
+    } else {
+        None
+    };
+
+    let network = Network::try_from(account.get_wallet().await.network()).map_err(|_| "ERROR: unsupported network")?;
+    let recipient = recipient
+        .map(|address| {
+            address
+                .require_network(BdkNetwork::from(network))
+                .map_err(|_| "ERROR: recipient address is for another network")
+        })
+        .transpose()?;
+
+    let chain = Arc::new(connect_blockchain_client(state).await?);
+    let sweeper = AccountSweeper::new(chain, account.clone());
+
+    let paper_accounts = sweeper
+        .get_funded_paper_accounts(private_key, bip38_passphrase.as_deref(), network)
+        .await
+        .map_err(|_| "ERROR: could not probe private key, check the key and passphrase")?;
+    if paper_accounts.is_empty() {
+        return Err("ERROR: private key holds no funds");
+    }
+
+    let mut txids = Vec::new();
+    for paper_account in paper_accounts {
+        let psbt = match &recipient {
+            Some(address) => paper_account.create_sweep_psbt(address.script_pubkey(), fee_rate).await,
+            None => sweeper.get_sweep_psbt(&paper_account, fee_rate).await,
+        }
+        .map_err(|_| "ERROR: could not build sweep transaction")?;
+
+        let balance = paper_account.get_balance().await.total();
+        let fee = psbt.fee().map_err(|_| "ERROR: could not compute fee")?;
+
+        state.print(
+            json!({
+                "address": paper_account.get_address().await.to_string(),
+                "scriptType": format!("{:?}", paper_account.get_script_type()),
+                "balance": balance.to_sat(),
+                "fee": fee.to_sat(),
+            }),
+            || {
+                format!(
+                    "\nSWEEP PREVIEW\nfrom: {:?} key | balance: {} sats\nfee: {} sats | swept: {} sats",
+                    paper_account.get_script_type(),
+                    balance.to_sat(),
+                    fee.to_sat(),
+                    balance.to_sat().saturating_sub(fee.to_sat())
+                )
+            },
         );
-    });
 
-    Ok(())
+        if !confirm("Broadcast sweep?") {
+            continue;
+        }
+
+        let transaction = psbt
+            .extract_tx()
+            .map_err(|_| "ERROR: could not finalize sweep transaction")?;
+        txids.push(broadcast_transaction(words, state, transaction).await?);
+    }
+
+    Ok(txids)
 }
 
-async fn poll_for_user_input() {
-    println!("Proton Wallet CLI launched. Enter \"help\" to view available commands. Press Ctrl-D to quit.");
+async fn poll_for_user_input(data_dir: PathBuf, json: bool, config: Option<Config>) {
+    let mut state = CliState::new(data_dir, json);
 
-    let mut onchain_wallet: Option<Arc<Mutex<Wallet<MemoryPersisted, MemoryPersisted>>>> = None;
+    if let Some(config) = config {
+        if let Err(e) = state.load_config(config) {
+            return state.print_error(&format!("ERROR: {}", e));
+        }
+    }
+
+    // The banner and prompt would make the JSON output unparseable
+    if !state.json {
+        println!("Proton Wallet CLI launched. Enter \"help\" to view available commands. Press Ctrl-D to quit.");
+    }
 
     loop {
-        print!("> ");
-        io::stdout().flush().unwrap(); // Without flushing, the `>` doesn't print
+        if !state.json {
+            print!("> ");
+            io::stdout().flush().unwrap(); // Without flushing, the `>` doesn't print
+        }
         let mut line = String::new();
         if let Err(e) = io::stdin().read_line(&mut line) {
-            break println!("ERROR: {}", e);
+            break state.print_error(&format!("ERROR: {}", e));
         }
 
         if line.is_empty() {
@@ -284,59 +1167,154 @@ async fn poll_for_user_input() {
         let mut words = line.split_whitespace();
         if let Some(word) = words.next() {
             match word {
+                "login" => match login(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(username) => state.print(json!({ "username": username }), || {
+                        format!("INFO: logged in as {}", username)
+                    }),
+                },
+                "logout" => match logout(&state) {
+                    Err(err) => state.print_error(err),
+                    Ok(()) => state.print(json!({ "loggedOut": true }), || "INFO: logged out".to_string()),
+                },
+                "chain:fees" => {
+                    if let Err(err) = get_chain_fees(&state).await {
+                        state.print_error(err)
+                    }
+                }
+                "chain:tip" => {
+                    if let Err(err) = get_chain_tip(&state).await {
+                        state.print_error(err)
+                    }
+                }
                 "onchain:wallet" => {
-                    let wallet = create_wallet(&mut words);
+                    let wallet = create_wallet(&mut words, &state.data_dir);
 
                     match wallet {
-                        Err(err) => println!("{:?}", err),
+                        Err(err) => state.print_error(err),
                         Ok(wallet) => {
-                            onchain_wallet = Some(wallet.clone());
+                            state.wallet = Some(wallet.clone());
 
                             let created = wallet.lock().unwrap();
-                            println!(
-                                "INFO: wallet was succesfully created. fingerprint: {}. network: {}",
-                                created.get_fingerprint(),
-                                created.get_network().to_string()
+                            state.print(
+                                json!({
+                                    "fingerprint": created.get_fingerprint(),
+                                    "network": created.get_network().to_string(),
+                                    "reopenedAccounts": created.get_accounts().len(),
+                                }),
+                                || {
+                                    format!(
+                                        "INFO: wallet was succesfully created. fingerprint: {}. network: {}. reopened accounts: {}",
+                                        created.get_fingerprint(),
+                                        created.get_network(),
+                                        created.get_accounts().len()
+                                    )
+                                },
                             );
                         }
                     }
                 }
+                "onchain:wallet:use" => match use_wallet(&mut words, &mut state) {
+                    Err(err) => state.print_error(err),
+                    Ok(name) => state.print(json!({ "wallet": name }), || format!("INFO: now using wallet {}", name)),
+                },
                 "onchain:wallet:balance" => {
-                    if let Err(err) = get_wallet_balance(onchain_wallet.clone()).await {
-                        println!("{:?}", err)
+                    if let Err(err) = get_wallet_balance(&state).await {
+                        state.print_error(err)
                     }
                 }
-                "onchain:account" => match add_account(&mut words, onchain_wallet.clone()) {
-                    Err(err) => println!("{:?}", err),
+                "onchain:wallet:watch" => match watch_account(&mut words, &mut state) {
+                    Err(err) => state.print_error(err),
                     Ok(derivation_path) => {
-                        println!(
-                            "INFO: account was succesfully added to wallet. derivation path: {}",
-                            derivation_path
-                        );
+                        state.print(json!({ "derivationPath": derivation_path.to_string() }), || {
+                            format!(
+                                "INFO: watch-only account was succesfully added. derivation path: {}",
+                                derivation_path
+                            )
+                        })
                     }
                 },
-                "onchain:account:sync" => match sync_account(&mut words, onchain_wallet.clone()).await {
-                    Err(err) => println!("{:?}", err),
+                "onchain:account" => match add_account(&mut words, state.wallet.clone(), &state.data_dir) {
+                    Err(err) => state.print_error(err),
                     Ok(derivation_path) => {
-                        println!("INFO: account synced. {}", &derivation_path.to_string())
+                        state.print(json!({ "derivationPath": derivation_path.to_string() }), || {
+                            format!(
+                                "INFO: account was succesfully added to wallet. derivation path: {}",
+                                derivation_path
+                            )
+                        })
                     }
                 },
+                "onchain:account:sync" => match sync_account(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(derivation_path) => state
+                        .print(json!({ "derivationPath": derivation_path.to_string() }), || {
+                            format!("INFO: account synced. {}", derivation_path)
+                        }),
+                },
                 "onchain:account:balance" => {
-                    if let Err(err) = get_account_balance(&mut words, onchain_wallet.clone()).await {
-                        println!("{:?}", err)
+                    if let Err(err) = get_account_balance(&mut words, &state).await {
+                        state.print_error(err)
                     }
                 }
                 "onchain:account:transactions" => {
-                    if let Err(err) = get_account_transactions(&mut words, onchain_wallet.clone()).await {
-                        println!("{:?}", err)
+                    if let Err(err) = get_account_transactions(&mut words, &state).await {
+                        state.print_error(err)
                     }
                 }
                 "onchain:account:utxos" => {
-                    if let Err(err) = get_account_utxos(&mut words, onchain_wallet.clone()).await {
-                        println!("{:?}", err)
+                    if let Err(err) = get_account_utxos(&mut words, &state).await {
+                        state.print_error(err)
                     }
                 }
-                _ => println!("Unknown command. See `\"help\" for available commands."),
+                "onchain:account:addresses" => {
+                    if let Err(err) = get_account_addresses(&mut words, &state).await {
+                        state.print_error(err)
+                    }
+                }
+                "onchain:address:label" => match label_address(&mut words, &state) {
+                    Err(err) => state.print_error(err),
+                    Ok((address, label)) => state.print(json!({ "address": address, "label": label }), || {
+                        format!("INFO: address {} labelled \"{}\"", address, label)
+                    }),
+                },
+                "onchain:psbt:create" => match create_psbt(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(file) => state.print(json!({ "file": file }), || {
+                        format!("INFO: unsigned PSBT written to {}", file.display())
+                    }),
+                },
+                "onchain:psbt:sign" => match sign_psbt(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(None) => state.print(json!({ "signed": false }), || "INFO: signing cancelled".to_string()),
+                    Ok(Some(finalized)) => state.print(json!({ "signed": true, "finalized": finalized }), || {
+                        format!("INFO: PSBT signed. finalized: {}", finalized)
+                    }),
+                },
+                "onchain:psbt:broadcast" => match broadcast_psbt(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(txid) => state.print(json!({ "txid": txid }), || {
+                        format!("INFO: transaction broadcasted. txid: {}", txid)
+                    }),
+                },
+                "onchain:sweep" => match sweep(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(txids) => state.print(json!({ "txids": txids }), || {
+                        format!(
+                            "INFO: {} sweep transaction(s) broadcasted. txids: {:?}",
+                            txids.len(),
+                            txids
+                        )
+                    }),
+                },
+                "onchain:account:send" => match send(&mut words, &state).await {
+                    Err(err) => state.print_error(err),
+                    Ok(None) => state.print(json!({ "txid": null }), || "INFO: transaction cancelled".to_string()),
+                    Ok(Some(txid)) => state.print(json!({ "txid": txid }), || {
+                        format!("INFO: transaction broadcasted. txid: {}", txid)
+                    }),
+                },
+                _ => state.print_error("Unknown command. See `\"help\" for available commands."),
             }
         }
     }
@@ -344,5 +1322,21 @@ async fn poll_for_user_input() {
 
 #[tokio::main]
 async fn main() {
-    poll_for_user_input().await;
+    let json = env::args().any(|arg| arg == "--json");
+    let config = match env::args().find_map(|arg| arg.strip_prefix("--config=").map(Config::load)) {
+        Some(Err(e)) => return println!("ERROR: {}", e),
+        Some(Ok(config)) => Some(config),
+        None => None,
+    };
+
+    let data_dir = env::args()
+        .find_map(|arg| arg.strip_prefix("--data-dir=").map(PathBuf::from))
+        .or_else(|| config.as_ref().and_then(|config| config.data_dir.clone()))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR));
+
+    if let Err(e) = fs::create_dir_all(&data_dir) {
+        return println!("ERROR: could not create data directory: {}", e);
+    }
+
+    poll_for_user_input(data_dir, json, config).await;
 }
@@ -0,0 +1,98 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use andromeda_api::{Auth, EnvId, Store, StoreFailure, StoredAuth, WalletAuthStore};
+
+/// Auth store persisting the session in a file, so that the CLI stays logged
+/// in across runs.
+///
+/// # Notes
+///
+/// The session tokens are stored unencrypted, only readable by the current
+/// user on unix systems.
+#[derive(Debug, Clone)]
+pub struct FileAuthStore {
+    env: EnvId,
+    path: PathBuf,
+    auth: Arc<Mutex<Auth>>,
+}
+
+impl FileAuthStore {
+    /// Creates a store for the given environment, restoring the session
+    /// persisted in the file if any
+    pub fn new(env: String, path: PathBuf) -> Self {
+        let auth = fs::read_to_string(&path)
+            .ok()
+            .and_then(|serialized| serde_json::from_str::<StoredAuth>(&serialized).ok())
+            .map_or(Auth::None, StoredAuth::into_auth);
+
+        Self {
+            env: WalletAuthStore::from_env_str(env, Arc::new(Mutex::new(Auth::None))).env,
+            path,
+            auth: Arc::new(Mutex::new(auth)),
+        }
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.auth.lock().unwrap().uid().is_some()
+    }
+
+    fn persist(&self, auth: &Auth) -> Result<(), String> {
+        let Some(stored_auth) = StoredAuth::from_auth(auth) else {
+            // Session was logged out
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            };
+        };
+
+        let serialized = serde_json::to_string(&stored_auth).map_err(|e| e.to_string())?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // The file is created readable by the current user only, so that the
+        // tokens are never exposed, even briefly
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&self.path).map_err(|e| e.to_string())?;
+
+        // The mode only applies on creation: a file left by a previous version
+        // is restricted before the tokens get written
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))
+                .map_err(|e| e.to_string())?;
+        }
+
+        file.write_all(serialized.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+impl Store for FileAuthStore {
+    fn env(&self) -> EnvId {
+        self.env.clone()
+    }
+
+    fn get_auth(&self) -> Auth {
+        self.auth.lock().unwrap().clone()
+    }
+
+    fn set_auth(&mut self, auth: Auth) -> Result<Auth, StoreFailure> {
+        // A failure to persist only means logging in again on the next run
+        if let Err(e) = self.persist(&auth) {
+            eprintln!("WARNING: could not persist the session: {}", e);
+        }
+        *self.auth.lock().unwrap() = auth.clone();
+
+        Ok(auth)
+    }
+}